@@ -0,0 +1,196 @@
+//! Conversion of encoded CBOR into its deterministic encoding, as described in
+//! [section 4.2 of RFC 8949].
+//!
+//! The encoder already uses the shortest form for arguments and floats, so
+//! this is concerned with what can't be determined while encoding: sorting the
+//! entries of maps and giving every item a definite length.
+//!
+//! [section 4.2 of RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html#name-deterministically-encoded-c
+
+use alloc::vec::Vec;
+
+use musli::Context;
+use musli_common::reader::Reader;
+use musli_common::writer::Writer;
+
+use crate::en::encode_argument;
+use crate::error::Error;
+use crate::header::{Header, Major, EIGHT_BYTES, FOUR_BYTES, INDEFINITE, ONE_BYTE, TWO_BYTES};
+
+/// Write the deterministic encoding of the single item in `input` to `writer`.
+pub(crate) fn canonicalize<'buf, C, W>(
+    cx: &mut C,
+    mut input: &[u8],
+    mut writer: W,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    let mut out = Vec::with_capacity(input.len());
+    item(cx, &mut input, &mut out)?;
+
+    if !input.is_empty() {
+        return Err(cx.message("trailing bytes after encoded value"));
+    }
+
+    writer.write_bytes(cx.adapt(), &out)
+}
+
+/// Read the header of the next item and its argument, which is `None` for
+/// items of indefinite length.
+fn read_header<'buf, C>(cx: &mut C, input: &mut &[u8]) -> Result<(Header, Option<u64>), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+{
+    let header = Header::from_byte(input.read_byte(cx.adapt())?);
+
+    let argument = match header.info {
+        info @ 0..=23 => info as u64,
+        ONE_BYTE => input.read_byte(cx.adapt())? as u64,
+        TWO_BYTES => u16::from_be_bytes(input.read_array(cx.adapt())?) as u64,
+        FOUR_BYTES => u32::from_be_bytes(input.read_array(cx.adapt())?) as u64,
+        EIGHT_BYTES => u64::from_be_bytes(input.read_array(cx.adapt())?),
+        INDEFINITE => return Ok((header, None)),
+        info => {
+            return Err(cx.message(format_args!("reserved additional information {info}")));
+        }
+    };
+
+    Ok((header, Some(argument)))
+}
+
+/// Consume the break which terminates an item of indefinite length, if it is
+/// next.
+fn read_break(input: &mut &[u8]) -> bool {
+    if let [b, rest @ ..] = *input {
+        if *b == Header::BREAK.byte() {
+            *input = rest;
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Take the given number of bytes from the input.
+fn take<'a, 'buf, C>(cx: &mut C, input: &mut &'a [u8], len: u64) -> Result<&'a [u8], C::Error>
+where
+    C: Context<'buf, Input = Error>,
+{
+    let Some((bytes, rest)) = usize::try_from(len)
+        .ok()
+        .filter(|len| *len <= input.len())
+        .map(|len| input.split_at(len))
+    else {
+        return Err(cx.message("unexpected end of input"));
+    };
+
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Write the deterministic encoding of the next item in `input` to `out`.
+fn item<'buf, C>(cx: &mut C, input: &mut &[u8], out: &mut Vec<u8>) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+{
+    let start = *input;
+    let (header, argument) = read_header(cx, input)?;
+
+    match (header.major, argument) {
+        (Major::Unsigned | Major::Negative, Some(value)) => {
+            encode_argument(cx, &mut *out, header.major, value)?;
+        }
+        (Major::Bytes | Major::String, Some(len)) => {
+            let bytes = take(cx, input, len)?;
+            encode_argument(cx, &mut *out, header.major, len)?;
+            out.extend_from_slice(bytes);
+        }
+        (Major::Bytes | Major::String, None) => {
+            let mut bytes = Vec::new();
+
+            while !read_break(input) {
+                let (chunk, len) = read_header(cx, input)?;
+
+                let (true, Some(len)) = (chunk.major == header.major, len) else {
+                    return Err(cx.message("bad chunk in string of indefinite length"));
+                };
+
+                bytes.extend_from_slice(take(cx, input, len)?);
+            }
+
+            encode_argument(cx, &mut *out, header.major, bytes.len() as u64)?;
+            out.extend_from_slice(&bytes);
+        }
+        (Major::Array, Some(len)) => {
+            encode_argument(cx, &mut *out, Major::Array, len)?;
+
+            for _ in 0..len {
+                item(cx, input, out)?;
+            }
+        }
+        (Major::Array, None) => {
+            let mut items = Vec::new();
+            let mut len = 0;
+
+            while !read_break(input) {
+                item(cx, input, &mut items)?;
+                len += 1;
+            }
+
+            encode_argument(cx, &mut *out, Major::Array, len)?;
+            out.extend_from_slice(&items);
+        }
+        (Major::Map, len) => {
+            let mut entries = Vec::new();
+
+            loop {
+                match len {
+                    Some(len) if entries.len() as u64 == len => break,
+                    None if read_break(input) => break,
+                    _ => {}
+                }
+
+                let mut key = Vec::new();
+                item(cx, input, &mut key)?;
+                let mut value = Vec::new();
+                item(cx, input, &mut value)?;
+                entries.push((key, value));
+            }
+
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            if entries.windows(2).any(|w| w[0].0 == w[1].0) {
+                return Err(cx.message("duplicate key in map"));
+            }
+
+            encode_argument(cx, &mut *out, Major::Map, entries.len() as u64)?;
+
+            for (key, value) in entries {
+                out.extend_from_slice(&key);
+                out.extend_from_slice(&value);
+            }
+        }
+        (Major::Tag, Some(tag)) => {
+            encode_argument(cx, &mut *out, Major::Tag, tag)?;
+            item(cx, input, out)?;
+        }
+        (Major::Simple, _) if header != Header::BREAK => {
+            // Simple values and floats are copied as-is, since the encoder
+            // already uses the shortest form for floats.
+            let len = start.len() - input.len();
+            out.extend_from_slice(&start[..len]);
+        }
+        _ => {
+            return Err(cx.message(format_args!(
+                "unexpected {} ({:#04x})",
+                header.major.name(),
+                header.byte()
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1592 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use musli::de::AsDecoder;
+use musli::de::{
+    Decoder, NumberHint, NumberVisitor, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder,
+    SizeHint, TypeHint, ValueVisitor, VariantDecoder, Visitor,
+};
+#[cfg(feature = "alloc")]
+use musli::mode::Mode;
+use musli::Context;
+use musli_common::reader::Reader;
+#[cfg(feature = "alloc")]
+use musli_common::reader::SliceReader;
+
+use crate::error::Error;
+use crate::float;
+use crate::header::{Header, Major, EIGHT_BYTES, FOUR_BYTES, INDEFINITE, ONE_BYTE, TWO_BYTES};
+use crate::tag;
+
+/// What a decoder is currently positioned at.
+#[derive(Debug, Clone, Copy)]
+enum State {
+    /// A regular data item.
+    Value,
+    /// The number of a semantic tag, which has already been read.
+    Tag(u64),
+}
+
+/// A decoded CBOR integer.
+#[derive(Debug, Clone, Copy)]
+enum Integer {
+    Unsigned(u64),
+    /// A negative integer, where `n` represents the value `-1 - n`.
+    Negative(u64),
+}
+
+/// A CBOR decoder.
+pub struct CborDecoder<R> {
+    reader: R,
+    state: State,
+}
+
+impl<R> CborDecoder<R> {
+    /// Construct a new CBOR decoder.
+    #[inline]
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            state: State::Value,
+        }
+    }
+
+    #[inline]
+    fn with_state(reader: R, state: State) -> Self {
+        Self { reader, state }
+    }
+}
+
+/// Decoder for an array or pack of values, which might have an indefinite
+/// length.
+pub struct CborSequenceDecoder<R> {
+    reader: R,
+    remaining: Option<usize>,
+}
+
+/// Decoder for a map or struct, which might have an indefinite length.
+pub struct CborMapDecoder<R> {
+    reader: R,
+    remaining: Option<usize>,
+}
+
+/// Decoder for a variant, which is either a map with a single entry or a
+/// value with a semantic tag.
+pub struct CborVariantDecoder<R> {
+    reader: R,
+    tag: Option<u64>,
+    indefinite: bool,
+}
+
+impl<'de, R> CborDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Skip over any value.
+    pub(crate) fn skip_any<'buf, C>(&mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let State::Tag(..) = self.state {
+            return Ok(());
+        }
+
+        let mark = cx.mark();
+        let header = self.read_header(cx)?;
+
+        match header.major {
+            Major::Unsigned | Major::Negative => {
+                self.read_integer_argument(cx, header, mark)?;
+            }
+            Major::Bytes | Major::String => match self.read_length(cx, header, mark)? {
+                Some(len) => {
+                    self.reader.skip(cx.adapt(), len)?;
+                }
+                None => {
+                    while let Some(len) = self.read_chunk(cx, header.major)? {
+                        self.reader.skip(cx.adapt(), len)?;
+                    }
+                }
+            },
+            Major::Array => match self.read_length(cx, header, mark)? {
+                Some(len) => {
+                    for _ in 0..len {
+                        self.skip_any(cx)?;
+                    }
+                }
+                None => {
+                    while !self.read_break(cx)? {
+                        self.skip_any(cx)?;
+                    }
+                }
+            },
+            Major::Map => match self.read_length(cx, header, mark)? {
+                Some(len) => {
+                    for _ in 0..len {
+                        self.skip_any(cx)?;
+                        self.skip_any(cx)?;
+                    }
+                }
+                None => {
+                    while !self.read_break(cx)? {
+                        self.skip_any(cx)?;
+                        self.skip_any(cx)?;
+                    }
+                }
+            },
+            Major::Tag => {
+                self.read_integer_argument(cx, header, mark)?;
+                self.skip_any(cx)?;
+            }
+            Major::Simple => {
+                if header == Header::BREAK {
+                    return Err(cx.marked_message(mark, "unexpected break"));
+                }
+
+                self.reader.skip(cx.adapt(), header.argument_len())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the header of a regular data item.
+    #[inline]
+    fn read_header<'buf, C>(&mut self, cx: &mut C) -> Result<Header, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.state {
+            State::Value => Ok(Header::from_byte(self.reader.read_byte(cx.adapt())?)),
+            State::Tag(..) => Err(cx.message("semantic tag can only be decoded as an integer")),
+        }
+    }
+
+    /// Peek the header of the next data item.
+    #[inline]
+    fn peek_header<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Header>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        Ok(self.reader.peek(cx.adapt())?.map(Header::from_byte))
+    }
+
+    /// Read the argument of the given header, or `None` if it indicates an
+    /// indefinite length.
+    #[inline]
+    fn read_argument<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        header: Header,
+        mark: C::Mark,
+    ) -> Result<Option<u64>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let r = &mut self.reader;
+
+        Ok(Some(match header.info {
+            info @ 0..=23 => info as u64,
+            ONE_BYTE => r.read_byte(cx.adapt())? as u64,
+            TWO_BYTES => u16::from_be_bytes(r.read_array(cx.adapt())?) as u64,
+            FOUR_BYTES => u32::from_be_bytes(r.read_array(cx.adapt())?) as u64,
+            EIGHT_BYTES => u64::from_be_bytes(r.read_array(cx.adapt())?),
+            INDEFINITE => return Ok(None),
+            info => {
+                return Err(
+                    cx.marked_message(mark, format_args!("reserved additional information {info}"))
+                )
+            }
+        }))
+    }
+
+    /// Read the argument of an integer or a tag, which cannot be indefinite.
+    #[inline]
+    fn read_integer_argument<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        header: Header,
+        mark: C::Mark,
+    ) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.read_argument(cx, header, mark)? {
+            Some(value) => Ok(value),
+            None => Err(cx.marked_message(
+                mark,
+                format_args!("{} cannot have an indefinite length", header.major.name()),
+            )),
+        }
+    }
+
+    /// Read the length of a string, array or map, or `None` if it is
+    /// indefinite.
+    #[inline]
+    fn read_length<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        header: Header,
+        mark: C::Mark,
+    ) -> Result<Option<usize>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let Some(len) = self.read_argument(cx, header, mark)? else {
+            return Ok(None);
+        };
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(Some(len)),
+            Err(..) => Err(cx.marked_message(mark, format_args!("length {len} is too large"))),
+        }
+    }
+
+    /// Read the length of the next chunk of a string of indefinite length, or
+    /// `None` if the string has ended.
+    #[inline]
+    fn read_chunk<'buf, C>(&mut self, cx: &mut C, major: Major) -> Result<Option<usize>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+        let header = self.read_header(cx)?;
+
+        if header == Header::BREAK {
+            return Ok(None);
+        }
+
+        if header.major != major {
+            return Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: major.name(),
+                    actual: header,
+                },
+            ));
+        }
+
+        match self.read_length(cx, header, mark)? {
+            Some(len) => Ok(Some(len)),
+            None => Err(cx.marked_message(mark, "chunks of strings must have a definite length")),
+        }
+    }
+
+    /// Consume the break which terminates an item of indefinite length, if it
+    /// is next.
+    #[inline]
+    fn read_break<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if self.reader.peek(cx.adapt())? == Some(Header::BREAK.byte()) {
+            self.reader.skip(cx.adapt(), 1)?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Decode the length of an item of the given major type.
+    #[inline]
+    fn decode_length<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        major: Major,
+        mark: C::Mark,
+    ) -> Result<Option<usize>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let header = self.read_header(cx)?;
+
+        if header.major != major {
+            return Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: major.name(),
+                    actual: header,
+                },
+            ));
+        }
+
+        self.read_length(cx, header, mark)
+    }
+
+    /// Decode a string of the given major type of indefinite length, by
+    /// concatenating its chunks.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode_chunks<'buf, C>(&mut self, cx: &mut C, major: Major) -> Result<Vec<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mut bytes = Vec::new();
+
+        while let Some(len) = self.read_chunk(cx, major)? {
+            let start = bytes.len();
+            bytes.resize(start + len, 0);
+            self.reader.read(cx.adapt(), &mut bytes[start..])?;
+        }
+
+        Ok(bytes)
+    }
+
+    /// Read the integer following the given header, if it is an integer.
+    #[inline]
+    fn read_integer<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        header: Header,
+        mark: C::Mark,
+    ) -> Result<Option<Integer>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        Ok(Some(match header.major {
+            Major::Unsigned => Integer::Unsigned(self.read_integer_argument(cx, header, mark)?),
+            Major::Negative => Integer::Negative(self.read_integer_argument(cx, header, mark)?),
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Decode an integer of any width, and convert it into `T`.
+    #[inline]
+    fn decode_integer<'buf, C, T>(self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: TryFrom<u64> + TryFrom<i64>,
+    {
+        let mark = cx.mark();
+
+        let value = match self.decode_wide(cx)? {
+            Wide::Unsigned(value) => u64::try_from(value)
+                .ok()
+                .and_then(|value| <T as TryFrom<u64>>::try_from(value).ok()),
+            Wide::Negative(n) => i64::try_from(n)
+                .ok()
+                .and_then(|n| <T as TryFrom<i64>>::try_from(-1 - n).ok()),
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => Err(cx.marked_message(mark, "integer out of range")),
+        }
+    }
+
+    /// Decode an integer which might be wider than 64 bits, either because it
+    /// is a negative integer or because it is a bignum.
+    #[inline]
+    fn decode_wide<'buf, C>(mut self, cx: &mut C) -> Result<Wide, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+
+        if let State::Tag(tag) = self.state {
+            return Ok(Wide::Unsigned(tag as u128));
+        }
+
+        let header = self.read_header(cx)?;
+
+        match header.major {
+            Major::Unsigned => {
+                let value = self.read_integer_argument(cx, header, mark)?;
+                Ok(Wide::Unsigned(value as u128))
+            }
+            Major::Negative => {
+                let n = self.read_integer_argument(cx, header, mark)?;
+                Ok(Wide::Negative(n as u128))
+            }
+            Major::Tag => {
+                let tag = self.read_integer_argument(cx, header, mark)?;
+                self.decode_bignum(cx, tag, mark)
+            }
+            _ => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "integer",
+                    actual: header,
+                },
+            )),
+        }
+    }
+
+    /// Decode the byte string of a bignum with the given tag.
+    #[inline]
+    fn decode_bignum<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        tag: u64,
+        mark: C::Mark,
+    ) -> Result<Wide, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if !matches!(tag, tag::POSITIVE_BIGNUM | tag::NEGATIVE_BIGNUM) {
+            return Err(cx.marked_message(
+                mark,
+                format_args!("expected integer but found semantic tag {tag}"),
+            ));
+        }
+
+        let Some(len) = self.decode_length(cx, Major::Bytes, mark)? else {
+            return Err(cx.marked_message(mark, "bignums must have a definite length"));
+        };
+
+        let mut bytes = [0; 16];
+
+        // Leading zeros do not affect the value of a bignum.
+        let len = match len.checked_sub(16) {
+            Some(zeros) => {
+                for _ in 0..zeros {
+                    if self.reader.read_byte(cx.adapt())? != 0 {
+                        return Err(cx.marked_message(mark, "bignum out of range"));
+                    }
+                }
+
+                16
+            }
+            None => len,
+        };
+
+        self.reader.read(cx.adapt(), &mut bytes[16 - len..])?;
+        let value = u128::from_be_bytes(bytes);
+
+        Ok(match tag {
+            tag::POSITIVE_BIGNUM => Wide::Unsigned(value),
+            _ => Wide::Negative(value),
+        })
+    }
+
+    /// Decode a floating point value, integers are converted.
+    #[inline]
+    fn decode_float<'buf, C>(mut self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+        let header = self.read_header(cx)?;
+
+        match header {
+            Header::F16 => {
+                let bits = u16::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                Ok(float::f16_to_f32(bits) as f64)
+            }
+            Header::F32 => Ok(f32::from_be_bytes(self.reader.read_array(cx.adapt())?) as f64),
+            Header::F64 => Ok(f64::from_be_bytes(self.reader.read_array(cx.adapt())?)),
+            header => match self.read_integer(cx, header, mark)? {
+                Some(Integer::Unsigned(value)) => Ok(value as f64),
+                Some(Integer::Negative(n)) => Ok(-1.0 - n as f64),
+                None => Err(cx.marked_message(
+                    mark,
+                    Expected {
+                        expected: "float",
+                        actual: header,
+                    },
+                )),
+            },
+        }
+    }
+}
+
+/// An integer which might be wider than 64 bits.
+enum Wide {
+    Unsigned(u128),
+    /// A negative integer, where `n` represents the value `-1 - n`.
+    Negative(u128),
+}
+
+/// A buffered value, which holds a copy of the bytes the value was decoded
+/// from.
+#[cfg(feature = "alloc")]
+pub struct CborBuffer {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl AsDecoder for CborBuffer {
+    type Error = Error;
+    type Decoder<'this>
+        = CborDecoder<SliceReader<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn as_decoder<'buf, C>(&self, _: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborDecoder::new(SliceReader::new(&self.bytes)))
+    }
+}
+
+/// A reader which keeps a copy of all the bytes read through it.
+#[cfg(feature = "alloc")]
+struct RecordingReader<R> {
+    reader: R,
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, R> Reader<'de> for RecordingReader<R>
+where
+    R: Reader<'de>,
+{
+    type Error = R::Error;
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn skip<'buf, C>(&mut self, cx: &mut C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx, &mut self.bytes[start..])
+    }
+
+    #[inline]
+    fn peek<'buf, C>(&mut self, cx: &mut C) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.reader.peek(cx)
+    }
+
+    #[inline]
+    fn read_bytes<'buf, C, V>(
+        &mut self,
+        cx: &mut C,
+        n: usize,
+        visitor: V,
+    ) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf>,
+        C::Input: From<Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx.adapt(), &mut self.bytes[start..])?;
+        visitor.visit_ref(cx, &self.bytes[start..])
+    }
+}
+
+/// Adapts a visitor of strings into a visitor of bytes, by validating that the
+/// bytes are UTF-8.
+struct StrVisitor<V>(V);
+
+impl<'de, 'buf, C, V> ValueVisitor<'de, 'buf, C, [u8]> for StrVisitor<V>
+where
+    C: Context<'buf>,
+    V: ValueVisitor<'de, 'buf, C, str>,
+{
+    type Ok = V::Ok;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn visit_owned(self, cx: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8_owned(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_owned(cx, string)
+    }
+
+    #[inline]
+    fn visit_borrowed(self, cx: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_borrowed(cx, string)
+    }
+
+    #[inline]
+    fn visit_ref(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_ref(cx, string)
+    }
+}
+
+#[musli::decoder]
+impl<'de, R> Decoder<'de> for CborDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    #[cfg(feature = "alloc")]
+    type Buffer = CborBuffer;
+    type Pack = CborSequenceDecoder<R>;
+    type Some = Self;
+    type Sequence = CborSequenceDecoder<R>;
+    type Tuple = CborSequenceDecoder<R>;
+    type Map = CborMapDecoder<R>;
+    type Struct = CborMapDecoder<R>;
+    type Variant = CborVariantDecoder<R>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR decoder")
+    }
+
+    #[inline]
+    fn type_hint<'buf, C>(&mut self, cx: &mut C) -> Result<TypeHint, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let header = match self.state {
+            State::Value => match self.peek_header(cx)? {
+                Some(header) => header,
+                None => return Ok(TypeHint::Any),
+            },
+            State::Tag(..) => return Ok(TypeHint::Number(NumberHint::U64)),
+        };
+
+        let size = match header.info {
+            info @ 0..=23 => SizeHint::Exact(info as usize),
+            _ => SizeHint::Any,
+        };
+
+        Ok(match header.major {
+            Major::Unsigned => TypeHint::Number(match header.info {
+                0..=ONE_BYTE => NumberHint::U8,
+                TWO_BYTES => NumberHint::U16,
+                FOUR_BYTES => NumberHint::U32,
+                _ => NumberHint::U64,
+            }),
+            Major::Negative => TypeHint::Number(match header.info {
+                0..=23 => NumberHint::I8,
+                ONE_BYTE => NumberHint::I16,
+                TWO_BYTES => NumberHint::I32,
+                FOUR_BYTES => NumberHint::I64,
+                _ => NumberHint::I128,
+            }),
+            Major::Bytes => TypeHint::Bytes(size),
+            Major::String => TypeHint::String(size),
+            Major::Array => TypeHint::Sequence(size),
+            Major::Map => TypeHint::Map(size),
+            Major::Tag => TypeHint::Variant,
+            Major::Simple => match header {
+                Header::FALSE | Header::TRUE => TypeHint::Bool,
+                Header::NULL | Header::UNDEFINED => TypeHint::Unit,
+                Header::F16 | Header::F32 => TypeHint::Number(NumberHint::F32),
+                Header::F64 => TypeHint::Number(NumberHint::F64),
+                _ => TypeHint::Any,
+            },
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, cx: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if !matches!(self.state, State::Value) {
+            return Err(cx.message("semantic tags cannot be buffered"));
+        }
+
+        let mut decoder = CborDecoder::new(RecordingReader {
+            reader: self.reader,
+            bytes: Vec::new(),
+        });
+
+        decoder.skip_any(cx)?;
+
+        Ok(CborBuffer {
+            bytes: decoder.reader.bytes,
+        })
+    }
+
+    #[inline]
+    fn decode_unit<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.read_header(cx)? {
+            Header::NULL | Header::UNDEFINED => Ok(()),
+            header => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "null",
+                    actual: header,
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(mut self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_length(cx, Major::Array, mark)?;
+        Ok(CborSequenceDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_array<'buf, C, const N: usize>(mut self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        if let Some(len) = self.decode_length(cx, Major::Bytes, mark)? {
+            if len != N {
+                return Err(cx.marked_message(
+                    mark,
+                    format_args! {
+                        "bad length, got {len} but expect {N}"
+                    },
+                ));
+            }
+
+            return self.reader.read_array(cx.adapt());
+        }
+
+        let mut array = [0; N];
+        let mut at = 0;
+
+        while let Some(len) = self.read_chunk(cx, Major::Bytes)? {
+            let Some(chunk) = array.get_mut(at..at + len) else {
+                return Err(
+                    cx.marked_message(mark, format_args!("bad length, got more than {N} bytes"))
+                );
+            };
+
+            self.reader.read(cx.adapt(), chunk)?;
+            at += len;
+        }
+
+        if at != N {
+            return Err(cx.marked_message(
+                mark,
+                format_args! {
+                    "bad length, got {at} but expect {N}"
+                },
+            ));
+        }
+
+        Ok(array)
+    }
+
+    #[inline]
+    fn decode_bytes<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let mark = cx.mark();
+
+        match self.decode_length(cx, Major::Bytes, mark)? {
+            Some(len) => self.reader.read_bytes(cx, len, visitor),
+            #[cfg(feature = "alloc")]
+            None => {
+                let bytes = self.decode_chunks(cx, Major::Bytes)?;
+                visitor.visit_owned(cx, bytes)
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => Err(cx.marked_message(
+                mark,
+                "byte strings of indefinite length are not supported without the alloc feature",
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        let mark = cx.mark();
+
+        match self.decode_length(cx, Major::String, mark)? {
+            Some(len) => self.reader.read_bytes(cx, len, StrVisitor(visitor)),
+            #[cfg(feature = "alloc")]
+            None => {
+                let bytes = self.decode_chunks(cx, Major::String)?;
+                StrVisitor(visitor).visit_owned(cx, bytes)
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => Err(cx.marked_message(
+                mark,
+                "text strings of indefinite length are not supported without the alloc feature",
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_bool<'buf, C>(mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.read_header(cx)? {
+            Header::FALSE => Ok(false),
+            Header::TRUE => Ok(true),
+            header => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "boolean",
+                    actual: header,
+                },
+            )),
+        }
+    }
+
+    /// Characters are encoded as text strings containing a single character.
+    #[inline]
+    fn decode_char<'buf, C>(mut self, cx: &mut C) -> Result<char, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        let len = match self.decode_length(cx, Major::String, mark)? {
+            Some(len) if len <= 4 => len,
+            Some(len) => {
+                return Err(cx.marked_message(mark, format_args!("bad character of length {len}")))
+            }
+            None => {
+                return Err(cx.marked_message(mark, "characters must have a definite length"));
+            }
+        };
+
+        let mut buf = [0; 4];
+        self.reader.read(cx.adapt(), &mut buf[..len])?;
+        let string = musli_common::str::from_utf8(&buf[..len]).map_err(|err| cx.custom(err))?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(cx.marked_message(mark, "expected a single character")),
+        }
+    }
+
+    #[inline]
+    fn decode_number<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: NumberVisitor<'de, 'buf, C>,
+    {
+        if let State::Tag(tag) = self.state {
+            return visitor.visit_u64(cx, tag);
+        }
+
+        let mark = cx.mark();
+        let header = self.read_header(cx)?;
+
+        match header {
+            Header::F16 => {
+                let bits = u16::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_f32(cx, float::f16_to_f32(bits))
+            }
+            Header::F32 => {
+                let value = f32::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_f32(cx, value)
+            }
+            Header::F64 => {
+                let value = f64::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_f64(cx, value)
+            }
+            header => match self.read_integer(cx, header, mark)? {
+                Some(Integer::Unsigned(value)) => match header.info {
+                    0..=ONE_BYTE => visitor.visit_u8(cx, value as u8),
+                    TWO_BYTES => visitor.visit_u16(cx, value as u16),
+                    FOUR_BYTES => visitor.visit_u32(cx, value as u32),
+                    _ => visitor.visit_u64(cx, value),
+                },
+                Some(Integer::Negative(n)) => {
+                    let value = -1 - n as i128;
+
+                    match header.info {
+                        0..=23 => visitor.visit_i8(cx, value as i8),
+                        ONE_BYTE => visitor.visit_i16(cx, value as i16),
+                        TWO_BYTES => visitor.visit_i32(cx, value as i32),
+                        FOUR_BYTES => visitor.visit_i64(cx, value as i64),
+                        _ => visitor.visit_i128(cx, value),
+                    }
+                }
+                None => Err(cx.marked_message(
+                    mark,
+                    Expected {
+                        expected: "number",
+                        actual: header,
+                    },
+                )),
+            },
+        }
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u128<'buf, C>(self, cx: &mut C) -> Result<u128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.decode_wide(cx)? {
+            Wide::Unsigned(value) => Ok(value),
+            Wide::Negative(..) => Err(cx.marked_message(mark, "integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i128<'buf, C>(self, cx: &mut C) -> Result<i128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        let value = match self.decode_wide(cx)? {
+            Wide::Unsigned(value) => i128::try_from(value).ok(),
+            Wide::Negative(n) => i128::try_from(n).ok().map(|n| -1 - n),
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => Err(cx.marked_message(mark, "integer out of range")),
+        }
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_f32<'buf, C>(self, cx: &mut C) -> Result<f32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.decode_float(cx)? as f32)
+    }
+
+    #[inline]
+    fn decode_f64<'buf, C>(self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_float(cx)
+    }
+
+    /// Optional values are either `null`, `undefined` or the value itself.
+    #[inline]
+    fn decode_option<'buf, C>(mut self, cx: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if matches!(self.state, State::Value)
+            && matches!(
+                self.peek_header(cx)?,
+                Some(Header::NULL | Header::UNDEFINED)
+            )
+        {
+            self.reader.skip(cx.adapt(), 1)?;
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(mut self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_length(cx, Major::Array, mark)?;
+        Ok(CborSequenceDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(mut self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.decode_length(cx, Major::Array, mark)? {
+            Some(actual) if actual != len => Err(cx.marked_message(
+                mark,
+                format_args!("tuple length mismatch: len: {len}, actual: {actual}"),
+            )),
+            actual => Ok(CborSequenceDecoder::new(self.reader, actual)),
+        }
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(mut self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_length(cx, Major::Map, mark)?;
+        Ok(CborMapDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_map(cx)
+    }
+
+    /// Variants are either encoded as a map with a single entry, or as a value
+    /// with a semantic tag in which case the tag is its `u64` number.
+    #[inline]
+    fn decode_variant<'buf, C>(mut self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let header = self.read_header(cx)?;
+
+        match header.major {
+            Major::Tag => {
+                let tag = self.read_integer_argument(cx, header, mark)?;
+                Ok(CborVariantDecoder::new(self.reader, Some(tag), false))
+            }
+            Major::Map => match self.read_length(cx, header, mark)? {
+                Some(1) => Ok(CborVariantDecoder::new(self.reader, None, false)),
+                None => Ok(CborVariantDecoder::new(self.reader, None, true)),
+                Some(len) => Err(cx.marked_message(
+                    mark,
+                    format_args!("expected variant to be a map with one entry, but got {len}"),
+                )),
+            },
+            _ => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "variant",
+                    actual: header,
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_any<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: Visitor<'de, Error = Self::Error>,
+    {
+        let header = match self.state {
+            State::Value => match self.peek_header(cx)? {
+                Some(header) => header,
+                None => return visitor.visit_any(cx, self, TypeHint::Any),
+            },
+            State::Tag(tag) => return visitor.visit_u64(cx, tag),
+        };
+
+        let size = match header.info {
+            info @ 0..=23 => SizeHint::Exact(info as usize),
+            _ => SizeHint::Any,
+        };
+
+        match header.major {
+            Major::Unsigned => match header.info {
+                0..=ONE_BYTE => {
+                    let value = self.decode_u8(cx)?;
+                    visitor.visit_u8(cx, value)
+                }
+                TWO_BYTES => {
+                    let value = self.decode_u16(cx)?;
+                    visitor.visit_u16(cx, value)
+                }
+                FOUR_BYTES => {
+                    let value = self.decode_u32(cx)?;
+                    visitor.visit_u32(cx, value)
+                }
+                _ => {
+                    let value = self.decode_u64(cx)?;
+                    visitor.visit_u64(cx, value)
+                }
+            },
+            Major::Negative => match header.info {
+                0..=23 => {
+                    let value = self.decode_i8(cx)?;
+                    visitor.visit_i8(cx, value)
+                }
+                ONE_BYTE => {
+                    let value = self.decode_i16(cx)?;
+                    visitor.visit_i16(cx, value)
+                }
+                TWO_BYTES => {
+                    let value = self.decode_i32(cx)?;
+                    visitor.visit_i32(cx, value)
+                }
+                FOUR_BYTES => {
+                    let value = self.decode_i64(cx)?;
+                    visitor.visit_i64(cx, value)
+                }
+                _ => {
+                    let value = self.decode_i128(cx)?;
+                    visitor.visit_i128(cx, value)
+                }
+            },
+            Major::Bytes => {
+                let visitor = visitor.visit_bytes(cx, size)?;
+                self.decode_bytes(cx, visitor)
+            }
+            Major::String => {
+                let visitor = visitor.visit_string(cx, size)?;
+                self.decode_string(cx, visitor)
+            }
+            Major::Array => {
+                let sequence = self.decode_sequence(cx)?;
+                visitor.visit_sequence(cx, sequence)
+            }
+            Major::Map => {
+                let map = self.decode_map(cx)?;
+                visitor.visit_map(cx, map)
+            }
+            Major::Tag => {
+                let mark = cx.mark();
+                let header = self.read_header(cx)?;
+                let tag = self.read_integer_argument(cx, header, mark)?;
+
+                // Bignums are visited as the integers they represent.
+                match tag {
+                    tag::POSITIVE_BIGNUM => match self.decode_bignum(cx, tag, mark)? {
+                        Wide::Unsigned(value) => visitor.visit_u128(cx, value),
+                        Wide::Negative(..) => unreachable!(),
+                    },
+                    tag::NEGATIVE_BIGNUM => match self.decode_bignum(cx, tag, mark)? {
+                        Wide::Negative(n) if n <= i128::MAX as u128 => {
+                            visitor.visit_i128(cx, -1 - n as i128)
+                        }
+                        _ => Err(cx.marked_message(mark, "integer out of range")),
+                    },
+                    tag => {
+                        let variant = CborVariantDecoder::new(self.reader, Some(tag), false);
+                        visitor.visit_variant(cx, variant)
+                    }
+                }
+            }
+            Major::Simple => match header {
+                Header::NULL | Header::UNDEFINED => {
+                    self.decode_unit(cx)?;
+                    visitor.visit_unit(cx)
+                }
+                Header::FALSE | Header::TRUE => {
+                    let value = self.decode_bool(cx)?;
+                    visitor.visit_bool(cx, value)
+                }
+                Header::F16 | Header::F32 => {
+                    let value = self.decode_f32(cx)?;
+                    visitor.visit_f32(cx, value)
+                }
+                Header::F64 => {
+                    let value = self.decode_f64(cx)?;
+                    visitor.visit_f64(cx, value)
+                }
+                _ => visitor.visit_any(cx, self, TypeHint::Any),
+            },
+        }
+    }
+}
+
+impl<R> CborSequenceDecoder<R> {
+    #[inline]
+    fn new(reader: R, remaining: Option<usize>) -> Self {
+        Self { reader, remaining }
+    }
+}
+
+impl<'de, R> CborSequenceDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Test if there is another element, consuming the break of an array of
+    /// indefinite length once it has been reached.
+    #[inline]
+    fn has_next<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match &mut self.remaining {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => {
+                if CborDecoder::new(self.reader.borrow_mut()).read_break(cx)? {
+                    self.remaining = Some(0);
+                    return Ok(false);
+                }
+
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl<'de, R> PackDecoder<'de> for CborSequenceDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if !self.has_next(cx)? {
+            return Err(cx.message("tried to decode past the pack"));
+        }
+
+        Ok(CborDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining elements.
+        while let Some(mut item) = SequenceDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, R> SequenceDecoder<'de> for CborSequenceDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::Exact(remaining),
+            None => SizeHint::Any,
+        }
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if !self.has_next(cx)? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining elements.
+        while let Some(mut item) = SequenceDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> CborMapDecoder<R> {
+    #[inline]
+    fn new(reader: R, remaining: Option<usize>) -> Self {
+        Self { reader, remaining }
+    }
+}
+
+impl<'de, R> PairsDecoder<'de> for CborMapDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::Exact(remaining),
+            None => SizeHint::Any,
+        }
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match &mut self.remaining {
+            Some(0) => return Ok(None),
+            Some(remaining) => {
+                *remaining -= 1;
+            }
+            None => {
+                if CborDecoder::new(self.reader.borrow_mut()).read_break(cx)? {
+                    self.remaining = Some(0);
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(Some(CborDecoder::new(self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining entries, both key and value.
+        while let Some(mut item) = PairsDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, R> PairDecoder<'de> for CborDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type First<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+    type Second = Self;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, _: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.skip_any(cx)?;
+        Ok(true)
+    }
+}
+
+impl<R> CborVariantDecoder<R> {
+    #[inline]
+    fn new(reader: R, tag: Option<u64>, indefinite: bool) -> Self {
+        Self {
+            reader,
+            tag,
+            indefinite,
+        }
+    }
+}
+
+impl<'de, R> VariantDecoder<'de> for CborVariantDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Tag<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = CborDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let state = match self.tag {
+            Some(tag) => State::Tag(tag),
+            None => State::Value,
+        };
+
+        Ok(CborDecoder::with_state(self.reader.borrow_mut(), state))
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.variant(cx)?.skip_any(cx)?;
+        Ok(true)
+    }
+
+    /// A variant encoded as a map of indefinite length must be terminated
+    /// after its single entry.
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.indefinite && !CborDecoder::new(self.reader.borrow_mut()).read_break(cx)? {
+            return Err(cx.message("expected variant to be a map with one entry"));
+        }
+
+        Ok(())
+    }
+}
+
+struct Expected {
+    expected: &'static str,
+    actual: Header,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { expected, actual } = *self;
+
+        write!(
+            f,
+            "Expected {expected} but was {} ({:#04x})",
+            actual.major.name(),
+            actual.byte()
+        )
+    }
+}
@@ -0,0 +1,932 @@
+use core::fmt;
+
+use musli::en::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
+use musli::Context;
+use musli_common::writer::Writer;
+
+use crate::error::Error;
+use crate::float;
+use crate::header::{Header, Major, EIGHT_BYTES, FOUR_BYTES, INDEFINITE, ONE_BYTE, TWO_BYTES};
+use crate::tag;
+
+/// A CBOR encoder.
+pub struct CborEncoder<W> {
+    writer: W,
+}
+
+impl<W> CborEncoder<W> {
+    /// Construct a new CBOR encoder.
+    #[inline]
+    pub(crate) fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+/// Encoder for a pack, which is encoded as an array of indefinite length so
+/// that it doesn't have to be buffered.
+pub struct CborPackEncoder<W> {
+    writer: W,
+}
+
+/// Encoder for a variant.
+///
+/// Variants are encoded as a map with a single entry, unless the tag of the
+/// variant is an `u64` in which case it is encoded as a semantic tag.
+pub struct CborVariantEncoder<W> {
+    writer: W,
+}
+
+/// Encoder for the tag of a variant.
+pub struct CborTagEncoder<W> {
+    writer: W,
+}
+
+impl<W> CborTagEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    /// Write the header of the single entry map which wraps a variant, and
+    /// return an encoder for its key.
+    #[inline]
+    fn entry<'buf, C>(mut self, cx: &mut C) -> Result<CborEncoder<W>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        encode_argument(cx, self.writer.borrow_mut(), Major::Map, 1)?;
+        Ok(CborEncoder::new(self.writer))
+    }
+}
+
+#[musli::encoder]
+impl<W> Encoder for CborEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type Pack = CborPackEncoder<W>;
+    type Some = Self;
+    type Sequence = Self;
+    type Tuple = Self;
+    type Map = Self;
+    type Struct = Self;
+    type Variant = CborVariantEncoder<W>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR encoder")
+    }
+
+    #[inline]
+    fn encode_unit<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), Header::NULL.byte())
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(mut self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer
+            .write_byte(cx.adapt(), Header::new(Major::Array, INDEFINITE).byte())?;
+
+        Ok(CborPackEncoder {
+            writer: self.writer,
+        })
+    }
+
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_bytes(cx, array.as_slice())
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(mut self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(
+            cx,
+            self.writer.borrow_mut(),
+            Major::Bytes,
+            bytes.len() as u64,
+        )?;
+        self.writer.write_bytes(cx.adapt(), bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        mut self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let len = vectors.iter().map(|v| v.len()).sum::<usize>();
+        encode_argument(cx, self.writer.borrow_mut(), Major::Bytes, len as u64)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(cx.adapt(), bytes)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(mut self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(
+            cx,
+            self.writer.borrow_mut(),
+            Major::String,
+            string.len() as u64,
+        )?;
+        self.writer.write_bytes(cx.adapt(), string.as_bytes())
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(mut self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let header = if value { Header::TRUE } else { Header::FALSE };
+        self.writer.write_byte(cx.adapt(), header.byte())
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut buf = [0; 4];
+        self.encode_string(cx, value.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(mut self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(cx, self.writer.borrow_mut(), Major::Unsigned, value)
+    }
+
+    /// Encode a 128-bit unsigned integer as a regular integer if it fits,
+    /// otherwise as a positive bignum.
+    #[inline]
+    fn encode_u128<'buf, C>(mut self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match u64::try_from(value) {
+            Ok(value) => self.encode_u64(cx, value),
+            Err(..) => encode_bignum(cx, self.writer.borrow_mut(), tag::POSITIVE_BIGNUM, value),
+        }
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(mut self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if value < 0 {
+            encode_argument(cx, self.writer.borrow_mut(), Major::Negative, !value as u64)
+        } else {
+            encode_argument(cx, self.writer.borrow_mut(), Major::Unsigned, value as u64)
+        }
+    }
+
+    /// Encode a 128-bit signed integer as a regular integer if it fits,
+    /// otherwise as a bignum.
+    #[inline]
+    fn encode_i128<'buf, C>(mut self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if value >= 0 {
+            return self.encode_u128(cx, value as u128);
+        }
+
+        // Negative integers are encoded as `-1 - n`, which gives them a
+        // larger range than `i64`.
+        let n = !value as u128;
+
+        match u64::try_from(n) {
+            Ok(n) => encode_argument(cx, self.writer.borrow_mut(), Major::Negative, n),
+            Err(..) => encode_bignum(cx, self.writer.borrow_mut(), tag::NEGATIVE_BIGNUM, n),
+        }
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    /// Floats are encoded using the shortest representation which preserves
+    /// their value.
+    #[inline]
+    fn encode_f32<'buf, C>(mut self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let half = if value.is_nan() {
+            Some(float::NAN)
+        } else {
+            float::f32_to_f16(value)
+        };
+
+        match half {
+            Some(half) => write_headed(
+                cx,
+                self.writer.borrow_mut(),
+                Header::F16,
+                half.to_be_bytes(),
+            ),
+            None => write_headed(
+                cx,
+                self.writer.borrow_mut(),
+                Header::F32,
+                value.to_be_bytes(),
+            ),
+        }
+    }
+
+    /// Floats are encoded using the shortest representation which preserves
+    /// their value.
+    #[inline]
+    fn encode_f64<'buf, C>(mut self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let single = value as f32;
+
+        if value.is_nan() || single as f64 == value {
+            return self.encode_f32(cx, single);
+        }
+
+        write_headed(
+            cx,
+            self.writer.borrow_mut(),
+            Header::F64,
+            value.to_be_bytes(),
+        )
+    }
+
+    /// Optional values are encoded as is, since they can be distinguished
+    /// from the `null` value used for [`encode_none`][Encoder::encode_none].
+    #[inline]
+    fn encode_some<'buf, C>(self, _: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_unit(cx)
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(
+        mut self,
+        cx: &mut C,
+        len: usize,
+    ) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(cx, self.writer.borrow_mut(), Major::Array, len as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_sequence(cx, len)
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(mut self, cx: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(cx, self.writer.borrow_mut(), Major::Map, len as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_map(cx, len)
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, _: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborVariantEncoder {
+            writer: self.writer,
+        })
+    }
+}
+
+#[musli::encoder]
+impl<W> Encoder for CborTagEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type Pack = CborPackEncoder<W>;
+    type Some = CborEncoder<W>;
+    type Sequence = CborEncoder<W>;
+    type Tuple = CborEncoder<W>;
+    type Map = CborEncoder<W>;
+    type Struct = CborEncoder<W>;
+    type Variant = CborVariantEncoder<W>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant tag supported by the CBOR encoder")
+    }
+
+    #[inline]
+    fn encode_unit<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_unit(cx)
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_pack(cx)
+    }
+
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_array(cx, array)
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bytes(cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bytes_vectored(cx, vectors)
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_string(cx, string)
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bool(cx, value)
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_char(cx, value)
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u8(cx, value)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u16(cx, value)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u32(cx, value)
+    }
+
+    /// An `u64` tag marks the variant as a semantic tag with the given number,
+    /// which is followed by the value of the variant.
+    #[inline]
+    fn encode_u64<'buf, C>(mut self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_argument(cx, self.writer.borrow_mut(), Major::Tag, value)
+    }
+
+    #[inline]
+    fn encode_u128<'buf, C>(self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u128(cx, value)
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_usize(cx, value)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i8(cx, value)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i16(cx, value)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i32(cx, value)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i64(cx, value)
+    }
+
+    #[inline]
+    fn encode_i128<'buf, C>(self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i128(cx, value)
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_isize(cx, value)
+    }
+
+    #[inline]
+    fn encode_f32<'buf, C>(self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_f32(cx, value)
+    }
+
+    #[inline]
+    fn encode_f64<'buf, C>(self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_f64(cx, value)
+    }
+
+    #[inline]
+    fn encode_some<'buf, C>(self, cx: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_some(cx)
+    }
+
+    #[inline]
+    fn encode_none<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_none(cx)
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_sequence(cx, len)
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_tuple(cx, len)
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_map(cx, len)
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_struct(cx, len)
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_variant(cx)
+    }
+}
+
+impl<W> SequenceEncoder for CborPackEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), Header::BREAK.byte())
+    }
+}
+
+impl<W> SequenceEncoder for CborEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W> PairsEncoder for CborEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W> PairEncoder for CborEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type First<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+    type Second<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W> VariantEncoder for CborVariantEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Tag<'this>
+        = CborTagEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = CborEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborTagEncoder {
+            writer: self.writer.borrow_mut(),
+        })
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(CborEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+/// Write a header followed by the given big-endian data.
+#[inline]
+fn write_headed<'buf, C, W, const N: usize>(
+    cx: &mut C,
+    mut writer: W,
+    header: Header,
+    data: [u8; N],
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    writer.write_byte(cx.adapt(), header.byte())?;
+    writer.write_array(cx.adapt(), data)
+}
+
+/// Encode the header of an item with the given argument, using the shortest
+/// representation which can hold it.
+#[inline]
+pub(crate) fn encode_argument<'buf, C, W>(
+    cx: &mut C,
+    mut writer: W,
+    major: Major,
+    value: u64,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    if value < ONE_BYTE as u64 {
+        writer.write_byte(cx.adapt(), Header::new(major, value as u8).byte())
+    } else if let Ok(value) = u8::try_from(value) {
+        write_headed(cx, writer, Header::new(major, ONE_BYTE), [value])
+    } else if let Ok(value) = u16::try_from(value) {
+        write_headed(
+            cx,
+            writer,
+            Header::new(major, TWO_BYTES),
+            value.to_be_bytes(),
+        )
+    } else if let Ok(value) = u32::try_from(value) {
+        write_headed(
+            cx,
+            writer,
+            Header::new(major, FOUR_BYTES),
+            value.to_be_bytes(),
+        )
+    } else {
+        write_headed(
+            cx,
+            writer,
+            Header::new(major, EIGHT_BYTES),
+            value.to_be_bytes(),
+        )
+    }
+}
+
+/// Encode a bignum with the given tag, which is a byte string holding the
+/// big-endian value without leading zeros.
+#[inline]
+fn encode_bignum<'buf, C, W>(
+    cx: &mut C,
+    mut writer: W,
+    tag: u64,
+    value: u128,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    let bytes = value.to_be_bytes();
+    let start = (value.leading_zeros() / 8) as usize;
+    let bytes = &bytes[start..];
+
+    encode_argument(cx, writer.borrow_mut(), Major::Tag, tag)?;
+    encode_argument(cx, writer.borrow_mut(), Major::Bytes, bytes.len() as u64)?;
+    writer.write_bytes(cx.adapt(), bytes)
+}
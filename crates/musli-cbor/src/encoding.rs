@@ -0,0 +1,262 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [DEFAULT] encoding configuration.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker;
+#[cfg(feature = "std")]
+use std::io;
+
+use musli::de::Decode;
+use musli::en::Encode;
+use musli::mode::{DefaultMode, Mode};
+use musli::Context;
+
+use crate::de::CborDecoder;
+use crate::en::CborEncoder;
+use crate::error::Error;
+use crate::fixed_bytes::FixedBytes;
+use crate::reader::{Reader, SliceReader};
+use crate::writer::Writer;
+
+/// The default configuration.
+///
+/// Numbers and floats are encoded using the shortest CBOR representation which
+/// can hold them exactly, collections and strings are encoded with a definite
+/// length using the shortest prefix for it.
+///
+/// Maps are encoded in the order in which their entries are encoded. To encode
+/// them in deterministic order use [Encoding::with_canonical].
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Encode the given value to the given [Writer] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn encode<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Writer,
+    Error: From<W::Error>,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.encode(writer, value)
+}
+
+/// Encode the given value to the given [Write][io::Write] using the [DEFAULT]
+/// configuration.
+#[cfg(feature = "std")]
+#[inline]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_writer(writer, value)
+}
+
+/// Encode the given value to a [Vec] using the [DEFAULT] configuration.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_vec(value)
+}
+
+/// Encode the given value to a fixed-size bytes using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn to_fixed_bytes<const N: usize, T>(value: &T) -> Result<FixedBytes<N>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_fixed_bytes::<N, _>(value)
+}
+
+/// Decode the given type `T` from the given [Reader] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn decode<'de, R, T>(reader: R) -> Result<T, Error>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.decode(reader)
+}
+
+/// Decode the given type `T` from the given slice using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.from_slice(bytes)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = DefaultMode> {
+    canonical: bool,
+    _marker: marker::PhantomData<M>,
+}
+
+impl Encoding<DefaultMode> {
+    /// Construct a new [`Encoding`] instance.
+    ///
+    /// ```rust
+    /// use musli_cbor::{Encoding};
+    /// use musli::{Encode, Decode};
+    /// use musli::mode::DefaultMode;
+    ///
+    /// const CONFIG: Encoding<DefaultMode> = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Struct<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Struct {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok(()) }
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            canonical: false,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: Mode,
+{
+    /// Change the mode of the encoding.
+    pub const fn with_mode<T>(self) -> Encoding<T>
+    where
+        T: Mode,
+    {
+        Encoding {
+            canonical: self.canonical,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Encode values using the deterministic encoding described in [section
+    /// 4.2 of RFC 8949].
+    ///
+    /// In addition to the shortest forms which are always used, the entries of
+    /// maps are sorted by the bytewise lexicographic order of their encoded
+    /// keys and packs are encoded as arrays of definite length. Encoding a map
+    /// with duplicate keys results in an error.
+    ///
+    /// Since this requires the encoded value to be buffered before it is
+    /// written, it is only available with the `alloc` feature.
+    ///
+    /// [section 4.2 of RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html#name-deterministically-encoded-c
+    #[cfg(feature = "alloc")]
+    pub const fn with_canonical(self) -> Self {
+        Encoding {
+            canonical: true,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Encode the given value to the given [`Writer`] using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::encode`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn encode_with<'buf, C, W, T>(
+        self,
+        cx: &mut C,
+        writer: W,
+        value: &T,
+    ) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        W: Writer,
+        Error: From<W::Error>,
+        T: ?Sized + Encode<M>,
+    {
+        #[cfg(feature = "alloc")]
+        if self.canonical {
+            let mut buf = Vec::new();
+            T::encode(value, cx, CborEncoder::new(&mut buf))?;
+            return crate::canonical::canonicalize(cx, &buf, writer);
+        }
+
+        T::encode(value, cx, CborEncoder::new(writer))
+    }
+
+    /// Decode the given type `T` from the given [Reader] using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::decode`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn decode_with<'de, 'buf, C, R, T>(self, cx: &mut C, reader: R) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        R: Reader<'de>,
+        Error: From<R::Error>,
+        T: Decode<'de, M>,
+    {
+        T::decode(cx, CborDecoder::new(reader))
+    }
+
+    /// Decode the given type `T` from the given [Reader] using the current
+    /// configuration.
+    #[inline]
+    pub fn decode<'de, R, T>(self, reader: R) -> Result<T, Error>
+    where
+        R: Reader<'de>,
+        Error: From<R::Error>,
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        self.decode_with(&mut cx, reader)
+    }
+
+    musli_common::encode_with_extensions!();
+
+    /// Decode the given type `T` from the given slice using the current
+    /// configuration.
+    #[inline]
+    pub fn from_slice<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        let mut reader = SliceReader::new(bytes);
+        T::decode(&mut cx, CborDecoder::<_>::new(&mut reader))
+    }
+}
+
+impl<M> Clone for Encoding<M>
+where
+    M: Mode,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            canonical: self.canonical,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Copy for Encoding<M> where M: Mode {}
@@ -0,0 +1,72 @@
+//! Conversions to and from half-precision floats, which are used to encode
+//! floats in their shortest form.
+
+/// The canonical encoding of NaN as a half-precision float.
+pub(crate) const NAN: u16 = 0x7e00;
+
+/// Convert the bits of a half-precision float into a single-precision float.
+///
+/// Every half-precision float can be represented exactly.
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    match exponent {
+        // Zero and subnormal numbers, which are `mantissa * 2^-24`.
+        0 => {
+            let value = mantissa as f32 * f32::from_bits(0x3380_0000);
+            f32::from_bits(sign | value.to_bits())
+        }
+        // Infinity and NaN.
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | mantissa << 13),
+        exponent => {
+            let exponent = exponent as u32 + 127 - 15;
+            f32::from_bits(sign | exponent << 23 | mantissa << 13)
+        }
+    }
+}
+
+/// Convert a single-precision float into the bits of a half-precision float,
+/// if it can be represented exactly.
+///
+/// NaN is not converted, since its payload cannot be preserved in general.
+pub(crate) fn f32_to_f16(value: f32) -> Option<u16> {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        return (mantissa == 0).then_some(sign | 0x7c00);
+    }
+
+    if exponent == 0 {
+        // Single-precision subnormals are too small to be represented, so
+        // only zero is exact.
+        return (mantissa == 0).then_some(sign);
+    }
+
+    let exponent = exponent - 127;
+
+    match exponent {
+        -14..=15 => {
+            if mantissa & 0x1fff != 0 {
+                return None;
+            }
+
+            Some(sign | ((exponent + 15) as u16) << 10 | (mantissa >> 13) as u16)
+        }
+        -24..=-15 => {
+            let mantissa = mantissa | 0x80_0000;
+            let shift = -(exponent + 1) as u32;
+
+            if mantissa & ((1 << shift) - 1) != 0 {
+                return None;
+            }
+
+            Some(sign | (mantissa >> shift) as u16)
+        }
+        _ => None,
+    }
+}
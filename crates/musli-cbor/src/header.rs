@@ -0,0 +1,156 @@
+//! Type flags available for CBOR.
+//!
+//! Every CBOR data item starts with an initial byte, which is represented by
+//! [`Header`]. The three high bits of it is the [`Major`] type of the item, and
+//! the five low bits are additional information which either embeds a small
+//! argument or describes how the argument follows. See [RFC 8949] for details.
+//!
+//! [RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html
+
+/// Additional information indicating that the argument is stored in the
+/// following byte.
+pub const ONE_BYTE: u8 = 24;
+/// Additional information indicating that the argument is stored in the
+/// following two big-endian bytes.
+pub const TWO_BYTES: u8 = 25;
+/// Additional information indicating that the argument is stored in the
+/// following four big-endian bytes.
+pub const FOUR_BYTES: u8 = 26;
+/// Additional information indicating that the argument is stored in the
+/// following eight big-endian bytes.
+pub const EIGHT_BYTES: u8 = 27;
+/// Additional information indicating that the item has an indefinite length,
+/// or that it is the break which terminates such an item.
+pub const INDEFINITE: u8 = 31;
+
+/// The major type of a data item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Major {
+    /// An unsigned integer.
+    Unsigned,
+    /// A negative integer, where an argument of `n` represents the value
+    /// `-1 - n`.
+    Negative,
+    /// A byte string.
+    Bytes,
+    /// A UTF-8 text string.
+    String,
+    /// An array of data items.
+    Array,
+    /// A map of pairs of data items.
+    Map,
+    /// A data item with a semantic tag.
+    Tag,
+    /// A simple value or a floating point number.
+    Simple,
+}
+
+impl Major {
+    /// Construct a major type from the three bits which represent it.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b111 {
+            0 => Self::Unsigned,
+            1 => Self::Negative,
+            2 => Self::Bytes,
+            3 => Self::String,
+            4 => Self::Array,
+            5 => Self::Map,
+            6 => Self::Tag,
+            _ => Self::Simple,
+        }
+    }
+
+    /// Coerce the major type into the three bits which represent it.
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        match self {
+            Self::Unsigned => 0,
+            Self::Negative => 1,
+            Self::Bytes => 2,
+            Self::String => 3,
+            Self::Array => 4,
+            Self::Map => 5,
+            Self::Tag => 6,
+            Self::Simple => 7,
+        }
+    }
+
+    /// A human readable name of the major type.
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Unsigned => "unsigned integer",
+            Self::Negative => "negative integer",
+            Self::Bytes => "byte string",
+            Self::String => "text string",
+            Self::Array => "array",
+            Self::Map => "map",
+            Self::Tag => "tag",
+            Self::Simple => "simple value",
+        }
+    }
+}
+
+/// The initial byte of a data item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// The major type of the item.
+    pub major: Major,
+    /// The additional information of the item.
+    pub info: u8,
+}
+
+impl Header {
+    /// The `false` value.
+    pub const FALSE: Self = Self::new(Major::Simple, 20);
+    /// The `true` value.
+    pub const TRUE: Self = Self::new(Major::Simple, 21);
+    /// The `null` value.
+    pub const NULL: Self = Self::new(Major::Simple, 22);
+    /// The `undefined` value.
+    pub const UNDEFINED: Self = Self::new(Major::Simple, 23);
+    /// A half-precision float.
+    pub const F16: Self = Self::new(Major::Simple, TWO_BYTES);
+    /// A single-precision float.
+    pub const F32: Self = Self::new(Major::Simple, FOUR_BYTES);
+    /// A double-precision float.
+    pub const F64: Self = Self::new(Major::Simple, EIGHT_BYTES);
+    /// The break which terminates an item of indefinite length.
+    pub const BREAK: Self = Self::new(Major::Simple, INDEFINITE);
+
+    /// Construct a new header.
+    ///
+    /// The additional information is truncated to five bits.
+    #[inline]
+    pub const fn new(major: Major, info: u8) -> Self {
+        Self {
+            major,
+            info: info & 0b11111,
+        }
+    }
+
+    /// Construct a header from its byte representation.
+    #[inline]
+    pub const fn from_byte(b: u8) -> Self {
+        Self::new(Major::from_bits(b >> 5), b)
+    }
+
+    /// Coerce the header into its byte representation.
+    #[inline]
+    pub const fn byte(self) -> u8 {
+        self.major.bits() << 5 | self.info
+    }
+
+    /// The number of bytes the argument of the item occupies after the initial
+    /// byte.
+    #[inline]
+    pub(crate) const fn argument_len(self) -> usize {
+        match self.info {
+            ONE_BYTE => 1,
+            TWO_BYTES => 2,
+            FOUR_BYTES => 4,
+            EIGHT_BYTES => 8,
+            _ => 0,
+        }
+    }
+}
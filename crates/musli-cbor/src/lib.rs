@@ -0,0 +1,146 @@
+//! [<img alt="github" src="https://img.shields.io/badge/github-udoprog/musli-8da0cb?style=for-the-badge&logo=github" height="20">](https://github.com/udoprog/musli)
+//! [<img alt="crates.io" src="https://img.shields.io/crates/v/musli-cbor.svg?style=for-the-badge&color=fc8d62&logo=rust" height="20">](https://crates.io/crates/musli-cbor)
+//! [<img alt="docs.rs" src="https://img.shields.io/badge/docs.rs-musli--cbor-66c2a5?style=for-the-badge&logoColor=white&logo=data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHhtbG5zPSJodHRwOi8vd3d3LnczLm9yZy8yMDAwL3N2ZyIgdmlld0JveD0iMCAwIDUxMiA1MTIiPjxwYXRoIGZpbGw9IiNmNWY1ZjUiIGQ9Ik00ODguNiAyNTAuMkwzOTIgMjE0VjEwNS41YzAtMTUtOS4zLTI4LjQtMjMuNC0zMy43bC0xMDAtMzcuNWMtOC4xLTMuMS0xNy4xLTMuMS0yNS4zIDBsLTEwMCAzNy41Yy0xNC4xIDUuMy0yMy40IDE4LjctMjMuNCAzMy43VjIxNGwtOTYuNiAzNi4yQzkuMyAyNTUuNSAwIDI2OC45IDAgMjgzLjlWMzk0YzAgMTMuNiA3LjcgMjYuMSAxOS45IDMyLjJsMTAwIDUwYzEwLjEgNS4xIDIyLjEgNS4xIDMyLjIgMGwxMDMuOS01MiAxMDMuOSA1MmMxMC4xIDUuMSAyMi4xIDUuMSAzMi4yIDBsMTAwLTUwYzEyLjItNi4xIDE5LjktMTguNiAxOS45LTMyLjJWMjgzLjljMC0xNS05LjMtMjguNC0yMy40LTMzLjd6TTM1OCAyMTQuOGwtODUgMzEuOXYtNjguMmw4NS0zN3Y3My4zek0xNTQgMTA0LjFsMTAyLTM4LjIgMTAyIDM4LjJ2LjZsLTEwMiA0MS40LTEwMi00MS40di0uNnptODQgMjkxLjFsLTg1IDQyLjV2LTc5LjFsODUtMzguOHY3NS40em0wLTExMmwtMTAyIDQxLjQtMTAyLTQxLjR2LS42bDEwMi0zOC4yIDEwMiAzOC4ydi42em0yNDAgMTEybC04NSA0Mi41di03OS4xbDg1LTM4Ljh2NzUuNHptMC0xMTJsLTEwMiA0MS40LTEwMi00MS40di0uNmwxMDItMzguMiAxMDIgMzguMnYuNnoiPjwvcGF0aD48L3N2Zz4K" height="20">](https://docs.rs/musli-cbor)
+//!
+//! A [CBOR] format for [Müsli].
+//!
+//! This allows Müsli types to be exchanged with any other implementation of
+//! the Concise Binary Object Representation described in [RFC 8949]. CBOR is
+//! self-descriptive, so it is upgrade stable in the same way as
+//! [`musli-descriptive`]:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//!
+//! It can also be fully converted back and from to the [Value] type.
+//!
+//! ```rust
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli_cbor::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(62),
+//! })?;
+//!
+//! let version1: Version1 = musli_cbor::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli_cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Configuring
+//!
+//! To configure the behavior of the CBOR format you can use the [Encoding]
+//! type. Here the [deterministic encoding] is enabled, which among other
+//! things sorts the entries of maps by their keys:
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! use musli_cbor::Encoding;
+//! use musli::mode::DefaultMode;
+//!
+//! const CONFIG: Encoding<DefaultMode> = Encoding::new().with_canonical();
+//!
+//! let mut map = HashMap::new();
+//! map.insert(String::from("b"), 2u32);
+//! map.insert(String::from("a"), 1u32);
+//!
+//! let out = CONFIG.to_vec(&map)?;
+//! assert_eq!(out, [0xa2, 0x61, b'a', 0x01, 0x61, b'b', 0x02]);
+//!
+//! let actual: HashMap<String, u32> = CONFIG.from_slice(&out)?;
+//! assert_eq!(map, actual);
+//! # Ok::<_, musli_cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Data model
+//!
+//! Müsli types are mapped to CBOR like this:
+//!
+//! * Numbers use the shortest CBOR representation which can hold them. 128-bit
+//!   numbers which do not fit in 64 bits are encoded as bignums.
+//! * Floats use the shortest representation which holds them exactly, half
+//!   precision floats included.
+//! * `char` is encoded as a text string.
+//! * `()` and `None` are encoded as `null`, while `Some(value)` is encoded as
+//!   `value`. `undefined` is decoded as `None`.
+//! * Structs and maps are encoded as maps. Use `#[musli(default_field_name =
+//!   "name")]` to use field names as keys, which is what most other
+//!   implementations expect.
+//! * Packed types are encoded as arrays of indefinite length.
+//! * Variants are encoded as a map with a single entry, mapping the tag of the
+//!   variant to its value.
+//! * Variants with an `u64` tag are encoded as a semantic tag, see
+//!   [`tag::Tagged`]. Semantic tags other than bignums are decoded as such
+//!   variants.
+//!
+//! Both definite and indefinite lengths are supported when decoding.
+//!
+//! This is the same model as the one used by [`serde_cbor`].
+//!
+//! [`musli-descriptive`]: https://docs.rs/musli-descriptive
+//! [`serde_cbor`]: https://docs.rs/serde_cbor
+//! [CBOR]: https://cbor.io
+//! [RFC 8949]: https://www.rfc-editor.org/rfc/rfc8949.html
+//! [deterministic encoding]: https://www.rfc-editor.org/rfc/rfc8949.html#name-deterministically-encoded-c
+//! [Müsli]: https://docs.rs/musli
+//! [Encoding]: https://docs.rs/musli-cbor/latest/musli_cbor/encoding/struct.Encoding.html
+//! [Value]: https://docs.rs/musli-value
+
+#![deny(missing_docs)]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod canonical;
+mod de;
+mod en;
+pub mod encoding;
+mod error;
+mod float;
+pub mod header;
+pub mod tag;
+#[cfg(feature = "test")]
+#[macro_use]
+pub mod test;
+
+/// Convenient result alias for use with `musli_cbor`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+pub use self::encoding::to_writer;
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding};
+pub use self::error::Error;
+#[cfg(feature = "test")]
+pub use self::test::transcode;
+#[doc(inline)]
+pub use musli_common::*;
@@ -0,0 +1,92 @@
+//! Support for CBOR [semantic tags].
+//!
+//! [semantic tags]: https://www.rfc-editor.org/rfc/rfc8949.html#name-tagging-of-items
+
+use musli::de::{Decode, Decoder, VariantDecoder};
+use musli::en::{Encode, Encoder, VariantEncoder};
+use musli::mode::Mode;
+use musli::Context;
+
+/// Tag for a date and time encoded as a text string.
+pub const DATE_TIME: u64 = 0;
+/// Tag for a date and time encoded as seconds since the epoch.
+pub const EPOCH_DATE_TIME: u64 = 1;
+/// Tag for an unsigned bignum, which is encoded as a byte string.
+pub const POSITIVE_BIGNUM: u64 = 2;
+/// Tag for a negative bignum, which is encoded as a byte string holding `n`
+/// where the value is `-1 - n`.
+pub const NEGATIVE_BIGNUM: u64 = 3;
+/// Tag which marks the data which follows as CBOR.
+pub const SELF_DESCRIBED: u64 = 55799;
+
+/// A value with a semantic tag.
+///
+/// This is encoded as a variant whose tag is the `u64` number of the semantic
+/// tag, and whose value is the tagged value. The CBOR encoder represents any
+/// variant with this shape as a semantic tag, while other formats encode it as
+/// a regular variant.
+///
+/// ```rust
+/// use musli_cbor::tag::{Tagged, EPOCH_DATE_TIME};
+///
+/// let time = Tagged::new(EPOCH_DATE_TIME, 1363896240u32);
+/// let bytes = musli_cbor::to_vec(&time)?;
+/// assert_eq!(bytes, [0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0]);
+///
+/// let decoded: Tagged<u32> = musli_cbor::from_slice(&bytes)?;
+/// assert_eq!(decoded, time);
+/// # Ok::<_, musli_cbor::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tagged<T> {
+    /// The number of the semantic tag.
+    pub tag: u64,
+    /// The tagged value.
+    pub value: T,
+}
+
+impl<T> Tagged<T> {
+    /// Construct a new tagged value.
+    pub const fn new(tag: u64, value: T) -> Self {
+        Self { tag, value }
+    }
+}
+
+impl<M, T> Encode<M> for Tagged<T>
+where
+    M: Mode,
+    T: Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let mut variant = encoder.encode_variant(cx)?;
+        variant.tag(cx)?.encode_u64(cx, self.tag)?;
+        let encoder = variant.variant(cx)?;
+        self.value.encode(cx, encoder)?;
+        variant.end(cx)
+    }
+}
+
+impl<'de, M, T> Decode<'de, M> for Tagged<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let mut variant = decoder.decode_variant(cx)?;
+        let tag = variant.tag(cx)?.decode_u64(cx)?;
+        let decoder = variant.variant(cx)?;
+        let value = T::decode(cx, decoder)?;
+        variant.end(cx)?;
+        Ok(Self { tag, value })
+    }
+}
@@ -24,6 +24,15 @@ impl<'a, S, E> RichError<'a, S, E> {
             error,
         }
     }
+
+    /// The range of the input that the error is marked with.
+    ///
+    /// What the range refers to depends on the format, but it is usually a
+    /// byte offset into the input being decoded. The range is empty if the
+    /// error was reported without a mark.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
 }
 
 impl<'buf, S, E> fmt::Display for RichError<'buf, S, E>
@@ -3,13 +3,19 @@ use core::fmt;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
+use musli::de::AsDecoder;
 use musli::de::{
     Decoder, NumberHint, NumberVisitor, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder,
     SizeHint, TypeHint, ValueVisitor, VariantDecoder, Visitor,
 };
+#[cfg(feature = "alloc")]
+use musli::mode::Mode;
 use musli::Context;
 use musli_common::int::{continuation as c, UsizeEncoding, Variable};
 use musli_common::reader::Reader;
+#[cfg(feature = "alloc")]
+use musli_common::reader::SliceReader;
 use musli_storage::de::StorageDecoder;
 
 use crate::error::Error;
@@ -69,17 +75,22 @@ where
 
         match tag.kind() {
             Kind::Number => {
-                if tag.data().is_none() {
-                    let _ = c::decode::<_, _, u128>(cx.adapt(), self.reader.borrow_mut())?;
-                }
+                let _ = c::decode::<_, _, u128>(cx.adapt(), self.reader.borrow_mut())?;
             }
-            Kind::Mark => {
-                if let Mark::Variant = tag.mark() {
+            Kind::Mark => match tag.mark() {
+                Mark::Variant => {
                     self.skip_any(cx)?;
                     self.skip_any(cx)?;
                 }
-            }
-            Kind::Bytes => {
+                Mark::Some => {
+                    self.skip_any(cx)?;
+                }
+                Mark::Char => {
+                    let _ = c::decode::<_, _, u32>(cx.adapt(), self.reader.borrow_mut())?;
+                }
+                _ => {}
+            },
+            Kind::Bytes | Kind::String => {
                 let len = if let Some(len) = tag.data() {
                     len as usize
                 } else {
@@ -175,6 +186,84 @@ where
     }
 }
 
+/// A buffered value, which holds a copy of the bytes the value was decoded
+/// from.
+#[cfg(feature = "alloc")]
+pub struct SelfBuffer {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl AsDecoder for SelfBuffer {
+    type Error = Error;
+    type Decoder<'this> = SelfDecoder<SliceReader<'this>> where Self: 'this;
+
+    #[inline]
+    fn as_decoder<'buf, C>(&self, _: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(SelfDecoder::new(SliceReader::new(&self.bytes)))
+    }
+}
+
+/// A reader which keeps a copy of all the bytes read through it.
+#[cfg(feature = "alloc")]
+struct RecordingReader<R> {
+    reader: R,
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, R> Reader<'de> for RecordingReader<R>
+where
+    R: Reader<'de>,
+{
+    type Error = R::Error;
+    type Mut<'this> = &'this mut Self where Self: 'this;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn skip<'buf, C>(&mut self, cx: &mut C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx, &mut self.bytes[start..])
+    }
+
+    #[inline]
+    fn peek<'buf, C>(&mut self, cx: &mut C) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.reader.peek(cx)
+    }
+
+    #[inline]
+    fn read_bytes<'buf, C, V>(
+        &mut self,
+        cx: &mut C,
+        n: usize,
+        visitor: V,
+    ) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf>,
+        C::Input: From<Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx.adapt(), &mut self.bytes[start..])?;
+        visitor.visit_ref(cx, &self.bytes[start..])
+    }
+}
+
 /// A length-prefixed decode wrapper.
 ///
 /// This simplifies implementing decoders that do not have any special handling
@@ -192,6 +281,8 @@ where
     Error: From<R::Error>,
 {
     type Error = Error;
+    #[cfg(feature = "alloc")]
+    type Buffer = SelfBuffer;
     type Pack = SelfPackDecoder<R>;
     type Some = Self;
     type Sequence = RemainingSelfDecoder<R>;
@@ -271,6 +362,25 @@ where
         }
     }
 
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, cx: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut decoder = SelfDecoder::new(RecordingReader {
+            reader: self.reader,
+            bytes: Vec::new(),
+        });
+
+        decoder.skip_any(cx)?;
+
+        Ok(SelfBuffer {
+            bytes: decoder.reader.bytes,
+        })
+    }
+
     #[inline]
     fn decode_unit<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
     where
@@ -13,7 +13,7 @@ use std::io;
 
 use musli::de::Decode;
 use musli::en::Encode;
-use musli::mode::{DefaultMode, Mode};
+use musli::mode::{Mode, TextMode};
 use musli::Context;
 
 use crate::de::JsonDecoder;
@@ -34,7 +34,7 @@ pub fn encode<W, T>(writer: W, value: &T) -> Result<(), Error>
 where
     W: Writer,
     Error: From<W::Error>,
-    T: ?Sized + Encode<DefaultMode>,
+    T: ?Sized + Encode<TextMode>,
 {
     DEFAULT.encode(writer, value)
 }
@@ -47,7 +47,7 @@ pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
 where
     W: io::Write,
     Error: From<io::Error>,
-    T: ?Sized + Encode<DefaultMode>,
+    T: ?Sized + Encode<TextMode>,
 {
     DEFAULT.to_writer(writer, value)
 }
@@ -57,7 +57,7 @@ where
 #[inline]
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
 where
-    T: ?Sized + Encode<DefaultMode>,
+    T: ?Sized + Encode<TextMode>,
 {
     DEFAULT.to_vec(value)
 }
@@ -67,7 +67,7 @@ where
 #[inline]
 pub fn to_string<T>(value: &T) -> Result<String, Error>
 where
-    T: ?Sized + Encode<DefaultMode>,
+    T: ?Sized + Encode<TextMode>,
 {
     DEFAULT.to_string(value)
 }
@@ -77,7 +77,7 @@ where
 #[inline]
 pub fn to_fixed_bytes<const N: usize, T>(value: &T) -> Result<FixedBytes<N>, Error>
 where
-    T: ?Sized + Encode<DefaultMode>,
+    T: ?Sized + Encode<TextMode>,
 {
     DEFAULT.to_fixed_bytes::<N, _>(value)
 }
@@ -88,7 +88,7 @@ where
 pub fn decode<'de, R, T>(reader: R) -> Result<T, Error>
 where
     R: Parser<'de>,
-    T: Decode<'de, DefaultMode>,
+    T: Decode<'de, TextMode>,
 {
     DEFAULT.decode(reader)
 }
@@ -98,7 +98,7 @@ where
 #[inline]
 pub fn from_str<'de, T>(string: &'de str) -> Result<T, Error>
 where
-    T: Decode<'de, DefaultMode>,
+    T: Decode<'de, TextMode>,
 {
     DEFAULT.from_str(string)
 }
@@ -108,17 +108,20 @@ where
 #[inline]
 pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
 where
-    T: Decode<'de, DefaultMode>,
+    T: Decode<'de, TextMode>,
 {
     DEFAULT.from_slice(bytes)
 }
 
 /// Setting up encoding with parameters.
-pub struct Encoding<M = DefaultMode> {
+///
+/// The default mode is [`TextMode`], which is human readable. This means that
+/// types like network addresses are encoded using their string form.
+pub struct Encoding<M = TextMode> {
     _marker: marker::PhantomData<M>,
 }
 
-impl Encoding<DefaultMode> {
+impl Encoding<TextMode> {
     /// Construct a new [Encoding].
     ///
     /// You can modify this using the available factory methods:
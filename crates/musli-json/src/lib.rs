@@ -62,7 +62,7 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 #[cfg(feature = "std")]
 pub use self::encoding::to_writer;
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding};
+pub use self::encoding::{decode, encode, from_slice, from_str, to_fixed_bytes, Encoding};
 #[cfg(feature = "alloc")]
 pub use self::encoding::{to_string, to_vec};
 pub use self::error::Error;
@@ -0,0 +1,57 @@
+#![allow(unused)]
+
+use std::collections::{BTreeSet, LinkedList};
+
+use musli::{Decode, Encode};
+use musli_common::context::{AllocBuf, AllocContext};
+
+#[derive(Encode)]
+struct From {
+    set: BTreeSet<u64>,
+    list: LinkedList<u32>,
+}
+
+#[derive(Decode)]
+struct Sequences {
+    #[musli(trace)]
+    set: BTreeSet<u32>,
+    #[musli(trace)]
+    list: LinkedList<u32>,
+}
+
+#[test]
+fn trace_sequence() {
+    let mut buf = AllocBuf::default();
+    let mut cx = AllocContext::new(&mut buf);
+
+    let from = From {
+        set: BTreeSet::from([1, u64::MAX]),
+        list: LinkedList::from([1, 2, 3]),
+    };
+
+    let encoding = musli_json::Encoding::new();
+
+    let Ok(bytes) = encoding.to_vec_with(&mut cx, &from) else {
+        if let Some(error) = cx.iter().next() {
+            panic!("{error}");
+        }
+
+        unreachable!()
+    };
+
+    let mut cx = AllocContext::new(&mut buf);
+
+    let Ok(..) = encoding.from_slice_with::<_, Sequences>(&mut cx, &bytes) else {
+        if let Some(error) = cx.iter().next() {
+            assert_eq!(
+                error.to_string(),
+                ".set[1]: arithmetic overflow (at bytes 9-19)"
+            );
+            return;
+        }
+
+        unreachable!()
+    };
+
+    panic!("expected decoding to error");
+}
@@ -6,7 +6,7 @@ use syn::Token;
 
 use crate::expander::{Result, TagMethod};
 use crate::internals::attr::{EnumTag, EnumTagging, Packing};
-use crate::internals::build::{Body, Build, BuildData, Enum, Field, Variant};
+use crate::internals::build::{Body, Build, BuildData, DecodeFrom, Enum, Field, Variant};
 
 pub(crate) fn expand_decode_entry(e: Build<'_>) -> Result<TokenStream> {
     e.validate_decode()?;
@@ -14,11 +14,32 @@ pub(crate) fn expand_decode_entry(e: Build<'_>) -> Result<TokenStream> {
     let ctx_var = syn::Ident::new("__ctx", Span::call_site());
     let root_decoder_var = syn::Ident::new("__decoder", Span::call_site());
 
-    let body = match &e.data {
-        BuildData::Struct(st) => decode_struct(&e, st, &ctx_var, &root_decoder_var, true)?,
-        BuildData::Enum(en) => decode_enum(&e, en, &ctx_var, &root_decoder_var, true)?,
+    let mut body = match (&e.decode_from, &e.data) {
+        (Some(from), _) => decode_from(&e, from, &ctx_var, &root_decoder_var),
+        (None, BuildData::Struct(st)) => decode_struct(&e, st, &ctx_var, &root_decoder_var, true)?,
+        (None, BuildData::Enum(en)) => decode_enum(&e, en, &ctx_var, &root_decoder_var, true)?,
     };
 
+    let self_type = match e.remote {
+        Some(remote) => {
+            let (_, type_generics, _) = e.input.generics.split_for_impl();
+            quote!(#remote #type_generics)
+        }
+        None => quote!(Self),
+    };
+
+    if let Some((_, path)) = e.validate {
+        let result_ok = &e.tokens.result_ok;
+        let value_var = e.cx.ident("value");
+        let validate = validate_value(&e, path, &ctx_var, &value_var);
+
+        body = quote! {
+            let #value_var: #self_type = { #body }?;
+            #validate
+            #result_ok(#value_var)
+        };
+    }
+
     if e.cx.has_errors() {
         return Err(());
     }
@@ -67,6 +88,46 @@ pub(crate) fn expand_decode_entry(e: Build<'_>) -> Result<TokenStream> {
     let c_param = e.cx.ident("C");
     let d_param = e.cx.ident("D");
 
+    if e.remote.is_some() {
+        let vis = &e.input.vis;
+        let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+
+        // The mode and decoder lifetime are parameters of the function rather
+        // than of the impl.
+        let (fn_generics, _, fn_where_clause) = e
+            .expansion
+            .as_impl_generics(syn::Generics::default(), e.tokens);
+
+        let fn_params = fn_generics.params;
+        let fn_lt = (!exists).then(|| quote!(#lt,));
+        let predicates = fn_where_clause
+            .into_iter()
+            .flat_map(|w| w.predicates)
+            .chain(
+                e.bounds
+                    .iter()
+                    .chain(e.decode_bounds.iter())
+                    .map(|(_, v)| v.clone()),
+            );
+
+        return Ok(quote! {
+            #[automatically_derived]
+            #[allow(clippy::init_numbered_fields)]
+            #[allow(clippy::let_unit_value)]
+            impl #impl_generics #type_ident #type_generics #where_clause {
+                #[inline]
+                #vis fn decode<#fn_lt #buf_lt, #fn_params, #c_param, #d_param>(#ctx_var: &mut #c_param, #root_decoder_var: #d_param) -> #core_result<#self_type, <#c_param as #context_t<#buf_lt>>::Error>
+                where
+                    #c_param: #context_t<#buf_lt, Input = <#d_param as #decoder_t<#lt>>::Error>,
+                    #d_param: #decoder_t<#lt>,
+                    #(#predicates,)*
+                {
+                    #body
+                }
+            }
+        });
+    }
+
     Ok(quote! {
         #[automatically_derived]
         #[allow(clippy::init_numbered_fields)]
@@ -84,6 +145,96 @@ pub(crate) fn expand_decode_entry(e: Build<'_>) -> Result<TokenStream> {
     })
 }
 
+/// Decode by converting from the type specified in `#[musli(from = <type>)]`
+/// or `#[musli(try_from = <type>)]`.
+fn decode_from(
+    e: &Build<'_>,
+    from: &DecodeFrom<'_>,
+    ctx_var: &syn::Ident,
+    decoder_var: &syn::Ident,
+) -> TokenStream {
+    let context_t = &e.tokens.context_t;
+    let decode_t_decode = &e.decode_t_decode;
+    let result_err = &e.tokens.result_err;
+    let result_ok = &e.tokens.result_ok;
+    let value_var = e.cx.ident("value");
+
+    match from {
+        DecodeFrom::From(ty) => {
+            let from_t = &e.tokens.from_t;
+
+            quote! {{
+                let #value_var: #ty = #decode_t_decode(#ctx_var, #decoder_var)?;
+                #result_ok(#from_t::from(#value_var))
+            }}
+        }
+        DecodeFrom::TryFrom(ty) => {
+            let try_from_t = &e.tokens.try_from_t;
+            let error_var = e.cx.ident("error");
+
+            quote! {{
+                let #value_var: #ty = #decode_t_decode(#ctx_var, #decoder_var)?;
+
+                match #try_from_t::try_from(#value_var) {
+                    #result_ok(#value_var) => #result_ok(#value_var),
+                    #result_err(#error_var) => #result_err(#context_t::custom(#ctx_var, #error_var)),
+                }
+            }}
+        }
+    }
+}
+
+/// Decode an enum marked with `#[musli(discriminant)]`, which is encoded as
+/// the discriminant of its variant.
+fn decode_discriminant(
+    e: &Build<'_>,
+    en: &Enum,
+    ty: &syn::Ident,
+    ctx_var: &syn::Ident,
+    decoder_var: &syn::Ident,
+) -> TokenStream {
+    let context_t = &e.tokens.context_t;
+    let decode_t_decode = &e.decode_t_decode;
+    let result_err = &e.tokens.result_err;
+    let result_ok = &e.tokens.result_ok;
+    let type_name = &en.name;
+    let tag_var = e.cx.ident("tag");
+
+    let mut consts = Vec::with_capacity(en.variants.len());
+    let mut arms = Vec::with_capacity(en.variants.len());
+
+    for v in &en.variants {
+        // Discriminants are declared as constants so that they can be used
+        // as patterns.
+        let const_ident = syn::Ident::new(&format!("__DISCRIMINANT{}", v.index), v.span);
+        let discriminant = &v.discriminant;
+        let path = &v.st.path;
+
+        consts.push(quote!(const #const_ident: #ty = #discriminant;));
+        arms.push(quote!(#const_ident => #path {}));
+    }
+
+    let fallback = match en.fallback {
+        Some(ident) => {
+            let self_path = &e.self_path;
+            quote!(#self_path::#ident {})
+        }
+        None => quote! {
+            return #result_err(#context_t::invalid_variant_tag(#ctx_var, #type_name, #tag_var))
+        },
+    };
+
+    quote! {{
+        #(#consts)*
+        let #tag_var: #ty = #decode_t_decode(#ctx_var, #decoder_var)?;
+
+        #result_ok(match #tag_var {
+            #(#arms,)*
+            _ => #fallback,
+        })
+    }}
+}
+
 fn decode_struct(
     e: &Build<'_>,
     st: &Body<'_>,
@@ -108,11 +259,6 @@ fn decode_enum(
     decoder_var: &syn::Ident,
     trace: bool,
 ) -> Result<TokenStream> {
-    if let Some(&(span, Packing::Packed)) = en.packing_span {
-        e.decode_packed_enum_diagnostics(span);
-        return Err(());
-    }
-
     let as_decoder_t = &e.tokens.as_decoder_t;
     let context_t = &e.tokens.context_t;
     let decoder_t = &e.tokens.decoder_t;
@@ -133,6 +279,14 @@ fn decode_enum(
         return Ok(quote!(#result_err(#context_t::uninhabitable(#ctx_var, #type_name))));
     }
 
+    if let Some(ty) = &en.discriminant {
+        return Ok(decode_discriminant(e, en, ty, ctx_var, decoder_var));
+    }
+
+    if let Packing::Packed = en.enum_packing {
+        return decode_untagged(e, en, ctx_var, decoder_var, trace);
+    }
+
     let body_decoder_var = e.cx.ident("body_decoder");
     let buffer_decoder_var = e.cx.ident("buffer_decoder");
     let buffer_var = e.cx.ident("buffer");
@@ -148,13 +302,15 @@ fn decode_enum(
 
     let mut fallback = match en.fallback {
         Some(ident) => {
+            let self_path = &e.self_path;
+
             quote! {{
                 if !#variant_decoder_t::skip_variant(&mut #variant_decoder_var, #ctx_var)? {
                     return #result_err(#context_t::invalid_variant_tag(#ctx_var, #type_name, #variant_tag_var));
                 }
 
                 #variant_decoder_t::end(#variant_decoder_var, #ctx_var)?;
-                Self::#ident {}
+                #self_path::#ident {}
             }}
         }
         None => quote! {
@@ -172,7 +328,7 @@ fn decode_enum(
 
             for v in en.variants.iter().filter(|v| !v.is_default) {
                 let (tag_pattern, tag_value, tag_variant) =
-                    build_tag_variant(e, v.span, v.index, &v.tag, &output);
+                    build_tag_variant(e, v.span, v.index, &v.tag, &v.aliases, &output);
 
                 tag_variants.push(tag_variant);
                 variant_output_tags.push((v, tag_pattern, tag_value));
@@ -234,7 +390,8 @@ fn decode_enum(
         }
         TagMethod::Any => {
             for v in en.variants.iter().filter(|v| !v.is_default) {
-                variant_output_tags.push((v, v.tag.clone(), v.tag.clone()));
+                let tag_pattern = build_tag_pattern(&v.tag, &v.aliases);
+                variant_output_tags.push((v, tag_pattern, v.tag.clone()));
             }
 
             let decode_t_decode = &e.decode_t_decode;
@@ -595,6 +752,97 @@ fn decode_enum(
     }
 }
 
+/// Decode an untagged enum.
+///
+/// The input is buffered and each variant is tried in declaration order. The
+/// first one which decodes successfully is returned, and if none do an error
+/// listing why each variant failed is reported.
+fn decode_untagged(
+    e: &Build<'_>,
+    en: &Enum,
+    ctx_var: &syn::Ident,
+    decoder_var: &syn::Ident,
+    trace: bool,
+) -> Result<TokenStream> {
+    let as_decoder_t = &e.tokens.as_decoder_t;
+    let context_t = &e.tokens.context_t;
+    let decoder_t = &e.tokens.decoder_t;
+    let result_err = &e.tokens.result_err;
+    let result_ok = &e.tokens.result_ok;
+    let option_some = &e.tokens.option_some;
+    let untagged_errors = &e.tokens.untagged_errors;
+    let mode_ident = e.mode_ident.as_path();
+    let type_name = &en.name;
+
+    let body_decoder_var = e.cx.ident("body_decoder");
+    let buffer_var = e.cx.ident("buffer");
+    let errors_var = e.cx.ident("errors");
+    let mark_var = e.cx.ident("mark");
+    let output_var = e.cx.ident("output");
+    let variant_ctx_var = e.cx.ident("variant_ctx");
+    let variant_tag_var = e.cx.ident("variant_tag");
+
+    let mut variants = Vec::with_capacity(en.variants.len());
+
+    for v in &en.variants {
+        let name = &v.st.name;
+        let decode = decode_variant(
+            e,
+            v,
+            &variant_ctx_var,
+            &body_decoder_var,
+            &variant_tag_var,
+            trace,
+        )?;
+
+        let enter_variant = trace.then(|| {
+            quote! {
+                #context_t::enter_variant(#variant_ctx_var, #name, #name);
+            }
+        });
+
+        let leave_variant = trace.then(|| {
+            quote! {
+                #context_t::leave_variant(#variant_ctx_var);
+            }
+        });
+
+        let leave = trace.then(|| {
+            quote! {
+                #context_t::leave_enum(#ctx_var);
+            }
+        });
+
+        variants.push(quote! {
+            if let #option_some(#output_var) = #untagged_errors::try_variant(&mut #errors_var, #ctx_var, #name, |#variant_ctx_var| {
+                let #body_decoder_var = #as_decoder_t::as_decoder(&#buffer_var, #variant_ctx_var)?;
+                #enter_variant
+                let #output_var = { #decode };
+                #leave_variant
+                #result_ok(#output_var)
+            }) {
+                #leave
+                return #result_ok(#output_var);
+            }
+        });
+    }
+
+    let enter = trace.then(|| {
+        quote! {
+            #context_t::enter_enum(#ctx_var, #type_name);
+        }
+    });
+
+    Ok(quote! {{
+        #enter
+        let #mark_var = #context_t::mark(#ctx_var);
+        let #buffer_var = #decoder_t::decode_buffer::<#mode_ident, _>(#decoder_var, #ctx_var)?;
+        let mut #errors_var = #untagged_errors::new(#type_name);
+        #(#variants)*
+        #result_err(#context_t::marked_message(#ctx_var, #mark_var, #errors_var))
+    }})
+}
+
 fn decode_variant(
     e: &Build,
     v: &Variant<'_>,
@@ -652,11 +900,36 @@ fn decode_tagged(
     let mut assigns = Punctuated::<_, Token![,]>::new();
 
     let mut fields_with = Vec::new();
+    let mut flattened = Vec::new();
 
     for f in &st.fields {
+        if f.skip.is_some() {
+            assigns.push(syn::FieldValue {
+                attrs: Vec::new(),
+                member: f.member.clone(),
+                colon_token: Some(<Token![:]>::default()),
+                expr: syn::Expr::Verbatim(default_value(e, f)),
+            });
+
+            continue;
+        }
+
+        if f.flatten.is_some() {
+            let var = &f.var;
+
+            assigns.push(syn::FieldValue {
+                attrs: Vec::new(),
+                member: f.member.clone(),
+                colon_token: Some(<Token![:]>::default()),
+                expr: syn::Expr::Verbatim(quote!(#var)),
+            });
+
+            flattened.push(f);
+            continue;
+        }
+
         let tag = &f.tag;
         let var = &f.var;
-        let decode_path = &f.decode_path.1;
 
         let formatted_tag = match &st.name_format_with {
             Some((_, path)) => quote!(&#path(&#tag)),
@@ -686,14 +959,16 @@ fn decode_tagged(
             }
         });
 
+        let decode = decode_field(e, f, ctx_var, &struct_decoder_var);
+
         let decode = quote! {
-            #var = #option_some(#decode_path(#ctx_var, #struct_decoder_var)?);
+            #var = #option_some(#decode);
         };
 
         fields_with.push((f, decode, (enter, leave)));
 
         let fallback = if f.default_attr.is_some() {
-            quote!(#default_function())
+            default_value(e, f)
         } else {
             quote! {
                 return #result_err(#context_t::expected_tag(#ctx_var, #type_name, #tag))
@@ -717,6 +992,8 @@ fn decode_tagged(
 
     let decode_tag;
     let mut output_enum = quote!();
+    // The pattern matching any reserved field.
+    let mut reserved = None;
 
     match st.field_tag_method {
         TagMethod::String => {
@@ -725,12 +1002,21 @@ fn decode_tagged(
 
             for (f, decode, trace) in fields_with {
                 let (output_pattern, output_tag, output) =
-                    build_tag_variant(e, f.span, f.index, &f.tag, &output);
+                    build_tag_variant(e, f.span, f.index, &f.tag, &f.aliases, &output);
 
                 outputs.push(output);
                 patterns.push((output_pattern, output_tag, decode, trace));
             }
 
+            // Reserved tags share an output variant indexed past all fields.
+            if let Some((tag, aliases)) = st.reserved.split_first() {
+                let (output_pattern, _, output) =
+                    build_tag_variant(e, st.span, st.fields.len(), tag, aliases, &output);
+
+                outputs.push(output);
+                reserved = Some(output_pattern);
+            }
+
             let patterns = outputs.iter().map(|o| o.as_arm(option_some));
 
             decode_tag = quote! {
@@ -768,9 +1054,19 @@ fn decode_tagged(
         }
         TagMethod::Any => {
             for (f, decode, trace) in fields_with {
-                patterns.push((f.tag.clone(), f.tag.clone(), decode, trace));
+                patterns.push((
+                    build_tag_pattern(&f.tag, &f.aliases),
+                    f.tag.clone(),
+                    decode,
+                    trace,
+                ));
             }
 
+            reserved = st
+                .reserved
+                .split_first()
+                .map(|(tag, aliases)| build_tag_pattern(tag, aliases));
+
             let decode_t_decode = &e.decode_t_decode;
 
             decode_tag = quote! {
@@ -817,13 +1113,43 @@ fn decode_tagged(
         });
     }
 
-    let mut body = quote! {
-        if !#skip_field {
-            return #result_err(#unsupported);
+    // The pattern matching any unknown field.
+    let mut unknown = quote!(#tag);
+
+    let mut body = if st.deny_unknown_fields {
+        match st.field_tag_method {
+            TagMethod::String => {
+                // The unknown string has been stored by the tag visitor.
+                unknown = quote!(_);
+
+                quote! {
+                    return #result_err(#context_t::invalid_field_string_tag(#ctx_var, #type_name));
+                }
+            }
+            TagMethod::Any => quote! {
+                return #result_err(#unsupported);
+            },
+        }
+    } else {
+        quote! {
+            if !#skip_field {
+                return #result_err(#unsupported);
+            }
         }
     };
 
-    if !patterns.is_empty() {
+    // Reserved fields are skipped even if unknown fields are denied.
+    let reserved = reserved.map(|pattern| {
+        quote! {
+            #pattern => {
+                if !#skip_field {
+                    return #result_err(#unsupported);
+                }
+            }
+        }
+    });
+
+    if !patterns.is_empty() || reserved.is_some() {
         let patterns = patterns
             .into_iter()
             .map(|(pattern_var, _, decode, (enter, leave))| {
@@ -838,7 +1164,7 @@ fn decode_tagged(
             });
 
         body = quote! {
-            match tag { #(#patterns,)* #tag => { #body } }
+            match tag { #(#patterns,)* #reserved #unknown => { #body } }
         }
     };
 
@@ -857,6 +1183,7 @@ fn decode_tagged(
     let decls = st
         .fields
         .iter()
+        .filter(|f| f.flatten.is_none() && f.skip.is_none())
         .map(|Field { var, .. }| quote!(let mut #var = #option_none;));
 
     let enter = (trace && trace_body).then(|| {
@@ -871,12 +1198,58 @@ fn decode_tagged(
         }
     });
 
+    // Flattened fields are decoded by buffering the struct, and then making
+    // one pass over it for the fields of this struct followed by one pass for
+    // each flattened field. Every pass only sees the entries which were not
+    // claimed by an earlier one.
+    let mut buffer = quote!();
+    let mut type_decoder = quote!(#parent_decoder_var);
+    let mut decode_flattened = Vec::with_capacity(flattened.len());
+
+    if !flattened.is_empty() {
+        let as_decoder_t = &e.tokens.as_decoder_t;
+        let flatten_decoder = &e.tokens.flatten_decoder;
+        let mode_ident = e.mode_ident.as_path();
+        let buffer_var = e.cx.ident("buffer");
+        let claimed_var = e.cx.ident("claimed");
+
+        buffer = quote! {
+            let #buffer_var = #decoder_t::decode_buffer::<#mode_ident, _>(#parent_decoder_var, #ctx_var)?;
+            let mut #claimed_var = #default_function();
+        };
+
+        let flatten_decoder_var = e.cx.ident("flatten_decoder");
+
+        let flatten_decoder = quote! {
+            let #flatten_decoder_var = #as_decoder_t::as_decoder(&#buffer_var, #ctx_var)?;
+            let #flatten_decoder_var = #flatten_decoder::new(#flatten_decoder_var, &mut #claimed_var);
+        };
+
+        for f in flattened {
+            let var = &f.var;
+            let decode = decode_field(e, f, ctx_var, &flatten_decoder_var);
+
+            decode_flattened.push(quote! {
+                let #var = {
+                    #flatten_decoder
+                    #decode
+                };
+            });
+        }
+
+        type_decoder = quote!({
+            #flatten_decoder
+            #flatten_decoder_var
+        });
+    }
+
     Ok(quote! {{
         #output_enum
         #(#decls)*
 
         #enter
-        let mut type_decoder = #decoder_t::decode_struct(#parent_decoder_var, #ctx_var, #fields_len)?;
+        #buffer
+        let mut type_decoder = #decoder_t::decode_struct(#type_decoder, #ctx_var, #fields_len)?;
 
         while let #option_some(mut #struct_decoder_var) = #pairs_decoder_t::next(&mut type_decoder, #ctx_var)? {
             #tag_stmt
@@ -884,6 +1257,7 @@ fn decode_tagged(
         }
 
         #pairs_decoder_t::end(type_decoder, #ctx_var)?;
+        #(#decode_flattened)*
         #leave
         #path { #assigns }
     }})
@@ -898,18 +1272,21 @@ fn decode_transparent(
     trace: bool,
     trace_body: bool,
 ) -> Result<TokenStream> {
-    let [f] = &st_.fields[..] else {
-        e.transparent_diagnostics(st_.span, &st_.fields);
-        return Err(());
-    };
+    let f = e.transparent_field(st_)?;
 
     let output_var = e.cx.ident("output");
 
     let context_t = &e.tokens.context_t;
     let type_name = &st_.name;
     let path = &st_.path;
-    let decode_path = &f.decode_path.1;
     let member = &f.member;
+    let decode = decode_field(e, f, ctx_var, decoder_var);
+
+    let skipped = st_.fields.iter().filter(|f| f.skip.is_some()).map(|f| {
+        let member = &f.member;
+        let default = default_value(e, f);
+        quote!(#member: #default)
+    });
 
     let enter = (trace && trace_body).then(|| {
         quote! {
@@ -927,7 +1304,8 @@ fn decode_transparent(
         #enter
 
         let #output_var = #path {
-            #member: #decode_path(#ctx_var, #decoder_var)?
+            #member: #decode,
+            #(#skipped,)*
         };
 
         #leave
@@ -949,21 +1327,29 @@ fn decode_packed(
     let context_t = &e.tokens.context_t;
     let type_name = &st_.name;
     let output_var = e.cx.ident("output");
+    let field_decoder_var = e.cx.ident("field_decoder");
 
     let mut assign = Vec::new();
 
     for f in &st_.fields {
-        if let Some(span) = f.default_attr {
+        let member = &f.member;
+
+        if f.skip.is_some() {
+            let default = default_value(e, f);
+            assign.push(quote!(#member: #default));
+            continue;
+        }
+
+        if let Some((span, _)) = f.default_attr {
             e.packed_default_diagnostics(span);
         }
 
-        let (_, decode_path) = &f.decode_path;
-        let member = &f.member;
+        let decode = decode_field(e, f, ctx_var, &field_decoder_var);
 
         assign.push(quote! {
             #member: {
-                let field_decoder = #pack_decoder_t::next(&mut unpack, #ctx_var)?;
-                #decode_path(#ctx_var, field_decoder)?
+                let #field_decoder_var = #pack_decoder_t::next(&mut unpack, #ctx_var)?;
+                #decode
             }
         });
     }
@@ -996,6 +1382,63 @@ fn decode_packed(
     }})
 }
 
+/// Call the function specified in `#[musli(validate = <path>)]` with a
+/// reference to the decoded value, reporting any error through the context.
+fn validate_value(
+    e: &Build<'_>,
+    path: &syn::Path,
+    ctx_var: &syn::Ident,
+    value_var: &syn::Ident,
+) -> TokenStream {
+    let context_t = &e.tokens.context_t;
+    let result_err = &e.tokens.result_err;
+    let error_var = e.cx.ident("error");
+
+    quote! {
+        if let #result_err(#error_var) = #path(&#value_var) {
+            return #result_err(#context_t::custom(#ctx_var, #error_var));
+        }
+    }
+}
+
+/// Decode a field from the given decoder, validating it if the field has a
+/// `#[musli(validate = <path>)]` attribute.
+fn decode_field(
+    e: &Build<'_>,
+    f: &Field<'_>,
+    ctx_var: &syn::Ident,
+    decoder_var: &syn::Ident,
+) -> TokenStream {
+    let decode_path = &f.decode_path.1;
+    let decode = quote!(#decode_path(#ctx_var, #decoder_var)?);
+
+    let Some((_, path)) = f.validate else {
+        return decode;
+    };
+
+    let ty = f.ty;
+    let value_var = e.cx.ident("value");
+    let validate = validate_value(e, path, ctx_var, &value_var);
+
+    quote! {{
+        let #value_var: #ty = #decode;
+        #validate
+        #value_var
+    }}
+}
+
+/// The default value of a field, which is either constructed using the path in
+/// `#[musli(default = <path>)]` or through `Default::default`.
+fn default_value(e: &Build<'_>, f: &Field<'_>) -> TokenStream {
+    match f.default_attr {
+        Some((_, Some(path))) => quote!(#path()),
+        _ => {
+            let default_function = &e.tokens.default_function;
+            quote!(#default_function())
+        }
+    }
+}
+
 /// Output type used when indirectly encoding a variant or field as type which
 /// might require special handling. Like a string.
 pub(crate) struct TagVariant<'a> {
@@ -1005,6 +1448,8 @@ pub(crate) struct TagVariant<'a> {
     variant: syn::Ident,
     /// The tag this variant corresponds to.
     tag: &'a syn::Expr,
+    /// Aliases which are also accepted for this variant.
+    aliases: &'a [syn::Expr],
 }
 
 impl TagVariant<'_> {
@@ -1018,7 +1463,7 @@ impl TagVariant<'_> {
 
         syn::Arm {
             attrs: Vec::new(),
-            pat: syn::Pat::Verbatim(self.tag.to_token_stream()),
+            pat: syn::Pat::Verbatim(build_tag_pattern(self.tag, self.aliases).to_token_stream()),
             guard: None,
             fat_arrow_token: <Token![=>]>::default(),
             body: Box::new(build_call(option_some, [body])),
@@ -1063,6 +1508,7 @@ fn build_tag_variant<'a>(
     span: Span,
     index: usize,
     tag: &'a syn::Expr,
+    aliases: &'a [syn::Expr],
     output: &syn::Ident,
 ) -> (syn::Expr, syn::Expr, TagVariant<'a>) {
     let variant = syn::Ident::new(&format!("Variant{}", index), span);
@@ -1073,6 +1519,7 @@ fn build_tag_variant<'a>(
         path: path.clone(),
         variant,
         tag,
+        aliases,
     };
 
     let expr = syn::Expr::Path(syn::ExprPath {
@@ -1087,3 +1534,12 @@ fn build_tag_variant<'a>(
         output,
     )
 }
+
+/// Build a pattern matching the given tag or any of its aliases.
+fn build_tag_pattern(tag: &syn::Expr, aliases: &[syn::Expr]) -> syn::Expr {
+    if aliases.is_empty() {
+        return tag.clone();
+    }
+
+    syn::Expr::Verbatim(quote!(#tag #(| #aliases)*))
+}
@@ -18,9 +18,19 @@ pub(crate) fn expand_encode_entry(e: Build<'_>) -> Result<TokenStream> {
     let c_param = e.cx.ident("C");
     let e_param = e.cx.ident("E");
 
-    let body = match &e.data {
-        BuildData::Struct(st) => encode_struct(&e, st, &ctx_var, &encoder_var, true)?,
-        BuildData::Enum(en) => encode_enum(&e, en, &ctx_var, &encoder_var, true)?,
+    let body = match (e.encode_into, &e.data) {
+        (Some(ty), _) => encode_into(&e, ty, &ctx_var, &encoder_var),
+        (None, BuildData::Struct(st)) => encode_struct(&e, st, &ctx_var, &encoder_var, true)?,
+        (None, BuildData::Enum(en)) => encode_enum(&e, en, &ctx_var, &encoder_var, true)?,
+    };
+
+    let fields = match &e.data {
+        BuildData::Struct(st)
+            if e.remote.is_none() && e.encode_into.is_none() && st.packing == Packing::Tagged =>
+        {
+            Some(encode_struct_fields(&e, st, &ctx_var, &encoder_var, true)?)
+        }
+        _ => None,
     };
 
     if e.cx.has_errors() {
@@ -47,8 +57,36 @@ pub(crate) fn expand_encode_entry(e: Build<'_>) -> Result<TokenStream> {
             .extend(e.bounds.iter().map(|(_, v)| v.clone()));
     }
 
+    if let Some(remote) = e.remote {
+        return Ok(encode_remote(&e, remote, body));
+    }
+
     let type_generics = &e.input.generics;
 
+    let fields = fields.map(|(len, encode)| {
+        let encode_fields_t = &e.tokens.encode_fields_t;
+        let pairs_encoder_t = &e.tokens.pairs_encoder_t;
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #encode_fields_t<#mode_ident> for #type_ident #type_generics #where_clause {
+                #[inline]
+                fn fields_len(&self) -> usize {
+                    #len
+                }
+
+                #[inline]
+                fn encode_fields<#buf_lt, #c_param, #e_param>(&self, #ctx_var: &mut #c_param, #encoder_var: &mut #e_param) -> #core_result<(), <#c_param as #context_t<#buf_lt>>::Error>
+                where
+                    #c_param: #context_t<#buf_lt, Input = <#e_param as #pairs_encoder_t>::Error>,
+                    #e_param: #pairs_encoder_t
+                {
+                    #encode
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         #[automatically_derived]
         impl #impl_generics #encode_t<#mode_ident> for #type_ident #type_generics #where_clause {
@@ -61,9 +99,75 @@ pub(crate) fn expand_encode_entry(e: Build<'_>) -> Result<TokenStream> {
                 #body
             }
         }
+
+        #fields
     })
 }
 
+/// Encode a type specified through `#[musli(remote = <path>)]`, which is done
+/// through an associated `encode` function on the type being derived.
+fn encode_remote(e: &Build<'_>, remote: &syn::Path, body: TokenStream) -> TokenStream {
+    let type_ident = &e.input.ident;
+    let vis = &e.input.vis;
+    let receiver = &e.receiver;
+
+    let encoder_var = e.cx.ident("encoder");
+    let ctx_var = e.cx.ident("ctx");
+    let buf_lt = e.cx.lifetime("'buf");
+    let c_param = e.cx.ident("C");
+    let e_param = e.cx.ident("E");
+
+    let context_t = &e.tokens.context_t;
+    let encoder_t = &e.tokens.encoder_t;
+    let core_result = &e.tokens.core_result;
+
+    let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+
+    // The mode is a parameter of the function rather than of the impl.
+    let (fn_generics, _, fn_where_clause) = e
+        .expansion
+        .as_impl_generics(syn::Generics::default(), e.tokens);
+
+    let fn_params = fn_generics.params;
+    let predicates = fn_where_clause
+        .into_iter()
+        .flat_map(|w| w.predicates)
+        .chain(e.bounds.iter().map(|(_, v)| v.clone()));
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #type_ident #type_generics #where_clause {
+            #[inline]
+            #vis fn encode<#buf_lt, #fn_params, #c_param, #e_param>(#receiver: &#remote #type_generics, #ctx_var: &mut #c_param, #encoder_var: #e_param) -> #core_result<<#e_param as #encoder_t>::Ok, <#c_param as #context_t<#buf_lt>>::Error>
+            where
+                #c_param: #context_t<#buf_lt, Input = <#e_param as #encoder_t>::Error>,
+                #e_param: #encoder_t,
+                #(#predicates,)*
+            {
+                #body
+            }
+        }
+    }
+}
+
+/// Encode by converting into the type specified in `#[musli(into = <type>)]`.
+fn encode_into(
+    e: &Build<'_>,
+    ty: &syn::Type,
+    ctx_var: &syn::Ident,
+    encoder_var: &syn::Ident,
+) -> TokenStream {
+    let encode_t_encode = &e.encode_t_encode;
+    let into_t = &e.tokens.into_t;
+    let receiver = &e.receiver;
+    let value_var = e.cx.ident("value");
+
+    quote! {{
+        let #value_var: #ty = #into_t::into(#receiver);
+        #encode_t_encode(&#value_var, #ctx_var, #encoder_var)
+    }}
+}
+
 /// Encode a struct.
 fn encode_struct(
     e: &Build<'_>,
@@ -75,7 +179,8 @@ fn encode_struct(
     let pack_var = e.cx.ident("pack");
     let output_var = e.cx.ident("output");
 
-    let (encoders, tests) = encode_fields(e, st, ctx_var, encoder_var, &pack_var, trace)?;
+    let (encoders, tests) =
+        encode_fields(e, st, ctx_var, &quote!(&mut #encoder_var), &pack_var, trace)?;
 
     let context_t = &e.tokens.context_t;
     let result_ok = &e.tokens.result_ok;
@@ -88,13 +193,7 @@ fn encode_struct(
 
     match st.packing {
         Packing::Transparent => {
-            let f = match &st.fields[..] {
-                [f] => f,
-                _ => {
-                    e.transparent_diagnostics(st.span, &st.fields);
-                    return Err(());
-                }
-            };
+            let f = e.transparent_field(st)?;
 
             let access = &f.self_access;
             let encode_path = &f.encode_path.1;
@@ -107,7 +206,7 @@ fn encode_struct(
             }};
         }
         Packing::Tagged => {
-            let len = length_test(st.fields.len(), &tests);
+            let len = length_test(st.unskipped_fields().count(), &tests);
             let decls = tests.iter().map(|t| &t.decl);
 
             let encoder_t = &e.tokens.encoder_t;
@@ -144,34 +243,75 @@ fn encode_struct(
     Ok(quote!(#result_ok(#encode)))
 }
 
+/// Encode the fields of a struct into an existing pairs encoder, as used by
+/// `#[musli(flatten)]`.
+///
+/// Returns the expression for the number of fields and the body of the
+/// encode.
+fn encode_struct_fields(
+    e: &Build<'_>,
+    st: &Body<'_>,
+    ctx_var: &syn::Ident,
+    encoder_var: &syn::Ident,
+    trace: bool,
+) -> Result<(TokenStream, TokenStream)> {
+    let pack_var = e.cx.ident("pack");
+
+    let (encoders, tests) = encode_fields(e, st, ctx_var, &quote!(#encoder_var), &pack_var, trace)?;
+
+    let result_ok = &e.tokens.result_ok;
+    let len = length_test(st.unskipped_fields().count(), &tests);
+    let decls = tests.iter().map(|t| &t.decl).collect::<Vec<_>>();
+
+    let len = quote! {
+        #(#decls)*
+        #len
+    };
+
+    let encode = quote! {
+        #(#decls)*
+        #(#encoders)*
+        #result_ok(())
+    };
+
+    Ok((len, encode))
+}
+
+/// A field which might not contribute exactly one entry to the length of a
+/// struct.
 struct FieldTest {
-    decl: TokenStream,
-    var: syn::Ident,
+    decl: Option<TokenStream>,
+    var: Option<syn::Ident>,
+    flatten: Option<syn::Expr>,
 }
 
 fn encode_fields(
     e: &Build<'_>,
     st: &Body<'_>,
     ctx_var: &syn::Ident,
-    encoder_var: &syn::Ident,
+    encoder_ref: &TokenStream,
     pack_var: &syn::Ident,
     trace: bool,
 ) -> Result<(Vec<TokenStream>, Vec<FieldTest>)> {
     let pair_encoder_t = &e.tokens.pair_encoder_t;
     let pairs_encoder_t = &e.tokens.pairs_encoder_t;
     let encode_t_encode = &e.encode_t_encode;
+    let encode_fields_t = &e.tokens.encode_fields_t;
     let sequence_encoder_t = &e.tokens.sequence_encoder_t;
     let context_t = &e.tokens.context_t;
+    let mode_ident = e.mode_ident.as_path();
 
     let sequence_decoder_next_var = e.cx.ident("sequence_decoder_next");
     let pair_encoder_var = e.cx.ident("pair_encoder");
     let field_encoder_var = e.cx.ident("field_encoder");
     let value_encoder_var = e.cx.ident("value_encoder");
+    let tag_var = e.cx.ident("tag");
+    let name_type = st.name_type.as_ref().map(|(_, ty)| quote!(: #ty));
 
     let mut encoders = Vec::with_capacity(st.fields.len());
     let mut tests = Vec::with_capacity(st.fields.len());
 
-    for f in &st.fields {
+    for f in st.unskipped_fields() {
         let encode_path = &f.encode_path.1;
         let access = &f.self_access;
         let tag = &f.tag;
@@ -204,12 +344,20 @@ fn encode_fields(
         let leave = trace.then(|| quote!(#context_t::leave_field(#ctx_var);));
 
         match f.packing {
+            // Flattened fields are not entered, since their fields are
+            // encoded inline as if they were part of this struct.
+            Packing::Tagged | Packing::Transparent if f.flatten.is_some() => {
+                encode = quote! {
+                    #encode_fields_t::<#mode_ident>::encode_fields(#access, #ctx_var, #encoder_ref)?;
+                };
+            }
             Packing::Tagged | Packing::Transparent => {
                 encode = quote! {
                     #enter
-                    let mut #pair_encoder_var = #pairs_encoder_t::next(&mut #encoder_var, #ctx_var)?;
+                    let mut #pair_encoder_var = #pairs_encoder_t::next(#encoder_ref, #ctx_var)?;
                     let #field_encoder_var = #pair_encoder_t::first(&mut #pair_encoder_var, #ctx_var)?;
-                    #encode_t_encode(&#tag, #ctx_var, #field_encoder_var)?;
+                    let #tag_var #name_type = #tag;
+                    #encode_t_encode(&#tag_var, #ctx_var, #field_encoder_var)?;
                     let #value_encoder_var = #pair_encoder_t::second(&mut #pair_encoder_var, #ctx_var)?;
                     #encode_path(#access, #ctx_var, #value_encoder_var)?;
                     #pair_encoder_t::end(#pair_encoder_var, #ctx_var)?;
@@ -226,6 +374,10 @@ fn encode_fields(
             }
         };
 
+        let flatten = f.flatten.map(|_| {
+            syn::Expr::Verbatim(quote!(#encode_fields_t::<#mode_ident>::fields_len(#access)))
+        });
+
         if let Some((_, skip_encoding_if_path)) = f.skip_encoding_if.as_ref() {
             let var = syn::Ident::new(&format!("t{}", f.index), f.span);
 
@@ -239,7 +391,17 @@ fn encode_fields(
                 }
             };
 
-            tests.push(FieldTest { decl, var })
+            tests.push(FieldTest {
+                decl: Some(decl),
+                var: Some(var),
+                flatten,
+            })
+        } else if flatten.is_some() {
+            tests.push(FieldTest {
+                decl: None,
+                var: None,
+                flatten,
+            })
         }
 
         encoders.push(encode);
@@ -266,6 +428,27 @@ fn encode_enum(
         return Err(());
     }
 
+    let receiver = &e.receiver;
+
+    if let (Some(ty), false) = (&en.discriminant, en.variants.is_empty()) {
+        let encode_t_encode = &e.encode_t_encode;
+        let tag_var = e.cx.ident("tag");
+
+        let variants = en.variants.iter().map(|v| {
+            let path = &v.st.path;
+            let discriminant = &v.discriminant;
+            quote!(#path {} => #discriminant)
+        });
+
+        return Ok(quote! {{
+            let #tag_var: #ty = match #receiver {
+                #(#variants),*
+            };
+
+            #encode_t_encode(&#tag_var, #ctx_var, #encoder_var)
+        }});
+    }
+
     let mut variants = Vec::with_capacity(en.variants.len());
 
     for v in &en.variants {
@@ -281,7 +464,7 @@ fn encode_enum(
         quote!(#result_err(#context_t::uninhabitable(#ctx_var, #type_name)))
     } else {
         quote! {
-            #result_ok(match self {
+            #result_ok(match #receiver {
                 #(#variants),*
             })
         }
@@ -299,7 +482,14 @@ fn encode_variant(
 ) -> Result<(syn::PatStruct, TokenStream)> {
     let pack_var = b.cx.ident("pack");
 
-    let (encoders, tests) = encode_fields(b, &v.st, ctx_var, encoder_var, &pack_var, true)?;
+    let (encoders, tests) = encode_fields(
+        b,
+        &v.st,
+        ctx_var,
+        &quote!(&mut #encoder_var),
+        &pack_var,
+        true,
+    )?;
 
     let encoder_t = &b.tokens.encoder_t;
     let pair_encoder_t = &b.tokens.pair_encoder_t;
@@ -315,10 +505,7 @@ fn encode_variant(
         None => {
             match v.st.packing {
                 Packing::Transparent => {
-                    let [f] = &v.st.fields[..] else {
-                        b.transparent_diagnostics(v.span, &v.st.fields);
-                        return Err(());
-                    };
+                    let f = b.transparent_field(&v.st)?;
 
                     let encode_path = &f.encode_path.1;
                     let var = &f.self_access;
@@ -336,7 +523,7 @@ fn encode_variant(
                 }
                 Packing::Tagged => {
                     let decls = tests.iter().map(|t| &t.decl);
-                    let len = length_test(v.st.fields.len(), &tests);
+                    let len = length_test(v.st.unskipped_fields().count(), &tests);
 
                     encode = quote! {{
                         let mut #encoder_var = #encoder_t::encode_struct(#encoder_var, #ctx_var, #len)?;
@@ -352,12 +539,15 @@ fn encode_variant(
                 let tag = &v.tag;
                 let variant_encoder = b.cx.ident("variant_encoder");
                 let tag_encoder = b.cx.ident("tag_encoder");
+                let tag_var = b.cx.ident("tag");
+                let name_type = en.name_type.as_ref().map(|(_, ty)| quote!(: #ty));
 
                 encode = quote! {{
                     let mut #variant_encoder = #encoder_t::encode_variant(#encoder_var, #ctx_var)?;
 
                     let #tag_encoder = #variant_encoder_t::tag(&mut #variant_encoder, #ctx_var)?;
-                    #encode_t_encode(&#tag, #ctx_var, #tag_encoder)?;
+                    let #tag_var #name_type = #tag;
+                    #encode_t_encode(&#tag_var, #ctx_var, #tag_encoder)?;
 
                     let #encoder_var = #variant_encoder_t::variant(&mut #variant_encoder, #ctx_var)?;
                     #encode;
@@ -374,9 +564,11 @@ fn encode_variant(
                 let mode_ident = b.mode_ident.as_path();
                 let tag = &v.tag;
                 let decls = tests.iter().map(|t| &t.decl);
+                // The tag is stored as an additional field.
+                let len = length_test(v.st.unskipped_fields().count() + 1, &tests);
 
                 encode = quote! {{
-                    let mut #encoder_var = #encoder_t::encode_struct(#encoder_var, #ctx_var, 0)?;
+                    let mut #encoder_var = #encoder_t::encode_struct(#encoder_var, #ctx_var, #len)?;
                     #pairs_encoder_t::insert::<#mode_ident, _, _, _>(&mut #encoder_var, #ctx_var, #field_tag, #tag)?;
                     #(#decls)*
                     #(#encoders)*
@@ -396,7 +588,7 @@ fn encode_variant(
 
                 let decls = tests.iter().map(|t| &t.decl);
 
-                let len = length_test(v.st.fields.len(), &tests);
+                let len = length_test(v.st.unskipped_fields().count(), &tests);
                 let struct_encoder = b.cx.ident("struct_encoder");
                 let content_struct = b.cx.ident("content_struct");
                 let pair = b.cx.ident("pair");
@@ -428,7 +620,15 @@ fn encode_variant(
         path: v.st.path.clone(),
         brace_token: syn::token::Brace::default(),
         fields: v.patterns.clone(),
-        rest: None,
+        rest: v
+            .st
+            .fields
+            .iter()
+            .any(|f| f.skip.is_some())
+            .then(|| syn::PatRest {
+                attrs: Vec::new(),
+                dot2_token: <Token![..]>::default(),
+            }),
     };
 
     if trace {
@@ -454,8 +654,16 @@ fn length_test(count: usize, tests: &[FieldTest]) -> Punctuated<TokenStream, Tok
     let count = count.saturating_sub(tests.len());
     punctuated.push(quote!(#count));
 
-    for FieldTest { var, .. } in tests {
-        punctuated.push(quote!(if #var { 1 } else { 0 }))
+    for FieldTest { var, flatten, .. } in tests {
+        let len = match flatten {
+            Some(len) => quote!(#len),
+            None => quote!(1),
+        };
+
+        match var {
+            Some(var) => punctuated.push(quote!(if #var { #len } else { 0 })),
+            None => punctuated.push(len),
+        }
     }
 
     punctuated
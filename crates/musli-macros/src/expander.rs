@@ -1,7 +1,7 @@
 use proc_macro2::{Span, TokenStream};
 use syn::spanned::Spanned;
 
-use crate::internals::attr::{self, DefaultTag, TypeAttr};
+use crate::internals::attr::{self, DefaultTag, NameAll, TypeAttr};
 use crate::internals::build::Build;
 use crate::internals::symbol::*;
 use crate::internals::tokens::Tokens;
@@ -29,6 +29,7 @@ pub(crate) struct FieldData<'a> {
     pub(crate) name: Option<syn::LitStr>,
     pub(crate) attr: attr::Field,
     pub(crate) ident: Option<&'a syn::Ident>,
+    pub(crate) ty: &'a syn::Type,
 }
 
 pub(crate) struct StructData<'a> {
@@ -43,6 +44,7 @@ pub(crate) struct VariantData<'a> {
     pub(crate) index: usize,
     pub(crate) attr: attr::VariantAttr,
     pub(crate) ident: &'a syn::Ident,
+    pub(crate) discriminant: Option<&'a syn::Expr>,
     pub(crate) fields: Vec<FieldData<'a>>,
 }
 
@@ -81,6 +83,7 @@ impl<'a> Expander<'a> {
                         .map(|ident| syn::LitStr::new(&ident.to_string(), ident.span())),
                     attr: attr::field_attrs(cx, &field.attrs),
                     ident: field.ident.as_ref(),
+                    ty: &field.ty,
                 })
                 .collect()
         }
@@ -105,6 +108,7 @@ impl<'a> Expander<'a> {
                         name: syn::LitStr::new(&variant.ident.to_string(), variant.ident.span()),
                         attr: attr::variant_attrs(&cx, &variant.attrs),
                         ident: &variant.ident,
+                        discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr),
                         fields: fields(&cx, &variant.fields),
                     });
 
@@ -156,11 +160,19 @@ impl<'a> Expander<'a> {
                 )?);
             }
 
-            builds.push(crate::internals::build::setup(
-                self,
-                Expansion::Default,
-                only,
-            )?);
+            // Built-in modes which have been declared explicitly are already
+            // covered by the moded expansions above.
+            if !modes.iter().any(|m| is_mode(m, &self.tokens.default_mode)) {
+                builds.push(crate::internals::build::setup(
+                    self,
+                    Expansion::Default,
+                    only,
+                )?);
+            }
+
+            if !modes.iter().any(|m| is_mode(m, &self.tokens.text_mode)) {
+                builds.push(crate::internals::build::setup(self, Expansion::Text, only)?);
+            }
         }
 
         Ok(builds)
@@ -217,19 +229,31 @@ pub(crate) trait Taggable {
         e: &Expander<'_>,
         mode: Mode<'_>,
         default_tag: Option<DefaultTag>,
+        name_all: Option<NameAll>,
     ) -> Result<(syn::Expr, Option<TagMethod>)>
     where
         Self: Sized,
     {
-        let (lit, tag_method) = match (self.rename(mode), default_tag, self.name()) {
-            (Some((_, rename)), _, _) => {
+        let (lit, tag_method) = match (self.rename(mode), name_all, default_tag, self.name()) {
+            (Some((_, rename)), _, _, _) => {
                 return Ok((rename_lit(rename), determine_tag_method(rename)))
             }
-            (None, Some(DefaultTag::Index), _) => (
+            (None, Some(name_all), _, Some(name)) => {
+                let name = syn::LitStr::new(&name_all.apply(&name.value()), name.span());
+                (name.into(), Some(TagMethod::String))
+            }
+            (None, Some(..), _, None) => {
+                e.cx.error_span(
+                    self.span(),
+                    format_args!("#[{ATTR}({RENAME_ALL})] is not supported with unnamed fields"),
+                );
+                return Err(());
+            }
+            (None, None, Some(DefaultTag::Index), _) => (
                 usize_suffixed(self.index(), self.span()).into(),
                 Some(TagMethod::Any),
             ),
-            (None, Some(DefaultTag::Name), None) => {
+            (None, None, Some(DefaultTag::Name), None) => {
                 e.cx.error_span(
                     self.span(),
                     format_args!(
@@ -238,7 +262,7 @@ pub(crate) trait Taggable {
                 );
                 return Err(());
             }
-            (None, Some(DefaultTag::Name), Some(ident)) => {
+            (None, None, Some(DefaultTag::Name), Some(ident)) => {
                 (ident.clone().into(), Some(TagMethod::String))
             }
             _ => (usize_suffixed(self.index(), self.span()).into(), None),
@@ -296,7 +320,7 @@ impl Taggable for VariantData<'_> {
 }
 
 /// Process rename literal to ensure it's always typed.
-fn rename_lit(expr: &syn::Expr) -> syn::Expr {
+pub(crate) fn rename_lit(expr: &syn::Expr) -> syn::Expr {
     match expr {
         syn::Expr::Lit(syn::ExprLit {
             lit: syn::Lit::Int(int),
@@ -328,3 +352,17 @@ pub(crate) fn determine_tag_method(expr: &syn::Expr) -> Option<TagMethod> {
         _ => None,
     }
 }
+
+/// Test if the declared `mode` refers to the built-in mode at `path`, such as
+/// `TextMode`, `mode::TextMode` or `musli::mode::TextMode`.
+fn is_mode(mode: &syn::Path, path: &syn::Path) -> bool {
+    if mode.segments.len() > path.segments.len() {
+        return false;
+    }
+
+    mode.segments
+        .iter()
+        .rev()
+        .zip(path.segments.iter().rev())
+        .all(|(a, b)| a.ident == b.ident && a.arguments.is_empty())
+}
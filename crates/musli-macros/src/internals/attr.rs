@@ -50,6 +50,151 @@ pub enum DefaultTag {
     Name,
 }
 
+/// Case conversion policy applied to the names of fields or variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameAll {
+    /// `#[musli(rename_all = "lowercase")]`.
+    Lower,
+    /// `#[musli(rename_all = "UPPERCASE")]`.
+    Upper,
+    /// `#[musli(rename_all = "PascalCase")]`.
+    Pascal,
+    /// `#[musli(rename_all = "camelCase")]`.
+    Camel,
+    /// `#[musli(rename_all = "snake_case")]`.
+    Snake,
+    /// `#[musli(rename_all = "SCREAMING_SNAKE_CASE")]`.
+    ScreamingSnake,
+    /// `#[musli(rename_all = "kebab-case")]`.
+    Kebab,
+    /// `#[musli(rename_all = "SCREAMING-KEBAB-CASE")]`.
+    ScreamingKebab,
+}
+
+impl NameAll {
+    const NAMES: &'static [(&'static str, NameAll)] = &[
+        ("lowercase", NameAll::Lower),
+        ("UPPERCASE", NameAll::Upper),
+        ("PascalCase", NameAll::Pascal),
+        ("camelCase", NameAll::Camel),
+        ("snake_case", NameAll::Snake),
+        ("SCREAMING_SNAKE_CASE", NameAll::ScreamingSnake),
+        ("kebab-case", NameAll::Kebab),
+        ("SCREAMING-KEBAB-CASE", NameAll::ScreamingKebab),
+    ];
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|&(_, name_all)| name_all)
+    }
+
+    /// Apply the case conversion to the given identifier.
+    ///
+    /// Words are separated by underscores, by a lowercase character or digit
+    /// being followed by an uppercase character, or by the last uppercase
+    /// character in a run of them being followed by a lowercase character.
+    /// This works both for `snake_case` fields and `PascalCase` variants, and
+    /// treats acronyms like the `HTTP` in `HTTPServer` as a single word.
+    pub(crate) fn apply(self, ident: &str) -> String {
+        let ident = ident.strip_prefix("r#").unwrap_or(ident);
+
+        match self {
+            Self::Lower => return ident.to_lowercase(),
+            Self::Upper => return ident.to_uppercase(),
+            _ => {}
+        }
+
+        let mut words = Vec::new();
+        let mut current = String::new();
+        let mut prev_lower = false;
+        let mut prev_upper = false;
+        let mut chars = ident.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '_' {
+                words.extend((!current.is_empty()).then(|| mem::take(&mut current)));
+                prev_lower = false;
+                prev_upper = false;
+                continue;
+            }
+
+            if c.is_uppercase() {
+                let next_lower = matches!(chars.peek(), Some(c) if c.is_lowercase());
+
+                if prev_lower || (prev_upper && next_lower) {
+                    words.push(mem::take(&mut current));
+                }
+            }
+
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            prev_upper = c.is_uppercase();
+            current.push(c);
+        }
+
+        words.extend((!current.is_empty()).then_some(current));
+
+        let (separator, first, rest): (&str, Case, Case) = match self {
+            Self::Pascal => ("", capitalize, capitalize),
+            Self::Camel => ("", str::to_lowercase, capitalize),
+            Self::Snake => ("_", str::to_lowercase, str::to_lowercase),
+            Self::ScreamingSnake => ("_", str::to_uppercase, str::to_uppercase),
+            Self::Kebab => ("-", str::to_lowercase, str::to_lowercase),
+            Self::ScreamingKebab => ("-", str::to_uppercase, str::to_uppercase),
+            Self::Lower | Self::Upper => unreachable!(),
+        };
+
+        let mut out = String::with_capacity(ident.len());
+
+        for (n, word) in words.iter().enumerate() {
+            if n == 0 {
+                out.push_str(&first(word));
+            } else {
+                out.push_str(separator);
+                out.push_str(&rest(word));
+            }
+        }
+
+        out
+    }
+}
+
+/// Function used to convert the case of a single word.
+type Case = fn(&str) -> String;
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(c) => c
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn parse_name_all(meta: &syn::meta::ParseNestedMeta) -> syn::Result<(Span, NameAll)> {
+    meta.input.parse::<Token![=]>()?;
+    let string = meta.input.parse::<syn::LitStr>()?;
+
+    match NameAll::parse(&string.value()) {
+        Some(name_all) => Ok((meta.path.span(), name_all)),
+        None => Err(syn::Error::new_spanned(
+            string,
+            format_args!(
+                "#[{ATTR}({RENAME_ALL})]: Bad value, expected one of {}",
+                NameAll::NAMES
+                    .iter()
+                    .map(|(name, _)| format!("\"{name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )),
+    }
+}
+
 /// If the type is tagged or not.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Packing {
@@ -198,17 +343,35 @@ layer! {
         default_variant_name: DefaultTag,
         /// `#[musli(default_field_name = "..")]`.
         default_field_name: DefaultTag,
+        /// `#[musli(rename_all = "..")]`.
+        rename_all: NameAll,
         /// If `#[musli(tag = <expr>)]` is specified.
         tag: syn::Expr,
         /// If `#[musli(content = <expr>)]` is specified.
         content: syn::Expr,
         /// `#[musli(packed)]` or `#[musli(transparent)]`.
         packing: Packing,
+        /// `#[musli(deny_unknown_fields)]`.
+        deny_unknown_fields: (),
+        /// `#[musli(discriminant)]`.
+        discriminant: (),
+        /// `#[musli(from = <type>)]`.
+        decode_from: syn::Type,
+        /// `#[musli(try_from = <type>)]`.
+        decode_try_from: syn::Type,
+        /// `#[musli(into = <type>)]`.
+        encode_into: syn::Type,
+        /// `#[musli(validate = <path>)]`.
+        validate: syn::Path,
+        /// `#[musli(remote = <path>)]`.
+        remote: syn::Path,
         @multiple
         /// Bounds in a where predicate.
         bounds: syn::WherePredicate,
         /// Bounds to require for a `Decode` implementation.
         decode_bounds: syn::WherePredicate,
+        /// Tags reserved through `#[musli(reserved(..))]`.
+        reserved: syn::Expr,
     }
 }
 
@@ -352,6 +515,12 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
                 return Ok(());
             }
 
+            // parse #[musli(rename_all = "..")]
+            if meta.path == RENAME_ALL {
+                new.rename_all.push(parse_name_all(&meta)?);
+                return Ok(());
+            }
+
             // parse #[musli(bound = {..})]
             if meta.path == BOUND {
                 meta.input.parse::<Token![=]>()?;
@@ -378,6 +547,62 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
                 return Ok(());
             }
 
+            // parse #[musli(deny_unknown_fields)]
+            if meta.path == DENY_UNKNOWN_FIELDS {
+                new.deny_unknown_fields.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // parse #[musli(discriminant)]
+            if meta.path == DISCRIMINANT {
+                new.discriminant.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // parse #[musli(from = <type>)]
+            if meta.path == FROM {
+                meta.input.parse::<Token![=]>()?;
+                new.decode_from
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(try_from = <type>)]
+            if meta.path == TRY_FROM {
+                meta.input.parse::<Token![=]>()?;
+                new.decode_try_from
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(into = <type>)]
+            if meta.path == INTO {
+                meta.input.parse::<Token![=]>()?;
+                new.encode_into
+                    .push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(validate = <path>)]
+            if meta.path == VALIDATE {
+                meta.input.parse::<Token![=]>()?;
+                new.validate.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(remote = <path>)]
+            if meta.path == REMOTE {
+                meta.input.parse::<Token![=]>()?;
+                new.remote.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(reserved(<expr>, ..))]
+            if meta.path == RESERVED {
+                parse_reserved(&meta, &mut new.reserved)?;
+                return Ok(());
+            }
+
             Err(syn::Error::new_spanned(
                 meta.path,
                 format_args!("#[{ATTR}] Unsupported type attribute"),
@@ -417,6 +642,21 @@ fn parse_bounds(
     Ok(())
 }
 
+fn parse_reserved(
+    meta: &syn::meta::ParseNestedMeta,
+    out: &mut Vec<(Span, syn::Expr)>,
+) -> syn::Result<()> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let tags = content.parse_terminated(syn::Expr::parse, Token![,])?;
+
+    for tag in tags {
+        out.push((tag.span(), tag));
+    }
+
+    Ok(())
+}
+
 layer! {
     VariantAttr, VariantLayerNew, VariantLayer {
         /// `#[musli(name_type)]`.
@@ -431,7 +671,13 @@ layer! {
         default_field: (),
         /// `#[musli(default_field_name = "..")]`.
         default_field_name: DefaultTag,
+        /// `#[musli(rename_all = "..")]`.
+        rename_all: NameAll,
         @multiple
+        /// Alternative tags accepted when decoding the variant.
+        alias: syn::Expr,
+        /// Field tags reserved through `#[musli(reserved(..))]`.
+        reserved: syn::Expr,
     }
 }
 
@@ -495,6 +741,19 @@ pub(crate) fn variant_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> VariantAttr
                 return Ok(());
             }
 
+            // parse #[musli(alias = <expr>)]
+            if meta.path == ALIAS {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(reserved(<expr>, ..))]
+            if meta.path == RESERVED {
+                parse_reserved(&meta, &mut new.reserved)?;
+                return Ok(());
+            }
+
             // parse #[musli(default)]
             if meta.path == DEFAULT {
                 new.default_field.push((meta.path.span(), ()));
@@ -520,6 +779,12 @@ pub(crate) fn variant_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> VariantAttr
                 return Ok(());
             }
 
+            // parse #[musli(rename_all = "..")]
+            if meta.path == RENAME_ALL {
+                new.rename_all.push(parse_name_all(&meta)?);
+                return Ok(());
+            }
+
             // parse #[musli(packed)]
             if meta.path == PACKED {
                 new.packing.push((meta.path.span(), Packing::Packed));
@@ -564,13 +829,27 @@ layer! {
         decode_path: syn::Path,
         /// Method to check if we want to skip encoding.
         skip_encoding_if: syn::Path,
+        /// Function used to validate the field after it's been decoded.
+        validate: syn::Path,
+        /// Function used to access the field of a remote type.
+        getter: syn::Path,
         /// Rename a field to the given literal.
         rename: syn::Expr,
-        /// Use a default value for the field if it's not available.
-        default_field: (),
+        /// Use a default value for the field if it's not available, optionally
+        /// constructed using the given function.
+        default_field: Option<syn::Path>,
+        /// Never encode the field, and always use its default value when
+        /// decoding.
+        skip: (),
+        /// Encode and decode the field as bytes.
+        bytes: (),
         /// Use the alternate TraceDecode for the field.
         trace: (),
+        /// Encode and decode the fields of the field inline in the container.
+        flatten: (),
         @multiple
+        /// Alternative tags accepted when decoding the field.
+        alias: syn::Expr,
     }
 }
 
@@ -588,6 +867,8 @@ impl Field {
             }
 
             (*span, encode_path)
+        } else if let Some(&(span, ())) = self.bytes(mode) {
+            (span, mode.encode_bytes_t_encode_bytes())
         } else {
             let trace = self.trace(mode).is_some();
             let encode_path = mode.encode_t_encode(trace);
@@ -608,6 +889,8 @@ impl Field {
             }
 
             (*span, decode_path)
+        } else if let Some(&(span, ())) = self.bytes(mode) {
+            (span, mode.decode_bytes_t_decode_bytes())
         } else {
             let trace = self.trace(mode).is_some();
             let decode_path = mode.decode_t_decode(trace);
@@ -689,6 +972,13 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            // parse #[musli(validate = <path>)]
+            if meta.path == VALIDATE {
+                meta.input.parse::<Token![=]>()?;
+                new.validate.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             // parse #[musli(rename = <expr>)]
             if meta.path == RENAME {
                 meta.input.parse::<Token![=]>()?;
@@ -696,9 +986,41 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
-            // parse #[musli(default)]
+            // parse #[musli(alias = <expr>)]
+            if meta.path == ALIAS {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // parse #[musli(default [= <path>])]
             if meta.path == DEFAULT {
-                new.default_field.push((meta.path.span(), ()));
+                let path = if meta.input.parse::<Option<Token![=]>>()?.is_some() {
+                    Some(meta.input.parse()?)
+                } else {
+                    None
+                };
+
+                new.default_field.push((meta.path.span(), path));
+                return Ok(());
+            }
+
+            // parse #[musli(bytes)]
+            if meta.path == BYTES {
+                new.bytes.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // parse #[musli(skip)]
+            if meta.path == SKIP {
+                new.skip.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // parse #[musli(getter = <path>)]
+            if meta.path == GETTER {
+                meta.input.parse::<Token![=]>()?;
+                new.getter.push((meta.path.span(), meta.input.parse()?));
                 return Ok(());
             }
 
@@ -708,6 +1030,12 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            // parse #[musli(flatten)]
+            if meta.path == FLATTEN {
+                new.flatten.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
             Err(syn::Error::new_spanned(
                 meta.path,
                 format_args!("#[{ATTR}] Unsupported field attribute"),
@@ -1,15 +1,17 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use proc_macro2::Span;
+use proc_macro2::{Literal, Span};
+use quote::{quote, ToTokens};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::Token;
 
 use crate::de::{build_call, build_reference};
-use crate::expander::Taggable;
+use crate::expander::{determine_tag_method, rename_lit, Taggable};
 use crate::expander::{
     Data, EnumData, Expander, FieldData, Result, StructData, TagMethod, VariantData,
 };
-use crate::internals::attr::{DefaultTag, EnumTagging, Packing};
+use crate::internals::attr::{DefaultTag, EnumTagging, NameAll, Packing};
 use crate::internals::symbol::*;
 use crate::internals::tokens::Tokens;
 use crate::internals::{Ctxt, Expansion, Mode, ModePath, Only};
@@ -26,6 +28,19 @@ pub(crate) struct Build<'a> {
     pub(crate) encode_t_encode: syn::Path,
     pub(crate) mode_ident: ModePath<'a>,
     pub(crate) enum_tagging_span: Option<Span>,
+    pub(crate) encode_into: Option<&'a syn::Type>,
+    pub(crate) decode_from: Option<DecodeFrom<'a>>,
+    pub(crate) validate: Option<&'a (Span, syn::Path)>,
+    pub(crate) remote: Option<&'a syn::Path>,
+    pub(crate) receiver: syn::Ident,
+    pub(crate) self_path: syn::Path,
+}
+
+/// How a type is decoded from a proxy type, as specified through
+/// `#[musli(from = <type>)]` or `#[musli(try_from = <type>)]`.
+pub(crate) enum DecodeFrom<'a> {
+    From(&'a syn::Type),
+    TryFrom(&'a syn::Type),
 }
 
 impl Build<'_> {
@@ -38,15 +53,6 @@ impl Build<'_> {
         );
     }
 
-    /// Emit diagnostics indicating that we tried to implement decode for a
-    /// packed enum.
-    pub(crate) fn decode_packed_enum_diagnostics(&self, span: Span) {
-        self.cx.error_span(
-            span,
-            format_args!("#[{ATTR}({PACKED})] cannot be used to decode enums"),
-        );
-    }
-
     /// Emit diagnostics indicating that we tried to use a `#[musli(default)]`
     /// annotation on a packed container.
     pub(crate) fn packed_default_diagnostics(&self, span: Span) {
@@ -56,21 +62,32 @@ impl Build<'_> {
         );
     }
 
-    /// Emit diagnostics for a transparent encode / decode that failed because
-    /// the wrong number of fields existed.
-    pub(crate) fn transparent_diagnostics(&self, span: Span, fields: &[Field]) {
-        if fields.is_empty() {
-            self.cx.error_span(
-                span,
-                format_args!("#[{ATTR}({TRANSPARENT})] types must have a single field",),
-            );
-        } else {
-            self.cx.error_span(
-                span,
-                format_args!(
-                    "#[{ATTR}({TRANSPARENT})] can only be used on types which have a single field",
-                ),
-            );
+    /// Get the single field of a transparent body, ignoring skipped fields.
+    ///
+    /// Emits diagnostics if the wrong number of fields exist.
+    pub(crate) fn transparent_field<'b, 'f>(&self, st: &'b Body<'f>) -> Result<&'b Field<'f>> {
+        let mut fields = st.unskipped_fields();
+
+        match (fields.next(), fields.next()) {
+            (Some(f), None) => Ok(f),
+            (None, _) => {
+                self.cx.error_span(
+                    st.span,
+                    format_args!("#[{ATTR}({TRANSPARENT})] types must have a single field",),
+                );
+
+                Err(())
+            }
+            _ => {
+                self.cx.error_span(
+                    st.span,
+                    format_args!(
+                        "#[{ATTR}({TRANSPARENT})] can only be used on types which have a single field",
+                    ),
+                );
+
+                Err(())
+            }
         }
     }
 
@@ -120,9 +137,17 @@ pub(crate) struct Body<'a> {
     pub(crate) packing: Packing,
     pub(crate) path: syn::Path,
     pub(crate) field_tag_method: TagMethod,
+    pub(crate) deny_unknown_fields: bool,
+    /// Reserved tags which are skipped when decoding.
+    pub(crate) reserved: Vec<syn::Expr>,
 }
 
-impl Body<'_> {
+impl<'a> Body<'a> {
+    /// Iterate over fields which are not skipped.
+    pub(crate) fn unskipped_fields(&self) -> impl Iterator<Item = &Field<'a>> + '_ {
+        self.fields.iter().filter(|f| f.skip.is_none())
+    }
+
     pub(crate) fn name_format(&self, value: &syn::Expr) -> syn::Expr {
         match self.name_format_with {
             Some((_, path)) => build_call(path, [build_reference(value.clone())]),
@@ -142,6 +167,7 @@ pub(crate) struct Enum<'a> {
     pub(crate) name_type: Option<&'a (Span, syn::Type)>,
     pub(crate) name_format_with: Option<&'a (Span, syn::Path)>,
     pub(crate) packing_span: Option<&'a (Span, Packing)>,
+    pub(crate) discriminant: Option<syn::Ident>,
 }
 
 impl Enum<'_> {
@@ -157,6 +183,8 @@ pub(crate) struct Variant<'a> {
     pub(crate) span: Span,
     pub(crate) index: usize,
     pub(crate) tag: syn::Expr,
+    pub(crate) aliases: Vec<syn::Expr>,
+    pub(crate) discriminant: syn::Expr,
     pub(crate) is_default: bool,
     pub(crate) st: Body<'a>,
     pub(crate) patterns: Punctuated<syn::FieldPat, Token![,]>,
@@ -168,12 +196,17 @@ pub(crate) struct Field<'a> {
     pub(crate) encode_path: (Span, syn::Path),
     pub(crate) decode_path: (Span, syn::Path),
     pub(crate) tag: syn::Expr,
+    pub(crate) aliases: Vec<syn::Expr>,
     pub(crate) skip_encoding_if: Option<&'a (Span, syn::Path)>,
-    pub(crate) default_attr: Option<Span>,
+    pub(crate) validate: Option<&'a (Span, syn::Path)>,
+    pub(crate) default_attr: Option<(Span, Option<&'a syn::Path>)>,
+    pub(crate) skip: Option<Span>,
     pub(crate) self_access: syn::Expr,
     pub(crate) member: syn::Member,
+    pub(crate) ty: &'a syn::Type,
     pub(crate) packing: Packing,
     pub(crate) var: syn::Ident,
+    pub(crate) flatten: Option<Span>,
 }
 
 /// Setup a build.
@@ -195,6 +228,38 @@ pub(crate) fn setup<'a>(
         }
     };
 
+    let decode_from = match (
+        e.type_attr.decode_from(mode),
+        e.type_attr.decode_try_from(mode),
+    ) {
+        (Some(..), Some((span, _))) if mode.only == Only::Decode => {
+            e.cx.error_span(
+                *span,
+                format_args!("#[{ATTR}({TRY_FROM})] cannot be combined with #[{ATTR}({FROM})]"),
+            );
+
+            return Err(());
+        }
+        (Some((_, ty)), _) => Some(DecodeFrom::From(ty)),
+        (None, Some((_, ty))) => Some(DecodeFrom::TryFrom(ty)),
+        (None, None) => None,
+    };
+
+    // Remote types are implemented through free functions which are generic
+    // over the mode, so they can't be specialized for individual modes.
+    let remote = match (e.type_attr.remote(mode), expansion) {
+        (Some((_, path)), Expansion::Generic { .. }) => Some(path),
+        (Some((span, _)), _) => {
+            e.cx.error_span(
+                *span,
+                format_args!("#[{ATTR}({REMOTE})] cannot be combined with #[{ATTR}({MODE})]"),
+            );
+
+            return Err(());
+        }
+        (None, _) => None,
+    };
+
     Ok(Build {
         input: e.input,
         cx: &e.cx,
@@ -207,33 +272,82 @@ pub(crate) fn setup<'a>(
         encode_t_encode: mode.encode_t_encode(false),
         mode_ident: mode.mode_ident(),
         enum_tagging_span: e.type_attr.enum_tagging_span(mode),
+        encode_into: e.type_attr.encode_into(mode).map(|(_, ty)| ty),
+        decode_from,
+        validate: e.type_attr.validate(mode),
+        remote,
+        receiver: receiver(e, mode),
+        self_path: self_path(e, mode, e.input.ident.span()),
     })
 }
 
+/// The receiver through which fields are accessed when encoding, which is
+/// `self` unless `#[musli(remote = <path>)]` is used.
+fn receiver(e: &Expander, mode: Mode<'_>) -> syn::Ident {
+    match e.type_attr.remote(mode) {
+        Some(..) => e.cx.ident("remote"),
+        None => syn::Ident::new("self", Span::call_site()),
+    }
+}
+
+/// The path used to construct and match on the type being derived, which is
+/// `Self` unless `#[musli(remote = <path>)]` is used.
+fn self_path(e: &Expander, mode: Mode<'_>, span: Span) -> syn::Path {
+    match e.type_attr.remote(mode) {
+        Some((_, path)) => path.clone(),
+        None => syn::Path::from(syn::Ident::new("Self", span)),
+    }
+}
+
 fn setup_struct<'a>(e: &'a Expander, mode: Mode<'_>, data: &'a StructData<'a>) -> Result<Body<'a>> {
     let mut fields = Vec::with_capacity(data.fields.len());
 
     let default_field_name = e.type_attr.default_field_name(mode).map(|&(_, v)| v);
+    let name_all = e.type_attr.rename_all(mode).map(|&(_, v)| v);
     let packing = e
         .type_attr
         .packing(mode)
         .map(|&(_, p)| p)
         .unwrap_or_default();
-    let path = syn::Path::from(syn::Ident::new("Self", e.input.ident.span()));
+    let path = self_path(e, mode, e.input.ident.span());
     let mut tag_methods = TagMethods::new(&e.cx);
 
+    if let Some(&(span, ())) = e.type_attr.discriminant(mode) {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}({DISCRIMINANT})] is only supported on enums"),
+        );
+    }
+
     for f in &data.fields {
         fields.push(setup_field(
             e,
             mode,
             f,
             default_field_name,
+            name_all,
             packing,
             None,
             &mut tag_methods,
         )?);
     }
 
+    check_field_aliases(e, mode, &fields);
+    let deny_unknown_fields = deny_unknown_fields(e, mode, &fields);
+    let field_tag_method = tag_methods.pick();
+
+    let reserved = e.type_attr.reserved(mode);
+
+    check_reserved(
+        e,
+        mode,
+        reserved,
+        fields
+            .iter()
+            .filter(|f| f.flatten.is_none() && f.skip.is_none())
+            .map(|f| (f.span, &f.tag, &f.aliases[..])),
+    );
+
     Ok(Body {
         span: data.span,
         name: &data.name,
@@ -242,7 +356,9 @@ fn setup_struct<'a>(e: &'a Expander, mode: Mode<'_>, data: &'a StructData<'a>) -
         name_format_with: e.type_attr.name_format_with(mode),
         packing,
         path,
-        field_tag_method: tag_methods.pick(),
+        field_tag_method,
+        deny_unknown_fields,
+        reserved: expand_reserved(reserved, field_tag_method),
     })
 }
 
@@ -273,10 +389,81 @@ fn setup_enum<'a>(e: &'a Expander, mode: Mode<'_>, data: &'a EnumData<'a>) -> Re
         }
     }
 
+    let discriminant = match e.type_attr.discriminant(mode) {
+        Some(&(span, ())) => {
+            if enum_tagging.is_some() {
+                e.cx.error_span(
+                    span,
+                    format_args!("#[{ATTR}({DISCRIMINANT})] cannot be combined with #[{ATTR}({TAG})] or #[{ATTR}({CONTENT})]"),
+                );
+            }
+
+            if let Some(&(_, packing)) = packing_span {
+                e.cx.error_span(
+                    span,
+                    format_args!(
+                        "#[{ATTR}({DISCRIMINANT})] cannot be combined with #[{ATTR}({packing})]"
+                    ),
+                );
+            }
+
+            for v in data.variants.iter().filter(|v| !v.fields.is_empty()) {
+                e.cx.error_span(
+                    v.span,
+                    format_args!("#[{ATTR}({DISCRIMINANT})] variants cannot have fields"),
+                );
+            }
+
+            Some(repr_type(e.input))
+        }
+        None => None,
+    };
+
+    // Variants without an explicit discriminant are numbered from the last
+    // explicit one, the same way the compiler does it.
+    let mut last_discriminant = None;
+    let mut offset = 0usize;
+
     for v in &data.variants {
-        variants.push(setup_variant(e, mode, v, &mut fallback, &mut tag_methods)?);
+        if let Some(expr) = v.discriminant {
+            last_discriminant = Some(expr);
+            offset = 0;
+        }
+
+        let discriminant = match (last_discriminant, Literal::usize_unsuffixed(offset)) {
+            (Some(expr), _) if offset == 0 => expr.clone(),
+            (Some(expr), offset) => syn::Expr::Verbatim(quote!((#expr) + #offset)),
+            (None, offset) => syn::Expr::Verbatim(quote!(#offset)),
+        };
+
+        offset += 1;
+
+        variants.push(setup_variant(
+            e,
+            mode,
+            v,
+            discriminant,
+            &mut fallback,
+            &mut tag_methods,
+        )?);
     }
 
+    check_aliases(
+        e,
+        mode,
+        variants
+            .iter()
+            .filter(|v| !v.is_default)
+            .map(|v| (&v.tag, &v.aliases[..])),
+    );
+
+    check_reserved(
+        e,
+        mode,
+        e.type_attr.reserved(mode),
+        variants.iter().map(|v| (v.span, &v.tag, &v.aliases[..])),
+    );
+
     Ok(Enum {
         span: data.span,
         name: &data.name,
@@ -288,13 +475,43 @@ fn setup_enum<'a>(e: &'a Expander, mode: Mode<'_>, data: &'a EnumData<'a>) -> Re
         name_type: e.type_attr.name_type(mode),
         name_format_with: e.type_attr.name_format_with(mode),
         packing_span,
+        discriminant,
     })
 }
 
+/// Get the integer type declared through `#[repr(..)]`, which defaults to
+/// `isize` the same way it does for enums without one.
+fn repr_type(input: &syn::DeriveInput) -> syn::Ident {
+    const INTEGERS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut repr = None;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INTEGERS.iter().any(|integer| ident == integer) {
+                    repr = Some(ident.clone());
+                }
+            }
+
+            Ok(())
+        });
+    }
+
+    repr.unwrap_or_else(|| syn::Ident::new("isize", input.ident.span()))
+}
+
 fn setup_variant<'a>(
     e: &'a Expander<'_>,
     mode: Mode<'_>,
     data: &'a VariantData<'a>,
+    discriminant: syn::Expr,
     fallback: &mut Option<&'a syn::Ident>,
     tag_methods: &mut TagMethods,
 ) -> Result<Variant<'a>> {
@@ -313,14 +530,18 @@ fn setup_variant<'a>(
         .or_else(|| e.type_attr.default_field_name(mode))
         .map(|&(_, v)| v);
 
+    let name_all = data.attr.rename_all(mode).map(|&(_, v)| v);
+
     let (tag, tag_method) = data.expand_tag(
         e,
         mode,
         e.type_attr.default_variant_name(mode).map(|&(_, v)| v),
+        e.type_attr.rename_all(mode).map(|&(_, v)| v),
     )?;
     tag_methods.insert(data.span, tag_method);
+    let aliases = expand_aliases(data.attr.alias(mode), tag_methods);
 
-    let mut path = syn::Path::from(syn::Ident::new("Self", data.span));
+    let mut path = self_path(e, mode, data.span);
     path.segments.push(data.ident.clone().into());
 
     let is_default = if data.attr.default_attr(mode).is_some() {
@@ -355,16 +576,35 @@ fn setup_variant<'a>(
             mode,
             f,
             default_field_name,
+            name_all,
             variant_packing,
             Some(&mut patterns),
             &mut field_tag_methods,
         )?);
     }
 
+    check_field_aliases(e, mode, &fields);
+    let deny_unknown_fields = deny_unknown_fields(e, mode, &fields);
+    let field_tag_method = field_tag_methods.pick();
+
+    let reserved = data.attr.reserved(mode);
+
+    check_reserved(
+        e,
+        mode,
+        reserved,
+        fields
+            .iter()
+            .filter(|f| f.flatten.is_none() && f.skip.is_none())
+            .map(|f| (f.span, &f.tag, &f.aliases[..])),
+    );
+
     Ok(Variant {
         span: data.span,
         index: data.index,
         tag,
+        aliases,
+        discriminant,
         is_default,
         patterns,
         st: Body {
@@ -374,8 +614,10 @@ fn setup_variant<'a>(
             packing: variant_packing,
             name_type: data.attr.name_type(mode),
             name_format_with: data.attr.name_format_with(mode),
-            field_tag_method: field_tag_methods.pick(),
+            field_tag_method,
             path,
+            deny_unknown_fields,
+            reserved: expand_reserved(reserved, field_tag_method),
         },
     })
 }
@@ -385,16 +627,67 @@ fn setup_field<'a>(
     mode: Mode<'_>,
     data: &'a FieldData<'a>,
     default_field_name: Option<DefaultTag>,
+    name_all: Option<NameAll>,
     packing: Packing,
     patterns: Option<&mut Punctuated<syn::FieldPat, Token![,]>>,
     tag_methods: &mut TagMethods,
 ) -> Result<Field<'a>> {
     let encode_path = data.attr.encode_path_expanded(mode, data.span);
     let decode_path = data.attr.decode_path_expanded(mode, data.span);
-    let (tag, tag_method) = data.expand_tag(e, mode, default_field_name)?;
-    tag_methods.insert(data.span, tag_method);
+    let (tag, tag_method) = data.expand_tag(e, mode, default_field_name, name_all)?;
+    let skip = data.attr.skip(mode).map(|&(s, ())| s);
+
+    // Skipped fields are never tagged, so they don't affect the tag method.
+    let aliases = if skip.is_none() {
+        tag_methods.insert(data.span, tag_method);
+        expand_aliases(data.attr.alias(mode), tag_methods)
+    } else {
+        Vec::new()
+    };
+
     let skip_encoding_if = data.attr.skip_encoding_if(mode);
-    let default_attr = data.attr.default_field(mode).map(|&(s, ())| s);
+    let validate = data.attr.validate(mode);
+    let default_attr = data
+        .attr
+        .default_field(mode)
+        .map(|(s, path)| (*s, path.as_ref()));
+    let flatten = data.attr.flatten(mode).map(|&(s, ())| s);
+    let getter = data.attr.getter(mode);
+
+    if let Some((span, _)) = getter.filter(|_| mode.only == Only::Encode) {
+        if e.type_attr.remote(mode).is_none() {
+            e.cx.error_span(
+                *span,
+                format_args!("#[{ATTR}({GETTER})] can only be used in #[{ATTR}({REMOTE})] types"),
+            );
+        } else if patterns.is_some() {
+            e.cx.error_span(
+                *span,
+                format_args!("#[{ATTR}({GETTER})] cannot be used in enum variants"),
+            );
+        }
+    }
+
+    if let (Some((span, ())), Some(..)) = (data.attr.bytes(mode), data.attr.encode_path(mode)) {
+        e.cx.error_span(
+            *span,
+            format_args!("#[{ATTR}({BYTES})] cannot be combined with #[{ATTR}({WITH})]"),
+        );
+    }
+
+    if let (Some(span), Some(..)) = (skip, flatten) {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}({SKIP})] cannot be combined with #[{ATTR}({FLATTEN})]"),
+        );
+    }
+
+    if let (Some(span), Packing::Packed | Packing::Transparent) = (flatten, packing) {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}({FLATTEN})] fields cannot be used in a {packing} container"),
+        );
+    }
 
     let member = match data.ident {
         Some(ident) => syn::Member::Named(ident.clone()),
@@ -404,7 +697,8 @@ fn setup_field<'a>(
         }),
     };
 
-    let self_access = if let Some(patterns) = patterns {
+    // Skipped fields are never accessed, so they are left out of the pattern.
+    let self_access = if let Some(patterns) = patterns.filter(|_| skip.is_none()) {
         match data.ident {
             Some(ident) => {
                 patterns.push(syn::FieldPat {
@@ -446,23 +740,21 @@ fn setup_field<'a>(
             }
         }
     } else {
-        let expr = syn::Expr::Field(syn::ExprField {
+        let receiver = syn::Expr::Path(syn::ExprPath {
             attrs: Vec::new(),
-            base: Box::new(syn::Expr::Path(syn::ExprPath {
-                attrs: Vec::new(),
-                qself: None,
-                path: <Token![self]>::default().into(),
-            })),
-            dot_token: <Token![.]>::default(),
-            member: member.clone(),
+            qself: None,
+            path: receiver(e, mode).into(),
         });
 
-        syn::Expr::Reference(syn::ExprReference {
-            attrs: Vec::new(),
-            and_token: <Token![&]>::default(),
-            mutability: None,
-            expr: Box::new(expr),
-        })
+        match getter {
+            Some((_, path)) => build_call(path, [receiver]),
+            None => build_reference(syn::Expr::Field(syn::ExprField {
+                attrs: Vec::new(),
+                base: Box::new(receiver),
+                dot_token: <Token![.]>::default(),
+                member: member.clone(),
+            })),
+        }
     };
 
     let var = match &member {
@@ -476,15 +768,133 @@ fn setup_field<'a>(
         encode_path,
         decode_path,
         tag,
+        aliases,
         skip_encoding_if,
+        validate,
         default_attr,
+        skip,
         self_access,
         member,
+        ty: data.ty,
         packing,
         var,
+        flatten,
     })
 }
 
+/// Expand aliases to tag expressions, registering their tag methods.
+fn expand_aliases(aliases: &[(Span, syn::Expr)], tag_methods: &mut TagMethods) -> Vec<syn::Expr> {
+    aliases
+        .iter()
+        .map(|(span, alias)| {
+            tag_methods.insert(*span, determine_tag_method(alias));
+            rename_lit(alias)
+        })
+        .collect()
+}
+
+/// Test if unknown fields should be rejected, which cannot be combined with
+/// flattened fields since they are the ones which receive unknown fields.
+fn deny_unknown_fields(e: &Expander, mode: Mode<'_>, fields: &[Field<'_>]) -> bool {
+    let Some(&(span, ())) = e.type_attr.deny_unknown_fields(mode) else {
+        return false;
+    };
+
+    if mode.only == Only::Decode && fields.iter().any(|f| f.flatten.is_some()) {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "#[{ATTR}({DENY_UNKNOWN_FIELDS})] cannot be combined with #[{ATTR}({FLATTEN})] fields"
+            ),
+        );
+    }
+
+    true
+}
+
+/// Check that the aliases of fields which are decoded by tag are unique.
+fn check_field_aliases(e: &Expander, mode: Mode<'_>, fields: &[Field<'_>]) {
+    check_aliases(
+        e,
+        mode,
+        fields
+            .iter()
+            .filter(|f| f.flatten.is_none() && f.skip.is_none())
+            .map(|f| (&f.tag, &f.aliases[..])),
+    );
+}
+
+/// Check that no alias collides with a tag or with another alias.
+fn check_aliases<'a, I>(e: &Expander, mode: Mode<'_>, tags: I)
+where
+    I: Clone + IntoIterator<Item = (&'a syn::Expr, &'a [syn::Expr])>,
+{
+    // Aliases only affect decoding.
+    if mode.only != Only::Decode {
+        return;
+    }
+
+    let mut seen = HashSet::new();
+
+    for (tag, _) in tags.clone() {
+        seen.insert(tag.to_token_stream().to_string());
+    }
+
+    for (_, aliases) in tags {
+        for alias in aliases {
+            if !seen.insert(alias.to_token_stream().to_string()) {
+                e.cx.error_span(
+                    alias.span(),
+                    format_args!("#[{ATTR}({ALIAS})] collides with another tag"),
+                );
+            }
+        }
+    }
+}
+
+/// Check that no tag or alias collides with a reserved tag.
+fn check_reserved<'a, I>(e: &Expander, mode: Mode<'_>, reserved: &[(Span, syn::Expr)], tags: I)
+where
+    I: IntoIterator<Item = (Span, &'a syn::Expr, &'a [syn::Expr])>,
+{
+    // Only report collisions once, since both derives see the same tags.
+    if mode.only != Only::Decode || reserved.is_empty() {
+        return;
+    }
+
+    let reserved = reserved
+        .iter()
+        .map(|(_, tag)| (rename_lit(tag).to_token_stream().to_string(), tag))
+        .collect::<HashMap<_, _>>();
+
+    for (span, tag, aliases) in tags {
+        let tags = [(span, tag)]
+            .into_iter()
+            .chain(aliases.iter().map(|alias| (alias.span(), alias)));
+
+        for (span, tag) in tags {
+            if let Some(tag) = reserved.get(&tag.to_token_stream().to_string()) {
+                let tag = tag.to_token_stream();
+
+                e.cx.error_span(
+                    span,
+                    format_args!("Tag `{tag}` is reserved through #[{ATTR}({RESERVED})]"),
+                );
+            }
+        }
+    }
+}
+
+/// Expand reserved tags to the ones which can be matched against tags decoded
+/// using the given tag method.
+fn expand_reserved(reserved: &[(Span, syn::Expr)], tag_method: TagMethod) -> Vec<syn::Expr> {
+    reserved
+        .iter()
+        .filter(|(_, tag)| determine_tag_method(tag).map_or(true, |m| m == tag_method))
+        .map(|(_, tag)| rename_lit(tag))
+        .collect()
+}
+
 struct TagMethods<'a> {
     cx: &'a Ctxt,
     methods: BTreeSet<TagMethod>,
@@ -5,6 +5,7 @@ use crate::internals::{Mode, ModePath, Only};
 pub(crate) enum Expansion<'a> {
     Generic { mode_ident: &'a syn::Ident },
     Default,
+    Text,
     Moded { mode_ident: &'a syn::Path },
 }
 
@@ -23,6 +24,12 @@ impl<'a> Expansion<'a> {
                 tokens,
                 only,
             },
+            Expansion::Text => Mode {
+                ident: None,
+                mode_path: ModePath::Path(&tokens.text_mode),
+                tokens,
+                only,
+            },
             Expansion::Moded { mode_ident } => Mode {
                 ident: Some(mode_ident),
                 mode_path: ModePath::Path(mode_ident),
@@ -78,6 +85,7 @@ impl<'a> Expansion<'a> {
                 (impl_generics, path, Some(where_clause))
             }
             Expansion::Default => (generics, tokens.default_mode.clone(), None),
+            Expansion::Text => (generics, tokens.text_mode.clone(), None),
             Expansion::Moded { mode_ident } => (generics, mode_ident.clone(), None),
         }
     }
@@ -38,49 +38,45 @@ impl<'a> Mode<'a> {
 
     /// Construct a typed encode call.
     pub(crate) fn encode_t_encode(&self, trace: bool) -> syn::Path {
-        let moded_ident = &self.mode_path;
-
-        let (mut encode_t, name) = if trace {
-            (self.tokens.trace_encode_t.clone(), "trace_encode")
+        if trace {
+            self.typed_call(&self.tokens.trace_encode_t, "trace_encode")
         } else {
-            (self.tokens.encode_t.clone(), "encode")
-        };
-
-        if let Some(segment) = encode_t.segments.last_mut() {
-            add_mode_argument(moded_ident, segment);
+            self.typed_call(&self.tokens.encode_t, "encode")
         }
-
-        encode_t
-            .segments
-            .push(syn::PathSegment::from(syn::Ident::new(
-                name,
-                encode_t.span(),
-            )));
-        encode_t
     }
 
     /// Construct a typed encode call.
     pub(crate) fn decode_t_decode(&self, trace: bool) -> syn::Path {
-        let moded_ident = &self.mode_path;
-
-        let (mut decode_t, method) = if trace {
-            (self.tokens.trace_decode_t.clone(), "trace_decode")
+        if trace {
+            self.typed_call(&self.tokens.trace_decode_t, "trace_decode")
         } else {
-            (self.tokens.decode_t.clone(), "decode")
-        };
+            self.typed_call(&self.tokens.decode_t, "decode")
+        }
+    }
+
+    /// Construct a typed call to encode bytes.
+    pub(crate) fn encode_bytes_t_encode_bytes(&self) -> syn::Path {
+        self.typed_call(&self.tokens.encode_bytes_t, "encode_bytes")
+    }
+
+    /// Construct a typed call to decode bytes.
+    pub(crate) fn decode_bytes_t_decode_bytes(&self) -> syn::Path {
+        self.typed_call(&self.tokens.decode_bytes_t, "decode_bytes")
+    }
+
+    /// Construct a call to the given method of a trait parameterized by the
+    /// mode.
+    fn typed_call(&self, trait_t: &syn::Path, method: &str) -> syn::Path {
+        let mut path = trait_t.clone();
 
-        if let Some(segment) = decode_t.segments.last_mut() {
-            add_mode_argument(moded_ident, segment);
+        if let Some(segment) = path.segments.last_mut() {
+            add_mode_argument(&self.mode_path, segment);
         }
 
-        decode_t
-            .segments
-            .push(syn::PathSegment::from(syn::Ident::new(
-                method,
-                decode_t.span(),
-            )));
+        path.segments
+            .push(syn::PathSegment::from(syn::Ident::new(method, path.span())));
 
-        decode_t
+        path
     }
 }
 
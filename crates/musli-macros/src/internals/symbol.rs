@@ -5,8 +5,10 @@ use syn::{Ident, Path};
 #[derive(Copy, Clone)]
 pub struct Symbol(&'static str);
 
+pub const ALIAS: Symbol = Symbol("alias");
 pub const ATTR: Symbol = Symbol("musli");
 pub const BOUND: Symbol = Symbol("bound");
+pub const BYTES: Symbol = Symbol("bytes");
 pub const DECODE_BOUND: Symbol = Symbol("decode_bound");
 pub const DECODE_ONLY: Symbol = Symbol("decode_only");
 pub const ENCODE_ONLY: Symbol = Symbol("encode_only");
@@ -15,12 +17,24 @@ pub const CRATE: Symbol = Symbol("crate");
 pub const DEFAULT_FIELD_NAME: Symbol = Symbol("default_field_name");
 pub const DEFAULT_VARIANT_NAME: Symbol = Symbol("default_variant_name");
 pub const DEFAULT: Symbol = Symbol("default");
+pub const DENY_UNKNOWN_FIELDS: Symbol = Symbol("deny_unknown_fields");
+pub const DISCRIMINANT: Symbol = Symbol("discriminant");
+pub const FLATTEN: Symbol = Symbol("flatten");
+pub const FROM: Symbol = Symbol("from");
+pub const GETTER: Symbol = Symbol("getter");
+pub const INTO: Symbol = Symbol("into");
 pub const MODE: Symbol = Symbol("mode");
 pub const PACKED: Symbol = Symbol("packed");
+pub const REMOTE: Symbol = Symbol("remote");
+pub const RESERVED: Symbol = Symbol("reserved");
 pub const RENAME: Symbol = Symbol("rename");
+pub const RENAME_ALL: Symbol = Symbol("rename_all");
+pub const SKIP: Symbol = Symbol("skip");
 pub const SKIP_ENCODING_IF: Symbol = Symbol("skip_encoding_if");
 pub const TAG: Symbol = Symbol("tag");
 pub const TRANSPARENT: Symbol = Symbol("transparent");
+pub const TRY_FROM: Symbol = Symbol("try_from");
+pub const VALIDATE: Symbol = Symbol("validate");
 pub const WITH: Symbol = Symbol("with");
 
 impl PartialEq<Symbol> for Ident {
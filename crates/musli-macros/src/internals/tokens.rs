@@ -4,6 +4,7 @@ use syn::Token;
 pub(crate) struct Tokens {
     pub(crate) as_decoder_t: syn::Path,
     pub(crate) decode_t: syn::Path,
+    pub(crate) decode_bytes_t: syn::Path,
     pub(crate) trace_decode_t: syn::Path,
     pub(crate) context_t: syn::Path,
     pub(crate) core_result: syn::Path,
@@ -11,9 +12,14 @@ pub(crate) struct Tokens {
     pub(crate) default_function: syn::Path,
     pub(crate) default_mode: syn::Path,
     pub(crate) encode_t: syn::Path,
+    pub(crate) encode_bytes_t: syn::Path,
+    pub(crate) encode_fields_t: syn::Path,
     pub(crate) trace_encode_t: syn::Path,
     pub(crate) encoder_t: syn::Path,
+    pub(crate) flatten_decoder: syn::Path,
     pub(crate) fmt: syn::Path,
+    pub(crate) from_t: syn::Path,
+    pub(crate) into_t: syn::Path,
     pub(crate) mode_t: syn::Path,
     pub(crate) pack_decoder_t: syn::Path,
     pub(crate) pair_decoder_t: syn::Path,
@@ -21,6 +27,9 @@ pub(crate) struct Tokens {
     pub(crate) pairs_decoder_t: syn::Path,
     pub(crate) pairs_encoder_t: syn::Path,
     pub(crate) sequence_encoder_t: syn::Path,
+    pub(crate) text_mode: syn::Path,
+    pub(crate) try_from_t: syn::Path,
+    pub(crate) untagged_errors: syn::Path,
     pub(crate) variant_decoder_t_tag: syn::Path,
     pub(crate) variant_decoder_t: syn::Path,
     pub(crate) variant_encoder_t: syn::Path,
@@ -38,14 +47,20 @@ impl Tokens {
             context_t: path(span, prefix, ["Context"]),
             core_result: core(span, ["result", "Result"]),
             decode_t: path(span, prefix, ["de", "Decode"]),
+            decode_bytes_t: path(span, prefix, ["de", "DecodeBytes"]),
             trace_decode_t: path(span, prefix, ["de", "TraceDecode"]),
             decoder_t: path(span, prefix, ["de", "Decoder"]),
             default_function: core(span, ["default", "Default", "default"]),
             default_mode: path(span, prefix, ["mode", "DefaultMode"]),
             encode_t: path(span, prefix, ["en", "Encode"]),
+            encode_bytes_t: path(span, prefix, ["en", "EncodeBytes"]),
+            encode_fields_t: path(span, prefix, ["en", "EncodeFields"]),
             trace_encode_t: path(span, prefix, ["en", "TraceEncode"]),
             encoder_t: path(span, prefix, ["en", "Encoder"]),
+            flatten_decoder: path(span, prefix, ["utils", "FlattenDecoder"]),
             fmt: core(span, ["fmt"]),
+            from_t: core(span, ["convert", "From"]),
+            into_t: core(span, ["convert", "Into"]),
             option_some: core(span, ["option", "Option", "Some"]),
             option_none: core(span, ["option", "Option", "None"]),
             result_ok: core(span, ["result", "Result", "Ok"]),
@@ -57,6 +72,9 @@ impl Tokens {
             pairs_decoder_t: path(span, prefix, ["de", "PairsDecoder"]),
             pairs_encoder_t: path(span, prefix, ["en", "PairsEncoder"]),
             sequence_encoder_t: path(span, prefix, ["en", "SequenceEncoder"]),
+            text_mode: path(span, prefix, ["mode", "TextMode"]),
+            try_from_t: core(span, ["convert", "TryFrom"]),
+            untagged_errors: path(span, prefix, ["utils", "UntaggedErrors"]),
             variant_decoder_t_tag: path(span, prefix, ["de", "VariantDecoder", "tag"]),
             variant_decoder_t: path(span, prefix, ["de", "VariantDecoder"]),
             variant_encoder_t: path(span, prefix, ["en", "VariantEncoder"]),
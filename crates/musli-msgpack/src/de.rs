@@ -0,0 +1,1325 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use musli::de::AsDecoder;
+use musli::de::{
+    Decoder, NumberHint, NumberVisitor, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder,
+    SizeHint, TypeHint, ValueVisitor, VariantDecoder, Visitor,
+};
+#[cfg(feature = "alloc")]
+use musli::mode::Mode;
+use musli::Context;
+use musli_common::reader::Reader;
+#[cfg(feature = "alloc")]
+use musli_common::reader::SliceReader;
+
+use crate::error::Error;
+use crate::marker::{Kind, Len, Marker};
+
+/// What a decoder is currently positioned at.
+#[derive(Debug, Clone, Copy)]
+enum State {
+    /// A regular value which starts with a marker.
+    Value,
+    /// The type of an extension, which has already been read.
+    ExtType(i8),
+    /// The data of an extension with the given length.
+    ExtData(usize),
+}
+
+/// A decoded MessagePack integer.
+#[derive(Debug, Clone, Copy)]
+enum Integer {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+/// A MessagePack decoder.
+pub struct MsgPackDecoder<R> {
+    reader: R,
+    state: State,
+}
+
+impl<R> MsgPackDecoder<R> {
+    /// Construct a new MessagePack decoder.
+    #[inline]
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            state: State::Value,
+        }
+    }
+
+    #[inline]
+    fn with_state(reader: R, state: State) -> Self {
+        Self { reader, state }
+    }
+}
+
+/// Decoder for a sequence or pack of values.
+pub struct MsgPackSequenceDecoder<R> {
+    reader: R,
+    remaining: usize,
+}
+
+/// Decoder for a map or struct.
+pub struct MsgPackMapDecoder<R> {
+    reader: R,
+    remaining: usize,
+}
+
+/// Decoder for a variant, which is either a map with a single entry or an
+/// extension.
+pub struct MsgPackVariantDecoder<R> {
+    reader: R,
+    ext: Option<(i8, usize)>,
+}
+
+impl<'de, R> MsgPackDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Skip over any value.
+    pub(crate) fn skip_any<'buf, C>(&mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.state {
+            State::Value => {}
+            State::ExtType(..) => return Ok(()),
+            State::ExtData(len) => return self.reader.skip(cx.adapt(), len),
+        }
+
+        let marker = Marker::from_byte(self.reader.read_byte(cx.adapt())?);
+
+        if let Some((kind, len)) = marker.prefix() {
+            let len = self.read_len(cx, len)?;
+
+            match kind {
+                Kind::String | Kind::Bytes => {
+                    self.reader.skip(cx.adapt(), len)?;
+                }
+                Kind::Array => {
+                    for _ in 0..len {
+                        self.skip_any(cx)?;
+                    }
+                }
+                Kind::Map => {
+                    for _ in 0..len {
+                        self.skip_any(cx)?;
+                        self.skip_any(cx)?;
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+
+        if let Some(len) = marker.ext() {
+            // The data is preceeded by the type of the extension.
+            let len = self.read_len(cx, len)?;
+            return self.reader.skip(cx.adapt(), len.saturating_add(1));
+        }
+
+        let len = match marker {
+            Marker::U8 | Marker::I8 => 1,
+            Marker::U16 | Marker::I16 => 2,
+            Marker::U32 | Marker::I32 | Marker::F32 => 4,
+            Marker::U64 | Marker::I64 | Marker::F64 => 8,
+            Marker::Reserved => {
+                return Err(cx.message(format_args!("unsupported marker {marker:?}")));
+            }
+            _ => 0,
+        };
+
+        self.reader.skip(cx.adapt(), len)
+    }
+
+    /// Read the marker of a regular value.
+    #[inline]
+    fn read_marker<'buf, C>(&mut self, cx: &mut C) -> Result<Marker, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.state {
+            State::Value => Ok(Marker::from_byte(self.reader.read_byte(cx.adapt())?)),
+            State::ExtType(..) => {
+                Err(cx.message("extension type can only be decoded as an integer"))
+            }
+            State::ExtData(..) => Err(cx.message("extension data can only be decoded as bytes")),
+        }
+    }
+
+    /// Read a length with the given width.
+    #[inline]
+    fn read_len<'buf, C>(&mut self, cx: &mut C, len: Len) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        Ok(match len {
+            Len::Fixed(len) => len as usize,
+            Len::U8 => self.reader.read_byte(cx.adapt())? as usize,
+            Len::U16 => u16::from_be_bytes(self.reader.read_array(cx.adapt())?) as usize,
+            Len::U32 => u32::from_be_bytes(self.reader.read_array(cx.adapt())?) as usize,
+        })
+    }
+
+    /// Read the integer following the given marker, if it is an integer marker.
+    #[inline]
+    fn read_integer<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        marker: Marker,
+    ) -> Result<Option<Integer>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let r = &mut self.reader;
+
+        Ok(Some(match marker {
+            Marker::FixPos(n) => Integer::Unsigned(n as u64),
+            Marker::FixNeg(n) => Integer::Signed(n as i64),
+            Marker::U8 => Integer::Unsigned(r.read_byte(cx.adapt())? as u64),
+            Marker::U16 => Integer::Unsigned(u16::from_be_bytes(r.read_array(cx.adapt())?) as u64),
+            Marker::U32 => Integer::Unsigned(u32::from_be_bytes(r.read_array(cx.adapt())?) as u64),
+            Marker::U64 => Integer::Unsigned(u64::from_be_bytes(r.read_array(cx.adapt())?)),
+            Marker::I8 => Integer::Signed(r.read_byte(cx.adapt())? as i8 as i64),
+            Marker::I16 => Integer::Signed(i16::from_be_bytes(r.read_array(cx.adapt())?) as i64),
+            Marker::I32 => Integer::Signed(i32::from_be_bytes(r.read_array(cx.adapt())?) as i64),
+            Marker::I64 => Integer::Signed(i64::from_be_bytes(r.read_array(cx.adapt())?)),
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Decode an integer of any width, and convert it into `T`.
+    #[inline]
+    fn decode_integer<'buf, C, T>(mut self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: TryFrom<u64> + TryFrom<i64>,
+    {
+        let mark = cx.mark();
+
+        let integer = if let State::ExtType(ty) = self.state {
+            Integer::Signed(ty as i64)
+        } else {
+            let marker = self.read_marker(cx)?;
+
+            match self.read_integer(cx, marker)? {
+                Some(integer) => integer,
+                None => {
+                    return Err(cx.marked_message(
+                        mark,
+                        Expected {
+                            expected: "integer",
+                            actual: marker,
+                        },
+                    ))
+                }
+            }
+        };
+
+        let value = match integer {
+            Integer::Unsigned(value) => <T as TryFrom<u64>>::try_from(value).ok(),
+            Integer::Signed(value) => <T as TryFrom<i64>>::try_from(value).ok(),
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => Err(cx.marked_message(mark, "integer out of range")),
+        }
+    }
+
+    /// Decode a 128-bit integer which does not fit in 64 bits, and is
+    /// therefore encoded as 16 big-endian bytes of binary data.
+    #[inline]
+    fn decode_wide<'buf, C>(&mut self, cx: &mut C) -> Result<Option<[u8; 16]>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if !matches!(self.state, State::Value)
+            || self.reader.peek(cx.adapt())? != Some(Marker::Bin8.byte())
+        {
+            return Ok(None);
+        }
+
+        let mark = cx.mark();
+        let len = self.decode_prefix(cx, Kind::Bytes, mark)?;
+
+        if len != 16 {
+            return Err(cx.marked_message(
+                mark,
+                format_args!("expected 16 bytes for a 128-bit integer, but got {len}"),
+            ));
+        }
+
+        Ok(Some(self.reader.read_array(cx.adapt())?))
+    }
+
+    /// Decode a floating point value, integers are converted.
+    #[inline]
+    fn decode_float<'buf, C>(mut self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+        let marker = self.read_marker(cx)?;
+
+        match marker {
+            Marker::F32 => Ok(f32::from_be_bytes(self.reader.read_array(cx.adapt())?) as f64),
+            Marker::F64 => Ok(f64::from_be_bytes(self.reader.read_array(cx.adapt())?)),
+            marker => match self.read_integer(cx, marker)? {
+                Some(Integer::Unsigned(value)) => Ok(value as f64),
+                Some(Integer::Signed(value)) => Ok(value as f64),
+                None => Err(cx.marked_message(
+                    mark,
+                    Expected {
+                        expected: "float",
+                        actual: marker,
+                    },
+                )),
+            },
+        }
+    }
+
+    /// Decode the length of a prefix.
+    #[inline]
+    fn decode_prefix<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        kind: Kind,
+        mark: C::Mark,
+    ) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let marker = self.read_marker(cx)?;
+
+        match marker.prefix() {
+            Some((actual, len)) if actual == kind => self.read_len(cx, len),
+            _ => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: kind.name(),
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    /// Decode the length of a string or binary data, which are treated
+    /// interchangeably since many implementations only support strings.
+    #[inline]
+    fn decode_raw_prefix<'buf, C>(&mut self, cx: &mut C, mark: C::Mark) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let State::ExtData(len) = self.state {
+            return Ok(len);
+        }
+
+        let marker = self.read_marker(cx)?;
+
+        match marker.prefix() {
+            Some((Kind::String | Kind::Bytes, len)) => self.read_len(cx, len),
+            _ => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "string or binary",
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    /// Peek the marker of the next value.
+    #[inline]
+    fn peek_marker<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Marker>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        Ok(self.reader.peek(cx.adapt())?.map(Marker::from_byte))
+    }
+}
+
+/// A buffered value, which holds a copy of the bytes the value was decoded
+/// from.
+#[cfg(feature = "alloc")]
+pub struct MsgPackBuffer {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl AsDecoder for MsgPackBuffer {
+    type Error = Error;
+    type Decoder<'this>
+        = MsgPackDecoder<SliceReader<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn as_decoder<'buf, C>(&self, _: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackDecoder::new(SliceReader::new(&self.bytes)))
+    }
+}
+
+/// A reader which keeps a copy of all the bytes read through it.
+#[cfg(feature = "alloc")]
+struct RecordingReader<R> {
+    reader: R,
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, R> Reader<'de> for RecordingReader<R>
+where
+    R: Reader<'de>,
+{
+    type Error = R::Error;
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn skip<'buf, C>(&mut self, cx: &mut C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx, &mut self.bytes[start..])
+    }
+
+    #[inline]
+    fn peek<'buf, C>(&mut self, cx: &mut C) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.reader.peek(cx)
+    }
+
+    #[inline]
+    fn read_bytes<'buf, C, V>(
+        &mut self,
+        cx: &mut C,
+        n: usize,
+        visitor: V,
+    ) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf>,
+        C::Input: From<Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let start = self.bytes.len();
+        self.bytes.resize(start + n, 0);
+        self.reader.read(cx.adapt(), &mut self.bytes[start..])?;
+        visitor.visit_ref(cx, &self.bytes[start..])
+    }
+}
+
+#[musli::decoder]
+impl<'de, R> Decoder<'de> for MsgPackDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    #[cfg(feature = "alloc")]
+    type Buffer = MsgPackBuffer;
+    type Pack = MsgPackSequenceDecoder<R>;
+    type Some = Self;
+    type Sequence = MsgPackSequenceDecoder<R>;
+    type Tuple = MsgPackSequenceDecoder<R>;
+    type Map = MsgPackMapDecoder<R>;
+    type Struct = MsgPackMapDecoder<R>;
+    type Variant = MsgPackVariantDecoder<R>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack decoder")
+    }
+
+    #[inline]
+    fn type_hint<'buf, C>(&mut self, cx: &mut C) -> Result<TypeHint, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let marker = match self.state {
+            State::Value => match self.peek_marker(cx)? {
+                Some(marker) => marker,
+                None => return Ok(TypeHint::Any),
+            },
+            State::ExtType(..) => return Ok(TypeHint::Number(NumberHint::I8)),
+            State::ExtData(len) => return Ok(TypeHint::Bytes(SizeHint::Exact(len))),
+        };
+
+        if let Some((kind, len)) = marker.prefix() {
+            let hint = match len {
+                Len::Fixed(len) => SizeHint::Exact(len as usize),
+                _ => SizeHint::Any,
+            };
+
+            return Ok(match kind {
+                Kind::String => TypeHint::String(hint),
+                Kind::Bytes => TypeHint::Bytes(hint),
+                Kind::Array => TypeHint::Sequence(hint),
+                Kind::Map => TypeHint::Map(hint),
+            });
+        }
+
+        if marker.ext().is_some() {
+            return Ok(TypeHint::Variant);
+        }
+
+        Ok(match marker {
+            Marker::FixPos(..) | Marker::U8 => TypeHint::Number(NumberHint::U8),
+            Marker::U16 => TypeHint::Number(NumberHint::U16),
+            Marker::U32 => TypeHint::Number(NumberHint::U32),
+            Marker::U64 => TypeHint::Number(NumberHint::U64),
+            Marker::FixNeg(..) | Marker::I8 => TypeHint::Number(NumberHint::I8),
+            Marker::I16 => TypeHint::Number(NumberHint::I16),
+            Marker::I32 => TypeHint::Number(NumberHint::I32),
+            Marker::I64 => TypeHint::Number(NumberHint::I64),
+            Marker::F32 => TypeHint::Number(NumberHint::F32),
+            Marker::F64 => TypeHint::Number(NumberHint::F64),
+            Marker::True | Marker::False => TypeHint::Bool,
+            Marker::Nil => TypeHint::Unit,
+            _ => TypeHint::Any,
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, cx: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if !matches!(self.state, State::Value) {
+            return Err(cx.message("extensions cannot be buffered"));
+        }
+
+        let mut decoder = MsgPackDecoder::new(RecordingReader {
+            reader: self.reader,
+            bytes: Vec::new(),
+        });
+
+        decoder.skip_any(cx)?;
+
+        Ok(MsgPackBuffer {
+            bytes: decoder.reader.bytes,
+        })
+    }
+
+    #[inline]
+    fn decode_unit<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.read_marker(cx)? {
+            Marker::Nil => Ok(()),
+            marker => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "nil",
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(mut self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_prefix(cx, Kind::Array, mark)?;
+        Ok(MsgPackSequenceDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_array<'buf, C, const N: usize>(mut self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_raw_prefix(cx, mark)?;
+
+        if len != N {
+            return Err(cx.marked_message(
+                mark,
+                format_args! {
+                    "bad length, got {len} but expect {N}"
+                },
+            ));
+        }
+
+        self.reader.read_array(cx.adapt())
+    }
+
+    #[inline]
+    fn decode_bytes<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_raw_prefix(cx, mark)?;
+        self.reader.read_bytes(cx, len, visitor)
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        struct Visitor<V>(V);
+
+        impl<'de, 'buf, C, V> ValueVisitor<'de, 'buf, C, [u8]> for Visitor<V>
+        where
+            C: Context<'buf>,
+            V: ValueVisitor<'de, 'buf, C, str>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            fn visit_owned(self, cx: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                let string =
+                    musli_common::str::from_utf8_owned(bytes).map_err(|err| cx.custom(err))?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        let mark = cx.mark();
+
+        if matches!(self.state, State::ExtData(..)) {
+            return Err(cx.marked_message(mark, "extension data can only be decoded as bytes"));
+        }
+
+        let len = self.decode_raw_prefix(cx, mark)?;
+        self.reader.read_bytes(cx, len, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_bool<'buf, C>(mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.read_marker(cx)? {
+            Marker::False => Ok(false),
+            Marker::True => Ok(true),
+            marker => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "boolean",
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    /// Characters are encoded as strings containing a single character.
+    #[inline]
+    fn decode_char<'buf, C>(mut self, cx: &mut C) -> Result<char, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_prefix(cx, Kind::String, mark)?;
+
+        if len > 4 {
+            return Err(cx.marked_message(mark, format_args!("bad character of length {len}")));
+        }
+
+        let mut buf = [0; 4];
+        self.reader.read(cx.adapt(), &mut buf[..len])?;
+        let string = musli_common::str::from_utf8(&buf[..len]).map_err(|err| cx.custom(err))?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(cx.marked_message(mark, "expected a single character")),
+        }
+    }
+
+    #[inline]
+    fn decode_number<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: NumberVisitor<'de, 'buf, C>,
+    {
+        if let State::ExtType(ty) = self.state {
+            return visitor.visit_i8(cx, ty);
+        }
+
+        let mark = cx.mark();
+        let marker = self.read_marker(cx)?;
+
+        match marker {
+            Marker::FixPos(n) => visitor.visit_u8(cx, n),
+            Marker::FixNeg(n) => visitor.visit_i8(cx, n),
+            Marker::U8 => {
+                let value = self.reader.read_byte(cx.adapt())?;
+                visitor.visit_u8(cx, value)
+            }
+            Marker::U16 => {
+                let value = u16::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_u16(cx, value)
+            }
+            Marker::U32 => {
+                let value = u32::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_u32(cx, value)
+            }
+            Marker::U64 => {
+                let value = u64::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_u64(cx, value)
+            }
+            Marker::I8 => {
+                let value = i8::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_i8(cx, value)
+            }
+            Marker::I16 => {
+                let value = i16::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_i16(cx, value)
+            }
+            Marker::I32 => {
+                let value = i32::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_i32(cx, value)
+            }
+            Marker::I64 => {
+                let value = i64::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_i64(cx, value)
+            }
+            Marker::F32 => {
+                let value = f32::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_f32(cx, value)
+            }
+            Marker::F64 => {
+                let value = f64::from_be_bytes(self.reader.read_array(cx.adapt())?);
+                visitor.visit_f64(cx, value)
+            }
+            marker => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "number",
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u128<'buf, C>(mut self, cx: &mut C) -> Result<u128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(bytes) = self.decode_wide(cx)? {
+            return Ok(u128::from_be_bytes(bytes));
+        }
+
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i128<'buf, C>(mut self, cx: &mut C) -> Result<i128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(bytes) = self.decode_wide(cx)? {
+            return Ok(i128::from_be_bytes(bytes));
+        }
+
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_f32<'buf, C>(self, cx: &mut C) -> Result<f32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.decode_float(cx)? as f32)
+    }
+
+    #[inline]
+    fn decode_f64<'buf, C>(self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_float(cx)
+    }
+
+    /// Optional values are either `nil` or the value itself.
+    #[inline]
+    fn decode_option<'buf, C>(mut self, cx: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if matches!(self.state, State::Value) && self.peek_marker(cx)? == Some(Marker::Nil) {
+            self.reader.skip(cx.adapt(), 1)?;
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(mut self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_prefix(cx, Kind::Array, mark)?;
+        Ok(MsgPackSequenceDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(mut self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let actual = self.decode_prefix(cx, Kind::Array, mark)?;
+
+        if len != actual {
+            return Err(cx.marked_message(
+                mark,
+                format_args!("tuple length mismatch: len: {len}, actual: {actual}"),
+            ));
+        }
+
+        Ok(MsgPackSequenceDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(mut self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let len = self.decode_prefix(cx, Kind::Map, mark)?;
+        Ok(MsgPackMapDecoder::new(self.reader, len))
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_map(cx)
+    }
+
+    /// Variants are either encoded as a map with a single entry, or as an
+    /// extension in which case the tag is its `i8` type and the value is its
+    /// data.
+    #[inline]
+    fn decode_variant<'buf, C>(mut self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let marker = self.read_marker(cx)?;
+
+        if let Some(len) = marker.ext() {
+            let len = self.read_len(cx, len)?;
+            let ty = self.reader.read_byte(cx.adapt())? as i8;
+
+            return Ok(MsgPackVariantDecoder {
+                reader: self.reader,
+                ext: Some((ty, len)),
+            });
+        }
+
+        match marker.prefix() {
+            Some((Kind::Map, len)) => {
+                let len = self.read_len(cx, len)?;
+
+                if len != 1 {
+                    return Err(cx.marked_message(
+                        mark,
+                        format_args!("expected variant to be a map with one entry, but got {len}"),
+                    ));
+                }
+
+                Ok(MsgPackVariantDecoder {
+                    reader: self.reader,
+                    ext: None,
+                })
+            }
+            _ => Err(cx.marked_message(
+                mark,
+                Expected {
+                    expected: "variant",
+                    actual: marker,
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_any<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: Visitor<'de, Error = Self::Error>,
+    {
+        let marker = match self.state {
+            State::Value => match self.peek_marker(cx)? {
+                Some(marker) => marker,
+                None => return visitor.visit_any(cx, self, TypeHint::Any),
+            },
+            State::ExtType(ty) => return visitor.visit_i8(cx, ty),
+            State::ExtData(len) => {
+                let visitor = visitor.visit_bytes(cx, SizeHint::Exact(len))?;
+                return self.decode_bytes(cx, visitor);
+            }
+        };
+
+        if let Some((kind, len)) = marker.prefix() {
+            let hint = match len {
+                Len::Fixed(len) => SizeHint::Exact(len as usize),
+                _ => SizeHint::Any,
+            };
+
+            return match kind {
+                Kind::String => {
+                    let visitor = visitor.visit_string(cx, hint)?;
+                    self.decode_string(cx, visitor)
+                }
+                Kind::Bytes => {
+                    let visitor = visitor.visit_bytes(cx, hint)?;
+                    self.decode_bytes(cx, visitor)
+                }
+                Kind::Array => {
+                    let sequence = self.decode_sequence(cx)?;
+                    visitor.visit_sequence(cx, sequence)
+                }
+                Kind::Map => {
+                    let map = self.decode_map(cx)?;
+                    visitor.visit_map(cx, map)
+                }
+            };
+        }
+
+        if marker.ext().is_some() {
+            let variant = self.decode_variant(cx)?;
+            return visitor.visit_variant(cx, variant);
+        }
+
+        match marker {
+            Marker::Nil => {
+                self.decode_unit(cx)?;
+                visitor.visit_unit(cx)
+            }
+            Marker::True | Marker::False => {
+                let value = self.decode_bool(cx)?;
+                visitor.visit_bool(cx, value)
+            }
+            Marker::FixPos(..) | Marker::U8 => {
+                let value = self.decode_u8(cx)?;
+                visitor.visit_u8(cx, value)
+            }
+            Marker::U16 => {
+                let value = self.decode_u16(cx)?;
+                visitor.visit_u16(cx, value)
+            }
+            Marker::U32 => {
+                let value = self.decode_u32(cx)?;
+                visitor.visit_u32(cx, value)
+            }
+            Marker::U64 => {
+                let value = self.decode_u64(cx)?;
+                visitor.visit_u64(cx, value)
+            }
+            Marker::FixNeg(..) | Marker::I8 => {
+                let value = self.decode_i8(cx)?;
+                visitor.visit_i8(cx, value)
+            }
+            Marker::I16 => {
+                let value = self.decode_i16(cx)?;
+                visitor.visit_i16(cx, value)
+            }
+            Marker::I32 => {
+                let value = self.decode_i32(cx)?;
+                visitor.visit_i32(cx, value)
+            }
+            Marker::I64 => {
+                let value = self.decode_i64(cx)?;
+                visitor.visit_i64(cx, value)
+            }
+            Marker::F32 => {
+                let value = self.decode_f32(cx)?;
+                visitor.visit_f32(cx, value)
+            }
+            Marker::F64 => {
+                let value = self.decode_f64(cx)?;
+                visitor.visit_f64(cx, value)
+            }
+            _ => visitor.visit_any(cx, self, TypeHint::Any),
+        }
+    }
+}
+
+impl<R> MsgPackSequenceDecoder<R> {
+    #[inline]
+    fn new(reader: R, remaining: usize) -> Self {
+        Self { reader, remaining }
+    }
+}
+
+impl<'de, R> PackDecoder<'de> for MsgPackSequenceDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.remaining = match self.remaining.checked_sub(1) {
+            Some(remaining) => remaining,
+            None => return Err(cx.message("tried to decode past the pack")),
+        };
+
+        Ok(MsgPackDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining elements.
+        while let Some(mut item) = SequenceDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, R> SequenceDecoder<'de> for MsgPackSequenceDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.remaining)
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining elements.
+        while let Some(mut item) = SequenceDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> MsgPackMapDecoder<R> {
+    #[inline]
+    fn new(reader: R, remaining: usize) -> Self {
+        Self { reader, remaining }
+    }
+}
+
+impl<'de, R> PairsDecoder<'de> for MsgPackMapDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.remaining)
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Skip remaining entries, both key and value.
+        while let Some(mut item) = PairsDecoder::next(&mut self, cx)? {
+            item.skip_any(cx)?;
+            item.skip_any(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, R> PairDecoder<'de> for MsgPackDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type First<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+    type Second = Self;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, _: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.skip_any(cx)?;
+        Ok(true)
+    }
+}
+
+impl<'de, R> VariantDecoder<'de> for MsgPackVariantDecoder<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Tag<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = MsgPackDecoder<R::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let state = match self.ext {
+            Some((ty, _)) => State::ExtType(ty),
+            None => State::Value,
+        };
+
+        Ok(MsgPackDecoder::with_state(self.reader.borrow_mut(), state))
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let state = match self.ext {
+            Some((_, len)) => State::ExtData(len),
+            None => State::Value,
+        };
+
+        Ok(MsgPackDecoder::with_state(self.reader.borrow_mut(), state))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.variant(cx)?.skip_any(cx)?;
+        Ok(true)
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+struct Expected {
+    expected: &'static str,
+    actual: Marker,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { expected, actual } = *self;
+
+        write!(f, "Expected {expected} but was {actual:?}",)
+    }
+}
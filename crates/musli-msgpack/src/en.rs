@@ -0,0 +1,1016 @@
+use core::fmt;
+
+use musli::en::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
+use musli::Context;
+use musli_common::fixed_bytes::FixedBytes;
+use musli_common::writer::Writer;
+
+use crate::error::Error;
+use crate::marker::{Kind, Marker};
+
+/// A MessagePack encoder.
+pub struct MsgPackEncoder<W, const P: usize> {
+    writer: W,
+    /// The extension type to use if this encoder is used for the data of an
+    /// extension.
+    ext: Option<i8>,
+}
+
+impl<W, const P: usize> MsgPackEncoder<W, P> {
+    /// Construct a new MessagePack encoder.
+    #[inline]
+    pub(crate) fn new(writer: W) -> Self {
+        Self { writer, ext: None }
+    }
+
+    /// Error unless the encoder is used to encode a plain value, since the data
+    /// of an extension can only be encoded as bytes.
+    #[inline]
+    fn check_value<'buf, C>(&self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let Some(ty) = self.ext {
+            return Err(cx.message(format_args!(
+                "data of extension type {ty} must be encoded as bytes"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Encoder for a pack, which is buffered so that it can be prefixed with the
+/// number of values it contains.
+pub struct MsgPackPackEncoder<W, const P: usize> {
+    writer: W,
+    pack_buf: FixedBytes<P>,
+    count: usize,
+}
+
+impl<W, const P: usize> MsgPackPackEncoder<W, P> {
+    /// Construct a new pack encoder.
+    #[inline]
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pack_buf: FixedBytes::new(),
+            count: 0,
+        }
+    }
+}
+
+/// Encoder for a variant.
+///
+/// Variants are encoded as a map with a single entry, unless the tag of the
+/// variant is an `i8` in which case it is encoded as an extension.
+pub struct MsgPackVariantEncoder<W, const P: usize> {
+    writer: W,
+    ext: Option<i8>,
+}
+
+/// Encoder for the tag of a variant.
+///
+/// This defers writing the map which wraps the variant until the tag is known
+/// to not be an extension type.
+pub struct MsgPackTagEncoder<'a, W, const P: usize> {
+    writer: W,
+    ext: &'a mut Option<i8>,
+}
+
+impl<'a, W, const P: usize> MsgPackTagEncoder<'a, W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    /// Write the header of the single entry map which wraps a variant, and
+    /// return an encoder for its key.
+    #[inline]
+    fn entry<'buf, C>(mut self, cx: &mut C) -> Result<MsgPackEncoder<W, P>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        self.writer
+            .write_byte(cx.adapt(), Marker::FixMap(1).byte())?;
+        Ok(MsgPackEncoder::new(self.writer))
+    }
+}
+
+#[musli::encoder]
+impl<W, const P: usize> Encoder for MsgPackEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type Pack = MsgPackPackEncoder<W, P>;
+    type Some = Self;
+    type Sequence = Self;
+    type Tuple = Self;
+    type Map = Self;
+    type Struct = Self;
+    type Variant = MsgPackVariantEncoder<W, P>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack encoder")
+    }
+
+    #[inline]
+    fn encode_unit<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        self.writer.write_byte(cx.adapt(), Marker::Nil.byte())
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        Ok(MsgPackPackEncoder::new(self.writer))
+    }
+
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_bytes(cx, array.as_slice())
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(mut self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match self.ext {
+            Some(ty) => encode_ext_prefix(cx, self.writer.borrow_mut(), ty, bytes.len())?,
+            None => encode_prefix(cx, self.writer.borrow_mut(), Kind::Bytes, bytes.len())?,
+        }
+
+        self.writer.write_bytes(cx.adapt(), bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        mut self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let len = vectors.iter().map(|v| v.len()).sum();
+
+        match self.ext {
+            Some(ty) => encode_ext_prefix(cx, self.writer.borrow_mut(), ty, len)?,
+            None => encode_prefix(cx, self.writer.borrow_mut(), Kind::Bytes, len)?,
+        }
+
+        for bytes in vectors {
+            self.writer.write_bytes(cx.adapt(), bytes)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(mut self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        encode_prefix(cx, self.writer.borrow_mut(), Kind::String, string.len())?;
+        self.writer.write_bytes(cx.adapt(), string.as_bytes())
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(mut self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        let marker = if value { Marker::True } else { Marker::False };
+        self.writer.write_byte(cx.adapt(), marker.byte())
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut buf = [0; 4];
+        self.encode_string(cx, value.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(mut self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        encode_unsigned(cx, self.writer.borrow_mut(), value)
+    }
+
+    /// Encode a 128-bit unsigned integer as a regular integer if it fits,
+    /// otherwise as 16 big-endian bytes of binary data.
+    #[inline]
+    fn encode_u128<'buf, C>(self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+
+        match u64::try_from(value) {
+            Ok(value) => self.encode_u64(cx, value),
+            Err(..) => self.encode_array(cx, value.to_be_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(mut self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        encode_signed(cx, self.writer.borrow_mut(), value)
+    }
+
+    /// Encode a 128-bit signed integer as a regular integer if it fits,
+    /// otherwise as 16 big-endian bytes of binary data.
+    #[inline]
+    fn encode_i128<'buf, C>(self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+
+        match i64::try_from(value) {
+            Ok(value) => self.encode_i64(cx, value),
+            Err(..) => self.encode_array(cx, value.to_be_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_f32<'buf, C>(mut self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        write_marked(
+            cx,
+            self.writer.borrow_mut(),
+            Marker::F32,
+            value.to_be_bytes(),
+        )
+    }
+
+    #[inline]
+    fn encode_f64<'buf, C>(mut self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        write_marked(
+            cx,
+            self.writer.borrow_mut(),
+            Marker::F64,
+            value.to_be_bytes(),
+        )
+    }
+
+    /// Optional values are encoded as is, since they can be distinguished
+    /// from the `nil` value used for [`encode_none`][Encoder::encode_none].
+    #[inline]
+    fn encode_some<'buf, C>(self, cx: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_unit(cx)
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(
+        mut self,
+        cx: &mut C,
+        len: usize,
+    ) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        encode_prefix(cx, self.writer.borrow_mut(), Kind::Array, len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_sequence(cx, len)
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(mut self, cx: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+        encode_prefix(cx, self.writer.borrow_mut(), Kind::Map, len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_map(cx, len)
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.check_value(cx)?;
+
+        Ok(MsgPackVariantEncoder {
+            writer: self.writer,
+            ext: None,
+        })
+    }
+}
+
+#[musli::encoder]
+impl<'a, W, const P: usize> Encoder for MsgPackTagEncoder<'a, W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type Pack = MsgPackPackEncoder<W, P>;
+    type Some = MsgPackEncoder<W, P>;
+    type Sequence = MsgPackEncoder<W, P>;
+    type Tuple = MsgPackEncoder<W, P>;
+    type Map = MsgPackEncoder<W, P>;
+    type Struct = MsgPackEncoder<W, P>;
+    type Variant = MsgPackVariantEncoder<W, P>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant tag supported by the MessagePack encoder")
+    }
+
+    #[inline]
+    fn encode_unit<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_unit(cx)
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_pack(cx)
+    }
+
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_array(cx, array)
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bytes(cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bytes_vectored(cx, vectors)
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_string(cx, string)
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_bool(cx, value)
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_char(cx, value)
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u8(cx, value)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u16(cx, value)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u32(cx, value)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u64(cx, value)
+    }
+
+    #[inline]
+    fn encode_u128<'buf, C>(self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_u128(cx, value)
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_usize(cx, value)
+    }
+
+    /// An `i8` tag marks the variant as an extension with the given type,
+    /// which is written once the length of its data is known.
+    #[inline]
+    fn encode_i8<'buf, C>(self, _: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        *self.ext = Some(value);
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i16(cx, value)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i32(cx, value)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i64(cx, value)
+    }
+
+    #[inline]
+    fn encode_i128<'buf, C>(self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_i128(cx, value)
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_isize(cx, value)
+    }
+
+    #[inline]
+    fn encode_f32<'buf, C>(self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_f32(cx, value)
+    }
+
+    #[inline]
+    fn encode_f64<'buf, C>(self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_f64(cx, value)
+    }
+
+    #[inline]
+    fn encode_some<'buf, C>(self, cx: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_some(cx)
+    }
+
+    #[inline]
+    fn encode_none<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_none(cx)
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_sequence(cx, len)
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_tuple(cx, len)
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_map(cx, len)
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_struct(cx, len)
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.entry(cx)?.encode_variant(cx)
+    }
+}
+
+impl<W, const P: usize> SequenceEncoder for MsgPackPackEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = MsgPackEncoder<&'this mut FixedBytes<P>, P>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.count = match self.count.checked_add(1) {
+            Some(count) => count,
+            None => return Err(cx.message("overflow")),
+        };
+
+        Ok(MsgPackEncoder::new(self.pack_buf.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        encode_prefix(cx, self.writer.borrow_mut(), Kind::Array, self.count)?;
+        self.writer
+            .write_bytes(cx.adapt(), self.pack_buf.as_slice())?;
+        Ok(())
+    }
+}
+
+impl<W, const P: usize> SequenceEncoder for MsgPackEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = MsgPackEncoder<W::Mut<'this>, P>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, const P: usize> PairsEncoder for MsgPackEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = MsgPackEncoder<W::Mut<'this>, P>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, const P: usize> PairEncoder for MsgPackEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type First<'this>
+        = MsgPackEncoder<W::Mut<'this>, P>
+    where
+        Self: 'this;
+    type Second<'this>
+        = MsgPackEncoder<W::Mut<'this>, P>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, const P: usize> VariantEncoder for MsgPackVariantEncoder<W, P>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Tag<'this>
+        = MsgPackTagEncoder<'this, W::Mut<'this>, P>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = MsgPackEncoder<W::Mut<'this>, P>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackTagEncoder {
+            writer: self.writer.borrow_mut(),
+            ext: &mut self.ext,
+        })
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(MsgPackEncoder {
+            writer: self.writer.borrow_mut(),
+            ext: self.ext,
+        })
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+/// Write a marker followed by the given big-endian data.
+#[inline]
+fn write_marked<'buf, C, W, const N: usize>(
+    cx: &mut C,
+    mut writer: W,
+    marker: Marker,
+    data: [u8; N],
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    writer.write_byte(cx.adapt(), marker.byte())?;
+    writer.write_array(cx.adapt(), data)
+}
+
+/// Encode an unsigned integer using the smallest representation which can hold
+/// it.
+#[inline]
+fn encode_unsigned<'buf, C, W>(cx: &mut C, mut writer: W, value: u64) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    if value < 0x80 {
+        writer.write_byte(cx.adapt(), Marker::FixPos(value as u8).byte())
+    } else if let Ok(value) = u8::try_from(value) {
+        write_marked(cx, writer, Marker::U8, [value])
+    } else if let Ok(value) = u16::try_from(value) {
+        write_marked(cx, writer, Marker::U16, value.to_be_bytes())
+    } else if let Ok(value) = u32::try_from(value) {
+        write_marked(cx, writer, Marker::U32, value.to_be_bytes())
+    } else {
+        write_marked(cx, writer, Marker::U64, value.to_be_bytes())
+    }
+}
+
+/// Encode a signed integer using the smallest representation which can hold
+/// it. Non-negative values are encoded as unsigned integers.
+#[inline]
+fn encode_signed<'buf, C, W>(cx: &mut C, mut writer: W, value: i64) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    if value >= 0 {
+        encode_unsigned(cx, writer, value as u64)
+    } else if value >= -32 {
+        writer.write_byte(cx.adapt(), Marker::FixNeg(value as i8).byte())
+    } else if let Ok(value) = i8::try_from(value) {
+        write_marked(cx, writer, Marker::I8, value.to_be_bytes())
+    } else if let Ok(value) = i16::try_from(value) {
+        write_marked(cx, writer, Marker::I16, value.to_be_bytes())
+    } else if let Ok(value) = i32::try_from(value) {
+        write_marked(cx, writer, Marker::I32, value.to_be_bytes())
+    } else {
+        write_marked(cx, writer, Marker::I64, value.to_be_bytes())
+    }
+}
+
+/// Encode a length prefix using the smallest marker which can hold it.
+#[inline]
+fn encode_prefix<'buf, C, W>(
+    cx: &mut C,
+    mut writer: W,
+    kind: Kind,
+    len: usize,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    let fixed = match kind {
+        Kind::String if len < 32 => Some(Marker::FixStr(len as u8)),
+        Kind::Array if len < 16 => Some(Marker::FixArray(len as u8)),
+        Kind::Map if len < 16 => Some(Marker::FixMap(len as u8)),
+        _ => None,
+    };
+
+    if let Some(marker) = fixed {
+        return writer.write_byte(cx.adapt(), marker.byte());
+    }
+
+    let (m8, m16, m32) = match kind {
+        Kind::String => (Some(Marker::Str8), Marker::Str16, Marker::Str32),
+        Kind::Bytes => (Some(Marker::Bin8), Marker::Bin16, Marker::Bin32),
+        Kind::Array => (None, Marker::Array16, Marker::Array32),
+        Kind::Map => (None, Marker::Map16, Marker::Map32),
+    };
+
+    if let (Some(marker), Ok(len)) = (m8, u8::try_from(len)) {
+        write_marked(cx, writer, marker, [len])
+    } else if let Ok(len) = u16::try_from(len) {
+        write_marked(cx, writer, m16, len.to_be_bytes())
+    } else if let Ok(len) = u32::try_from(len) {
+        write_marked(cx, writer, m32, len.to_be_bytes())
+    } else {
+        Err(cx.message(format_args!("{kind:?} of length {len} is too large")))
+    }
+}
+
+/// Encode the prefix of an extension, which is its length followed by its
+/// type.
+#[inline]
+fn encode_ext_prefix<'buf, C, W>(cx: &mut C, writer: W, ty: i8, len: usize) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    let ty = ty as u8;
+
+    let fixed = match len {
+        1 => Some(Marker::FixExt1),
+        2 => Some(Marker::FixExt2),
+        4 => Some(Marker::FixExt4),
+        8 => Some(Marker::FixExt8),
+        16 => Some(Marker::FixExt16),
+        _ => None,
+    };
+
+    if let Some(marker) = fixed {
+        write_marked(cx, writer, marker, [ty])
+    } else if let Ok(len) = u8::try_from(len) {
+        write_marked(cx, writer, Marker::Ext8, [len, ty])
+    } else if let Ok(len) = u16::try_from(len) {
+        let [a, b] = len.to_be_bytes();
+        write_marked(cx, writer, Marker::Ext16, [a, b, ty])
+    } else if let Ok(len) = u32::try_from(len) {
+        let [a, b, c, d] = len.to_be_bytes();
+        write_marked(cx, writer, Marker::Ext32, [a, b, c, d, ty])
+    } else {
+        Err(cx.message(format_args!("extension of length {len} is too large")))
+    }
+}
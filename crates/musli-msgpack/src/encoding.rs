@@ -0,0 +1,202 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [DEFAULT] encoding configuration.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker;
+#[cfg(feature = "std")]
+use std::io;
+
+use musli::de::Decode;
+use musli::en::Encode;
+use musli::mode::{DefaultMode, Mode};
+use musli::Context;
+
+use crate::de::MsgPackDecoder;
+use crate::en::MsgPackEncoder;
+use crate::error::Error;
+use crate::fixed_bytes::FixedBytes;
+use crate::reader::{Reader, SliceReader};
+use crate::writer::Writer;
+
+/// The maximum number of bytes a single pack is allowed to occupy by default.
+///
+/// See [Encoding::with_max_pack].
+pub const DEFAULT_MAX_PACK: usize = 256;
+
+/// The default configuration.
+///
+/// Numbers are encoded using the smallest MessagePack representation which
+/// can hold them, collections and strings use the smallest prefix for their
+/// length.
+///
+/// The maximum pack length permitted equals to [DEFAULT_MAX_PACK], which is
+/// 256. Trying to encode larger packs will result in a runtime error. This can
+/// be modified with [Encoding::with_max_pack].
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Encode the given value to the given [Writer] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn encode<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Writer,
+    Error: From<W::Error>,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.encode(writer, value)
+}
+
+/// Encode the given value to the given [Write][io::Write] using the [DEFAULT]
+/// configuration.
+#[cfg(feature = "std")]
+#[inline]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_writer(writer, value)
+}
+
+/// Encode the given value to a [Vec] using the [DEFAULT] configuration.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_vec(value)
+}
+
+/// Encode the given value to a fixed-size bytes using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn to_fixed_bytes<const N: usize, T>(value: &T) -> Result<FixedBytes<N>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_fixed_bytes::<N, _>(value)
+}
+
+/// Decode the given type `T` from the given [Reader] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn decode<'de, R, T>(reader: R) -> Result<T, Error>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.decode(reader)
+}
+
+/// Decode the given type `T` from the given slice using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.from_slice(bytes)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = DefaultMode, const P: usize = DEFAULT_MAX_PACK> {
+    _marker: marker::PhantomData<M>,
+}
+
+impl Encoding<DefaultMode, DEFAULT_MAX_PACK> {
+    /// Construct a new [`Encoding`] instance.
+    ///
+    /// ```rust
+    /// use musli_msgpack::{Encoding};
+    /// use musli::{Encode, Decode};
+    /// use musli::mode::DefaultMode;
+    ///
+    /// const CONFIG: Encoding<DefaultMode> = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Struct<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Struct {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok(()) }
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, const P: usize> Encoding<M, P>
+where
+    M: Mode,
+{
+    /// Change the mode of the encoding.
+    pub const fn with_mode<T>(self) -> Encoding<T, P>
+    where
+        T: Mode,
+    {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Modify the maximum pack sized allowed in the MessagePack format. This
+    /// defaults to [DEFAULT_MAX_PACK].
+    ///
+    /// Packs are buffered before they are written since MessagePack arrays are
+    /// prefixed with their number of elements, so this limits the number of
+    /// bytes a single pack is allowed to occupy.
+    pub const fn with_max_pack<const N: usize>(self) -> Encoding<M, N> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    musli_common::encoding_impls! {
+        MsgPackEncoder::<_, P>::new,
+        MsgPackDecoder::new
+    }
+
+    /// Decode the given type `T` from the given slice using the current
+    /// configuration.
+    #[inline]
+    pub fn from_slice<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        let mut reader = SliceReader::new(bytes);
+        T::decode(&mut cx, MsgPackDecoder::<_>::new(&mut reader))
+    }
+}
+
+impl<M, const P: usize> Clone for Encoding<M, P>
+where
+    M: Mode,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, const P: usize> Copy for Encoding<M, P> where M: Mode {}
@@ -0,0 +1,80 @@
+//! Support for the MessagePack [extension type].
+//!
+//! [extension type]: https://github.com/msgpack/msgpack/blob/master/spec.md#extension-types
+
+use musli::de::{Decode, DecodeBytes, Decoder, VariantDecoder};
+use musli::en::{Encode, EncodeBytes, Encoder, VariantEncoder};
+use musli::mode::Mode;
+use musli::Context;
+
+/// A value of a MessagePack extension type.
+///
+/// This is encoded as a variant whose tag is the `i8` type of the extension,
+/// and whose value is the data of the extension encoded as bytes. The
+/// MessagePack encoder represents any variant with this shape as an extension,
+/// while other formats encode it as a regular variant.
+///
+/// ```rust
+/// use musli_msgpack::ext::Ext;
+///
+/// let ext = Ext::new(5, vec![1u8, 2, 3, 4]);
+/// let bytes = musli_msgpack::to_vec(&ext)?;
+/// assert_eq!(bytes, [0xd6, 5, 1, 2, 3, 4]);
+///
+/// let decoded: Ext<Vec<u8>> = musli_msgpack::from_slice(&bytes)?;
+/// assert_eq!(decoded, ext);
+/// # Ok::<_, musli_msgpack::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ext<T> {
+    /// The type of the extension.
+    pub ty: i8,
+    /// The data of the extension.
+    pub data: T,
+}
+
+impl<T> Ext<T> {
+    /// Construct a new extension value.
+    pub const fn new(ty: i8, data: T) -> Self {
+        Self { ty, data }
+    }
+}
+
+impl<M, T> Encode<M> for Ext<T>
+where
+    M: Mode,
+    T: EncodeBytes<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let mut variant = encoder.encode_variant(cx)?;
+        variant.tag(cx)?.encode_i8(cx, self.ty)?;
+        let encoder = variant.variant(cx)?;
+        self.data.encode_bytes(cx, encoder)?;
+        variant.end(cx)
+    }
+}
+
+impl<'de, M, T> Decode<'de, M> for Ext<T>
+where
+    M: Mode,
+    T: DecodeBytes<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let mut variant = decoder.decode_variant(cx)?;
+        let ty = variant.tag(cx)?.decode_i8(cx)?;
+        let decoder = variant.variant(cx)?;
+        let data = T::decode_bytes(cx, decoder)?;
+        variant.end(cx)?;
+        Ok(Self { ty, data })
+    }
+}
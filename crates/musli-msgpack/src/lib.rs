@@ -0,0 +1,145 @@
+//! [<img alt="github" src="https://img.shields.io/badge/github-udoprog/musli-8da0cb?style=for-the-badge&logo=github" height="20">](https://github.com/udoprog/musli)
+//! [<img alt="crates.io" src="https://img.shields.io/crates/v/musli-msgpack.svg?style=for-the-badge&color=fc8d62&logo=rust" height="20">](https://crates.io/crates/musli-msgpack)
+//! [<img alt="docs.rs" src="https://img.shields.io/badge/docs.rs-musli--msgpack-66c2a5?style=for-the-badge&logoColor=white&logo=data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHhtbG5zPSJodHRwOi8vd3d3LnczLm9yZy8yMDAwL3N2ZyIgdmlld0JveD0iMCAwIDUxMiA1MTIiPjxwYXRoIGZpbGw9IiNmNWY1ZjUiIGQ9Ik00ODguNiAyNTAuMkwzOTIgMjE0VjEwNS41YzAtMTUtOS4zLTI4LjQtMjMuNC0zMy43bC0xMDAtMzcuNWMtOC4xLTMuMS0xNy4xLTMuMS0yNS4zIDBsLTEwMCAzNy41Yy0xNC4xIDUuMy0yMy40IDE4LjctMjMuNCAzMy43VjIxNGwtOTYuNiAzNi4yQzkuMyAyNTUuNSAwIDI2OC45IDAgMjgzLjlWMzk0YzAgMTMuNiA3LjcgMjYuMSAxOS45IDMyLjJsMTAwIDUwYzEwLjEgNS4xIDIyLjEgNS4xIDMyLjIgMGwxMDMuOS01MiAxMDMuOSA1MmMxMC4xIDUuMSAyMi4xIDUuMSAzMi4yIDBsMTAwLTUwYzEyLjItNi4xIDE5LjktMTguNiAxOS45LTMyLjJWMjgzLjljMC0xNS05LjMtMjguNC0yMy40LTMzLjd6TTM1OCAyMTQuOGwtODUgMzEuOXYtNjguMmw4NS0zN3Y3My4zek0xNTQgMTA0LjFsMTAyLTM4LjIgMTAyIDM4LjJ2LjZsLTEwMiA0MS40LTEwMi00MS40di0uNnptODQgMjkxLjFsLTg1IDQyLjV2LTc5LjFsODUtMzguOHY3NS40em0wLTExMmwtMTAyIDQxLjQtMTAyLTQxLjR2LS42bDEwMi0zOC4yIDEwMiAzOC4ydi42em0yNDAgMTEybC04NSA0Mi41di03OS4xbDg1LTM4Ljh2NzUuNHptMC0xMTJsLTEwMiA0MS40LTEwMi00MS40di0uNmwxMDItMzguMiAxMDIgMzguMnYuNnoiPjwvcGF0aD48L3N2Zz4K" height="20">](https://docs.rs/musli-msgpack)
+//!
+//! A [MessagePack] format for [Müsli].
+//!
+//! This allows Müsli types to be exchanged with any other MessagePack
+//! implementation, such as the ones available for Python and JavaScript.
+//! MessagePack is self-descriptive, so it is upgrade stable in the same way as
+//! [`musli-descriptive`]:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//!
+//! It can also be fully converted back and from to the [Value] type.
+//!
+//! ```rust
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli_msgpack::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(62),
+//! })?;
+//!
+//! let version1: Version1 = musli_msgpack::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli_msgpack::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Configuring
+//!
+//! To configure the behavior of the MessagePack format you can use the
+//! [Encoding] type:
+//!
+//! ```rust
+//! use musli_msgpack::Encoding;
+//! use musli::{Encode, Decode};
+//! use musli::mode::DefaultMode;
+//!
+//! const CONFIG: Encoding<DefaultMode, 1024> = Encoding::new()
+//!     .with_max_pack::<1024>();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Struct<'a> {
+//!     name: &'a str,
+//!     age: u32,
+//! }
+//!
+//! let mut out = Vec::new();
+//!
+//! let expected = Struct {
+//!     name: "Aristotle",
+//!     age: 61,
+//! };
+//!
+//! CONFIG.encode(&mut out, &expected)?;
+//! let actual = CONFIG.decode(&out[..])?;
+//!
+//! assert_eq!(expected, actual);
+//! # Ok::<_, musli_msgpack::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Data model
+//!
+//! Müsli types are mapped to MessagePack like this:
+//!
+//! * Numbers use the smallest MessagePack representation which can hold them.
+//!   128-bit numbers which do not fit in 64 bits are encoded as 16 big-endian
+//!   bytes of binary data.
+//! * `char` is encoded as a string.
+//! * `()` and `None` are encoded as `nil`, while `Some(value)` is encoded as
+//!   `value`.
+//! * Structs and maps are encoded as maps. Use `#[musli(default_field_name =
+//!   "name")]` to use field names as keys, which is what most other
+//!   implementations expect.
+//! * Packed types are encoded as arrays.
+//! * Variants are encoded as a map with a single entry, mapping the tag of the
+//!   variant to its value.
+//! * Variants with an `i8` tag and a value encoded as bytes are encoded as an
+//!   extension, see [`ext::Ext`].
+//!
+//! This is the same model as the one used by [`rmp-serde`], when it is
+//! configured to encode structs as maps.
+//!
+//! [`musli-descriptive`]: https://docs.rs/musli-descriptive
+//! [`rmp-serde`]: https://docs.rs/rmp-serde
+//! [MessagePack]: https://msgpack.org
+//! [Müsli]: https://docs.rs/musli
+//! [Encoding]: https://docs.rs/musli-msgpack/latest/musli_msgpack/encoding/struct.Encoding.html
+//! [Value]: https://docs.rs/musli-value
+
+#![deny(missing_docs)]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod de;
+mod en;
+pub mod encoding;
+mod error;
+pub mod ext;
+pub mod marker;
+#[cfg(feature = "test")]
+#[macro_use]
+pub mod test;
+
+/// Convenient result alias for use with `musli_msgpack`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+pub use self::encoding::to_writer;
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding};
+pub use self::error::Error;
+#[cfg(feature = "test")]
+pub use self::test::transcode;
+#[doc(inline)]
+pub use musli_common::*;
@@ -0,0 +1,250 @@
+//! Type flags available for MessagePack.
+//!
+//! Every MessagePack value starts with a single marker byte, which identifies
+//! the type of the value and in some cases embeds the value or its length. See
+//! the [MessagePack specification] for details.
+//!
+//! [MessagePack specification]: https://github.com/msgpack/msgpack/blob/master/spec.md
+
+/// The kind of a length-prefixed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A UTF-8 string.
+    String,
+    /// A binary blob.
+    Bytes,
+    /// An array of values.
+    Array,
+    /// A map of key-value pairs.
+    Map,
+}
+
+impl Kind {
+    /// A human readable name of the kind.
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Kind::String => "string",
+            Kind::Bytes => "binary",
+            Kind::Array => "array",
+            Kind::Map => "map",
+        }
+    }
+}
+
+/// The width of the length which follows a marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Len {
+    /// The length is embedded in the marker.
+    Fixed(u8),
+    /// The length is stored in a single byte.
+    U8,
+    /// The length is stored in two big-endian bytes.
+    U16,
+    /// The length is stored in four big-endian bytes.
+    U32,
+}
+
+/// A MessagePack marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    /// A positive integer in the range `0..=127` embedded in the marker.
+    FixPos(u8),
+    /// A negative integer in the range `-32..=-1` embedded in the marker.
+    FixNeg(i8),
+    /// A map with fewer than 16 entries.
+    FixMap(u8),
+    /// An array with fewer than 16 elements.
+    FixArray(u8),
+    /// A string shorter than 32 bytes.
+    FixStr(u8),
+    /// The `nil` value.
+    Nil,
+    /// The marker which is never used.
+    Reserved,
+    /// The `false` value.
+    False,
+    /// The `true` value.
+    True,
+    /// Binary data with a 8-bit length.
+    Bin8,
+    /// Binary data with a 16-bit length.
+    Bin16,
+    /// Binary data with a 32-bit length.
+    Bin32,
+    /// An extension with a 8-bit length.
+    Ext8,
+    /// An extension with a 16-bit length.
+    Ext16,
+    /// An extension with a 32-bit length.
+    Ext32,
+    /// A 32-bit float.
+    F32,
+    /// A 64-bit float.
+    F64,
+    /// An unsigned 8-bit integer.
+    U8,
+    /// An unsigned 16-bit integer.
+    U16,
+    /// An unsigned 32-bit integer.
+    U32,
+    /// An unsigned 64-bit integer.
+    U64,
+    /// A signed 8-bit integer.
+    I8,
+    /// A signed 16-bit integer.
+    I16,
+    /// A signed 32-bit integer.
+    I32,
+    /// A signed 64-bit integer.
+    I64,
+    /// An extension with 1 byte of data.
+    FixExt1,
+    /// An extension with 2 bytes of data.
+    FixExt2,
+    /// An extension with 4 bytes of data.
+    FixExt4,
+    /// An extension with 8 bytes of data.
+    FixExt8,
+    /// An extension with 16 bytes of data.
+    FixExt16,
+    /// A string with a 8-bit length.
+    Str8,
+    /// A string with a 16-bit length.
+    Str16,
+    /// A string with a 32-bit length.
+    Str32,
+    /// An array with a 16-bit length.
+    Array16,
+    /// An array with a 32-bit length.
+    Array32,
+    /// A map with a 16-bit length.
+    Map16,
+    /// A map with a 32-bit length.
+    Map32,
+}
+
+impl Marker {
+    /// Construct a marker from its byte representation.
+    pub const fn from_byte(b: u8) -> Self {
+        match b {
+            0x00..=0x7f => Self::FixPos(b),
+            0x80..=0x8f => Self::FixMap(b & 0x0f),
+            0x90..=0x9f => Self::FixArray(b & 0x0f),
+            0xa0..=0xbf => Self::FixStr(b & 0x1f),
+            0xc0 => Self::Nil,
+            0xc1 => Self::Reserved,
+            0xc2 => Self::False,
+            0xc3 => Self::True,
+            0xc4 => Self::Bin8,
+            0xc5 => Self::Bin16,
+            0xc6 => Self::Bin32,
+            0xc7 => Self::Ext8,
+            0xc8 => Self::Ext16,
+            0xc9 => Self::Ext32,
+            0xca => Self::F32,
+            0xcb => Self::F64,
+            0xcc => Self::U8,
+            0xcd => Self::U16,
+            0xce => Self::U32,
+            0xcf => Self::U64,
+            0xd0 => Self::I8,
+            0xd1 => Self::I16,
+            0xd2 => Self::I32,
+            0xd3 => Self::I64,
+            0xd4 => Self::FixExt1,
+            0xd5 => Self::FixExt2,
+            0xd6 => Self::FixExt4,
+            0xd7 => Self::FixExt8,
+            0xd8 => Self::FixExt16,
+            0xd9 => Self::Str8,
+            0xda => Self::Str16,
+            0xdb => Self::Str32,
+            0xdc => Self::Array16,
+            0xdd => Self::Array32,
+            0xde => Self::Map16,
+            0xdf => Self::Map32,
+            0xe0..=0xff => Self::FixNeg(b as i8),
+        }
+    }
+
+    /// Coerce marker into its byte representation.
+    ///
+    /// Values embedded in fixed markers are truncated to fit.
+    pub const fn byte(self) -> u8 {
+        match self {
+            Self::FixPos(n) => n & 0x7f,
+            Self::FixMap(n) => 0x80 | (n & 0x0f),
+            Self::FixArray(n) => 0x90 | (n & 0x0f),
+            Self::FixStr(n) => 0xa0 | (n & 0x1f),
+            Self::Nil => 0xc0,
+            Self::Reserved => 0xc1,
+            Self::False => 0xc2,
+            Self::True => 0xc3,
+            Self::Bin8 => 0xc4,
+            Self::Bin16 => 0xc5,
+            Self::Bin32 => 0xc6,
+            Self::Ext8 => 0xc7,
+            Self::Ext16 => 0xc8,
+            Self::Ext32 => 0xc9,
+            Self::F32 => 0xca,
+            Self::F64 => 0xcb,
+            Self::U8 => 0xcc,
+            Self::U16 => 0xcd,
+            Self::U32 => 0xce,
+            Self::U64 => 0xcf,
+            Self::I8 => 0xd0,
+            Self::I16 => 0xd1,
+            Self::I32 => 0xd2,
+            Self::I64 => 0xd3,
+            Self::FixExt1 => 0xd4,
+            Self::FixExt2 => 0xd5,
+            Self::FixExt4 => 0xd6,
+            Self::FixExt8 => 0xd7,
+            Self::FixExt16 => 0xd8,
+            Self::Str8 => 0xd9,
+            Self::Str16 => 0xda,
+            Self::Str32 => 0xdb,
+            Self::Array16 => 0xdc,
+            Self::Array32 => 0xdd,
+            Self::Map16 => 0xde,
+            Self::Map32 => 0xdf,
+            Self::FixNeg(n) => (n as u8) | 0xe0,
+        }
+    }
+
+    /// Get the kind and length width of a length-prefixed marker.
+    pub(crate) const fn prefix(self) -> Option<(Kind, Len)> {
+        Some(match self {
+            Self::FixStr(n) => (Kind::String, Len::Fixed(n)),
+            Self::Str8 => (Kind::String, Len::U8),
+            Self::Str16 => (Kind::String, Len::U16),
+            Self::Str32 => (Kind::String, Len::U32),
+            Self::Bin8 => (Kind::Bytes, Len::U8),
+            Self::Bin16 => (Kind::Bytes, Len::U16),
+            Self::Bin32 => (Kind::Bytes, Len::U32),
+            Self::FixArray(n) => (Kind::Array, Len::Fixed(n)),
+            Self::Array16 => (Kind::Array, Len::U16),
+            Self::Array32 => (Kind::Array, Len::U32),
+            Self::FixMap(n) => (Kind::Map, Len::Fixed(n)),
+            Self::Map16 => (Kind::Map, Len::U16),
+            Self::Map32 => (Kind::Map, Len::U32),
+            _ => return None,
+        })
+    }
+
+    /// Get the length width of an extension marker, where fixed extensions
+    /// embed the length of their data.
+    pub(crate) const fn ext(self) -> Option<Len> {
+        Some(match self {
+            Self::FixExt1 => Len::Fixed(1),
+            Self::FixExt2 => Len::Fixed(2),
+            Self::FixExt4 => Len::Fixed(4),
+            Self::FixExt8 => Len::Fixed(8),
+            Self::FixExt16 => Len::Fixed(16),
+            Self::Ext8 => Len::U8,
+            Self::Ext16 => Len::U16,
+            Self::Ext32 => Len::U32,
+            _ => return None,
+        })
+    }
+}
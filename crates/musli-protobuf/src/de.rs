@@ -0,0 +1,1332 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use musli::de::{
+    Decoder, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder, SizeHint, ValueVisitor,
+    VariantDecoder,
+};
+use musli::Context;
+use musli_common::reader::Reader;
+
+use crate::error::Error;
+use crate::wire::{self, EGROUP, I32, I64, LEN, MAX_FIELD_NUMBER, SGROUP, VARINT};
+
+/// The input being decoded, which keeps track of the state shared by all
+/// decoders.
+///
+/// Embedded messages are delimited by their position in the input rather than
+/// by limiting the reader, and a key which has been read but which doesn't
+/// belong to the repeated field being decoded is held on to until the
+/// enclosing message asks for it.
+pub(crate) struct Input<R> {
+    reader: R,
+    position: usize,
+    peeked: Option<(u32, u8)>,
+    packed_end: Option<usize>,
+}
+
+impl<R> Input<R> {
+    #[inline]
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+            peeked: None,
+            packed_end: None,
+        }
+    }
+}
+
+impl<'de, R> Input<R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    #[inline]
+    fn read_byte<'buf, C>(&mut self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let b = self.reader.read_byte(cx.adapt())?;
+        self.position += 1;
+        Ok(b)
+    }
+
+    #[inline]
+    fn read_array<'buf, C, const N: usize>(&mut self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let array = self.reader.read_array(cx.adapt())?;
+        self.position += N;
+        Ok(array)
+    }
+
+    #[inline]
+    fn read_bytes<'buf, C, V>(
+        &mut self,
+        cx: &mut C,
+        n: usize,
+        visitor: V,
+    ) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let ok = self.reader.read_bytes(cx, n, visitor)?;
+        self.position += n;
+        Ok(ok)
+    }
+
+    #[inline]
+    fn skip<'buf, C>(&mut self, cx: &mut C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        self.reader.skip(cx.adapt(), n)?;
+        self.position += n;
+        Ok(())
+    }
+
+    fn read_varint<'buf, C>(&mut self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+        let mut value = 0;
+
+        for shift in (0..70).step_by(7) {
+            let b = self.read_byte(cx)?;
+            value |= ((b & 0x7f) as u64) << shift;
+
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(cx.marked_message(mark, "varint is longer than 10 bytes"))
+    }
+
+    /// Read the length of a length-delimited value.
+    #[inline]
+    fn read_len<'buf, C>(&mut self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+        let len = self.read_varint(cx)?;
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(len),
+            Err(..) => Err(cx.marked_message(mark, format_args!("length {len} is too large"))),
+        }
+    }
+
+    /// Read the key of the next field in a message ending at `end`, or in the
+    /// root message which extends to the end of the input.
+    fn next_key<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        end: Option<usize>,
+    ) -> Result<Option<(u32, u8)>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if matches!(end, Some(end) if self.position >= end) {
+            return Ok(None);
+        }
+
+        if let Some(key) = self.peeked.take() {
+            return Ok(Some(key));
+        }
+
+        if end.is_none() && self.reader.peek(cx.adapt())?.is_none() {
+            return Ok(None);
+        }
+
+        let mark = cx.mark();
+        let key = self.read_varint(cx)?;
+        let number = key >> 3;
+
+        if number == 0 || number > MAX_FIELD_NUMBER as u64 {
+            return Err(cx.marked_message(mark, format_args!("invalid field number {number}")));
+        }
+
+        Ok(Some((number as u32, (key & 0b111) as u8)))
+    }
+
+    /// Read the wire type of the next occurrence of the field with the given
+    /// number, if it directly follows the current one.
+    #[inline]
+    fn next_occurrence<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        number: u32,
+        end: Option<usize>,
+    ) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.next_key(cx, end)? {
+            Some((n, wire)) if n == number => Ok(Some(wire)),
+            Some(key) => {
+                self.peeked = Some(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Skip over a value with the given wire type.
+    fn skip_value<'buf, C>(&mut self, cx: &mut C, wire: u8) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match wire {
+            VARINT => {
+                self.read_varint(cx)?;
+            }
+            I64 => self.skip(cx, 8)?,
+            LEN => {
+                let len = self.read_len(cx)?;
+                self.skip(cx, len)?;
+            }
+            I32 => self.skip(cx, 4)?,
+            SGROUP | EGROUP => return Err(cx.message("groups are not supported")),
+            wire => return Err(cx.message(format_args!("invalid wire type {wire}"))),
+        }
+
+        Ok(())
+    }
+
+    /// Skip over the remaining fields of a message ending at `end`.
+    fn skip_fields<'buf, C>(&mut self, cx: &mut C, end: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        while let Some((_, wire)) = self.next_key(cx, Some(end))? {
+            self.skip_value(cx, wire)?;
+        }
+
+        self.check_end(cx, end)
+    }
+
+    #[inline]
+    fn check_end<'buf, C>(&mut self, cx: &mut C, end: usize) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if self.position > end {
+            return Err(cx.message(format_args!(
+                "field extends {} bytes past the end of its message",
+                self.position - end
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// What a decoder is positioned at.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    /// The root message, which extends to the end of the input.
+    Root,
+    /// The value of a field with the given wire type, in a message ending at
+    /// `end`. If the field is an element of a repeated field it might be
+    /// packed.
+    Field {
+        number: u32,
+        wire: u8,
+        end: Option<usize>,
+        element: bool,
+    },
+    /// An element inside of a packed repeated field.
+    Packed,
+    /// The number of a field which has already been read.
+    Number(u32),
+    /// A value which is not present in the input, and which is decoded as the
+    /// default value of its protobuf type.
+    Absent,
+}
+
+/// A protobuf decoder.
+pub struct ProtoDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    kind: Kind,
+}
+
+impl<'a, R> ProtoDecoder<'a, R> {
+    /// Construct a new decoder for the root message.
+    #[inline]
+    pub(crate) fn new(input: &'a mut Input<R>) -> Self {
+        Self {
+            input,
+            kind: Kind::Root,
+        }
+    }
+
+    #[inline]
+    fn with_kind(input: &'a mut Input<R>, kind: Kind) -> Self {
+        Self { input, kind }
+    }
+}
+
+/// Decoder for the fields of a message.
+pub struct ProtoMessageDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    end: Option<usize>,
+}
+
+/// Decoder for a single field in a message.
+pub struct ProtoFieldDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    number: u32,
+    wire: u8,
+    end: Option<usize>,
+}
+
+/// Decoder for the elements of a repeated field.
+pub struct ProtoSequenceDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    number: u32,
+    end: Option<usize>,
+    first: Option<u8>,
+    done: bool,
+}
+
+/// Decoder for a map, which is a repeated field of entry messages.
+pub struct ProtoMapDecoder<'a, R> {
+    sequence: ProtoSequenceDecoder<'a, R>,
+    entry: Option<usize>,
+}
+
+/// Decoder for an entry in a map.
+pub struct ProtoEntryDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    end: usize,
+}
+
+/// Decoder for a variant, which is either an enum value or a message with a
+/// single field.
+pub struct ProtoVariantDecoder<'a, R> {
+    input: &'a mut Input<R>,
+    number: u32,
+    body: Option<u8>,
+    end: Option<usize>,
+}
+
+impl<'a, 'de, R> ProtoDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Prepare to decode a value which is naturally encoded with the given
+    /// wire type, returning the wire type to decode or `None` if the value is
+    /// absent.
+    ///
+    /// If this is the first element of a packed repeated field, this enters
+    /// the packed field.
+    fn begin<'buf, C>(&mut self, cx: &mut C, natural: u8) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.kind {
+            Kind::Field {
+                wire: LEN,
+                element: true,
+                ..
+            } if natural != LEN => {
+                let mark = cx.mark();
+                let len = self.input.read_len(cx)?;
+
+                if len == 0 {
+                    return Err(cx.marked_message(mark, "packed repeated field is empty"));
+                }
+
+                self.input.packed_end = Some(self.input.position + len);
+                Ok(Some(natural))
+            }
+            Kind::Field { wire, .. } => Ok(Some(wire)),
+            Kind::Packed if natural != LEN => Ok(Some(natural)),
+            Kind::Packed => Err(cx.message(format_args!(
+                "value with wire type {} cannot be packed",
+                wire::name(natural)
+            ))),
+            Kind::Absent => Ok(None),
+            Kind::Root => Err(cx.message("expected a message or a variant at the root")),
+            Kind::Number(..) => Err(cx.message("field numbers can only be decoded as integers")),
+        }
+    }
+
+    fn decode_unsigned<'buf, C>(mut self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let Kind::Number(number) = self.kind {
+            return Ok(number as u64);
+        }
+
+        let mark = cx.mark();
+
+        match self.begin(cx, VARINT)? {
+            None => Ok(0),
+            Some(VARINT) => self.input.read_varint(cx),
+            Some(I32) => Ok(u32::from_le_bytes(self.input.read_array(cx)?) as u64),
+            Some(I64) => Ok(u64::from_le_bytes(self.input.read_array(cx)?)),
+            Some(wire) => Err(cx.marked_message(mark, Unexpected("an integer", wire))),
+        }
+    }
+
+    fn decode_signed<'buf, C>(mut self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let Kind::Number(number) = self.kind {
+            return Ok(number as i64);
+        }
+
+        let mark = cx.mark();
+
+        match self.begin(cx, VARINT)? {
+            None => Ok(0),
+            Some(VARINT) => Ok(self.input.read_varint(cx)? as i64),
+            Some(I32) => Ok(i32::from_le_bytes(self.input.read_array(cx)?) as i64),
+            Some(I64) => Ok(i64::from_le_bytes(self.input.read_array(cx)?)),
+            Some(wire) => Err(cx.marked_message(mark, Unexpected("an integer", wire))),
+        }
+    }
+
+    /// Decode the length of a length-delimited value.
+    fn decode_len<'buf, C>(
+        &mut self,
+        cx: &mut C,
+        what: &'static str,
+    ) -> Result<Option<usize>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+
+        match self.begin(cx, LEN)? {
+            None => Ok(None),
+            Some(LEN) => Ok(Some(self.input.read_len(cx)?)),
+            Some(wire) => Err(cx.marked_message(mark, Unexpected(what, wire))),
+        }
+    }
+
+    /// Decode a fixed-width value with the given wire type, which is either
+    /// `I32` or `I64`.
+    fn decode_fixed<'buf, C, const N: usize>(
+        &mut self,
+        cx: &mut C,
+        natural: u8,
+    ) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let mark = cx.mark();
+
+        match self.begin(cx, natural)? {
+            None => Ok([0; N]),
+            Some(wire) if wire == natural => self.input.read_array(cx),
+            Some(wire) => Err(cx.marked_message(mark, Unexpected(wire::name(natural), wire))),
+        }
+    }
+
+    /// Decode an embedded message, returning the position at which it ends.
+    fn decode_message<'buf, C>(&mut self, cx: &mut C) -> Result<Option<usize>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.kind {
+            Kind::Root => Ok(None),
+            _ => match self.decode_len(cx, "a message")? {
+                Some(len) => Ok(Some(self.input.position + len)),
+                None => Ok(Some(self.input.position)),
+            },
+        }
+    }
+
+    /// Decode a repeated field, which must be the value of a field in a
+    /// message.
+    fn decode_repeated<'buf, C>(self, cx: &mut C) -> Result<ProtoSequenceDecoder<'a, R>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let (number, first, end) = match self.kind {
+            Kind::Field {
+                number,
+                wire,
+                end,
+                element: false,
+            } => (number, Some(wire), end),
+            Kind::Absent => (0, None, None),
+            _ => return Err(cx.message("repeated values can only be decoded from fields")),
+        };
+
+        Ok(ProtoSequenceDecoder {
+            input: self.input,
+            number,
+            end,
+            done: first.is_none(),
+            first,
+        })
+    }
+}
+
+/// Adapts a visitor of strings into a visitor of bytes, by validating that the
+/// bytes are UTF-8.
+struct StrVisitor<V>(V);
+
+impl<'de, 'buf, C, V> ValueVisitor<'de, 'buf, C, [u8]> for StrVisitor<V>
+where
+    C: Context<'buf>,
+    V: ValueVisitor<'de, 'buf, C, str>,
+{
+    type Ok = V::Ok;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.expecting(f)
+    }
+
+    #[inline]
+    fn visit_owned(self, cx: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8_owned(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_owned(cx, string)
+    }
+
+    #[inline]
+    fn visit_borrowed(self, cx: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_borrowed(cx, string)
+    }
+
+    #[inline]
+    fn visit_ref(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        let string = musli_common::str::from_utf8(bytes).map_err(|err| cx.custom(err))?;
+        self.0.visit_ref(cx, string)
+    }
+}
+
+/// Convert a decoded integer into a narrower type.
+#[inline]
+fn narrow<'buf, C, T, U>(cx: &mut C, value: U) -> Result<T, C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    T: TryFrom<U>,
+    U: Copy + fmt::Display,
+{
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(..) => Err(cx.message(format_args!(
+            "integer {value} is out of range for {}",
+            core::any::type_name::<T>()
+        ))),
+    }
+}
+
+#[musli::decoder]
+impl<'a, 'de, R> Decoder<'de> for ProtoDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Pack = ProtoSequenceDecoder<'a, R>;
+    type Some = Self;
+    type Sequence = ProtoSequenceDecoder<'a, R>;
+    type Tuple = ProtoSequenceDecoder<'a, R>;
+    type Map = ProtoMapDecoder<'a, R>;
+    type Struct = ProtoMessageDecoder<'a, R>;
+    type Variant = ProtoVariantDecoder<'a, R>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf decoder")
+    }
+
+    /// The unit type is decoded from a message, whose fields are skipped.
+    #[inline]
+    fn decode_unit<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.decode_message(cx)? {
+            self.input.skip_fields(cx, end)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_repeated(cx)
+    }
+
+    /// Arrays are decoded from bytes of the same length.
+    #[inline]
+    fn decode_array<'buf, C, const N: usize>(mut self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        let Some(len) = self.decode_len(cx, "an array")? else {
+            return Ok([0; N]);
+        };
+
+        if len != N {
+            return Err(
+                cx.marked_message(mark, format_args!("bad length, got {len} but expect {N}"))
+            );
+        }
+
+        self.input.read_array(cx)
+    }
+
+    #[inline]
+    fn decode_bytes<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        match self.decode_len(cx, "bytes")? {
+            Some(len) => self.input.read_bytes(cx, len, visitor),
+            None => visitor.visit_borrowed(cx, &[]),
+        }
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        match self.decode_len(cx, "a string")? {
+            Some(len) => self.input.read_bytes(cx, len, StrVisitor(visitor)),
+            None => visitor.visit_borrowed(cx, ""),
+        }
+    }
+
+    #[inline]
+    fn decode_bool<'buf, C>(self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.decode_unsigned(cx)? != 0)
+    }
+
+    #[inline]
+    fn decode_char<'buf, C>(self, cx: &mut C) -> Result<char, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let value = self.decode_u32(cx)?;
+
+        match char::from_u32(value) {
+            Some(c) => Ok(c),
+            None => Err(cx.marked_message(mark, format_args!("{value} is not a valid character"))),
+        }
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_unsigned(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_unsigned(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_unsigned(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_unsigned(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_unsigned(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_signed(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_signed(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_signed(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_signed(cx)
+    }
+
+    #[inline]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_signed(cx)?;
+        narrow(cx, value)
+    }
+
+    #[inline]
+    fn decode_f32<'buf, C>(mut self, cx: &mut C) -> Result<f32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(f32::from_le_bytes(self.decode_fixed(cx, I32)?))
+    }
+
+    #[inline]
+    fn decode_f64<'buf, C>(mut self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(f64::from_le_bytes(self.decode_fixed(cx, I64)?))
+    }
+
+    #[inline]
+    fn decode_fixed_u32<'buf, C>(mut self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(u32::from_le_bytes(self.decode_fixed(cx, I32)?))
+    }
+
+    #[inline]
+    fn decode_fixed_u64<'buf, C>(mut self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(u64::from_le_bytes(self.decode_fixed(cx, I64)?))
+    }
+
+    #[inline]
+    fn decode_fixed_i32<'buf, C>(mut self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(i32::from_le_bytes(self.decode_fixed(cx, I32)?))
+    }
+
+    #[inline]
+    fn decode_fixed_i64<'buf, C>(mut self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(i64::from_le_bytes(self.decode_fixed(cx, I64)?))
+    }
+
+    /// Fields which are present are decoded as `Some`.
+    #[inline]
+    fn decode_option<'buf, C>(self, _: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match self.kind {
+            Kind::Absent => Ok(None),
+            _ => Ok(Some(self)),
+        }
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_repeated(cx)
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_repeated(cx)
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoMapDecoder {
+            sequence: self.decode_repeated(cx)?,
+            entry: None,
+        })
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(mut self, cx: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let end = self.decode_message(cx)?;
+
+        Ok(ProtoMessageDecoder {
+            input: self.input,
+            end,
+        })
+    }
+
+    #[inline]
+    fn decode_variant<'buf, C>(mut self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        let (number, body, end) = match self.kind {
+            Kind::Root => match self.input.next_key(cx, None)? {
+                Some((number, wire)) => (number, Some(wire), None),
+                None => return Err(cx.marked_message(mark, "expected a variant")),
+            },
+            _ => match self.begin(cx, VARINT)? {
+                None => (0, None, None),
+                Some(VARINT) => {
+                    let value = self.input.read_varint(cx)?;
+                    (narrow(cx, value)?, None, None)
+                }
+                Some(LEN) => {
+                    let len = self.input.read_len(cx)?;
+                    let end = self.input.position + len;
+
+                    match self.input.next_key(cx, Some(end))? {
+                        Some((number, wire)) => (number, Some(wire), Some(end)),
+                        None => return Err(cx.marked_message(mark, "expected a variant")),
+                    }
+                }
+                Some(wire) => {
+                    return Err(cx.marked_message(mark, Unexpected("a variant", wire)));
+                }
+            },
+        };
+
+        Ok(ProtoVariantDecoder {
+            input: self.input,
+            number,
+            body,
+            end,
+        })
+    }
+}
+
+impl<'a, 'de, R> PairsDecoder<'de> for ProtoMessageDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = ProtoFieldDecoder<'this, R>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Any
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some((number, wire)) = self.input.next_key(cx, self.end)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ProtoFieldDecoder {
+            input: &mut *self.input,
+            number,
+            wire,
+            end: self.end,
+        }))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.end {
+            self.input.skip_fields(cx, end)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'de, R> PairDecoder<'de> for ProtoFieldDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type First<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+    type Second = ProtoDecoder<'a, R>;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoDecoder::with_kind(
+            self.input,
+            Kind::Number(self.number),
+        ))
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, _: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoDecoder::with_kind(
+            self.input,
+            Kind::Field {
+                number: self.number,
+                wire: self.wire,
+                end: self.end,
+                element: false,
+            },
+        ))
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.input.skip_value(cx, self.wire)?;
+        Ok(true)
+    }
+}
+
+impl<'a, 'de, R> ProtoSequenceDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Get the wire type of the next occurrence of the repeated field.
+    fn next_occurrence<'buf, C>(&mut self, cx: &mut C) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if let Some(wire) = self.first.take() {
+            return Ok(Some(wire));
+        }
+
+        if self.done {
+            return Ok(None);
+        }
+
+        let wire = self.input.next_occurrence(cx, self.number, self.end)?;
+        self.done = wire.is_none();
+        Ok(wire)
+    }
+
+    /// Get what the decoder for the next element is positioned at.
+    fn next_kind<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Kind>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if self.in_packed(cx)? {
+            return Ok(Some(Kind::Packed));
+        }
+
+        let Some(wire) = self.next_occurrence(cx)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Kind::Field {
+            number: self.number,
+            wire,
+            end: self.end,
+            element: true,
+        }))
+    }
+
+    /// Test if there are more elements in the packed field being decoded.
+    fn in_packed<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let Some(end) = self.input.packed_end else {
+            return Ok(false);
+        };
+
+        if self.input.position < end {
+            return Ok(true);
+        }
+
+        self.input.packed_end = None;
+        self.input.check_end(cx, end)?;
+        Ok(false)
+    }
+}
+
+impl<'a, 'de, R> PackDecoder<'de> for ProtoSequenceDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+
+    /// Elements which are missing are decoded as their default value.
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let kind = self.next_kind(cx)?.unwrap_or(Kind::Absent);
+        Ok(ProtoDecoder::with_kind(&mut *self.input, kind))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        SequenceDecoder::end(self, cx)
+    }
+}
+
+impl<'a, 'de, R> SequenceDecoder<'de> for ProtoSequenceDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Any
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(kind) = self.next_kind(cx)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ProtoDecoder::with_kind(&mut *self.input, kind)))
+    }
+
+    /// Skip over any remaining occurrences of the repeated field.
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.input.packed_end.take() {
+            let remaining = end.saturating_sub(self.input.position);
+            self.input.skip(cx, remaining)?;
+        }
+
+        while let Some(wire) = self.next_occurrence(cx)? {
+            self.input.skip_value(cx, wire)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'de, R> PairsDecoder<'de> for ProtoMapDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Decoder<'this>
+        = ProtoEntryDecoder<'this, R>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Any
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.entry.take() {
+            self.sequence.input.skip_fields(cx, end)?;
+        }
+
+        let mark = cx.mark();
+
+        let Some(wire) = self.sequence.next_occurrence(cx)? else {
+            return Ok(None);
+        };
+
+        if wire != LEN {
+            return Err(cx.marked_message(mark, Unexpected("a map entry", wire)));
+        }
+
+        let len = self.sequence.input.read_len(cx)?;
+        let end = self.sequence.input.position + len;
+        self.entry = Some(end);
+
+        Ok(Some(ProtoEntryDecoder {
+            input: &mut *self.sequence.input,
+            end,
+        }))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.entry.take() {
+            self.sequence.input.skip_fields(cx, end)?;
+        }
+
+        SequenceDecoder::end(self.sequence, cx)
+    }
+}
+
+impl<'a, 'de, R> ProtoEntryDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    /// Get the wire type of the field with the given number in the entry,
+    /// skipping over any unknown fields before it.
+    fn field<'buf, C>(&mut self, cx: &mut C, number: u32) -> Result<Option<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        while let Some((n, wire)) = self.input.next_key(cx, Some(self.end))? {
+            if n == number {
+                return Ok(Some(wire));
+            }
+
+            if n == 2 {
+                self.input.peeked = Some((n, wire));
+                return Ok(None);
+            }
+
+            self.input.skip_value(cx, wire)?;
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn decoder(
+        input: &mut Input<R>,
+        number: u32,
+        wire: Option<u8>,
+        end: usize,
+    ) -> ProtoDecoder<'_, R> {
+        let kind = match wire {
+            Some(wire) => Kind::Field {
+                number,
+                wire,
+                end: Some(end),
+                element: false,
+            },
+            None => Kind::Absent,
+        };
+
+        ProtoDecoder::with_kind(input, kind)
+    }
+}
+
+impl<'a, 'de, R> PairDecoder<'de> for ProtoEntryDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type First<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+    type Second = ProtoDecoder<'a, R>;
+
+    /// The key of an entry is field 1, which is decoded as its default value
+    /// if it is missing.
+    #[inline]
+    fn first<'buf, C>(&mut self, cx: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let wire = self.field(cx, 1)?;
+        Ok(Self::decoder(self.input, 1, wire, self.end))
+    }
+
+    /// The value of an entry is field 2, which is decoded as its default value
+    /// if it is missing.
+    #[inline]
+    fn second<'buf, C>(mut self, cx: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let wire = self.field(cx, 2)?;
+        Ok(Self::decoder(self.input, 2, wire, self.end))
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Remaining fields are skipped by the map decoder.
+        Ok(true)
+    }
+}
+
+impl<'a, 'de, R> VariantDecoder<'de> for ProtoVariantDecoder<'a, R>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+{
+    type Error = Error;
+    type Tag<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = ProtoDecoder<'this, R>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoDecoder::with_kind(
+            &mut *self.input,
+            Kind::Number(self.number),
+        ))
+    }
+
+    /// The value of an enum value is absent, and decodes as an empty message.
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let kind = match self.body.take() {
+            Some(wire) => Kind::Field {
+                number: self.number,
+                wire,
+                end: self.end,
+                element: false,
+            },
+            None => Kind::Absent,
+        };
+
+        Ok(ProtoDecoder::with_kind(&mut *self.input, kind))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(wire) = self.body.take() {
+            self.input.skip_value(cx, wire)?;
+        }
+
+        Ok(true)
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(end) = self.end {
+            self.input.skip_fields(cx, end)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A value was encoded with an unexpected wire type.
+struct Unexpected(&'static str, u8);
+
+impl fmt::Display for Unexpected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(expected, wire) = *self;
+        write!(f, "Expected {expected} but was {}", wire::name(wire))
+    }
+}
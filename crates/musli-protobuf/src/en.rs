@@ -0,0 +1,874 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use musli::en::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
+use musli::Context;
+use musli_common::writer::Writer;
+
+use crate::error::Error;
+use crate::wire::{self, Fields, I32, I64, LEN, MAX_FIELD_NUMBER, VARINT};
+
+/// A protobuf encoder.
+///
+/// Every value except for the root message is encoded as a field, so the
+/// encoder keeps track of the number of the field being encoded.
+pub struct ProtoEncoder<W> {
+    writer: W,
+    field: Option<u32>,
+}
+
+impl<W> ProtoEncoder<W> {
+    /// Construct a new encoder for the root message.
+    #[inline]
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            field: None,
+        }
+    }
+
+    #[inline]
+    fn field(writer: W, number: u32) -> Self {
+        Self {
+            writer,
+            field: Some(number),
+        }
+    }
+}
+
+impl<W> ProtoEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    /// Get the number of the field being encoded, since only messages and
+    /// variants can be encoded at the root.
+    #[inline]
+    fn number<'buf, C>(&self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.field {
+            Some(number) => Ok(number),
+            None => Err(cx.message("expected a message or a variant at the root")),
+        }
+    }
+
+    /// Write the key of the field being encoded.
+    #[inline]
+    fn key<'buf, C>(&mut self, cx: &mut C, wire: u8) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let number = self.number(cx)?;
+        wire::write_key(cx, self.writer.borrow_mut(), number, wire)
+    }
+
+    #[inline]
+    fn varint<'buf, C>(mut self, cx: &mut C, value: u64) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        self.key(cx, VARINT)?;
+        wire::write_varint(cx, self.writer, value)
+    }
+
+    /// Write a fixed-width value, which is either `I32` or `I64`.
+    #[inline]
+    fn fixed<'buf, C, const N: usize>(
+        mut self,
+        cx: &mut C,
+        wire: u8,
+        array: [u8; N],
+    ) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        self.key(cx, wire)?;
+        self.writer.write_array(cx.adapt(), array)
+    }
+
+    #[inline]
+    fn len_delimited<'buf, C>(mut self, cx: &mut C, vectors: &[&[u8]]) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let len = vectors.iter().map(|v| v.len()).sum::<usize>();
+        self.key(cx, LEN)?;
+        wire::write_varint(cx, self.writer.borrow_mut(), len as u64)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(cx.adapt(), bytes)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encoder for a message, which buffers its fields since the encoding of an
+/// embedded message is prefixed with its length.
+pub struct ProtoMessageEncoder<W> {
+    writer: W,
+    field: Option<u32>,
+    buf: Vec<u8>,
+}
+
+/// Encoder for a field in a message.
+pub struct ProtoFieldEncoder<'a> {
+    buf: &'a mut Vec<u8>,
+    number: Option<u32>,
+}
+
+/// Encoder for a repeated field, which is packed if all of its elements are
+/// scalars.
+pub struct ProtoSequenceEncoder<W> {
+    writer: W,
+    field: u32,
+    buf: Vec<u8>,
+}
+
+/// Encoder for a map, which is encoded as a repeated field of entry messages.
+pub struct ProtoMapEncoder<W> {
+    writer: W,
+    field: u32,
+}
+
+/// Encoder for an entry in a map, which is a message where the key is field 1
+/// and the value is field 2.
+pub struct ProtoEntryEncoder<W> {
+    writer: W,
+    field: u32,
+    entry: Vec<u8>,
+}
+
+/// Encoder for a variant.
+///
+/// Variants with an empty value are encoded as enum values, other variants as
+/// a message where the tag of the variant is the number of the field holding
+/// its value, in the same way as a `oneof`.
+pub struct ProtoVariantEncoder<W> {
+    writer: W,
+    field: Option<u32>,
+    number: Option<u32>,
+    buf: Vec<u8>,
+}
+
+/// Encoder for the number of a field or the tag of a variant.
+pub struct ProtoNumberEncoder<'a> {
+    number: &'a mut Option<u32>,
+    min: u32,
+}
+
+impl ProtoNumberEncoder<'_> {
+    #[inline]
+    fn store<'buf, C, T>(self, cx: &mut C, value: T) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: Copy + fmt::Display + TryInto<u32>,
+    {
+        match value.try_into() {
+            Ok(number) if (self.min..=MAX_FIELD_NUMBER).contains(&number) => {
+                *self.number = Some(number);
+                Ok(())
+            }
+            _ => Err(cx.message(format_args!(
+                "field number {value} is not in the range {}..={MAX_FIELD_NUMBER}",
+                self.min
+            ))),
+        }
+    }
+}
+
+#[musli::encoder]
+impl<W> Encoder for ProtoEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type Pack = ProtoSequenceEncoder<W>;
+    type Some = Self;
+    type Sequence = ProtoSequenceEncoder<W>;
+    type Tuple = ProtoSequenceEncoder<W>;
+    type Map = ProtoMapEncoder<W>;
+    type Struct = ProtoMessageEncoder<W>;
+    type Variant = ProtoVariantEncoder<W>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf encoder")
+    }
+
+    /// The unit type is encoded as an empty message.
+    #[inline]
+    fn encode_unit<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.field.is_none() {
+            return Ok(());
+        }
+
+        self.len_delimited(cx, &[])
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_sequence(cx, 0)
+    }
+
+    /// Arrays are encoded as bytes.
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.len_delimited(cx, &[&array])
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.len_delimited(cx, &[bytes])
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.len_delimited(cx, vectors)
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.len_delimited(cx, &[string.as_bytes()])
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value)
+    }
+
+    #[inline]
+    fn encode_u128<'buf, C>(self, cx: &mut C, _: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Err(cx.message("128-bit integers are not supported by protobuf"))
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    /// Signed integers are encoded like `int64`, where negative values are
+    /// sign-extended to ten bytes. Use [`SInt`][crate::types::SInt] for zigzag
+    /// encoding.
+    #[inline]
+    fn encode_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.varint(cx, value as u64)
+    }
+
+    #[inline]
+    fn encode_i128<'buf, C>(self, cx: &mut C, _: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Err(cx.message("128-bit integers are not supported by protobuf"))
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value as i64)
+    }
+
+    #[inline]
+    fn encode_f32<'buf, C>(self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I32, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_f64<'buf, C>(self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I64, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_fixed_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I32, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_fixed_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I64, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_fixed_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I32, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_fixed_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.fixed(cx, I64, value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_some<'buf, C>(self, _: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    /// Absent values are not encoded at all.
+    #[inline]
+    fn encode_none<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoSequenceEncoder {
+            field: self.number(cx)?,
+            writer: self.writer,
+            buf: Vec::new(),
+        })
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_sequence(cx, len)
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoMapEncoder {
+            field: self.number(cx)?,
+            writer: self.writer,
+        })
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, _: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoMessageEncoder {
+            writer: self.writer,
+            field: self.field,
+            buf: Vec::new(),
+        })
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, _: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoVariantEncoder {
+            writer: self.writer,
+            field: self.field,
+            number: None,
+            buf: Vec::new(),
+        })
+    }
+}
+
+#[musli::encoder]
+impl Encoder for ProtoNumberEncoder<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field number")
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.store(cx, value)
+    }
+}
+
+impl<W> PairsEncoder for ProtoMessageEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = ProtoFieldEncoder<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoFieldEncoder {
+            buf: &mut self.buf,
+            number: None,
+        })
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(field) = self.field {
+            wire::write_key(cx, self.writer.borrow_mut(), field, LEN)?;
+            wire::write_varint(cx, self.writer.borrow_mut(), self.buf.len() as u64)?;
+        }
+
+        self.writer.write_bytes(cx.adapt(), &self.buf)
+    }
+}
+
+impl PairEncoder for ProtoFieldEncoder<'_> {
+    type Ok = ();
+    type Error = Error;
+    type First<'this>
+        = ProtoNumberEncoder<'this>
+    where
+        Self: 'this;
+    type Second<'this>
+        = ProtoEncoder<&'this mut Vec<u8>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoNumberEncoder {
+            number: &mut self.number,
+            min: 1,
+        })
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(number) = self.number else {
+            return Err(cx.message("field number must be encoded before its value"));
+        };
+
+        Ok(ProtoEncoder::field(&mut *self.buf, number))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W> SequenceEncoder for ProtoSequenceEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = ProtoEncoder<&'this mut Vec<u8>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoEncoder::field(&mut self.buf, self.field))
+    }
+
+    /// Elements are encoded as a sequence of fields with the same number, and
+    /// if all of them turn out to be scalars they are packed into a single
+    /// field.
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let mut len = 0;
+
+        for (wire, value) in Fields::new(&self.buf) {
+            if wire == LEN {
+                return self.writer.write_bytes(cx.adapt(), &self.buf);
+            }
+
+            len += value.len();
+        }
+
+        wire::write_key(cx, self.writer.borrow_mut(), self.field, LEN)?;
+        wire::write_varint(cx, self.writer.borrow_mut(), len as u64)?;
+
+        for (_, value) in Fields::new(&self.buf) {
+            self.writer.write_bytes(cx.adapt(), value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W> PairsEncoder for ProtoMapEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = ProtoEntryEncoder<W::Mut<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoEntryEncoder {
+            writer: self.writer.borrow_mut(),
+            field: self.field,
+            entry: Vec::new(),
+        })
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W> PairEncoder for ProtoEntryEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type First<'this>
+        = ProtoEncoder<&'this mut Vec<u8>>
+    where
+        Self: 'this;
+    type Second<'this>
+        = ProtoEncoder<&'this mut Vec<u8>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoEncoder::field(&mut self.entry, 1))
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoEncoder::field(&mut self.entry, 2))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        wire::write_key(cx, self.writer.borrow_mut(), self.field, LEN)?;
+        wire::write_varint(cx, self.writer.borrow_mut(), self.entry.len() as u64)?;
+        self.writer.write_bytes(cx.adapt(), &self.entry)
+    }
+}
+
+impl<W> VariantEncoder for ProtoVariantEncoder<W>
+where
+    W: Writer,
+    Error: From<W::Error>,
+{
+    type Ok = ();
+    type Error = Error;
+    type Tag<'this>
+        = ProtoNumberEncoder<'this>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = ProtoEncoder<&'this mut Vec<u8>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(ProtoNumberEncoder {
+            number: &mut self.number,
+            min: 0,
+        })
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(number) = self.number else {
+            return Err(cx.message("variant tag must be encoded before its value"));
+        };
+
+        Ok(ProtoEncoder::field(&mut self.buf, number))
+    }
+
+    /// A variant whose value is encoded as an empty field is written as an
+    /// enum value, otherwise the variant is written as a message holding the
+    /// single field.
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(number) = self.number else {
+            return Err(cx.message("variant tag must be encoded before ending the variant"));
+        };
+
+        let mut fields = Fields::new(&self.buf);
+
+        if let (Some((LEN, [0])), None, Some(field)) = (fields.next(), fields.next(), self.field) {
+            wire::write_key(cx, self.writer.borrow_mut(), field, VARINT)?;
+            return wire::write_varint(cx, self.writer, number as u64);
+        }
+
+        if number == 0 {
+            return Err(cx.message("variant tag 0 is only supported for enum values in a field"));
+        }
+
+        if let Some(field) = self.field {
+            wire::write_key(cx, self.writer.borrow_mut(), field, LEN)?;
+            wire::write_varint(cx, self.writer.borrow_mut(), self.buf.len() as u64)?;
+        }
+
+        self.writer.write_bytes(cx.adapt(), &self.buf)
+    }
+}
@@ -0,0 +1,230 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [DEFAULT] encoding configuration.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker;
+#[cfg(feature = "std")]
+use std::io;
+
+use musli::de::Decode;
+use musli::en::Encode;
+use musli::mode::{DefaultMode, Mode};
+use musli::Context;
+
+use crate::de::{Input, ProtoDecoder};
+use crate::en::ProtoEncoder;
+use crate::error::Error;
+use crate::fixed_bytes::FixedBytes;
+use crate::reader::{Reader, SliceReader};
+use crate::writer::Writer;
+
+/// The default configuration.
+///
+/// Fields are encoded in the order in which they are declared, and repeated
+/// fields of scalars are always packed.
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Encode the given value to the given [Writer] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn encode<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Writer,
+    Error: From<W::Error>,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.encode(writer, value)
+}
+
+/// Encode the given value to the given [Write][io::Write] using the [DEFAULT]
+/// configuration.
+#[cfg(feature = "std")]
+#[inline]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: io::Write,
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_writer(writer, value)
+}
+
+/// Encode the given value to a [Vec] using the [DEFAULT] configuration.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_vec(value)
+}
+
+/// Encode the given value to a fixed-size bytes using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn to_fixed_bytes<const N: usize, T>(value: &T) -> Result<FixedBytes<N>, Error>
+where
+    T: ?Sized + Encode<DefaultMode>,
+{
+    DEFAULT.to_fixed_bytes::<N, _>(value)
+}
+
+/// Decode the given type `T` from the given [Reader] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn decode<'de, R, T>(reader: R) -> Result<T, Error>
+where
+    R: Reader<'de>,
+    Error: From<R::Error>,
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.decode(reader)
+}
+
+/// Decode the given type `T` from the given slice using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Decode<'de, DefaultMode>,
+{
+    DEFAULT.from_slice(bytes)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = DefaultMode> {
+    _marker: marker::PhantomData<M>,
+}
+
+impl Encoding<DefaultMode> {
+    /// Construct a new [`Encoding`] instance.
+    ///
+    /// ```rust
+    /// use musli_protobuf::{Encoding};
+    /// use musli::{Encode, Decode};
+    /// use musli::mode::DefaultMode;
+    ///
+    /// const CONFIG: Encoding<DefaultMode> = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Struct<'a> {
+    ///     #[musli(rename = 1)]
+    ///     name: &'a str,
+    ///     #[musli(rename = 2)]
+    ///     age: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Struct {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok(()) }
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: Mode,
+{
+    /// Change the mode of the encoding.
+    pub const fn with_mode<T>(self) -> Encoding<T>
+    where
+        T: Mode,
+    {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Encode the given value to the given [`Writer`] using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::encode`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn encode_with<'buf, C, W, T>(
+        self,
+        cx: &mut C,
+        writer: W,
+        value: &T,
+    ) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        W: Writer,
+        Error: From<W::Error>,
+        T: ?Sized + Encode<M>,
+    {
+        T::encode(value, cx, ProtoEncoder::new(writer))
+    }
+
+    /// Decode the given type `T` from the given [Reader] using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::decode`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn decode_with<'de, 'buf, C, R, T>(self, cx: &mut C, reader: R) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        R: Reader<'de>,
+        Error: From<R::Error>,
+        T: Decode<'de, M>,
+    {
+        let mut input = Input::new(reader);
+        T::decode(cx, ProtoDecoder::new(&mut input))
+    }
+
+    /// Decode the given type `T` from the given [Reader] using the current
+    /// configuration.
+    #[inline]
+    pub fn decode<'de, R, T>(self, reader: R) -> Result<T, Error>
+    where
+        R: Reader<'de>,
+        Error: From<R::Error>,
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        self.decode_with(&mut cx, reader)
+    }
+
+    musli_common::encode_with_extensions!();
+
+    /// Decode the given type `T` from the given slice using the current
+    /// configuration.
+    #[inline]
+    pub fn from_slice<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        let mut input = Input::new(SliceReader::new(bytes));
+        T::decode(&mut cx, ProtoDecoder::new(&mut input))
+    }
+}
+
+impl<M> Clone for Encoding<M>
+where
+    M: Mode,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Copy for Encoding<M> where M: Mode {}
@@ -0,0 +1,122 @@
+//! [<img alt="github" src="https://img.shields.io/badge/github-udoprog/musli-8da0cb?style=for-the-badge&logo=github" height="20">](https://github.com/udoprog/musli)
+//! [<img alt="crates.io" src="https://img.shields.io/crates/v/musli-protobuf.svg?style=for-the-badge&color=fc8d62&logo=rust" height="20">](https://crates.io/crates/musli-protobuf)
+//! [<img alt="docs.rs" src="https://img.shields.io/badge/docs.rs-musli--protobuf-66c2a5?style=for-the-badge&logoColor=white&logo=data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHhtbG5zPSJodHRwOi8vd3d3LnczLm9yZy8yMDAwL3N2ZyIgdmlld0JveD0iMCAwIDUxMiA1MTIiPjxwYXRoIGZpbGw9IiNmNWY1ZjUiIGQ9Ik00ODguNiAyNTAuMkwzOTIgMjE0VjEwNS41YzAtMTUtOS4zLTI4LjQtMjMuNC0zMy43bC0xMDAtMzcuNWMtOC4xLTMuMS0xNy4xLTMuMS0yNS4zIDBsLTEwMCAzNy41Yy0xNC4xIDUuMy0yMy40IDE4LjctMjMuNCAzMy43VjIxNGwtOTYuNiAzNi4yQzkuMyAyNTUuNSAwIDI2OC45IDAgMjgzLjlWMzk0YzAgMTMuNiA3LjcgMjYuMSAxOS45IDMyLjJsMTAwIDUwYzEwLjEgNS4xIDIyLjEgNS4xIDMyLjIgMGwxMDMuOS01MiAxMDMuOSA1MmMxMC4xIDUuMSAyMi4xIDUuMSAzMi4yIDBsMTAwLTUwYzEyLjItNi4xIDE5LjktMTguNiAxOS45LTMyLjJWMjgzLjljMC0xNS05LjMtMjguNC0yMy40LTMzLjd6TTM1OCAyMTQuOGwtODUgMzEuOXYtNjguMmw4NS0zN3Y3My4zek0xNTQgMTA0LjFsMTAyLTM4LjIgMTAyIDM4LjJ2LjZsLTEwMiA0MS40LTEwMi00MS40di0uNnptODQgMjkxLjFsLTg1IDQyLjV2LTc5LjFsODUtMzguOHY3NS40em0wLTExMmwtMTAyIDQxLjQtMTAyLTQxLjR2LS42bDEwMi0zOC4yIDEwMiAzOC4ydi42em0yNDAgMTEybC04NSA0Mi41di03OS4xbDg1LTM4Ljh2NzUuNHptMC0xMTJsLTEwMiA0MS40LTEwMi00MS40di0uNmwxMDItMzguMiAxMDIgMzguMnYuNnoiPjwvcGF0aD48L3N2Zz4K" height="20">](https://docs.rs/musli-protobuf)
+//!
+//! A [Protocol Buffers] format for [Müsli].
+//!
+//! This encodes Müsli types using the protobuf [wire format], which allows
+//! them to be exchanged with gRPC services and anything else which speaks
+//! protobuf without generated code. Messages are plain structs where each
+//! field is given its protobuf field number with `#[musli(rename = N)]`.
+//!
+//! Like protobuf itself this format is upgrade stable:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//!
+//! But since the wire format is not self-descriptive it can't be decoded into
+//! the [Value] type.
+//!
+//! ```rust
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version1 {
+//!     #[musli(rename = 1)]
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version2 {
+//!     #[musli(rename = 1)]
+//!     name: String,
+//!     #[musli(rename = 2, default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli_protobuf::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(62),
+//! })?;
+//!
+//! assert_eq!(version2, b"\x0a\x09Aristotle\x10\x3e");
+//!
+//! let version1: Version1 = musli_protobuf::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli_protobuf::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Data model
+//!
+//! The value being encoded must be a struct, which is encoded as the fields of
+//! a message, or an enum. Values are mapped to protobuf like this:
+//!
+//! * Integers and `bool` are encoded as varints like `uint64` and `int64`,
+//!   where negative numbers are sign-extended. Use [`types::SInt`] for the
+//!   zigzag encoded `sint32` and `sint64`, and [`types::Fixed`] for `fixed32`,
+//!   `fixed64`, `sfixed32` and `sfixed64`. 128-bit integers are not supported.
+//! * `f32` and `f64` are encoded as `float` and `double`.
+//! * Strings, bytes and byte arrays are length-delimited. Byte containers such as `Vec<u8>`
+//!   need `#[musli(bytes)]`, or they are encoded as repeated integers.
+//! * Structs are embedded messages, and `()` is an empty message.
+//! * `Option` maps to field presence, so `None` is not encoded at all. Since the
+//!   field is then missing, it needs `#[musli(default)]`.
+//! * Sequences, tuples and packs are repeated fields, which are packed if every
+//!   element is a scalar. Empty sequences are not encoded at all, so they also
+//!   need `#[musli(default)]`.
+//! * Maps are repeated entry messages, where the key is field 1 and the value is
+//!   field 2.
+//! * Variants without a value are encoded as enum values, where the tag of the
+//!   variant is the enum value. Other variants are encoded like a `oneof`, as a
+//!   message with a single field whose number is the tag of the variant.
+//!
+//! Fields which are missing from the input, such as the key or value of a map
+//! entry, are decoded as the default value of their protobuf type. Elements of
+//! a repeated field are expected to be contiguous, and packed and unpacked
+//! repeated fields are both accepted.
+//!
+//! Since embedded messages are prefixed with their length they are buffered
+//! while they are being encoded, so this format requires an allocator.
+//!
+//! [Protocol Buffers]: https://protobuf.dev
+//! [wire format]: https://protobuf.dev/programming-guides/encoding/
+//! [Müsli]: https://docs.rs/musli
+//! [Value]: https://docs.rs/musli-value
+
+#![deny(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod de;
+mod en;
+pub mod encoding;
+mod error;
+#[cfg(feature = "test")]
+#[macro_use]
+pub mod test;
+pub mod types;
+pub mod wire;
+
+/// Convenient result alias for use with `musli_protobuf`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+pub use self::encoding::to_writer;
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding};
+pub use self::error::Error;
+#[cfg(feature = "test")]
+pub use self::test::transcode;
+#[doc(inline)]
+pub use musli_common::*;
@@ -0,0 +1,147 @@
+//! Wrappers which select how an integer is encoded.
+//!
+//! Protobuf has several encodings for integers, while Müsli only has one kind
+//! of integer per width. Plain integers are encoded as `int32`, `int64`,
+//! `uint32` or `uint64`, and these wrappers are used for the other types.
+
+use musli::de::{Decode, Decoder};
+use musli::en::{Encode, Encoder};
+use musli::mode::Mode;
+use musli::Context;
+
+use crate::wire::{zigzag_decode, zigzag_encode};
+
+/// A signed integer encoded using zigzag encoding, which is the `sint32` and
+/// `sint64` types in protobuf.
+///
+/// Zigzag encoding maps small negative numbers to small unsigned numbers,
+/// which makes them a lot more compact than the regular encoding of signed
+/// integers where every negative number uses ten bytes.
+///
+/// ```rust
+/// use musli::{Decode, Encode};
+/// use musli_protobuf::types::SInt;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Message {
+///     #[musli(rename = 1)]
+///     value: SInt<i32>,
+/// }
+///
+/// let bytes = musli_protobuf::to_vec(&Message { value: SInt(-2) })?;
+/// assert_eq!(bytes, [0x08, 0x03]);
+///
+/// let decoded: Message = musli_protobuf::from_slice(&bytes)?;
+/// assert_eq!(decoded, Message { value: SInt(-2) });
+/// # Ok::<_, musli_protobuf::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SInt<T>(pub T);
+
+/// An integer encoded with a fixed width, which is the `fixed32`, `fixed64`,
+/// `sfixed32` and `sfixed64` types in protobuf.
+///
+/// This is more compact than the variable-length encoding for large numbers.
+/// The value is encoded through [`Encoder::encode_fixed_u32`] and its siblings,
+/// so other formats encode it as a plain integer.
+///
+/// ```rust
+/// use musli::{Decode, Encode};
+/// use musli_protobuf::types::Fixed;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Message {
+///     #[musli(rename = 1)]
+///     value: Fixed<u32>,
+/// }
+///
+/// let bytes = musli_protobuf::to_vec(&Message { value: Fixed(1) })?;
+/// assert_eq!(bytes, [0x0d, 0x01, 0x00, 0x00, 0x00]);
+///
+/// let decoded: Message = musli_protobuf::from_slice(&bytes)?;
+/// assert_eq!(decoded, Message { value: Fixed(1) });
+/// # Ok::<_, musli_protobuf::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Fixed<T>(pub T);
+
+macro_rules! sint {
+    ($ty:ty) => {
+        impl<M> Encode<M> for SInt<$ty>
+        where
+            M: Mode,
+        {
+            #[inline]
+            fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: Encoder,
+            {
+                encoder.encode_u64(cx, zigzag_encode(self.0 as i64))
+            }
+        }
+
+        impl<'de, M> Decode<'de, M> for SInt<$ty>
+        where
+            M: Mode,
+        {
+            #[inline]
+            fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+            where
+                C: Context<'buf, Input = D::Error>,
+                D: Decoder<'de>,
+            {
+                let value = zigzag_decode(decoder.decode_u64(cx)?);
+
+                match <$ty>::try_from(value) {
+                    Ok(value) => Ok(Self(value)),
+                    Err(..) => Err(cx.message(format_args!(
+                        "integer {value} is out of range for {}",
+                        stringify!($ty)
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! fixed {
+    ($ty:ty, $encode:ident, $decode:ident) => {
+        impl<M> Encode<M> for Fixed<$ty>
+        where
+            M: Mode,
+        {
+            #[inline]
+            fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: Encoder,
+            {
+                encoder.$encode(cx, self.0)
+            }
+        }
+
+        impl<'de, M> Decode<'de, M> for Fixed<$ty>
+        where
+            M: Mode,
+        {
+            #[inline]
+            fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+            where
+                C: Context<'buf, Input = D::Error>,
+                D: Decoder<'de>,
+            {
+                Ok(Self(decoder.$decode(cx)?))
+            }
+        }
+    };
+}
+
+sint!(i32);
+sint!(i64);
+fixed!(u32, encode_fixed_u32, decode_fixed_u32);
+fixed!(u64, encode_fixed_u64, decode_fixed_u64);
+fixed!(i32, encode_fixed_i32, decode_fixed_i32);
+fixed!(i64, encode_fixed_i64, decode_fixed_i64);
@@ -0,0 +1,147 @@
+//! Constants and helpers for the [protobuf wire format].
+//!
+//! [protobuf wire format]: https://protobuf.dev/programming-guides/encoding/
+
+use musli::Context;
+use musli_common::writer::Writer;
+
+use crate::error::Error;
+
+/// Wire type of `int32`, `int64`, `uint32`, `uint64`, `sint32`, `sint64`,
+/// `bool` and `enum` values.
+pub const VARINT: u8 = 0;
+/// Wire type of `fixed64`, `sfixed64` and `double` values.
+pub const I64: u8 = 1;
+/// Wire type of strings, bytes, embedded messages and packed repeated fields.
+pub const LEN: u8 = 2;
+/// Wire type of the deprecated start group marker.
+pub const SGROUP: u8 = 3;
+/// Wire type of the deprecated end group marker.
+pub const EGROUP: u8 = 4;
+/// Wire type of `fixed32`, `sfixed32` and `float` values.
+pub const I32: u8 = 5;
+
+/// The largest valid field number.
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// Get the name of a wire type for diagnostics.
+pub(crate) fn name(wire: u8) -> &'static str {
+    match wire {
+        VARINT => "VARINT",
+        I64 => "I64",
+        LEN => "LEN",
+        SGROUP => "SGROUP",
+        EGROUP => "EGROUP",
+        I32 => "I32",
+        _ => "unknown wire type",
+    }
+}
+
+/// Encode a signed integer using zigzag encoding, as used by `sint32` and
+/// `sint64`.
+#[inline]
+pub(crate) const fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Decode a zigzag encoded integer.
+#[inline]
+pub(crate) const fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Write a variable-length integer.
+pub(crate) fn write_varint<'buf, C, W>(
+    cx: &mut C,
+    mut writer: W,
+    mut value: u64,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    let mut buf = [0; 10];
+    let mut len = 0;
+
+    while value >= 0x80 {
+        buf[len] = (value as u8) | 0x80;
+        value >>= 7;
+        len += 1;
+    }
+
+    buf[len] = value as u8;
+    writer.write_bytes(cx.adapt(), &buf[..=len])
+}
+
+/// Write the key of a field, which combines its number and wire type.
+#[inline]
+pub(crate) fn write_key<'buf, C, W>(
+    cx: &mut C,
+    writer: W,
+    number: u32,
+    wire: u8,
+) -> Result<(), C::Error>
+where
+    C: Context<'buf, Input = Error>,
+    W: Writer,
+    Error: From<W::Error>,
+{
+    write_varint(cx, writer, (number as u64) << 3 | wire as u64)
+}
+
+/// Read a variable-length integer from the front of an already encoded buffer.
+fn take_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+
+    for (n, b) in input.iter().enumerate().take(10) {
+        value |= ((b & 0x7f) as u64) << (n * 7);
+
+        if b & 0x80 == 0 {
+            *input = &input[n + 1..];
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// An iterator over the fields in an already encoded buffer, producing the
+/// wire type and the encoded value of each field.
+pub(crate) struct Fields<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Fields<'a> {
+    #[inline]
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = take_varint(&mut self.input)?;
+        let start = self.input;
+
+        let len = match (key & 0b111) as u8 {
+            VARINT => {
+                take_varint(&mut self.input)?;
+                return Some((VARINT, &start[..start.len() - self.input.len()]));
+            }
+            I64 => 8,
+            I32 => 4,
+            LEN => {
+                let len = usize::try_from(take_varint(&mut self.input)?).ok()?;
+                start.len() - self.input.len() + len
+            }
+            _ => return None,
+        };
+
+        let value = start.get(..len)?;
+        self.input = &start[len..];
+        Some(((key & 0b111) as u8, value))
+    }
+}
@@ -0,0 +1,200 @@
+//! Encodings which are byte-for-byte compatible with the layout used by other
+//! serialization libraries.
+//!
+//! This allows Müsli to read and write data which is produced or consumed by
+//! existing services that use [serde] together with:
+//! * [bincode] 1.x with its default configuration through [BINCODE], which
+//!   uses fixed-width little-endian integers and 64-bit lengths.
+//! * [postcard] 1.x through [POSTCARD], which uses variable-length integers
+//!   and zigzag encoding for signed integers.
+//!
+//! These formats do not store field or variant names, so the layout of a type
+//! is determined entirely by the order of its fields and variants. Field tags
+//! are not encoded, and fields are decoded in the order in which they are
+//! declared, exactly like `#[musli(packed)]`. This also means that types used
+//! with these encodings should not rely on `#[musli(default)]`,
+//! `#[musli(skip_encoding_if)]` or named tags. Enum variants are identified by
+//! their index, which is how [serde] numbers variants.
+//!
+//! Note that a `CString` is encoded by Müsli together with its nul terminator,
+//! while [serde] leaves it out, so such values are not compatible.
+//!
+//! ```rust
+//! use musli::{Encode, Decode};
+//! use musli_storage::layout::{BINCODE, POSTCARD};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let person = Person {
+//!     name: String::from("Aristotle"),
+//!     age: 61,
+//! };
+//!
+//! let bytes = BINCODE.to_vec(&person)?;
+//! assert_eq!(bytes, b"\x09\0\0\0\0\0\0\0Aristotle\x3d\0\0\0");
+//! assert_eq!(BINCODE.from_slice::<Person>(&bytes)?, person);
+//!
+//! let bytes = POSTCARD.to_vec(&person)?;
+//! assert_eq!(bytes, b"\x09Aristotle\x3d");
+//! assert_eq!(POSTCARD.from_slice::<Person>(&bytes)?, person);
+//! # Ok::<_, musli_storage::Error>(())
+//! ```
+//!
+//! [bincode]: https://docs.rs/bincode/1
+//! [postcard]: https://docs.rs/postcard/1
+//! [serde]: https://serde.rs
+
+mod de;
+mod en;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker;
+#[cfg(feature = "std")]
+use std::io;
+
+use musli::de::Decode;
+use musli::en::Encode;
+use musli::mode::{DefaultMode, Mode};
+use musli::Context;
+
+use crate::error::Error;
+use crate::fixed_bytes::FixedBytes;
+use crate::int::{Fixed, FixedUsize, IntegerEncoding, LittleEndian, UsizeEncoding, Variable};
+use crate::reader::{Reader, SliceReader};
+use crate::writer::Writer;
+
+pub use self::de::LayoutDecoder;
+pub use self::en::LayoutEncoder;
+
+/// The layout used by [bincode] 1.x when using its default configuration, such
+/// as through `bincode::serialize`.
+///
+/// [bincode]: https://docs.rs/bincode/1
+pub const BINCODE: Encoding<DefaultMode, Bincode> = Encoding::new();
+
+/// The layout used by [postcard] 1.x.
+///
+/// [postcard]: https://docs.rs/postcard/1
+pub const POSTCARD: Encoding<DefaultMode, Postcard> = Encoding::new();
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::Bincode {}
+    impl Sealed for super::Postcard {}
+}
+
+/// Trait governing the details of a layout which can't be expressed through
+/// its integer and length encodings.
+pub trait Layout: private::Sealed {
+    /// How integers are encoded.
+    type Integer: IntegerEncoding;
+    /// How lengths are encoded.
+    type Length: UsizeEncoding;
+    /// If a character is prefixed with the length of its UTF-8 encoding.
+    const CHAR_LENGTH: bool;
+}
+
+/// The [bincode] 1.x layout.
+///
+/// [bincode]: https://docs.rs/bincode/1
+#[non_exhaustive]
+pub enum Bincode {}
+
+impl Layout for Bincode {
+    type Integer = Fixed<LittleEndian>;
+    type Length = FixedUsize<u64, LittleEndian>;
+    const CHAR_LENGTH: bool = false;
+}
+
+/// The [postcard] 1.x layout.
+///
+/// [postcard]: https://docs.rs/postcard/1
+#[non_exhaustive]
+pub enum Postcard {}
+
+impl Layout for Postcard {
+    type Integer = Variable;
+    type Length = Variable;
+    const CHAR_LENGTH: bool = true;
+}
+
+/// Setting up encoding with a layout compatible with another library.
+pub struct Encoding<M = DefaultMode, F = Bincode>
+where
+    M: Mode,
+    F: Layout,
+{
+    _marker: marker::PhantomData<(M, F)>,
+}
+
+impl<F> Encoding<DefaultMode, F>
+where
+    F: Layout,
+{
+    /// Construct a new [Encoding] instance for the layout `F`.
+    ///
+    /// ```rust
+    /// use musli_storage::layout::{Encoding, Postcard};
+    /// use musli::mode::DefaultMode;
+    ///
+    /// const CONFIG: Encoding<DefaultMode, Postcard> = Encoding::new();
+    ///
+    /// let bytes = CONFIG.to_vec(&(-1i32, 'ä'))?;
+    /// assert_eq!(bytes, [0x01, 0x02, 0xc3, 0xa4]);
+    /// # Ok::<_, musli_storage::Error>(())
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M, F> Encoding<M, F>
+where
+    M: Mode,
+    F: Layout,
+{
+    /// Change the mode of the encoding.
+    pub const fn with_mode<T>(self) -> Encoding<T, F>
+    where
+        T: Mode,
+    {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    musli_common::encoding_impls! {
+        LayoutEncoder::<_, F, Error>::new,
+        LayoutDecoder::<_, F, Error>::new
+    }
+
+    musli_common::encoding_from_slice_impls! {
+        LayoutEncoder::<_, F, Error>::new,
+        LayoutDecoder::<_, F, Error>::new
+    }
+}
+
+impl<M, F> Clone for Encoding<M, F>
+where
+    M: Mode,
+    F: Layout,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M, F> Copy for Encoding<M, F>
+where
+    M: Mode,
+    F: Layout,
+{
+}
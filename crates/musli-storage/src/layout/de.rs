@@ -0,0 +1,907 @@
+use core::fmt;
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use musli::de::{
+    Decoder, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder, SizeHint, ValueVisitor,
+    VariantDecoder,
+};
+use musli::Context;
+use musli_common::int::{IntegerEncoding, UsizeEncoding};
+use musli_common::reader::Reader;
+
+use super::Layout;
+
+/// A decoder for the layout `F`.
+pub struct LayoutDecoder<R, F, E> {
+    reader: R,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+impl<R, F, E> LayoutDecoder<R, F, E> {
+    /// Construct a new layout decoder.
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+/// A length-prefixed decode wrapper.
+#[doc(hidden)]
+pub struct LimitedLayoutDecoder<R, F, E> {
+    remaining: usize,
+    decoder: LayoutDecoder<R, F, E>,
+}
+
+/// Decoder for the fields of a struct, which are decoded in order without
+/// their tags.
+pub struct LayoutStructDecoder<R, F, E> {
+    reader: R,
+    index: usize,
+    len: usize,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+/// Decoder for a single field in a struct.
+pub struct LayoutFieldDecoder<R, F, E> {
+    reader: R,
+    index: usize,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+/// Decoder for the index of a variant, which is always encoded as a `u32`.
+pub struct LayoutTagDecoder<R, F, E> {
+    reader: R,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+impl<'de, R, F, E> LayoutTagDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    #[inline]
+    fn decode_index<'buf, C, T>(self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = E>,
+        T: TryFrom<u32>,
+        T::Error: 'static + Send + Sync + fmt::Display + fmt::Debug,
+    {
+        let index: u32 = F::Integer::decode_unsigned(cx.adapt(), self.reader)?;
+        T::try_from(index).map_err(|error| cx.custom(error))
+    }
+}
+
+/// Decoder for the tag of a field, which is the index of the field since it
+/// is not encoded.
+pub struct LayoutFieldTagDecoder<E> {
+    index: usize,
+    _marker: marker::PhantomData<E>,
+}
+
+impl<E> LayoutFieldTagDecoder<E> {
+    #[inline]
+    fn decode_index<'buf, C, T>(self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = E>,
+        T: TryFrom<usize>,
+        T::Error: 'static + Send + Sync + fmt::Display + fmt::Debug,
+    {
+        T::try_from(self.index).map_err(|error| cx.custom(error))
+    }
+}
+
+#[musli::decoder]
+impl<'de, R, F, E> Decoder<'de> for LayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type Pack = Self;
+    type Some = Self;
+    type Sequence = LimitedLayoutDecoder<R, F, E>;
+    type Tuple = Self;
+    type Map = LimitedLayoutDecoder<R, F, E>;
+    type Struct = LayoutStructDecoder<R, F, E>;
+    type Variant = Self;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the layout decoder")
+    }
+
+    #[inline]
+    fn decode_unit<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(self, _: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn decode_array<'buf, C, const N: usize>(mut self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.reader.read_array(cx.adapt())
+    }
+
+    #[inline(always)]
+    fn decode_bytes<'buf, C, V>(mut self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let len = F::Length::decode_usize(cx.adapt(), self.reader.borrow_mut())?;
+        self.reader.read_bytes(cx, len, visitor)
+    }
+
+    #[inline(always)]
+    fn decode_string<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        struct Visitor<V>(V);
+
+        impl<'de, 'buf, C, V> ValueVisitor<'de, 'buf, C, [u8]> for Visitor<V>
+        where
+            C: Context<'buf>,
+            V: ValueVisitor<'de, 'buf, C, str>,
+        {
+            type Ok = V::Ok;
+
+            #[inline(always)]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline(always)]
+            fn visit_owned(self, cx: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                let string =
+                    musli_common::str::from_utf8_owned(bytes).map_err(|error| cx.custom(error))?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline(always)]
+            fn visit_borrowed(self, cx: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    musli_common::str::from_utf8(bytes).map_err(|error| cx.custom(error))?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline(always)]
+            fn visit_ref(self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    musli_common::str::from_utf8(bytes).map_err(|error| cx.custom(error))?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        self.decode_bytes(cx, Visitor(visitor))
+    }
+
+    #[inline(always)]
+    fn decode_bool<'buf, C>(mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let byte = self.reader.read_byte(cx.adapt())?;
+
+        match byte {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(cx.marked_message(mark, BadBoolean { actual: b })),
+        }
+    }
+
+    #[inline(always)]
+    fn decode_char<'buf, C>(mut self, cx: &mut C) -> Result<char, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+        let mut buf = [0; 4];
+
+        let len = if F::CHAR_LENGTH {
+            F::Length::decode_usize(cx.adapt(), self.reader.borrow_mut())?
+        } else {
+            buf[0] = self.reader.read_byte(cx.adapt())?;
+            utf8_width(buf[0])
+        };
+
+        let Some(rest) = buf.get_mut(usize::from(!F::CHAR_LENGTH)..len) else {
+            return Err(cx.marked_message(mark, BadCharacterLength { actual: len }));
+        };
+
+        for b in rest {
+            *b = self.reader.read_byte(cx.adapt())?;
+        }
+
+        match core::str::from_utf8(&buf[..len]) {
+            Ok(string) => {
+                let mut chars = string.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(cx.marked_message(mark, BadCharacterLength { actual: len })),
+                }
+            }
+            Err(error) => Err(cx.marked_message(mark, error)),
+        }
+    }
+
+    #[inline(always)]
+    fn decode_u8<'buf, C>(mut self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.reader.read_byte(cx.adapt())
+    }
+
+    #[inline(always)]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_unsigned(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_unsigned(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_unsigned(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_u128<'buf, C>(self, cx: &mut C) -> Result<u128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_unsigned(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.decode_u8(cx)? as i8)
+    }
+
+    #[inline(always)]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_signed(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_signed(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_signed(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_i128<'buf, C>(self, cx: &mut C) -> Result<i128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::decode_signed(cx.adapt(), self.reader)
+    }
+
+    #[inline(always)]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_u64(cx)?;
+        usize::try_from(value).map_err(|error| cx.custom(error))
+    }
+
+    #[inline(always)]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let value = self.decode_i64(cx)?;
+        isize::try_from(value).map_err(|error| cx.custom(error))
+    }
+
+    #[inline(always)]
+    fn decode_f32<'buf, C>(mut self, cx: &mut C) -> Result<f32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(f32::from_le_bytes(self.reader.read_array(cx.adapt())?))
+    }
+
+    #[inline(always)]
+    fn decode_f64<'buf, C>(mut self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(f64::from_le_bytes(self.reader.read_array(cx.adapt())?))
+    }
+
+    #[inline]
+    fn decode_option<'buf, C>(mut self, cx: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mark = cx.mark();
+
+        match self.reader.read_byte(cx.adapt())? {
+            0 => Ok(None),
+            1 => Ok(Some(self)),
+            b => Err(cx.marked_message(mark, BadOption { actual: b })),
+        }
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        LimitedLayoutDecoder::new(cx, self)
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(self, _: &mut C, _: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        LimitedLayoutDecoder::new(cx, self)
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, _: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutStructDecoder {
+            reader: self.reader,
+            index: 0,
+            len,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    #[inline]
+    fn decode_variant<'buf, C>(self, _: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+}
+
+impl<'de, R, F, E> PackDecoder<'de> for LayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type Decoder<'this>
+        = LayoutDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'de, R, F, E> LimitedLayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    #[inline]
+    fn new<'buf, C>(cx: &mut C, mut decoder: LayoutDecoder<R, F, E>) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = E>,
+    {
+        let remaining = F::Length::decode_usize(cx.adapt(), &mut decoder.reader)?;
+        Ok(Self { remaining, decoder })
+    }
+}
+
+impl<'de, R, F, E> SequenceDecoder<'de> for LimitedLayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type Decoder<'this>
+        = LayoutDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.remaining)
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(LayoutDecoder::new(self.decoder.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'de, R, F, E> PairsDecoder<'de> for LimitedLayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+
+    type Decoder<'this>
+        = LayoutDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.remaining)
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(LayoutDecoder::new(self.decoder.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'de, R, F, E> PairDecoder<'de> for LayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type First<'this>
+        = LayoutDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+    type Second = Self;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, _: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(false)
+    }
+}
+
+impl<'de, R, F, E> VariantDecoder<'de> for LayoutDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type Tag<'this>
+        = LayoutTagDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = LayoutDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutTagDecoder {
+            reader: self.reader.borrow_mut(),
+            _marker: marker::PhantomData,
+        })
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutDecoder::new(self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(false)
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'de, R, F, E> PairsDecoder<'de> for LayoutStructDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+
+    type Decoder<'this>
+        = LayoutFieldDecoder<R::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.len - self.index)
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.index == self.len {
+            return Ok(None);
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        Ok(Some(LayoutFieldDecoder {
+            reader: self.reader.borrow_mut(),
+            index,
+            _marker: marker::PhantomData,
+        }))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'de, R, F, E> PairDecoder<'de> for LayoutFieldDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+    type First<'this>
+        = LayoutFieldTagDecoder<E>
+    where
+        Self: 'this;
+    type Second = LayoutDecoder<R, F, E>;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutFieldTagDecoder {
+            index: self.index,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, _: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutDecoder::new(self.reader))
+    }
+
+    /// Fields which are not recognized, such as fields marked with
+    /// `#[musli(skip)]`, were never encoded so skipping them consumes nothing.
+    #[inline]
+    fn skip_second<'buf, C>(self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(true)
+    }
+}
+
+#[musli::decoder]
+impl<'de, R, F, E> Decoder<'de> for LayoutTagDecoder<R, F, E>
+where
+    R: Reader<'de>,
+    F: Layout,
+    E: From<R::Error>,
+    E: musli::error::Error,
+{
+    type Error = E;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a variant index")
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+}
+
+#[musli::decoder]
+impl<'de, E> Decoder<'de> for LayoutFieldTagDecoder<E>
+where
+    E: musli::error::Error,
+{
+    type Error = E;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a field index")
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, _: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.index)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_index(cx)
+    }
+}
+
+/// The width of a UTF-8 encoded character, as determined by its first byte.
+#[inline]
+fn utf8_width(b: u8) -> usize {
+    match b {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 0,
+    }
+}
+
+struct BadBoolean {
+    actual: u8,
+}
+
+impl fmt::Display for BadBoolean {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual } = *self;
+        write!(f, "Bad boolean byte 0x{actual:02x}")
+    }
+}
+
+struct BadOption {
+    actual: u8,
+}
+
+impl fmt::Display for BadOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual } = *self;
+        write!(f, "Bad option byte 0x{actual:02x}")
+    }
+}
+
+struct BadCharacterLength {
+    actual: usize,
+}
+
+impl fmt::Display for BadCharacterLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual } = *self;
+        write!(f, "Bad character encoded using {actual} bytes")
+    }
+}
@@ -0,0 +1,731 @@
+use core::fmt;
+use core::marker;
+
+use musli::en::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
+use musli::Context;
+use musli_common::int::{IntegerEncoding, UsizeEncoding};
+use musli_common::writer::Writer;
+
+use super::Layout;
+
+/// An encoder producing the layout `F`.
+pub struct LayoutEncoder<W, F, E> {
+    writer: W,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+impl<W, F, E> LayoutEncoder<W, F, E> {
+    /// Construct a new layout encoder.
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+/// Encoder for the fields of a struct, which are encoded without their tags.
+pub struct LayoutStructEncoder<W, F, E> {
+    writer: W,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+impl<W, F, E> LayoutStructEncoder<W, F, E> {
+    #[inline]
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+/// Encoder for the index of a variant, which is always encoded as a `u32`.
+pub struct LayoutTagEncoder<W, F, E> {
+    writer: W,
+    _marker: marker::PhantomData<(F, E)>,
+}
+
+impl<W, F, E> LayoutTagEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    #[inline]
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    fn encode_index<'buf, C, T>(self, cx: &mut C, index: T) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = E>,
+        u32: TryFrom<T>,
+        <u32 as TryFrom<T>>::Error: 'static + Send + Sync + fmt::Display + fmt::Debug,
+    {
+        let index = u32::try_from(index).map_err(|error| cx.custom(error))?;
+        F::Integer::encode_unsigned(cx.adapt(), self.writer, index)
+    }
+}
+
+/// Encoder for the index of a field, which is not encoded.
+pub struct LayoutFieldTagEncoder<E> {
+    _marker: marker::PhantomData<E>,
+}
+
+impl<E> LayoutFieldTagEncoder<E> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+#[musli::encoder]
+impl<W, F, E> Encoder for LayoutEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+
+    type Pack = Self;
+    type Some = Self;
+    type Sequence = Self;
+    type Tuple = Self;
+    type Map = Self;
+    type Struct = LayoutStructEncoder<W, F, E>;
+    type Variant = Self;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the layout encoder")
+    }
+
+    #[inline(always)]
+    fn encode_unit<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn encode_pack<'buf, C>(self, _: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn encode_array<'buf, C, const N: usize>(
+        mut self,
+        cx: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_array(cx.adapt(), array)
+    }
+
+    #[inline(always)]
+    fn encode_bytes<'buf, C>(mut self, cx: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Length::encode_usize(cx.adapt(), self.writer.borrow_mut(), bytes.len())?;
+        self.writer.write_bytes(cx.adapt(), bytes)
+    }
+
+    #[inline(always)]
+    fn encode_bytes_vectored<'buf, C>(
+        mut self,
+        cx: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let len = vectors.iter().map(|v| v.len()).sum();
+        F::Length::encode_usize(cx.adapt(), self.writer.borrow_mut(), len)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(cx.adapt(), bytes)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn encode_string<'buf, C>(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_bytes(cx, string.as_bytes())
+    }
+
+    #[inline(always)]
+    fn encode_usize<'buf, C>(mut self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_unsigned(cx.adapt(), self.writer.borrow_mut(), value as u64)
+    }
+
+    #[inline(always)]
+    fn encode_isize<'buf, C>(mut self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_signed(cx.adapt(), self.writer.borrow_mut(), value as i64)
+    }
+
+    #[inline(always)]
+    fn encode_bool<'buf, C>(mut self, cx: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), u8::from(value))
+    }
+
+    /// Characters are encoded as UTF-8, which is optionally prefixed with its
+    /// length depending on the layout.
+    #[inline(always)]
+    fn encode_char<'buf, C>(mut self, cx: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut buf = [0; 4];
+        let bytes = value.encode_utf8(&mut buf).as_bytes();
+
+        if F::CHAR_LENGTH {
+            F::Length::encode_usize(cx.adapt(), self.writer.borrow_mut(), bytes.len())?;
+        }
+
+        self.writer.write_bytes(cx.adapt(), bytes)
+    }
+
+    #[inline(always)]
+    fn encode_u8<'buf, C>(mut self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), value)
+    }
+
+    #[inline(always)]
+    fn encode_u16<'buf, C>(mut self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_unsigned(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_u32<'buf, C>(mut self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_unsigned(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_u64<'buf, C>(mut self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_unsigned(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_u128<'buf, C>(mut self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_unsigned(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u8(cx, value as u8)
+    }
+
+    #[inline(always)]
+    fn encode_i16<'buf, C>(mut self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_signed(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_i32<'buf, C>(mut self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_signed(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_i64<'buf, C>(mut self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_signed(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    #[inline(always)]
+    fn encode_i128<'buf, C>(mut self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Integer::encode_signed(cx.adapt(), self.writer.borrow_mut(), value)
+    }
+
+    /// Floats are always encoded as little-endian IEEE 754 values.
+    #[inline(always)]
+    fn encode_f32<'buf, C>(mut self, cx: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_array(cx.adapt(), value.to_le_bytes())
+    }
+
+    /// Floats are always encoded as little-endian IEEE 754 values.
+    #[inline(always)]
+    fn encode_f64<'buf, C>(mut self, cx: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_array(cx.adapt(), value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn encode_some<'buf, C>(mut self, cx: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), 1)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn encode_none<'buf, C>(mut self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.writer.write_byte(cx.adapt(), 0)
+    }
+
+    #[inline(always)]
+    fn encode_sequence<'buf, C>(
+        mut self,
+        cx: &mut C,
+        len: usize,
+    ) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Length::encode_usize(cx.adapt(), self.writer.borrow_mut(), len)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn encode_tuple<'buf, C>(self, _: &mut C, _: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // NB: A tuple has statically known fixed length.
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn encode_map<'buf, C>(mut self, cx: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        F::Length::encode_usize(cx.adapt(), self.writer.borrow_mut(), len)?;
+        Ok(self)
+    }
+
+    #[inline(always)]
+    fn encode_struct<'buf, C>(self, _: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // NB: A struct has a statically known set of fields.
+        Ok(LayoutStructEncoder::new(self.writer))
+    }
+
+    #[inline(always)]
+    fn encode_variant<'buf, C>(self, _: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+}
+
+impl<W, F, E> SequenceEncoder for LayoutEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type Encoder<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, F, E> PairsEncoder for LayoutEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type Encoder<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, F, E> PairEncoder for LayoutEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type First<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+    type Second<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, F, E> VariantEncoder for LayoutEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type Tag<'this>
+        = LayoutTagEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutTagEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, F, E> PairsEncoder for LayoutStructEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type Encoder<'this>
+        = LayoutStructEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutStructEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<W, F, E> PairEncoder for LayoutStructEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+    type First<'this>
+        = LayoutFieldTagEncoder<E>
+    where
+        Self: 'this;
+    type Second<'this>
+        = LayoutEncoder<W::Mut<'this>, F, E>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutFieldTagEncoder::new())
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(LayoutEncoder::new(self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+#[musli::encoder]
+impl<W, F, E> Encoder for LayoutTagEncoder<W, F, E>
+where
+    W: Writer,
+    F: Layout,
+    E: From<W::Error>,
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a variant index")
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_index(cx, value)
+    }
+}
+
+#[musli::encoder]
+impl<E> Encoder for LayoutFieldTagEncoder<E>
+where
+    E: musli::error::Error,
+{
+    type Ok = ();
+    type Error = E;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a field index")
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, _: &mut C, _: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, _: &mut C, _: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, _: &mut C, _: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, _: &mut C, _: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, _: &mut C, _: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, _: &mut C, _: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
@@ -87,6 +87,25 @@
 //! # Ok::<_, musli_storage::Error>(())
 //! ```
 //!
+//! <br>
+//!
+//! ## Compatible layouts
+//!
+//! The [layout] module provides encodings which are byte-for-byte compatible
+//! with [bincode] 1.x and [postcard] 1.x, for when data has to be exchanged
+//! with services which use them through serde:
+//!
+//! ```rust
+//! use musli_storage::layout::{BINCODE, POSTCARD};
+//!
+//! assert_eq!(BINCODE.to_vec(&(1u16, "a"))?, b"\x01\0\x01\0\0\0\0\0\0\0a");
+//! assert_eq!(POSTCARD.to_vec(&(1u16, "a"))?, b"\x01\x01a");
+//! # Ok::<_, musli_storage::Error>(())
+//! ```
+//!
+//! [bincode]: https://docs.rs/bincode/1
+//! [postcard]: https://docs.rs/postcard/1
+//! [layout]: https://docs.rs/musli-storage/latest/musli_storage/layout/index.html
 //! [default encoding format]: https://docs.rs/musli-storage/latest/musli-storage/struct.Encoding.html
 //! [musli-wire]: https://docs.rs/musli-wire
 //! [Müsli]: https://docs.rs/musli
@@ -107,6 +126,7 @@ pub mod de;
 pub mod en;
 pub mod encoding;
 mod error;
+pub mod layout;
 #[cfg(feature = "test")]
 pub mod test;
 
@@ -0,0 +1,58 @@
+//! Helpers shared by tests which compare formats against golden bytes, or
+//! against the bytes produced by a serde implementation of the same format.
+
+/// Assert that encoding `$value` with the given format produces `$expected`,
+/// and that `$expected` decodes back into `$value`.
+///
+/// The format is the name of a crate which exposes `to_vec` and `from_slice`
+/// functions, such as `musli_msgpack`. Other functions can be used by naming
+/// them, as in `musli_toml::{to_string, from_str}`.
+#[macro_export]
+macro_rules! assert_golden {
+    ($format:ident::{$encode:ident, $decode:ident}, $value:expr, $expected:expr $(,)?) => {{
+        let value = $value;
+        let expected = $expected;
+        let out =
+            ::$format::$encode(&value).expect(concat!(stringify!($format), ": failed to encode"));
+        assert_eq!(
+            out, *expected,
+            concat!(stringify!($format), ": encoding does not match")
+        );
+        let decoded = $crate::interop::decoded_as(&value, ::$format::$decode(expected))
+            .expect(concat!(stringify!($format), ": failed to decode"));
+        assert_eq!(
+            decoded, value,
+            concat!(stringify!($format), ": decoding does not match")
+        );
+    }};
+
+    ($format:ident, $value:expr, $expected:expr $(,)?) => {
+        $crate::assert_golden!($format::{to_vec, from_slice}, $value, $expected)
+    };
+}
+
+/// Assert that `$input` decodes into `$expected` with the given format.
+///
+/// See [`assert_golden!`] for what is expected of the format.
+#[macro_export]
+macro_rules! assert_decode {
+    ($format:ident::$decode:ident, $input:expr, $expected:expr $(,)?) => {{
+        let expected = $expected;
+        let decoded = $crate::interop::decoded_as(&expected, ::$format::$decode($input))
+            .expect(concat!(stringify!($format), ": failed to decode"));
+        assert_eq!(
+            decoded, expected,
+            concat!(stringify!($format), ": decoding does not match")
+        );
+    }};
+
+    ($format:ident, $input:expr, $expected:expr $(,)?) => {
+        $crate::assert_decode!($format::from_slice, $input, $expected)
+    };
+}
+
+/// Helper to infer the type being decoded from the value it is compared to.
+#[doc(hidden)]
+pub fn decoded_as<T, E>(_: &T, result: Result<T, E>) -> Result<T, E> {
+    result
+}
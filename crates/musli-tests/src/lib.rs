@@ -119,6 +119,7 @@ deny!("rkyv", "model_tuple", "model_map_string_key", "model_usize");
 deny!("dlhn", "model_map", "model_128");
 deny!("bitcode", "model_128");
 
+pub mod interop;
 mod mode;
 pub mod models;
 pub mod utils;
@@ -172,6 +173,10 @@ macro_rules! feature_matrix {
         $call!(musli_wire, musli_wire_buf $(, $($tt)*)*);
         #[cfg(feature = "musli-descriptive")]
         $call!(musli_descriptive, musli_descriptive_buf $(, $($tt)*)*);
+        #[cfg(feature = "musli-msgpack")]
+        $call!(musli_msgpack, musli_msgpack_buf $(, $($tt)*)*);
+        #[cfg(feature = "musli-cbor")]
+        $call!(musli_cbor, musli_cbor_buf $(, $($tt)*)*);
         #[cfg(feature = "musli-storage")]
         $call!(musli_storage, musli_storage_buf $(, $($tt)*)*);
         #[cfg(feature = "musli-storage")]
@@ -10,6 +10,7 @@ pub mod musli_json {
 
     use ::musli_json::Encoding;
     use ::musli_json::Error;
+    use musli::mode::TextMode;
     use musli::{Decode, Encode};
 
     const ENCODING: Encoding = Encoding::new();
@@ -25,7 +26,7 @@ pub mod musli_json {
     #[inline(always)]
     pub fn encode<'buf, T>(buf: &'buf mut Vec<u8>, value: &T) -> Result<&'buf [u8], Error>
     where
-        T: Encode,
+        T: Encode<TextMode>,
     {
         ENCODING.encode(&mut *buf, value)?;
         Ok(buf.as_slice())
@@ -34,7 +35,7 @@ pub mod musli_json {
     #[inline(always)]
     pub fn decode<'de, T>(data: &'de [u8]) -> Result<T, Error>
     where
-        T: Decode<'de>,
+        T: Decode<'de, TextMode>,
     {
         ENCODING.from_slice(data)
     }
@@ -193,6 +194,80 @@ pub mod musli_descriptive {
     }
 }
 
+#[cfg(feature = "musli-msgpack")]
+pub mod musli_msgpack {
+    use alloc::vec::Vec;
+
+    use ::musli_msgpack::Encoding;
+    use musli::mode::DefaultMode;
+    use musli::{Decode, Encode};
+    use musli_msgpack::Error;
+
+    const ENCODING: Encoding<DefaultMode> = Encoding::new();
+
+    pub fn buffer() -> Vec<u8> {
+        Vec::with_capacity(4096)
+    }
+
+    pub fn reset<T>(buf: &mut Vec<u8>, _: usize, _: &T) {
+        buf.clear();
+    }
+
+    #[inline(always)]
+    pub fn encode<'buf, T>(buf: &'buf mut Vec<u8>, value: &T) -> Result<&'buf [u8], Error>
+    where
+        T: Encode,
+    {
+        ENCODING.encode(&mut *buf, value)?;
+        Ok(buf.as_slice())
+    }
+
+    #[inline(always)]
+    pub fn decode<'de, T>(data: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de>,
+    {
+        ENCODING.decode(data)
+    }
+}
+
+#[cfg(feature = "musli-cbor")]
+pub mod musli_cbor {
+    use alloc::vec::Vec;
+
+    use ::musli_cbor::Encoding;
+    use musli::mode::DefaultMode;
+    use musli::{Decode, Encode};
+    use musli_cbor::Error;
+
+    const ENCODING: Encoding<DefaultMode> = Encoding::new();
+
+    pub fn buffer() -> Vec<u8> {
+        Vec::with_capacity(4096)
+    }
+
+    pub fn reset<T>(buf: &mut Vec<u8>, _: usize, _: &T) {
+        buf.clear();
+    }
+
+    #[inline(always)]
+    pub fn encode<'buf, T>(buf: &'buf mut Vec<u8>, value: &T) -> Result<&'buf [u8], Error>
+    where
+        T: Encode,
+    {
+        ENCODING.encode(&mut *buf, value)?;
+        Ok(buf.as_slice())
+    }
+
+    #[inline(always)]
+    pub fn decode<'de, T>(data: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de>,
+    {
+        ENCODING.decode(data)
+    }
+}
+
 #[cfg(feature = "musli-value")]
 pub mod musli_value {
     use ::musli_value::Value;
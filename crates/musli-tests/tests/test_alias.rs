@@ -0,0 +1,95 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Named {
+    #[musli(alias = "user", alias = "login")]
+    username: String,
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct NamedOld {
+    user: String,
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Indexed {
+    #[musli(rename = 2, alias = 0)]
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct IndexedOld {
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_variant_name = "name")]
+pub enum Animal {
+    #[musli(alias = "Doggo")]
+    Dog,
+    Cat,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_variant_name = "name")]
+pub enum AnimalOld {
+    Doggo,
+}
+
+#[test]
+fn alias_string() {
+    let old = NamedOld {
+        user: String::from("root"),
+        id: 1,
+    };
+
+    let json = musli_json::to_string(&old).unwrap();
+    assert_eq!(json, r#"{"user":"root","id":1}"#);
+
+    let new: Named = musli_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        new,
+        Named {
+            username: String::from("root"),
+            id: 1
+        }
+    );
+
+    let new: Named = musli_json::from_str(r#"{"login":"admin","id":2}"#).unwrap();
+    assert_eq!(new.username, "admin");
+
+    // Aliases are never produced when encoding.
+    let json = musli_json::to_string(&new).unwrap();
+    assert_eq!(json, r#"{"username":"admin","id":2}"#);
+}
+
+#[test]
+fn alias_index() {
+    let out = musli_tests::wire::to_vec(&IndexedOld { value: 42 }).unwrap();
+    let new: Indexed = musli_tests::wire::decode(out.as_slice()).unwrap();
+    assert_eq!(new, Indexed { value: 42 });
+
+    musli_tests::rt!(Indexed { value: 7 });
+}
+
+#[test]
+fn alias_variant() {
+    let out = musli_tests::wire::to_vec(&AnimalOld::Doggo).unwrap();
+    let new: Animal = musli_tests::wire::decode(out.as_slice()).unwrap();
+    assert_eq!(new, Animal::Dog);
+
+    let json = musli_json::to_string(&Animal::Dog).unwrap();
+    assert_eq!(json, r#"{"Dog":{}}"#);
+
+    let new: Animal = musli_json::from_str(r#"{"Doggo":{}}"#).unwrap();
+    assert_eq!(new, Animal::Dog);
+    let new: Animal = musli_json::from_str(r#"{"Cat":{}}"#).unwrap();
+    assert_eq!(new, Animal::Cat);
+}
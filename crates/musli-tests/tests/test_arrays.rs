@@ -0,0 +1,129 @@
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+use musli::de::Decoder;
+use musli::mode::Mode;
+use musli::{Context, Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Element {
+    name: String,
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Arrays {
+    empty: [u32; 0],
+    floats: [f32; 3],
+    numbers: [u64; 4],
+    strings: [String; 2],
+    elements: [Element; 2],
+    nested: [[u8; 2]; 2],
+}
+
+#[test]
+fn test_arrays() {
+    musli_tests::rt!(Arrays {
+        empty: [],
+        floats: [1.0, 2.5, -3.0],
+        numbers: [1, 2, u64::MAX, 0],
+        strings: [String::from("Hello"), String::from("World")],
+        elements: [
+            Element {
+                name: String::from("a"),
+                value: 1,
+            },
+            Element {
+                name: String::from("b"),
+                value: 2,
+            },
+        ],
+        nested: [[1, 2], [3, 4]],
+    });
+}
+
+#[test]
+fn test_array_length_mismatch() {
+    let out = musli_tests::wire::to_vec(&[String::from("a"), String::from("b")])
+        .expect("failed to encode");
+    let result = musli_tests::wire::from_slice::<[String; 3]>(out.as_slice());
+    assert!(result.is_err());
+
+    let out = musli_tests::storage::to_vec(&[String::from("a"), String::from("b")])
+        .expect("failed to encode");
+    let result = musli_tests::storage::from_slice::<[String; 3]>(out.as_slice());
+    assert!(result.is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct ByteArray {
+    id: [u8; 4],
+}
+
+#[test]
+fn test_byte_array_encoding() {
+    let out = musli_tests::s::to_vec(&ByteArray { id: [1, 2, 3, 4] }).expect("failed to encode");
+    assert_eq!(out, [65, 4, 0, 100, 1, 2, 3, 4]);
+
+    let value = musli_tests::s::from_slice::<ByteArray>(&out).expect("failed to decode");
+    assert_eq!(value, ByteArray { id: [1, 2, 3, 4] });
+}
+
+const FAIL: u32 = 100;
+const PANIC: u32 = 200;
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// An element which counts how many times it has been dropped, and which
+/// fails or panics when decoding one of the sentinel values.
+#[derive(Debug)]
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+impl<'de, M> Decode<'de, M> for Counted
+where
+    M: Mode,
+{
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        match decoder.decode_u32(cx)? {
+            FAIL => Err(cx.message("failing element")),
+            PANIC => panic!("panicking element"),
+            _ => Ok(Counted),
+        }
+    }
+}
+
+#[test]
+fn test_array_drops_decoded_elements_on_error() {
+    DROPS.with(|drops| drops.set(0));
+
+    let out = musli_tests::wire::to_vec(&[1u32, 2, FAIL, 4]).expect("failed to encode");
+    let result = musli_tests::wire::from_slice::<[Counted; 4]>(out.as_slice());
+    assert!(result.is_err());
+
+    assert_eq!(DROPS.with(Cell::get), 2);
+}
+
+#[test]
+fn test_array_drops_decoded_elements_on_panic() {
+    DROPS.with(|drops| drops.set(0));
+
+    let out = musli_tests::wire::to_vec(&[1u32, 2, 3, PANIC]).expect("failed to encode");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        musli_tests::wire::from_slice::<[Counted; 4]>(out.as_slice())
+    }));
+    assert!(result.is_err());
+
+    assert_eq!(DROPS.with(Cell::get), 3);
+}
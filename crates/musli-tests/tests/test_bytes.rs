@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use musli::compat::Bytes;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Fields<'a> {
+    #[musli(bytes)]
+    vec: Vec<u8>,
+    #[musli(bytes)]
+    slice: &'a [u8],
+    #[musli(bytes)]
+    boxed: Box<[u8]>,
+    #[musli(bytes)]
+    cow: Cow<'a, [u8]>,
+    #[musli(bytes)]
+    deque: VecDeque<u8>,
+    #[musli(bytes)]
+    array: [u8; 4],
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Wrapped<'a> {
+    vec: Bytes<Vec<u8>>,
+    slice: Bytes<&'a [u8]>,
+    boxed: Bytes<Box<[u8]>>,
+    cow: Bytes<Cow<'a, [u8]>>,
+    deque: Bytes<VecDeque<u8>>,
+    array: Bytes<[u8; 4]>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Sequence {
+    vec: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Packed {
+    #[musli(bytes)]
+    vec: Vec<u8>,
+}
+
+#[test]
+fn bytes_fields() {
+    let fields = Fields {
+        vec: vec![1, 2, 3],
+        slice: &[4, 5],
+        boxed: Box::new([6]),
+        cow: Cow::Borrowed(&[7, 8]),
+        deque: VecDeque::from([9, 10]),
+        array: [11, 12, 13, 14],
+    };
+
+    let out = musli_wire::to_vec(&fields).unwrap();
+    let actual: Fields<'_> = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, fields);
+    assert!(matches!(actual.cow, Cow::Borrowed(..)));
+
+    let out = musli_storage::to_vec(&fields).unwrap();
+    let actual: Fields<'_> = musli_storage::from_slice(&out).unwrap();
+    assert_eq!(actual, fields);
+
+    let wrapped = Wrapped {
+        vec: Bytes(fields.vec.clone()),
+        slice: Bytes(fields.slice),
+        boxed: Bytes(fields.boxed.clone()),
+        cow: Bytes(fields.cow.clone()),
+        deque: Bytes(fields.deque.clone()),
+        array: Bytes(fields.array),
+    };
+
+    // The field attribute and the wrapper are encoded the same way.
+    let wrapped_out = musli_storage::to_vec(&wrapped).unwrap();
+    assert_eq!(wrapped_out, out);
+
+    let actual: Wrapped<'_> = musli_storage::from_slice(&wrapped_out).unwrap();
+    assert_eq!(actual, wrapped);
+}
+
+#[test]
+fn bytes_compact() {
+    let vec = (0..=255).collect::<Vec<u8>>();
+
+    let sequence = musli_wire::to_vec(&Sequence { vec: vec.clone() }).unwrap();
+    let bytes = musli_wire::to_vec(&Packed { vec: vec.clone() }).unwrap();
+    assert!(bytes.len() < sequence.len());
+
+    let actual: Packed = musli_wire::from_slice(&bytes).unwrap();
+    assert_eq!(actual, Packed { vec });
+}
@@ -0,0 +1,598 @@
+#![cfg(all(feature = "std", feature = "musli-cbor"))]
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::mode::DefaultMode;
+use musli::{Decode, Encode};
+use musli_cbor::tag::{self, Tagged};
+use musli_cbor::Encoding;
+use musli_tests::{assert_decode, assert_golden};
+use musli_value::Value;
+use serde::{Deserialize, Serialize, Serializer};
+
+const CANONICAL: Encoding<DefaultMode> = Encoding::new().with_canonical();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Named {
+    id: u32,
+    name: String,
+    score: f64,
+    delta: i64,
+    tags: Vec<String>,
+    parent: Option<u64>,
+    attributes: BTreeMap<String, i16>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerdeNamed {
+    id: u32,
+    name: String,
+    score: f64,
+    delta: i64,
+    tags: Vec<String>,
+    parent: Option<u64>,
+    attributes: BTreeMap<String, i16>,
+}
+
+#[test]
+fn named_structs() {
+    for (id, delta, parent) in [
+        (0, 0, None),
+        (23, -24, Some(24)),
+        (255, -25, Some(u64::MAX)),
+        (65535, -257, Some(70000)),
+        (u32::MAX, i64::MIN, Some(1 << 32)),
+    ] {
+        let mut attributes = BTreeMap::new();
+        attributes.insert(String::from("a"), i16::MIN);
+        attributes.insert(String::from("b"), -1);
+        attributes.insert(String::from("c"), i16::MAX);
+
+        let expected = serde_cbor::to_vec(&SerdeNamed {
+            id,
+            name: "a".repeat(40),
+            score: 0.1,
+            delta,
+            tags: vec![String::from("x"); 30],
+            parent,
+            attributes: attributes.clone(),
+        })
+        .unwrap();
+
+        assert_golden!(
+            musli_cbor,
+            Named {
+                id,
+                name: "a".repeat(40),
+                score: 0.1,
+                delta,
+                tags: vec![String::from("x"); 30],
+                parent,
+                attributes,
+            },
+            &expected,
+        );
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Packed {
+    a: u8,
+    b: i32,
+    c: String,
+    d: bool,
+    e: f32,
+}
+
+#[test]
+fn packed_structs() {
+    let value = Packed {
+        a: 200,
+        b: -100_000,
+        c: String::from("packed"),
+        d: true,
+        e: 1.5,
+    };
+
+    // Packs are encoded as arrays of indefinite length, unless the canonical
+    // encoding is used.
+    let expected = serde_cbor::to_vec(&(200u8, -100_000i32, "packed", true, 1.5f32)).unwrap();
+    let out = musli_cbor::to_vec(&value).unwrap();
+    assert_eq!(out[0], 0x9f);
+    assert_eq!(out[1..out.len() - 1], expected[1..]);
+    assert_eq!(out[out.len() - 1], 0xff);
+
+    assert_eq!(CANONICAL.to_vec(&value).unwrap(), expected);
+    assert_decode!(musli_cbor, &expected, value);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct WithBytes {
+    #[musli(bytes)]
+    data: Vec<u8>,
+}
+
+struct SerdeBytes<'a>(&'a [u8]);
+
+impl Serialize for SerdeBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[derive(Serialize)]
+struct SerdeWithBytes<'a> {
+    data: SerdeBytes<'a>,
+}
+
+#[test]
+fn bytes() {
+    for len in [0, 23, 24, 256, 65536] {
+        let data = vec![7u8; len];
+        let expected = serde_cbor::to_vec(&SerdeWithBytes {
+            data: SerdeBytes(&data),
+        })
+        .unwrap();
+        assert_golden!(musli_cbor, WithBytes { data }, &expected);
+    }
+}
+
+#[test]
+fn floats() {
+    for value in [
+        0.0,
+        -0.0,
+        1.0,
+        1.5,
+        -4.0,
+        65504.0,
+        100000.0,
+        0.00006103515625,
+        5.960464477539063e-8,
+        3.4028234663852886e38,
+        1.1,
+        1.0e300,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        let expected = serde_cbor::to_vec(&value).unwrap();
+        assert_golden!(musli_cbor, value, &expected);
+
+        if value as f32 as f64 == value || value.is_infinite() {
+            let expected = serde_cbor::to_vec(&(value as f32)).unwrap();
+            assert_golden!(musli_cbor, value as f32, &expected);
+        }
+    }
+
+    assert_eq!(musli_cbor::to_vec(&f64::NAN).unwrap(), [0xf9, 0x7e, 0x00]);
+    assert_eq!(musli_cbor::to_vec(&f32::NAN).unwrap(), [0xf9, 0x7e, 0x00]);
+
+    let decoded: f64 = musli_cbor::from_slice(&[0xf9, 0x7e, 0x00]).unwrap();
+    assert!(decoded.is_nan());
+
+    // Golden bytes from appendix A of RFC 8949.
+    assert_decode!(musli_cbor, &[0xf9, 0x3c, 0x00], 1.0f32);
+    assert_decode!(musli_cbor, &[0xf9, 0x7b, 0xff], 65504.0f64);
+    assert_decode!(musli_cbor, &[0xf9, 0x00, 0x01], 5.960464477539063e-8f64);
+    assert_decode!(musli_cbor, &[0xf9, 0xc4, 0x00], -4.0f32);
+    assert_decode!(musli_cbor, &[0xfa, 0x47, 0xc3, 0x50, 0x00], 100000.0f64);
+}
+
+#[test]
+fn wide_integers() {
+    // 128-bit values which fit in 64 bits are encoded as regular integers.
+    let expected = serde_cbor::to_vec(&u64::MAX).unwrap();
+    assert_golden!(musli_cbor, u64::MAX as u128, &expected);
+
+    let expected = serde_cbor::to_vec(&-(1i128 << 64)).unwrap();
+    assert_golden!(musli_cbor, -(1i128 << 64), &expected);
+
+    // Golden bytes from appendix A of RFC 8949, which are bignums.
+    let mut expected = vec![0xc2, 0x49, 0x01];
+    expected.extend_from_slice(&[0; 8]);
+    assert_golden!(musli_cbor, 1u128 << 64, &expected);
+    assert_golden!(musli_cbor, 1i128 << 64, &expected);
+
+    let mut expected = vec![0xc3, 0x49, 0x01];
+    expected.extend_from_slice(&[0; 8]);
+    assert_golden!(musli_cbor, -(1i128 << 64) - 1, &expected);
+
+    let mut expected = vec![0xc2, 0x50];
+    expected.extend_from_slice(&[0xff; 16]);
+    assert_golden!(musli_cbor, u128::MAX, &expected);
+
+    let mut expected = vec![0xc3, 0x50, 0x7f];
+    expected.extend_from_slice(&[0xff; 15]);
+    assert_golden!(musli_cbor, i128::MIN, &expected);
+
+    // Leading zeros are accepted, and bignums can hold small values.
+    assert_decode!(musli_cbor, &[0xc2, 0x43, 0x00, 0x00, 0x2a], 42u64);
+    assert_decode!(musli_cbor, &[0xc3, 0x41, 0x2a], -43i32);
+
+    assert!(musli_cbor::from_slice::<u128>(&[0xc3, 0x41, 0x2a]).is_err());
+    assert!(musli_cbor::from_slice::<u8>(&[0x19, 0x01, 0x00]).is_err());
+    assert!(musli_cbor::from_slice::<i64>(&[0x1b, 0xff, 0, 0, 0, 0, 0, 0, 0]).is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Message {
+    #[musli(transparent, rename = "Text")]
+    Text(String),
+    #[musli(rename = "Move", default_field_name = "name")]
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Serialize, Deserialize)]
+enum SerdeMessage {
+    Text(String),
+    Move { x: i32, y: i32 },
+}
+
+#[test]
+fn variants() {
+    let expected = serde_cbor::to_vec(&SerdeMessage::Text(String::from("hi"))).unwrap();
+    assert_golden!(musli_cbor, Message::Text(String::from("hi")), &expected);
+
+    let expected = serde_cbor::to_vec(&SerdeMessage::Move { x: -1, y: 300 }).unwrap();
+    assert_golden!(musli_cbor, Message::Move { x: -1, y: 300 }, &expected);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Indefinite {
+    a: u32,
+    b: Vec<u32>,
+}
+
+#[test]
+fn indefinite_lengths() {
+    // Golden bytes from appendix A of RFC 8949.
+    assert_decode!(
+        musli_cbor,
+        &[0x5f, 0x42, 0x01, 0x02, 0x43, 0x03, 0x04, 0x05, 0xff],
+        WithBytesValue(vec![1, 2, 3, 4, 5]),
+    );
+    assert_decode!(
+        musli_cbor,
+        b"\x7f\x65strea\x64ming\xff",
+        String::from("streaming")
+    );
+    assert_decode!(musli_cbor, &[0x9f, 0xff], Vec::<u32>::new());
+    assert_decode!(
+        musli_cbor,
+        &[0x9f, 0x01, 0x82, 0x02, 0x03, 0x9f, 0x04, 0x05, 0xff, 0xff],
+        (1u32, vec![2u32, 3], vec![4u32, 5]),
+    );
+    assert_decode!(
+        musli_cbor,
+        b"\xbf\x61a\x01\x61b\x9f\x02\x03\xff\xff",
+        Indefinite {
+            a: 1,
+            b: vec![2, 3],
+        },
+    );
+    assert_decode!(
+        musli_cbor,
+        b"\x82\x61a\xbf\x61b\x61c\xff",
+        (
+            String::from("a"),
+            HashMap::from([(String::from("b"), String::from("c"))]),
+        ),
+    );
+    assert_decode!(
+        musli_cbor,
+        b"\x5f\x41\x01\x42\x02\x03\xff",
+        ArrayValue([1, 2, 3])
+    );
+
+    // A variant encoded as a map of indefinite length.
+    assert_decode!(
+        musli_cbor,
+        b"\xbf\x64Text\x62hi\xff",
+        Message::Text(String::from("hi"))
+    );
+
+    // Unknown fields of indefinite length are skipped.
+    assert_decode!(
+        musli_cbor,
+        b"\xbf\x61a\x01\x61c\x7f\x61x\xff\x61d\xbf\x01\x9f\xff\xff\x61b\x80\xff",
+        Indefinite { a: 1, b: vec![] },
+    );
+
+    // Chunks of a different major type and missing breaks are errors.
+    assert!(musli_cbor::from_slice::<String>(b"\x7f\x41a\xff").is_err());
+    assert!(musli_cbor::from_slice::<Vec<u32>>(&[0x9f, 0x01]).is_err());
+    assert!(musli_cbor::from_slice::<Message>(b"\xbf\x64Text\x62hi\x01\x02\xff").is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct WithBytesValue(#[musli(bytes)] Vec<u8>);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+struct ArrayValue(#[musli(bytes)] [u8; 3]);
+
+#[test]
+fn tags() {
+    // Golden bytes from appendix A of RFC 8949.
+    let mut expected = vec![0xc0, 0x74];
+    expected.extend_from_slice(b"2013-03-21T20:04:00Z");
+    assert_golden!(
+        musli_cbor,
+        Tagged::new(tag::DATE_TIME, String::from("2013-03-21T20:04:00Z")),
+        &expected,
+    );
+
+    assert_golden!(
+        musli_cbor,
+        Tagged::new(tag::EPOCH_DATE_TIME, 1363896240.5f64),
+        &[0xc1, 0xfb, 0x41, 0xd4, 0x52, 0xd9, 0xec, 0x20, 0x00, 0x00],
+    );
+
+    let mut expected = vec![0xd8, 0x20, 0x76];
+    expected.extend_from_slice(b"http://www.example.com");
+    assert_golden!(
+        musli_cbor,
+        Tagged::new(32, String::from("http://www.example.com")),
+        &expected,
+    );
+
+    // Tags can be nested, and a tagged value which is not expected is an
+    // error.
+    assert_golden!(
+        musli_cbor,
+        Tagged::new(tag::SELF_DESCRIBED, Tagged::new(tag::EPOCH_DATE_TIME, 1u8)),
+        &[0xd9, 0xd9, 0xf7, 0xc1, 0x01],
+    );
+
+    assert!(musli_cbor::from_slice::<u32>(&[0xc1, 0x01]).is_err());
+    assert!(musli_cbor::from_slice::<Tagged<u32>>(&[0x01]).is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Unordered {
+    zz: u32,
+    b: u32,
+    a: u32,
+}
+
+#[test]
+fn canonical() {
+    let value = Unordered { zz: 1, b: 2, a: 3 };
+
+    assert_eq!(
+        CANONICAL.to_vec(&value).unwrap(),
+        b"\xa3\x61a\x03\x61b\x02\x62zz\x01"
+    );
+    assert_eq!(
+        musli_cbor::to_vec(&value).unwrap(),
+        b"\xa3\x62zz\x01\x61b\x02\x61a\x03"
+    );
+
+    // Keys are sorted by their encoding, so shorter keys sort first.
+    let map = HashMap::from([(1000u32, 0u8), (10, 1), (100, 2)]);
+
+    assert_eq!(
+        CANONICAL.to_vec(&map).unwrap(),
+        [0xa3, 0x0a, 0x01, 0x18, 0x64, 0x02, 0x19, 0x03, 0xe8, 0x00]
+    );
+
+    let map = HashMap::from([
+        (String::from("aa"), Some(1u32)),
+        (String::from("b"), None),
+        (String::from("z"), Some(2)),
+    ]);
+
+    let out = CANONICAL.to_vec(&map).unwrap();
+    assert_eq!(out, b"\xa3\x61b\xf6\x61z\x02\x62aa\x01");
+
+    // Nested maps are sorted, and packs are given a definite length.
+    let value = vec![
+        HashMap::from([(
+            2u8,
+            Packed {
+                a: 1,
+                b: 2,
+                c: String::new(),
+                d: false,
+                e: 0.0,
+            },
+        )]),
+        HashMap::new(),
+    ];
+
+    let out = CANONICAL.to_vec(&value).unwrap();
+    assert_eq!(
+        out,
+        [0x82, 0xa1, 0x02, 0x85, 0x01, 0x02, 0x60, 0xf4, 0xf9, 0x00, 0x00, 0xa0]
+    );
+
+    // Duplicate keys are rejected.
+    let value = Value::Map(vec![
+        (Value::String(String::from("a")), Value::Bool(true)),
+        (Value::String(String::from("a")), Value::Bool(false)),
+    ]);
+
+    assert!(musli_cbor::to_vec(&value).is_ok());
+    assert!(CANONICAL.to_vec(&value).is_err());
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[musli(bytes)]
+    data: &'a [u8],
+}
+
+#[test]
+fn zero_copy() {
+    let expected = serde_cbor::to_vec(&SerdeWithBytes {
+        data: SerdeBytes(b"borrowed"),
+    })
+    .unwrap();
+
+    // Prepend the name field to the map with one entry.
+    let mut input = vec![0xa2, 0x64];
+    input.extend_from_slice(b"name");
+    input.extend_from_slice(&serde_cbor::to_vec(&"zero copy").unwrap());
+    input.extend_from_slice(&expected[1..]);
+
+    let decoded: Borrowed<'_> = musli_cbor::from_slice(&input).unwrap();
+
+    assert_eq!(
+        decoded,
+        Borrowed {
+            name: "zero copy",
+            data: b"borrowed",
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Version1 {
+    id: u32,
+}
+
+#[test]
+fn skip_unknown_fields() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(String::from("key"), 1);
+
+    let input = serde_cbor::to_vec(&SerdeNamed {
+        id: 42,
+        name: String::from("name"),
+        score: 1.0,
+        delta: -1,
+        tags: vec![String::from("tag")],
+        parent: Some(1),
+        attributes,
+    })
+    .unwrap();
+
+    let decoded: Version1 = musli_cbor::from_slice(&input).unwrap();
+    assert_eq!(decoded, Version1 { id: 42 });
+}
+
+#[test]
+fn decode_any() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(String::from("key"), -300);
+
+    let expected = serde_cbor::to_vec(&SerdeNamed {
+        id: 42,
+        name: String::from("name"),
+        score: 1.1,
+        delta: -1,
+        tags: vec![String::from("tag")],
+        parent: None,
+        attributes: attributes.clone(),
+    })
+    .unwrap();
+
+    let value: Value = musli_cbor::from_slice(&expected).unwrap();
+    let decoded: Named = musli_value::decode(&value).unwrap();
+
+    assert_eq!(
+        decoded,
+        Named {
+            id: 42,
+            name: String::from("name"),
+            score: 1.1,
+            delta: -1,
+            tags: vec![String::from("tag")],
+            parent: None,
+            attributes,
+        }
+    );
+
+    let out = musli_cbor::to_vec(&value).unwrap();
+    assert_eq!(out, expected);
+
+    let expected = serde_cbor::to_vec(&Some(3u64)).unwrap();
+    let value: Value = musli_cbor::from_slice(&expected).unwrap();
+    assert_eq!(musli_value::decode::<Option<u64>>(&value).unwrap(), Some(3));
+
+    let expected = serde_cbor::to_vec(&SerdeMessage::Move { x: -1, y: 300 }).unwrap();
+    let value: Value = musli_cbor::from_slice(&expected).unwrap();
+    let out = musli_cbor::to_vec(&value).unwrap();
+    assert_eq!(out, expected);
+
+    // Semantic tags are decoded as variants, and bignums as numbers.
+    for expected in [
+        &[0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0][..],
+        &[0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        &[0xf9, 0x3c, 0x00],
+        &[0xf5],
+        &[0xf6],
+    ] {
+        let value: Value = musli_cbor::from_slice(expected).unwrap();
+        let out = musli_cbor::to_vec(&value).unwrap();
+        assert_eq!(out, expected);
+    }
+
+    let value: Value = musli_cbor::from_slice(&[0xc1, 0x01]).unwrap();
+    let decoded: Tagged<u8> = musli_value::decode(&value).unwrap();
+    assert_eq!(decoded, Tagged::new(tag::EPOCH_DATE_TIME, 1));
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    tag = "type",
+    default_field_name = "name",
+    default_variant_name = "name"
+)]
+enum InternallyTagged {
+    Create { name: String, size: u32 },
+    Delete { name: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum SerdeInternallyTagged {
+    Create { name: String, size: u32 },
+    Delete { name: String },
+}
+
+#[test]
+fn internally_tagged() {
+    let expected = serde_cbor::to_vec(&SerdeInternallyTagged::Create {
+        name: String::from("file"),
+        size: 1024,
+    })
+    .unwrap();
+
+    let decoded: InternallyTagged = musli_cbor::from_slice(&expected).unwrap();
+
+    assert_eq!(
+        decoded,
+        InternallyTagged::Create {
+            name: String::from("file"),
+            size: 1024,
+        }
+    );
+
+    let expected = serde_cbor::to_vec(&SerdeInternallyTagged::Delete {
+        name: String::from("file"),
+    })
+    .unwrap();
+
+    assert_golden!(
+        musli_cbor,
+        InternallyTagged::Delete {
+            name: String::from("file"),
+        },
+        &expected,
+    );
+}
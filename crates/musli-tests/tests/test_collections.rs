@@ -0,0 +1,19 @@
+use std::collections::{BTreeSet, LinkedList};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Collections {
+    set: BTreeSet<String>,
+    list: LinkedList<u32>,
+    nested: BTreeSet<Vec<u8>>,
+}
+
+#[test]
+fn test_collections() {
+    musli_tests::rt!(Collections {
+        set: BTreeSet::from([String::from("b"), String::from("a")]),
+        list: LinkedList::from([3, 1, 2]),
+        nested: BTreeSet::from([vec![1, 2], vec![], vec![3]]),
+    });
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "std")]
+
+use std::fmt;
+
+use musli::{Decode, Encode};
+use musli_json::context::{AllocBuf, AllocContext};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(try_from = String, into = String)]
+pub struct Email(String);
+
+#[derive(Debug)]
+pub struct InvalidEmail;
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid email")
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = InvalidEmail;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if !value.contains('@') {
+            return Err(InvalidEmail);
+        }
+
+        Ok(Email(value))
+    }
+}
+
+impl From<&Email> for String {
+    fn from(value: &Email) -> Self {
+        value.0.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct User {
+    name: String,
+    email: Email,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Dto {
+    id: u32,
+    label: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(from = Dto, into = Dto)]
+pub enum Item {
+    Labeled(u32, String),
+}
+
+impl From<Dto> for Item {
+    fn from(value: Dto) -> Self {
+        Item::Labeled(value.id, value.label)
+    }
+}
+
+impl From<&Item> for Dto {
+    fn from(value: &Item) -> Self {
+        let Item::Labeled(id, label) = value;
+
+        Dto {
+            id: *id,
+            label: label.clone(),
+        }
+    }
+}
+
+#[test]
+fn try_from_into() {
+    let user = User {
+        name: String::from("root"),
+        email: Email(String::from("root@example.com")),
+    };
+
+    let json = musli_json::to_string(&user).unwrap();
+    assert_eq!(json, r#"{"name":"root","email":"root@example.com"}"#);
+
+    let actual: User = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, user);
+
+    assert!(musli_json::from_str::<User>(r#"{"name":"root","email":"root"}"#).is_err());
+}
+
+#[test]
+fn try_from_error_path() {
+    let mut buf = AllocBuf::default();
+    let mut cx = AllocContext::new(&mut buf);
+
+    let result = musli_json::Encoding::new()
+        .from_str_with::<_, User>(&mut cx, r#"{"name":"root","email":"root"}"#);
+
+    assert!(result.is_err());
+
+    let errors = cx.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_eq!(errors, [".email: invalid email (at byte 29)"]);
+}
+
+#[test]
+fn from_into() {
+    let item = Item::Labeled(1, String::from("first"));
+
+    let json = musli_json::to_string(&item).unwrap();
+    assert_eq!(json, r#"{"id":1,"label":"first"}"#);
+
+    let actual: Item = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, item);
+
+    let out = musli_storage::to_vec(&item).unwrap();
+    let actual: Item = musli_storage::from_slice(&out).unwrap();
+    assert_eq!(actual, item);
+}
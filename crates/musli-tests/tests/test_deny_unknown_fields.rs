@@ -0,0 +1,114 @@
+#![cfg(feature = "std")]
+
+use musli::mode::{DefaultMode, TextMode};
+use musli::{Decode, Encode};
+
+const DEFAULT_JSON: musli_json::Encoding<DefaultMode> = musli_json::Encoding::new().with_mode();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Config {
+    name: String,
+    port: u16,
+    verbose: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", deny_unknown_fields)]
+pub struct StrictConfig {
+    name: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Indexed {
+    a: u32,
+    b: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(deny_unknown_fields)]
+pub struct StrictIndexed {
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", default_variant_name = "name")]
+pub enum Command {
+    Start { id: u32, force: bool },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    default_field_name = "name",
+    default_variant_name = "name",
+    deny_unknown_fields
+)]
+pub enum StrictCommand {
+    Start { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+#[musli(mode = TextMode, deny_unknown_fields)]
+pub struct ModeAware {
+    name: String,
+}
+
+#[test]
+fn deny_unknown_string_fields() {
+    let config = Config {
+        name: String::from("server"),
+        port: 8080,
+        verbose: true,
+    };
+
+    let json = musli_json::to_string(&config).unwrap();
+    assert!(musli_json::from_str::<StrictConfig>(&json).is_err());
+
+    let actual: StrictConfig = musli_json::from_str(r#"{"port":8080,"name":"server"}"#).unwrap();
+
+    assert_eq!(
+        actual,
+        StrictConfig {
+            name: String::from("server"),
+            port: 8080,
+        }
+    );
+}
+
+#[test]
+fn deny_unknown_indexed_fields() {
+    let out = musli_wire::to_vec(&Indexed { a: 1, b: 2 }).unwrap();
+    assert!(musli_wire::from_slice::<StrictIndexed>(&out).is_err());
+
+    let out = musli_wire::to_vec(&StrictIndexed { a: 1 }).unwrap();
+    let actual: StrictIndexed = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, StrictIndexed { a: 1 });
+}
+
+#[test]
+fn deny_unknown_variant_fields() {
+    let json = musli_json::to_string(&Command::Start { id: 1, force: true }).unwrap();
+    assert!(musli_json::from_str::<StrictCommand>(&json).is_err());
+
+    let actual: StrictCommand = musli_json::from_str(r#"{"Start":{"id":1}}"#).unwrap();
+    assert_eq!(actual, StrictCommand::Start { id: 1 });
+}
+
+#[test]
+fn deny_unknown_fields_mode() {
+    let json = r#"{"name":"server","port":8080}"#;
+
+    // Only the text mode used by default in JSON is strict.
+    assert!(musli_json::from_str::<ModeAware>(json).is_err());
+
+    let actual: ModeAware = DEFAULT_JSON.from_str(json).unwrap();
+
+    assert_eq!(
+        actual,
+        ModeAware {
+            name: String::from("server"),
+        }
+    );
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(discriminant)]
+#[repr(u8)]
+pub enum Op {
+    Add = 1,
+    Sub = 7,
+    Mul,
+    Div = 3,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(discriminant)]
+#[repr(i16)]
+pub enum Status {
+    Ok = 200,
+    Missing = 404,
+    Negative = -1,
+    #[musli(default)]
+    Unknown = 0,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(discriminant)]
+pub enum Implicit {
+    First,
+    Second,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Request {
+    op: Op,
+    status: Status,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Raw {
+    op: u8,
+    status: i16,
+}
+
+#[test]
+fn discriminant_tags() {
+    assert_eq!(musli_json::to_string(&Op::Add).unwrap(), "1");
+    assert_eq!(musli_json::to_string(&Op::Sub).unwrap(), "7");
+    assert_eq!(musli_json::to_string(&Op::Mul).unwrap(), "8");
+    assert_eq!(musli_json::to_string(&Op::Div).unwrap(), "3");
+    assert_eq!(musli_json::to_string(&Status::Negative).unwrap(), "-1");
+    assert_eq!(musli_json::to_string(&Implicit::Second).unwrap(), "1");
+
+    for op in [Op::Add, Op::Sub, Op::Mul, Op::Div] {
+        let out = musli_storage::to_vec(&op).unwrap();
+        assert_eq!(out, [op as u8]);
+    }
+
+    let request = Request {
+        op: Op::Mul,
+        status: Status::Missing,
+    };
+
+    let string = musli_json::to_string(&request).unwrap();
+    assert_eq!(string, r#"{"op":8,"status":404}"#);
+    assert_eq!(musli_json::from_str::<Request>(&string).unwrap(), request);
+
+    let out = musli_wire::to_vec(&request).unwrap();
+    let actual: Request = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, request);
+}
+
+#[test]
+fn discriminant_unknown() {
+    let out = musli_wire::to_vec(&Raw { op: 2, status: 1 }).unwrap();
+    assert!(musli_wire::from_slice::<Request>(&out).is_err());
+
+    let out = musli_wire::to_vec(&Raw { op: 3, status: 500 }).unwrap();
+    let actual: Request = musli_wire::from_slice(&out).unwrap();
+
+    assert_eq!(
+        actual,
+        Request {
+            op: Op::Div,
+            status: Status::Unknown,
+        }
+    );
+}
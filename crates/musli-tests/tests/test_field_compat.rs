@@ -91,3 +91,36 @@ fn test_simple_struct_compat_to_empty() {
 
     assert_eq!(to, SimpleStructToEmpty);
 }
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct SkippedStructFrom {
+    pub field: String,
+    pub small: u8,
+    pub large: u64,
+    pub signed: i32,
+    pub character: char,
+    pub string: String,
+    pub option: Option<u32>,
+    pub other: OtherStruct,
+}
+
+#[test]
+fn test_skip_any_descriptive() {
+    let to = musli_tests::s::transcode::<_, SimpleStructTo>(SkippedStructFrom {
+        field: String::from("Aristotle"),
+        small: 1,
+        large: u64::MAX,
+        signed: -42,
+        character: 'ä',
+        string: String::from("Plato"),
+        option: Some(108),
+        other: OTHER,
+    });
+
+    assert_eq!(
+        to,
+        SimpleStructTo {
+            field: String::from("Aristotle"),
+        }
+    );
+}
@@ -0,0 +1,270 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+
+use musli::{Decode, Encode};
+use musli_value::Value;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Metadata {
+    id: u32,
+    #[musli(skip_encoding_if = Option::is_none, default)]
+    owner: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Message {
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Extra {
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+    #[musli(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", default_variant_name = "name")]
+pub enum Enum {
+    Message {
+        body: String,
+        #[musli(flatten)]
+        metadata: Metadata,
+    },
+}
+
+macro_rules! descriptive {
+    ($expr:expr) => {{
+        let expected = $expr;
+        let out = musli_descriptive::to_vec(&expected).expect("failed to encode");
+        let actual = musli_descriptive::from_slice(out.as_slice()).expect("failed to decode");
+        assert_eq!(expected, actual);
+        actual
+    }};
+}
+
+#[test]
+fn flatten_json() {
+    let message = Message {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: Some(String::from("root")),
+        },
+    };
+
+    let json = musli_json::to_string(&message).unwrap();
+    assert_eq!(json, r#"{"body":"Hello","id":42,"owner":"root"}"#);
+    assert_eq!(musli_json::from_str::<Message>(&json).unwrap(), message);
+
+    let message = Message {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: None,
+        },
+    };
+
+    let json = musli_json::to_string(&message).unwrap();
+    assert_eq!(json, r#"{"body":"Hello","id":42}"#);
+    assert_eq!(musli_json::from_str::<Message>(&json).unwrap(), message);
+
+    // Flattened fields can be interleaved with the fields of the container.
+    let message: Message = musli_json::from_str(r#"{"id":7,"body":"Hi","unknown":1}"#).unwrap();
+    assert_eq!(
+        message,
+        Message {
+            body: String::from("Hi"),
+            metadata: Metadata { id: 7, owner: None },
+        }
+    );
+
+    let value = Enum::Message {
+        body: String::from("Hello"),
+        metadata: Metadata { id: 1, owner: None },
+    };
+
+    let json = musli_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"Message":{"body":"Hello","id":1}}"#);
+    assert_eq!(musli_json::from_str::<Enum>(&json).unwrap(), value);
+}
+
+#[test]
+fn flatten_json_options() {
+    let message: Message =
+        musli_json::from_str(r#"{"body":"Hello","id":42,"owner":null}"#).unwrap();
+    assert_eq!(
+        message,
+        Message {
+            body: String::from("Hello"),
+            metadata: Metadata {
+                id: 42,
+                owner: None,
+            },
+        }
+    );
+
+    // Values buffered from formats without a dedicated optional type store
+    // `None` as a unit and `Some` as the value itself, but mismatched types
+    // are still reported.
+    assert_eq!(
+        musli_value::decode::<Option<u32>>(&Value::Unit).unwrap(),
+        None
+    );
+    assert_eq!(
+        musli_value::decode::<Option<u32>>(&Value::Number(42u32.into())).unwrap(),
+        Some(42)
+    );
+    assert!(musli_value::decode::<Option<u32>>(&Value::String(String::from("root"))).is_err());
+
+    let value = musli_value::encode(Some(String::from("root"))).unwrap();
+    assert_eq!(
+        musli_value::decode::<Option<String>>(&value).unwrap(),
+        Some(String::from("root"))
+    );
+}
+
+#[test]
+fn flatten_json_remaining() {
+    let extra: Extra =
+        musli_json::from_str(r#"{"body":"Hello","id":42,"tags":["a","b"],"count":3}"#).unwrap();
+
+    assert_eq!(extra.body, "Hello");
+    assert_eq!(
+        extra.metadata,
+        Metadata {
+            id: 42,
+            owner: None
+        }
+    );
+
+    let mut keys = extra.extra.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, ["count", "tags"]);
+
+    let json = musli_json::to_string(&extra).unwrap();
+    assert_eq!(musli_json::from_str::<Extra>(&json).unwrap(), extra);
+}
+
+#[test]
+fn flatten_descriptive() {
+    descriptive!(Message {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: Some(String::from("root")),
+        },
+    });
+
+    descriptive!(Message {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: None
+        },
+    });
+
+    descriptive!(Enum::Message {
+        body: String::from("Hello"),
+        metadata: Metadata { id: 1, owner: None },
+    });
+
+    let mut extra = HashMap::new();
+    extra.insert(String::from("count"), Value::String(String::from("three")));
+
+    descriptive!(Extra {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: None
+        },
+        extra,
+    });
+
+    // Flattened fields are encoded inline.
+    let out = musli_descriptive::to_vec(&Message {
+        body: String::from("Hello"),
+        metadata: Metadata {
+            id: 42,
+            owner: None,
+        },
+    })
+    .unwrap();
+
+    #[derive(Debug, PartialEq, Decode)]
+    #[musli(default_field_name = "name")]
+    struct Inline {
+        body: String,
+        id: u32,
+    }
+
+    let inline: Inline = musli_descriptive::from_slice(out.as_slice()).unwrap();
+
+    assert_eq!(
+        inline,
+        Inline {
+            body: String::from("Hello"),
+            id: 42,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Inner {
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Middle {
+    b: u32,
+    #[musli(flatten)]
+    inner: Inner,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Outer {
+    c: u32,
+    #[musli(flatten)]
+    middle: Middle,
+}
+
+#[test]
+fn flatten_nested() {
+    let outer = Outer {
+        c: 1,
+        middle: Middle {
+            b: 2,
+            inner: Inner { a: 3 },
+        },
+    };
+
+    let json = musli_json::to_string(&outer).unwrap();
+    assert_eq!(json, r#"{"c":1,"b":2,"a":3}"#);
+
+    // Decoding nested flattened fields is not supported.
+    let error = musli_json::from_str::<Outer>(&json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("flattened field which has flattened fields of its own"));
+
+    let out = musli_descriptive::to_vec(&outer).unwrap();
+    let error = musli_descriptive::from_slice::<Outer>(out.as_slice()).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("flattened field which has flattened fields of its own"));
+
+    // The flattened field can still be decoded on its own.
+    let middle = musli_json::from_str::<Middle>(&json).unwrap();
+    assert_eq!(middle, outer.middle);
+}
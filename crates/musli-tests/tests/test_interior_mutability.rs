@@ -0,0 +1,72 @@
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, Encode, Decode)]
+struct Metrics {
+    enabled: AtomicBool,
+    requests: AtomicU32,
+    balance: AtomicI64,
+    total: AtomicUsize,
+}
+
+#[test]
+fn test_atomics() {
+    let metrics = Metrics {
+        enabled: AtomicBool::new(true),
+        requests: AtomicU32::new(42),
+        balance: AtomicI64::new(-1000),
+        total: AtomicUsize::new(usize::MAX),
+    };
+
+    let out = musli_tests::wire::to_vec(&metrics).expect("failed to encode");
+    let decoded: Metrics = musli_tests::wire::from_slice(out.as_slice()).expect("failed to decode");
+
+    assert!(decoded.enabled.load(Ordering::Relaxed));
+    assert_eq!(decoded.requests.load(Ordering::Relaxed), 42);
+    assert_eq!(decoded.balance.load(Ordering::Relaxed), -1000);
+    assert_eq!(decoded.total.load(Ordering::Relaxed), usize::MAX);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Cells {
+    cell: Cell<u32>,
+    ref_cell: RefCell<Vec<String>>,
+}
+
+#[test]
+fn test_cells() {
+    musli_tests::rt!(Cells {
+        cell: Cell::new(42),
+        ref_cell: RefCell::new(vec![String::from("Hello"), String::from("World")]),
+    });
+}
+
+#[test]
+fn test_ref_cell_mutably_borrowed() {
+    let value = RefCell::new(String::from("Hello"));
+    let _guard = value.borrow_mut();
+    assert!(musli_tests::wire::to_vec(&value).is_err());
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Locks {
+    mutex: Mutex<String>,
+    rw_lock: RwLock<Vec<u32>>,
+}
+
+#[test]
+fn test_locks() {
+    let locks = Locks {
+        mutex: Mutex::new(String::from("Hello")),
+        rw_lock: RwLock::new(vec![1, 2, 3]),
+    };
+
+    let out = musli_tests::wire::to_vec(&locks).expect("failed to encode");
+    let decoded: Locks = musli_tests::wire::from_slice(out.as_slice()).expect("failed to decode");
+
+    assert_eq!(*decoded.mutex.lock().unwrap(), "Hello");
+    assert_eq!(*decoded.rw_lock.read().unwrap(), [1, 2, 3]);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    tag = "type",
+    default_field_name = "name",
+    default_variant_name = "name"
+)]
+pub enum InternallyTagged {
+    Create { name: String, size: u32 },
+    Delete { name: String },
+}
+
+/// The fields of `InternallyTagged::Create`, including its tag.
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Create {
+    #[musli(rename = "type")]
+    ty: String,
+    name: String,
+    size: u32,
+}
+
+/// The tag is stored as a field of the variant, so formats which store the
+/// length of a struct up front must count it.
+#[test]
+fn internally_tagged_struct_length() {
+    let create: Create = musli_tests::wire::transcode(InternallyTagged::Create {
+        name: String::from("file"),
+        size: 1024,
+    });
+
+    assert_eq!(
+        create,
+        Create {
+            ty: String::from("Create"),
+            name: String::from("file"),
+            size: 1024,
+        }
+    );
+
+    let create: Create = musli_tests::s::transcode(InternallyTagged::Create {
+        name: String::from("file"),
+        size: 1024,
+    });
+
+    assert_eq!(create.size, 1024);
+
+    musli_descriptive::rt!(InternallyTagged::Create {
+        name: String::from("file"),
+        size: 1024,
+    });
+
+    musli_descriptive::rt!(InternallyTagged::Delete {
+        name: String::from("file"),
+    });
+}
@@ -0,0 +1,245 @@
+#![cfg(all(feature = "std", feature = "musli-storage"))]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use musli::{Decode, Encode};
+use musli_storage::layout::{BINCODE, POSTCARD};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Assert that musli produces the same bytes as bincode and postcard, and that
+/// the bytes they produce can be decoded by musli and the other way around.
+#[track_caller]
+fn assert_interop<T>(value: T)
+where
+    T: Debug + PartialEq + Encode + for<'de> Decode<'de> + Serialize + DeserializeOwned,
+{
+    let expected = bincode::serialize(&value).expect("bincode: failed to encode");
+    let out = BINCODE.to_vec(&value).expect("bincode: failed to encode");
+    assert_eq!(out, expected, "bincode: encoding does not match");
+    let decoded: T = BINCODE
+        .from_slice(&expected)
+        .expect("bincode: failed to decode");
+    assert_eq!(decoded, value, "bincode: decoding does not match");
+    let decoded: T = bincode::deserialize(&out).expect("bincode: failed to decode");
+    assert_eq!(decoded, value, "bincode: decoding does not match");
+
+    let expected = postcard::to_allocvec(&value).expect("postcard: failed to encode");
+    let out = POSTCARD.to_vec(&value).expect("postcard: failed to encode");
+    assert_eq!(out, expected, "postcard: encoding does not match");
+    let decoded: T = POSTCARD
+        .from_slice(&expected)
+        .expect("postcard: failed to decode");
+    assert_eq!(decoded, value, "postcard: decoding does not match");
+    let decoded: T = postcard::from_bytes(&out).expect("postcard: failed to decode");
+    assert_eq!(decoded, value, "postcard: decoding does not match");
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Primitives {
+    boolean: bool,
+    character: char,
+    unsigned8: u8,
+    unsigned16: u16,
+    unsigned32: u32,
+    unsigned64: u64,
+    unsigned128: u128,
+    unsignedsize: usize,
+    signed8: i8,
+    signed16: i16,
+    signed32: i32,
+    signed64: i64,
+    signed128: i128,
+    signedsize: isize,
+    float32: f32,
+    float64: f64,
+    unit: (),
+}
+
+#[test]
+fn primitives() {
+    for (character, n, f) in [
+        ('a', 0, 0.0),
+        ('ä', 1, -1.5),
+        ('€', -1, f64::MAX),
+        ('🎉', i64::MAX, f64::MIN_POSITIVE),
+        ('\u{7f}', i64::MIN, f64::INFINITY),
+    ] {
+        assert_interop(Primitives {
+            boolean: n % 2 == 0,
+            character,
+            unsigned8: n as u8,
+            unsigned16: n as u16,
+            unsigned32: n as u32,
+            unsigned64: n as u64,
+            unsigned128: (n as u128) << 64 | n as u128,
+            unsignedsize: n as usize,
+            signed8: n as i8,
+            signed16: n as i16,
+            signed32: n as i32,
+            signed64: n,
+            signed128: (n as i128) << 64,
+            signedsize: n as isize,
+            float32: f as f32,
+            float64: f,
+            unit: (),
+        });
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Collections {
+    string: String,
+    bytes: Vec<u8>,
+    array: [u8; 4],
+    numbers: Vec<u32>,
+    nested: Vec<Vec<String>>,
+    map: BTreeMap<String, Vec<i16>>,
+    tuple: (u8, String, (i32, bool)),
+    none: Option<u64>,
+    some: Option<Option<String>>,
+}
+
+#[test]
+fn collections() {
+    let mut map = BTreeMap::new();
+    map.insert(String::from("a"), vec![1, -2, 300]);
+    map.insert(String::from("b"), Vec::new());
+
+    assert_interop(Collections {
+        string: "x".repeat(200),
+        bytes: vec![1, 2, 3, 255],
+        array: [4, 5, 6, 7],
+        numbers: vec![0, 127, 128, 16384, u32::MAX],
+        nested: vec![vec![], vec![String::from("a"), String::from("ab")]],
+        map,
+        tuple: (1, String::from("two"), (-3, true)),
+        none: None,
+        some: Some(Some(String::from("some"))),
+    });
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Point(i32, i32);
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Empty;
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Nested {
+    meters: Meters,
+    point: Point,
+    empty: Empty,
+    points: Vec<Point>,
+    duration: Duration,
+}
+
+#[test]
+fn structs() {
+    assert_interop(Nested {
+        meters: Meters(1.5),
+        point: Point(-1, 1),
+        empty: Empty,
+        points: vec![Point(0, 0), Point(i32::MIN, i32::MAX)],
+        duration: Duration::new(u64::MAX, 999_999_999),
+    });
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(u8, u8, u8),
+    Nested(Option<Box<Message>>),
+}
+
+#[test]
+fn enums() {
+    assert_interop(Message::Quit);
+    assert_interop(Message::Move { x: -1, y: 1000 });
+    assert_interop(Message::Write(String::from("hello")));
+    assert_interop(Message::ChangeColor(1, 2, 3));
+    assert_interop(Message::Nested(Some(Box::new(Message::Quit))));
+    assert_interop(vec![
+        Message::Nested(None),
+        Message::Write(String::new()),
+        Message::Quit,
+    ]);
+
+    assert_interop::<Result<u32, String>>(Ok(42));
+    assert_interop::<Result<u32, String>>(Err(String::from("error")));
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+struct Skipped {
+    first: u32,
+    #[musli(skip)]
+    #[serde(skip)]
+    skipped: u32,
+    last: String,
+}
+
+#[test]
+fn skipped_fields() {
+    assert_interop(Skipped {
+        first: 1,
+        skipped: 0,
+        last: String::from("last"),
+    });
+}
+
+#[test]
+fn golden() {
+    assert_eq!(
+        BINCODE.to_vec(&(1u16, -1i32, 'ä')).unwrap(),
+        [0x01, 0x00, 0xff, 0xff, 0xff, 0xff, 0xc3, 0xa4]
+    );
+
+    assert_eq!(
+        POSTCARD.to_vec(&(300u16, -1i32, 'ä')).unwrap(),
+        [0xac, 0x02, 0x01, 0x02, 0xc3, 0xa4]
+    );
+
+    assert_eq!(
+        BINCODE.to_vec(&Message::Write(String::from("a"))).unwrap(),
+        [0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'a']
+    );
+
+    assert_eq!(
+        POSTCARD.to_vec(&Message::Write(String::from("a"))).unwrap(),
+        [0x02, 0x01, b'a']
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(BINCODE.from_slice::<bool>(&[2]).is_err());
+    assert!(BINCODE.from_slice::<Option<u8>>(&[2, 0]).is_err());
+    assert!(BINCODE.from_slice::<char>(&[0xff]).is_err());
+    assert!(BINCODE.from_slice::<char>(&[0xc3]).is_err());
+    assert!(POSTCARD.from_slice::<char>(&[0x02, b'a', b'b']).is_err());
+    assert!(POSTCARD.from_slice::<char>(&[0x05, 0, 0, 0, 0, 0]).is_err());
+    assert!(POSTCARD.from_slice::<Message>(&[0x05]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn models() {
+    use musli_tests::models::*;
+
+    let mut rng = musli_tests::rng();
+
+    // `Allocated` is left out since Müsli encodes a `CString` together with
+    // its nul terminator, while serde does not.
+    for _ in 0..100 {
+        assert_interop::<Primitives>(Generate::generate(&mut rng));
+        assert_interop::<MediumEnum>(Generate::generate(&mut rng));
+        assert_interop::<LargeStruct>(Generate::generate(&mut rng));
+    }
+}
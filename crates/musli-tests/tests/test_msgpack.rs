@@ -0,0 +1,382 @@
+#![cfg(all(feature = "std", feature = "musli-msgpack"))]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use musli::{Decode, Encode};
+use musli_msgpack::ext::Ext;
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Assert that musli and rmp-serde produce the same bytes, and that the bytes
+/// produced by rmp-serde can be decoded by musli.
+#[track_caller]
+fn assert_interop<T>(value: T, expected: &[u8])
+where
+    T: Debug + PartialEq + Encode + for<'de> Decode<'de>,
+{
+    let out = musli_msgpack::to_vec(&value).expect("failed to encode");
+    assert_eq!(out, expected);
+    let decoded: T = musli_msgpack::from_slice(expected).expect("failed to decode");
+    assert_eq!(decoded, value);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Named {
+    id: u32,
+    name: String,
+    score: f64,
+    delta: i64,
+    tags: Vec<String>,
+    parent: Option<u64>,
+    attributes: BTreeMap<String, i16>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerdeNamed {
+    id: u32,
+    name: String,
+    score: f64,
+    delta: i64,
+    tags: Vec<String>,
+    parent: Option<u64>,
+    attributes: BTreeMap<String, i16>,
+}
+
+#[test]
+fn named_structs() {
+    for (id, delta, parent) in [
+        (0, 0, None),
+        (127, -32, Some(128)),
+        (255, -33, Some(u64::MAX)),
+        (65535, -129, Some(70000)),
+        (u32::MAX, i64::MIN, Some(1 << 32)),
+    ] {
+        let mut attributes = BTreeMap::new();
+        attributes.insert(String::from("a"), i16::MIN);
+        attributes.insert(String::from("b"), -1);
+        attributes.insert(String::from("c"), i16::MAX);
+
+        let expected = rmp_serde::to_vec_named(&SerdeNamed {
+            id,
+            name: "a".repeat(40),
+            score: 0.5,
+            delta,
+            tags: vec![String::from("x"); 20],
+            parent,
+            attributes: attributes.clone(),
+        })
+        .unwrap();
+
+        assert_interop(
+            Named {
+                id,
+                name: "a".repeat(40),
+                score: 0.5,
+                delta,
+                tags: vec![String::from("x"); 20],
+                parent,
+                attributes,
+            },
+            &expected,
+        );
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Packed {
+    a: u8,
+    b: i32,
+    c: String,
+    d: bool,
+    e: f32,
+}
+
+#[derive(Serialize)]
+struct SerdePacked {
+    a: u8,
+    b: i32,
+    c: String,
+    d: bool,
+    e: f32,
+}
+
+#[test]
+fn packed_structs() {
+    let expected = rmp_serde::to_vec(&SerdePacked {
+        a: 200,
+        b: -100_000,
+        c: String::from("packed"),
+        d: true,
+        e: 1.5,
+    })
+    .unwrap();
+
+    assert_interop(
+        Packed {
+            a: 200,
+            b: -100_000,
+            c: String::from("packed"),
+            d: true,
+            e: 1.5,
+        },
+        &expected,
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct WithBytes {
+    #[musli(bytes)]
+    data: Vec<u8>,
+}
+
+struct SerdeBytes<'a>(&'a [u8]);
+
+impl Serialize for SerdeBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[derive(Serialize)]
+struct SerdeWithBytes<'a> {
+    data: SerdeBytes<'a>,
+}
+
+#[test]
+fn bytes() {
+    for len in [0, 255, 256, 65536] {
+        let data = vec![7u8; len];
+        let expected = rmp_serde::to_vec_named(&SerdeWithBytes {
+            data: SerdeBytes(&data),
+        })
+        .unwrap();
+        assert_interop(WithBytes { data }, &expected);
+    }
+}
+
+#[test]
+fn wide_integers() {
+    let expected = rmp_serde::to_vec(&i128::MIN).unwrap();
+    assert_interop(i128::MIN, &expected);
+
+    let expected = rmp_serde::to_vec(&u128::MAX).unwrap();
+    assert_interop(u128::MAX, &expected);
+
+    // 128-bit values which fit in 64 bits are encoded as regular integers, but
+    // the binary representation used by rmp-serde is accepted.
+    let expected = rmp_serde::to_vec(&42u128).unwrap();
+    let decoded: u128 = musli_msgpack::from_slice(&expected).unwrap();
+    assert_eq!(decoded, 42);
+    assert_eq!(musli_msgpack::to_vec(&42u128).unwrap(), [42]);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Message {
+    #[musli(transparent, rename = "Text")]
+    Text(String),
+    #[musli(rename = "Move", default_field_name = "name")]
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Serialize, Deserialize)]
+enum SerdeMessage {
+    Text(String),
+    Move { x: i32, y: i32 },
+}
+
+#[test]
+fn variants() {
+    let expected = rmp_serde::to_vec_named(&SerdeMessage::Text(String::from("hi"))).unwrap();
+    assert_interop(Message::Text(String::from("hi")), &expected);
+
+    let expected = rmp_serde::to_vec_named(&SerdeMessage::Move { x: -1, y: 300 }).unwrap();
+    assert_interop(Message::Move { x: -1, y: 300 }, &expected);
+}
+
+struct SerdeExt<'a>(i8, &'a [u8]);
+
+impl Serialize for SerdeExt<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("_ExtStruct", &(self.0, SerdeBytes(self.1)))
+    }
+}
+
+#[test]
+fn ext() {
+    for len in [1, 2, 3, 4, 8, 16, 17, 255, 256] {
+        let data = vec![1u8; len];
+        let expected = rmp_serde::to_vec(&SerdeExt(-5, &data)).unwrap();
+        assert_interop(Ext::new(-5, data), &expected);
+    }
+
+    // Extensions nested in other values.
+    let data = [1u8, 2, 3, 4];
+    let expected = rmp_serde::to_vec(&vec![SerdeExt(1, &data), SerdeExt(2, &data)]).unwrap();
+    assert_interop(vec![Ext::new(1, data), Ext::new(2, data)], &expected);
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[musli(bytes)]
+    data: &'a [u8],
+}
+
+#[test]
+fn zero_copy() {
+    let expected = rmp_serde::to_vec_named(&SerdeWithBytes {
+        data: SerdeBytes(b"borrowed"),
+    })
+    .unwrap();
+
+    // Prepend the name field to the map with one entry.
+    let mut input = vec![0x82, 0xa4];
+    input.extend_from_slice(b"name");
+    input.extend_from_slice(&rmp_serde::to_vec("zero copy").unwrap());
+    input.extend_from_slice(&expected[1..]);
+
+    let decoded: Borrowed<'_> = musli_msgpack::from_slice(&input).unwrap();
+
+    assert_eq!(
+        decoded,
+        Borrowed {
+            name: "zero copy",
+            data: b"borrowed",
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Version1 {
+    id: u32,
+}
+
+#[test]
+fn skip_unknown_fields() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(String::from("key"), 1);
+
+    let input = rmp_serde::to_vec_named(&SerdeNamed {
+        id: 42,
+        name: String::from("name"),
+        score: 1.0,
+        delta: -1,
+        tags: vec![String::from("tag")],
+        parent: Some(1),
+        attributes,
+    })
+    .unwrap();
+
+    let decoded: Version1 = musli_msgpack::from_slice(&input).unwrap();
+    assert_eq!(decoded, Version1 { id: 42 });
+}
+
+#[test]
+fn decode_any() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(String::from("key"), -300);
+
+    let expected = rmp_serde::to_vec_named(&SerdeNamed {
+        id: 42,
+        name: String::from("name"),
+        score: 1.0,
+        delta: -1,
+        tags: vec![String::from("tag")],
+        parent: None,
+        attributes: attributes.clone(),
+    })
+    .unwrap();
+
+    let value: musli_value::Value = musli_msgpack::from_slice(&expected).unwrap();
+    let decoded: Named = musli_value::decode(&value).unwrap();
+
+    assert_eq!(
+        decoded,
+        Named {
+            id: 42,
+            name: String::from("name"),
+            score: 1.0,
+            delta: -1,
+            tags: vec![String::from("tag")],
+            parent: None,
+            attributes,
+        }
+    );
+
+    let out = musli_msgpack::to_vec(&value).unwrap();
+    assert_eq!(out, expected);
+
+    let expected = rmp_serde::to_vec(&Some(3u64)).unwrap();
+    let value: musli_value::Value = musli_msgpack::from_slice(&expected).unwrap();
+    assert_eq!(musli_value::decode::<Option<u64>>(&value).unwrap(), Some(3));
+
+    let expected = rmp_serde::to_vec_named(&SerdeMessage::Move { x: -1, y: 300 }).unwrap();
+    let value: musli_value::Value = musli_msgpack::from_slice(&expected).unwrap();
+    let out = musli_msgpack::to_vec(&value).unwrap();
+    assert_eq!(out, expected);
+
+    let expected = rmp_serde::to_vec(&SerdeExt(3, b"data")).unwrap();
+    let value: musli_value::Value = musli_msgpack::from_slice(&expected).unwrap();
+    let out = musli_msgpack::to_vec(&value).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    tag = "type",
+    default_field_name = "name",
+    default_variant_name = "name"
+)]
+enum InternallyTagged {
+    Create { name: String, size: u32 },
+    Delete { name: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum SerdeInternallyTagged {
+    Create { name: String, size: u32 },
+    Delete { name: String },
+}
+
+#[test]
+fn internally_tagged() {
+    let expected = rmp_serde::to_vec_named(&SerdeInternallyTagged::Create {
+        name: String::from("file"),
+        size: 1024,
+    })
+    .unwrap();
+
+    let decoded: InternallyTagged = musli_msgpack::from_slice(&expected).unwrap();
+
+    assert_eq!(
+        decoded,
+        InternallyTagged::Create {
+            name: String::from("file"),
+            size: 1024,
+        }
+    );
+
+    let expected = rmp_serde::to_vec_named(&SerdeInternallyTagged::Delete {
+        name: String::from("file"),
+    })
+    .unwrap();
+
+    assert_interop(
+        InternallyTagged::Delete {
+            name: String::from("file"),
+        },
+        &expected,
+    );
+}
@@ -0,0 +1,49 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use musli::compat::Bytes;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Addresses {
+    ipv4: Ipv4Addr,
+    ipv6: Ipv6Addr,
+    ip: IpAddr,
+    socket_v4: SocketAddrV4,
+    socket_v6: SocketAddrV6,
+    socket: SocketAddr,
+}
+
+fn addresses() -> Addresses {
+    Addresses {
+        ipv4: Ipv4Addr::LOCALHOST,
+        ipv6: Ipv6Addr::LOCALHOST,
+        ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+        socket_v4: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 80),
+        socket_v6: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0),
+        socket: SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 443, 0, 0)),
+    }
+}
+
+#[test]
+fn test_net() {
+    musli_tests::rt!(Addresses, addresses());
+
+    let octets = musli_tests::wire::transcode::<_, Bytes<[u8; 4]>>(Ipv4Addr::LOCALHOST);
+    assert_eq!(octets, Bytes([127, 0, 0, 1]));
+}
+
+#[test]
+fn test_net_human_readable() {
+    let string = musli_json::to_string(&addresses()).expect("failed to encode");
+    assert_eq!(
+        string,
+        r#"{"ipv4":"127.0.0.1","ipv6":"::1","ip":"10.0.0.1","socket_v4":"127.0.0.1:80","socket_v6":"[::1]:8080","socket":"[::]:443"}"#
+    );
+
+    let decoded: Addresses = musli_json::from_str(&string).expect("failed to decode");
+    assert_eq!(decoded, addresses());
+
+    assert!(musli_json::from_str::<Ipv4Addr>(r#""127.0.0.256""#).is_err());
+    assert!(musli_json::from_str::<SocketAddr>(r#""127.0.0.1""#).is_err());
+}
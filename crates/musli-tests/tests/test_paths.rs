@@ -0,0 +1,82 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Paths {
+    path: PathBuf,
+    os_string: OsString,
+}
+
+/// Mirrors the platform representation of paths.
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Platform {
+    #[musli(transparent)]
+    Utf8(String),
+    #[musli(transparent)]
+    Unix(Vec<u8>),
+    #[musli(transparent)]
+    Windows(Vec<u16>),
+}
+
+#[test]
+fn test_paths() {
+    musli_tests::rt!(Paths {
+        path: PathBuf::from("/usr/lib/musli"),
+        os_string: OsString::from("Hello World"),
+    });
+
+    let platform = musli_tests::wire::transcode::<_, Platform>(PathBuf::from("target/debug"));
+    assert_eq!(platform, Platform::Utf8(String::from("target/debug")));
+}
+
+#[test]
+fn test_paths_human_readable() {
+    let string = musli_json::to_string(&PathBuf::from("/tmp/x")).expect("failed to encode");
+    assert_eq!(string, r#""/tmp/x""#);
+
+    let path: PathBuf = musli_json::from_str(&string).expect("failed to decode");
+    assert_eq!(path, PathBuf::from("/tmp/x"));
+
+    let string = musli_json::to_string(&Paths {
+        path: PathBuf::from("/usr/lib/musli"),
+        os_string: OsString::from("Hello World"),
+    })
+    .expect("failed to encode");
+    assert_eq!(string, r#"{"0":"/usr/lib/musli","1":"Hello World"}"#);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_paths_unix() {
+    use std::os::unix::ffi::OsStringExt;
+
+    musli_tests::rt!(Paths {
+        path: PathBuf::from(OsString::from_vec(vec![b'a', 0xff, b'b'])),
+        os_string: OsString::from_vec(vec![0x80]),
+    });
+
+    let path = PathBuf::from(OsString::from_vec(vec![b'a', 0xff, b'b']));
+    let string = musli_json::to_string(&path).expect("failed to encode");
+    let decoded: PathBuf = musli_json::from_str(&string).expect("failed to decode");
+    assert_eq!(decoded, path);
+
+    let out =
+        musli_tests::wire::to_vec(&Platform::Windows(vec![0xd800])).expect("failed to encode");
+    assert!(musli_tests::wire::from_slice::<PathBuf>(out.as_slice()).is_err());
+}
+
+#[test]
+#[cfg(windows)]
+fn test_paths_windows() {
+    use std::os::windows::ffi::OsStringExt;
+
+    musli_tests::rt!(Paths {
+        path: PathBuf::from(OsString::from_wide(&[0x61, 0xd800, 0x62])),
+        os_string: OsString::from_wide(&[0xdc00]),
+    });
+
+    let out = musli_tests::wire::to_vec(&Platform::Unix(vec![0xff])).expect("failed to encode");
+    assert!(musli_tests::wire::from_slice::<PathBuf>(out.as_slice()).is_err());
+}
@@ -0,0 +1,542 @@
+#![cfg(all(feature = "std", feature = "musli-protobuf"))]
+
+use std::collections::BTreeMap;
+
+use musli::compat::Bytes as ByteArray;
+use musli::{Decode, Encode};
+use musli_protobuf::types::{Fixed, SInt};
+use musli_tests::assert_golden;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test1 {
+    #[musli(rename = 1)]
+    a: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test2 {
+    #[musli(rename = 2)]
+    b: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test3 {
+    #[musli(rename = 3)]
+    c: Test1,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test4 {
+    #[musli(rename = 4)]
+    d: String,
+    #[musli(rename = 6)]
+    e: Vec<i32>,
+}
+
+/// The examples from the protobuf encoding guide.
+#[test]
+fn encoding_guide() {
+    assert_golden!(musli_protobuf, Test1 { a: 150 }, &[0x08, 0x96, 0x01]);
+
+    assert_golden!(
+        musli_protobuf,
+        Test2 {
+            b: String::from("testing"),
+        },
+        b"\x12\x07testing",
+    );
+
+    assert_golden!(
+        musli_protobuf,
+        Test3 {
+            c: Test1 { a: 150 },
+        },
+        &[0x1a, 0x03, 0x08, 0x96, 0x01],
+    );
+
+    assert_golden!(
+        musli_protobuf,
+        Test4 {
+            d: String::from("hello"),
+            e: vec![1, 2, 3],
+        },
+        b"\x22\x05hello\x32\x03\x01\x02\x03",
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Packed {
+    #[musli(rename = 4)]
+    d: Vec<u32>,
+}
+
+#[test]
+fn packed_repeated() {
+    assert_golden!(
+        musli_protobuf,
+        Packed {
+            d: vec![3, 270, 86942],
+        },
+        &[0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05],
+    );
+
+    // Unpacked encodings and packed fields split into several records are
+    // accepted too.
+    let decoded: Packed =
+        musli_protobuf::from_slice(&[0x20, 0x03, 0x20, 0x8e, 0x02, 0x22, 0x03, 0x9e, 0xa7, 0x05])
+            .unwrap();
+
+    assert_eq!(
+        decoded,
+        Packed {
+            d: vec![3, 270, 86942]
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Integers {
+    #[musli(rename = 1)]
+    int32: i32,
+    #[musli(rename = 2)]
+    int64: i64,
+    #[musli(rename = 3)]
+    uint32: u32,
+    #[musli(rename = 4)]
+    uint64: u64,
+    #[musli(rename = 5)]
+    sint32: SInt<i32>,
+    #[musli(rename = 6)]
+    sint64: SInt<i64>,
+    #[musli(rename = 7)]
+    boolean: bool,
+}
+
+#[test]
+fn varints() {
+    assert_golden!(
+        musli_protobuf,
+        Integers {
+            int32: -1,
+            int64: -2,
+            uint32: u32::MAX,
+            uint64: 1,
+            sint32: SInt(-1),
+            sint64: SInt(i64::MIN),
+            boolean: true,
+        },
+        &[
+            // int32 = -1, sign-extended to ten bytes
+            &[0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01][..],
+            // int64 = -2
+            &[0x10, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            // uint32 = 4294967295
+            &[0x18, 0xff, 0xff, 0xff, 0xff, 0x0f],
+            // uint64 = 1
+            &[0x20, 0x01],
+            // sint32 = -1
+            &[0x28, 0x01],
+            // sint64 = i64::MIN
+            &[0x30, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+            // bool = true
+            &[0x38, 0x01],
+        ]
+        .concat(),
+    );
+
+    for (value, expected) in [(0, 0), (-1, 1), (1, 2), (-2, 3), (i32::MAX, 0xfffffffe)] {
+        let out = musli_protobuf::to_vec(&Test5 { a: SInt(value) }).unwrap();
+        let decoded: Test5 = musli_protobuf::from_slice(&out).unwrap();
+        assert_eq!(decoded.a, SInt(value));
+
+        let zigzag: Test6 = musli_protobuf::from_slice(&out).unwrap();
+        assert_eq!(zigzag.a, expected);
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test5 {
+    #[musli(rename = 1)]
+    a: SInt<i32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Test6 {
+    #[musli(rename = 1)]
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct FixedWidth {
+    #[musli(rename = 1)]
+    fixed32: Fixed<u32>,
+    #[musli(rename = 2)]
+    sfixed64: Fixed<i64>,
+    #[musli(rename = 3)]
+    float: f32,
+    #[musli(rename = 4)]
+    double: f64,
+}
+
+#[test]
+fn fixed_width() {
+    assert_golden!(
+        musli_protobuf,
+        FixedWidth {
+            fixed32: Fixed(1),
+            sfixed64: Fixed(-2),
+            float: 1.0,
+            double: -2.5,
+        },
+        &[
+            0x0d, 0x01, 0x00, 0x00, 0x00, // fixed32 = 1
+            0x11, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // sfixed64 = -2
+            0x1d, 0x00, 0x00, 0x80, 0x3f, // float = 1.0
+            0x21, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0xc0, // double = -2.5
+        ],
+    );
+}
+
+#[test]
+fn fixed_width_other_formats() {
+    // Fixed-width integers are plain integers in other formats, so bit patterns
+    // which are NaN when read as floats must survive unchanged.
+    macro_rules! roundtrip {
+        ($($format:ident),*) => {
+            $(
+                for value in [0, 1, u32::MAX, 0x7f800001, 0xffc00001] {
+                    let out = $format::to_vec(&Fixed(value)).unwrap();
+                    let decoded: Fixed<u32> = $format::from_slice(&out).unwrap();
+                    assert_eq!(decoded, Fixed(value), stringify!($format));
+                }
+
+                for value in [0x7ff0000000000001, 0xfff8000000000001, u64::MAX] {
+                    let out = $format::to_vec(&Fixed(value)).unwrap();
+                    let decoded: Fixed<u64> = $format::from_slice(&out).unwrap();
+                    assert_eq!(decoded, Fixed(value), stringify!($format));
+                }
+
+                for value in [i32::MIN, -1, 0x7f800001] {
+                    let out = $format::to_vec(&Fixed(value)).unwrap();
+                    let decoded: Fixed<i32> = $format::from_slice(&out).unwrap();
+                    assert_eq!(decoded, Fixed(value), stringify!($format));
+                }
+
+                for value in [i64::MIN, -1, 0x7ff0000000000001] {
+                    let out = $format::to_vec(&Fixed(value)).unwrap();
+                    let decoded: Fixed<i64> = $format::from_slice(&out).unwrap();
+                    assert_eq!(decoded, Fixed(value), stringify!($format));
+                }
+            )*
+        };
+    }
+
+    roundtrip!(
+        musli_storage,
+        musli_wire,
+        musli_descriptive,
+        musli_cbor,
+        musli_msgpack,
+        musli_json
+    );
+
+    assert_eq!(
+        musli_json::to_string(&Fixed(u32::MAX)).unwrap(),
+        "4294967295"
+    );
+    let decoded: Fixed<i64> = musli_json::from_str("-2").unwrap();
+    assert_eq!(decoded, Fixed(-2));
+
+    // Integers which prefer a fixed width are still regular integers in CBOR.
+    assert_eq!(
+        musli_cbor::to_vec(&Fixed(0x7f800001u32)).unwrap(),
+        [0x1a, 0x7f, 0x80, 0x00, 0x01]
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct ByteArrays {
+    #[musli(rename = 1)]
+    four: ByteArray<[u8; 4]>,
+    #[musli(rename = 2, bytes)]
+    eight: [u8; 8],
+}
+
+#[test]
+fn byte_arrays() {
+    // Byte arrays are always length-delimited, even when they have the width
+    // of a fixed-width value.
+    assert_golden!(
+        musli_protobuf,
+        ByteArrays {
+            four: ByteArray([1, 2, 3, 4]),
+            eight: [1, 2, 3, 4, 5, 6, 7, 8],
+        },
+        &[
+            0x0a, 0x04, 0x01, 0x02, 0x03, 0x04, // four
+            0x12, 0x08, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // eight
+        ],
+    );
+
+    // A fixed-width value is not accepted where a byte array is expected.
+    assert!(musli_protobuf::from_slice::<ByteArrays>(&[0x0d, 0x01, 0x02, 0x03, 0x04]).is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Bytes {
+    #[musli(rename = 1, bytes)]
+    data: Vec<u8>,
+    #[musli(rename = 2, default)]
+    names: Vec<String>,
+}
+
+#[test]
+fn length_delimited() {
+    assert_golden!(
+        musli_protobuf,
+        Bytes {
+            data: vec![1, 2, 3],
+            names: vec![String::from("a"), String::from("bc")],
+        },
+        &[
+            0x0a, 0x03, 0x01, 0x02, 0x03, // data
+            0x12, 0x01, b'a', // names[0]
+            0x12, 0x02, b'b', b'c', // names[1]
+        ],
+    );
+
+    // Empty repeated fields are not encoded.
+    assert_golden!(
+        musli_protobuf,
+        Bytes {
+            data: Vec::new(),
+            names: Vec::new(),
+        },
+        &[0x0a, 0x00],
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Presence {
+    #[musli(rename = 1, default)]
+    a: Option<u32>,
+    #[musli(rename = 2, default)]
+    b: Option<String>,
+    #[musli(rename = 3, default)]
+    c: Option<Test1>,
+}
+
+#[test]
+fn option_presence() {
+    assert_golden!(
+        musli_protobuf,
+        Presence {
+            a: None,
+            b: None,
+            c: None,
+        },
+        &[],
+    );
+
+    assert_golden!(
+        musli_protobuf,
+        Presence {
+            a: Some(0),
+            b: Some(String::new()),
+            c: Some(Test1 { a: 0 }),
+        },
+        &[0x08, 0x00, 0x12, 0x00, 0x1a, 0x02, 0x08, 0x00],
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Version2 {
+    #[musli(rename = 1)]
+    a: i32,
+    #[musli(rename = 2)]
+    b: String,
+    #[musli(rename = 3)]
+    c: Test1,
+    #[musli(rename = 4)]
+    d: Vec<u32>,
+    #[musli(rename = 5)]
+    e: f32,
+    #[musli(rename = 6)]
+    f: f64,
+}
+
+#[test]
+fn skip_unknown_fields() {
+    let input = musli_protobuf::to_vec(&Version2 {
+        a: 150,
+        b: String::from("unknown"),
+        c: Test1 { a: 1 },
+        d: vec![1, 2, 3],
+        e: 1.0,
+        f: 2.0,
+    })
+    .unwrap();
+
+    let decoded: Test1 = musli_protobuf::from_slice(&input).unwrap();
+    assert_eq!(decoded, Test1 { a: 150 });
+
+    // The known field is last, after fields of every wire type.
+    let mut input = input[3..].to_vec();
+    input.extend_from_slice(&[0x08, 0x96, 0x01]);
+
+    let decoded: Test1 = musli_protobuf::from_slice(&input).unwrap();
+    assert_eq!(decoded, Test1 { a: 150 });
+
+    // Unknown fields in an embedded message.
+    let decoded: Test3 =
+        musli_protobuf::from_slice(&[0x1a, 0x07, 0x12, 0x02, b'h', b'i', 0x08, 0x96, 0x01])
+            .unwrap();
+
+    assert_eq!(
+        decoded,
+        Test3 {
+            c: Test1 { a: 150 }
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Repeated {
+    #[musli(rename = 1, default)]
+    messages: Vec<Test1>,
+    #[musli(rename = 2, default)]
+    map: BTreeMap<String, i32>,
+}
+
+#[test]
+fn repeated_messages_and_maps() {
+    let mut map = BTreeMap::new();
+    map.insert(String::from("a"), 1);
+    map.insert(String::from("b"), 0);
+
+    assert_golden!(
+        musli_protobuf,
+        Repeated {
+            messages: vec![Test1 { a: 1 }, Test1 { a: 2 }],
+            map,
+        },
+        &[
+            0x0a, 0x02, 0x08, 0x01, // messages[0]
+            0x0a, 0x02, 0x08, 0x02, // messages[1]
+            0x12, 0x05, 0x0a, 0x01, b'a', 0x10, 0x01, // "a" => 1
+            0x12, 0x05, 0x0a, 0x01, b'b', 0x10, 0x00, // "b" => 0
+        ],
+    );
+
+    // Keys and values which are missing from an entry have their default
+    // value, and unknown fields in an entry are skipped.
+    let decoded: Repeated = musli_protobuf::from_slice(&[
+        0x12, 0x02, 0x10, 0x05, // missing key
+        0x12, 0x03, 0x0a, 0x01, b'c', // missing value
+        0x12, 0x07, 0x0a, 0x01, b'd', 0x18, 0x01, 0x10, 0x02, // unknown field
+    ])
+    .unwrap();
+
+    let mut map = BTreeMap::new();
+    map.insert(String::new(), 5);
+    map.insert(String::from("c"), 0);
+    map.insert(String::from("d"), 2);
+
+    assert_eq!(
+        decoded,
+        Repeated {
+            messages: Vec::new(),
+            map,
+        }
+    );
+}
+
+#[derive(Debug, Default, PartialEq, Encode, Decode)]
+enum Corpus {
+    #[default]
+    #[musli(rename = 0)]
+    Universal,
+    #[musli(rename = 1)]
+    Web,
+    #[musli(rename = 2)]
+    Images,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Payload {
+    #[musli(rename = 4, transparent)]
+    Name(String),
+    #[musli(rename = 9, transparent)]
+    Message(Test1),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Enums {
+    #[musli(rename = 1, default)]
+    corpus: Corpus,
+    #[musli(rename = 2)]
+    payload: Payload,
+    #[musli(rename = 3, default)]
+    corpora: Vec<Corpus>,
+}
+
+#[test]
+fn enums_and_oneofs() {
+    assert_golden!(
+        musli_protobuf,
+        Enums {
+            corpus: Corpus::Images,
+            payload: Payload::Name(String::from("x")),
+            corpora: vec![Corpus::Web, Corpus::Universal],
+        },
+        &[
+            0x08, 0x02, // corpus = IMAGES
+            0x12, 0x03, 0x22, 0x01, b'x', // payload { name = "x" }
+            0x1a, 0x02, 0x01, 0x00, // corpora = [WEB, UNIVERSAL]
+        ],
+    );
+
+    assert_golden!(
+        musli_protobuf,
+        Enums {
+            corpus: Corpus::Universal,
+            payload: Payload::Message(Test1 { a: 150 }),
+            corpora: Vec::new(),
+        },
+        &[
+            0x08, 0x00, // corpus = UNIVERSAL
+            0x12, 0x05, 0x4a, 0x03, 0x08, 0x96, 0x01, // payload { message = { a = 150 } }
+        ],
+    );
+
+    // A missing enum field has the value 0.
+    let decoded: Enums = musli_protobuf::from_slice(&[0x12, 0x03, 0x22, 0x01, b'x']).unwrap();
+    assert_eq!(decoded.corpus, Corpus::Universal);
+
+    // A variant can also be the root.
+    assert_golden!(
+        musli_protobuf,
+        Payload::Name(String::from("x")),
+        &[0x22, 0x01, b'x']
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Zero {
+    #[musli(rename = 0)]
+    a: u32,
+}
+
+#[test]
+fn errors() {
+    assert!(musli_protobuf::to_vec(&Zero { a: 1 }).is_err());
+    assert!(musli_protobuf::to_vec(&42u32).is_err());
+    assert!(musli_protobuf::to_vec(&Test1 { a: 1 }).is_ok());
+
+    // A string cannot be decoded from a varint.
+    assert!(musli_protobuf::from_slice::<Test2>(&[0x10, 0x01]).is_err());
+    // Truncated input.
+    assert!(musli_protobuf::from_slice::<Test2>(&[0x12, 0x07, b't']).is_err());
+    // An embedded message which claims to be shorter than its fields.
+    assert!(musli_protobuf::from_slice::<Test3>(&[0x1a, 0x01, 0x08, 0x96, 0x01]).is_err());
+}
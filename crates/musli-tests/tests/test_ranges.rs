@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Ranges {
+    range: Range<u32>,
+    range_inclusive: RangeInclusive<u32>,
+    range_from: RangeFrom<i64>,
+    range_to: RangeTo<u8>,
+    range_to_inclusive: RangeToInclusive<u8>,
+    range_full: RangeFull,
+    included: Bound<String>,
+    excluded: Bound<u32>,
+    unbounded: Bound<u32>,
+    less: Ordering,
+    equal: Ordering,
+    greater: Ordering,
+}
+
+#[test]
+fn test_ranges() {
+    musli_tests::rt!(Ranges {
+        range: 1..10,
+        range_inclusive: 2..=20,
+        range_from: -5..,
+        range_to: ..42,
+        range_to_inclusive: ..=255,
+        range_full: ..,
+        included: Bound::Included(String::from("a")),
+        excluded: Bound::Excluded(7),
+        unbounded: Bound::Unbounded,
+        less: Ordering::Less,
+        equal: Ordering::Equal,
+        greater: Ordering::Greater,
+    });
+
+    let (start, end) = musli_tests::storage::transcode::<_, (u32, u32)>(3u32..7);
+    assert_eq!((start, end), (3, 7));
+}
+
+#[test]
+fn test_range_start_after_end() {
+    #[allow(clippy::reversed_empty_ranges)]
+    let out = musli_tests::wire::to_vec(&(7u32..3)).expect("failed to encode");
+    let error = musli_tests::wire::from_slice::<Range<u32>>(out.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("start is greater than end"));
+
+    let out = musli_tests::wire::to_vec(&RangeInclusive::new(7u32, 3)).expect("failed to encode");
+    let error = musli_tests::wire::from_slice::<RangeInclusive<u32>>(out.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("start is greater than end"));
+
+    let range = musli_tests::wire::transcode::<_, Range<u32>>(3u32..3);
+    assert!(range.is_empty());
+}
+
+#[test]
+fn test_range_inclusive_exhausted() {
+    let mut range = 1u32..=1;
+    assert_eq!(range.next(), Some(1));
+    assert!(range.is_empty());
+
+    // The exhausted state is not preserved.
+    let range = musli_tests::wire::transcode::<_, RangeInclusive<u32>>(range);
+    assert_eq!(range, 1..=1);
+    assert!(!range.is_empty());
+}
+
+#[test]
+fn test_ordering() {
+    assert_eq!(musli_tests::wire::transcode::<_, i8>(Ordering::Less), -1);
+    assert_eq!(musli_tests::wire::transcode::<_, i8>(Ordering::Greater), 1);
+
+    let out = musli_tests::wire::to_vec(&2i8).expect("failed to encode");
+    assert!(musli_tests::wire::from_slice::<Ordering>(out.as_slice()).is_err());
+}
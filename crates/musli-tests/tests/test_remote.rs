@@ -0,0 +1,136 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode};
+
+mod foreign {
+    #[derive(Debug, PartialEq)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Wrapper<T> {
+        pub inner: T,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Secret {
+        pub id: u32,
+        value: String,
+    }
+
+    impl Secret {
+        pub fn new(id: u32, value: &str) -> Self {
+            Self {
+                id,
+                value: value.to_owned(),
+            }
+        }
+
+        pub fn value(&self) -> &String {
+            &self.value
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Encode, Decode)]
+#[musli(remote = foreign::Point, default_field_name = "name")]
+struct PointDef {
+    x: u32,
+    #[musli(rename = "why")]
+    y: u32,
+}
+
+#[allow(dead_code)]
+#[derive(Encode, Decode)]
+#[musli(remote = foreign::Shape, default_variant_name = "name")]
+enum ShapeDef {
+    #[musli(default_field_name = "name")]
+    Circle {
+        radius: u32,
+    },
+    Square(u32),
+}
+
+#[allow(dead_code)]
+#[derive(Encode, Decode)]
+#[musli(remote = foreign::Wrapper, default_field_name = "name")]
+#[musli(bound = {T: Encode<M>}, decode_bound = {T: Decode<'de, M>})]
+struct WrapperDef<T> {
+    inner: T,
+}
+
+#[allow(dead_code)]
+#[derive(Encode)]
+#[musli(remote = foreign::Secret, default_field_name = "name")]
+struct SecretDef {
+    id: u32,
+    #[musli(getter = foreign::Secret::value)]
+    value: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Drawing {
+    #[musli(with = PointDef)]
+    origin: foreign::Point,
+    #[musli(with = ShapeDef)]
+    shape: foreign::Shape,
+    #[musli(with = WrapperDef)]
+    label: foreign::Wrapper<String>,
+}
+
+#[derive(Encode)]
+#[musli(default_field_name = "name")]
+pub struct Envelope {
+    #[musli(with = SecretDef)]
+    secret: foreign::Secret,
+}
+
+#[test]
+fn remote_roundtrip() {
+    let drawing = Drawing {
+        origin: foreign::Point { x: 1, y: 2 },
+        shape: foreign::Shape::Circle { radius: 3 },
+        label: foreign::Wrapper {
+            inner: String::from("circle"),
+        },
+    };
+
+    let string = musli_json::to_string(&drawing).unwrap();
+    assert_eq!(
+        string,
+        r#"{"origin":{"x":1,"why":2},"shape":{"Circle":{"radius":3}},"label":{"inner":"circle"}}"#
+    );
+    assert_eq!(musli_json::from_str::<Drawing>(&string).unwrap(), drawing);
+
+    let drawing = Drawing {
+        origin: foreign::Point { x: 4, y: 5 },
+        shape: foreign::Shape::Square(6),
+        label: foreign::Wrapper {
+            inner: String::from("square"),
+        },
+    };
+
+    let out = musli_storage::to_vec(&drawing).unwrap();
+    let actual: Drawing = musli_storage::from_slice(&out).unwrap();
+    assert_eq!(actual, drawing);
+}
+
+#[test]
+fn remote_getter() {
+    let envelope = Envelope {
+        secret: foreign::Secret::new(1, "hunter2"),
+    };
+
+    let string = musli_json::to_string(&envelope).unwrap();
+    assert_eq!(string, r#"{"secret":{"id":1,"value":"hunter2"}}"#);
+}
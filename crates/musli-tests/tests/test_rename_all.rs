@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+
+use musli::mode::TextMode;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(rename_all = "camelCase")]
+pub struct Camel {
+    user_id: u32,
+    display_name: String,
+    #[musli(rename = "explicit")]
+    renamed_field: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(rename_all = "kebab-case")]
+pub struct Kebab {
+    user_id: u32,
+    r#type: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Screaming {
+    FirstVariant,
+    #[musli(rename_all = "PascalCase")]
+    SecondVariant {
+        inner_value: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(rename_all = "snake_case")]
+pub enum Snake {
+    HttpServer,
+    V2Api,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(rename_all = "snake_case")]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Acronyms {
+    HTTPServer,
+    ParseURL,
+    IOError,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = TextMode, rename_all = "camelCase")]
+pub struct ModeAware {
+    user_id: u32,
+}
+
+macro_rules! json {
+    ($expr:expr, $json:expr) => {{
+        let expected = $expr;
+        let string = musli_json::to_string(&expected).unwrap();
+        assert_eq!(string, $json);
+        let actual = musli_json::from_str(&string).unwrap();
+        assert_eq!(expected, actual);
+    }};
+}
+
+#[test]
+fn rename_all_fields() {
+    json!(
+        Camel {
+            user_id: 1,
+            display_name: String::from("root"),
+            renamed_field: 2,
+        },
+        r#"{"userId":1,"displayName":"root","explicit":2}"#
+    );
+
+    json!(
+        Kebab {
+            user_id: 1,
+            r#type: String::from("admin"),
+        },
+        r#"{"user-id":1,"type":"admin"}"#
+    );
+}
+
+#[test]
+fn rename_all_variants() {
+    json!(Screaming::FirstVariant, r#"{"FIRST_VARIANT":{}}"#);
+    json!(
+        Screaming::SecondVariant { inner_value: 42 },
+        r#"{"SECOND_VARIANT":{"InnerValue":42}}"#
+    );
+    json!(Snake::HttpServer, r#"{"http_server":{}}"#);
+    json!(Snake::V2Api, r#"{"v2_api":{}}"#);
+    json!(Acronyms::HTTPServer, r#"{"http_server":{}}"#);
+    json!(Acronyms::ParseURL, r#"{"parse_url":{}}"#);
+    json!(Acronyms::IOError, r#"{"io_error":{}}"#);
+}
+
+#[test]
+fn rename_all_mode() {
+    json!(ModeAware { user_id: 7 }, r#"{"userId":7}"#);
+
+    // Other modes keep the default index-based field tags.
+    musli_tests::rt!(ModeAware, ModeAware { user_id: 7 });
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Config {
+    name: String,
+    port: u16,
+    verbose: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    default_field_name = "name",
+    deny_unknown_fields,
+    reserved("port", "verbose")
+)]
+pub struct StrictConfig {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Indexed {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(deny_unknown_fields, reserved(1, "b"))]
+pub struct StrictIndexed {
+    a: u32,
+    #[musli(rename = 2)]
+    c: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", default_variant_name = "name")]
+pub enum Command {
+    Start { id: u32, force: bool },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(
+    default_field_name = "name",
+    default_variant_name = "name",
+    deny_unknown_fields,
+    reserved("Stop")
+)]
+pub enum StrictCommand {
+    #[musli(reserved("force"))]
+    Start { id: u32 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_variant_name = "name")]
+pub enum OldCommand {
+    Start,
+    Stop,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_variant_name = "name", reserved("Stop"))]
+pub enum FallbackCommand {
+    Start,
+    #[musli(default)]
+    Unknown,
+}
+
+#[test]
+fn reserved_string_fields() {
+    let config = Config {
+        name: String::from("server"),
+        port: 8080,
+        verbose: true,
+    };
+
+    let json = musli_json::to_string(&config).unwrap();
+    let actual: StrictConfig = musli_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        actual,
+        StrictConfig {
+            name: String::from("server"),
+        }
+    );
+
+    assert!(musli_json::from_str::<StrictConfig>(r#"{"name":"server","other":1}"#).is_err());
+}
+
+#[test]
+fn reserved_indexed_fields() {
+    let out = musli_wire::to_vec(&Indexed { a: 1, b: 2, c: 3 }).unwrap();
+    let actual: StrictIndexed = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, StrictIndexed { a: 1, c: 3 });
+
+    let out = musli_storage::to_vec(&Indexed { a: 1, b: 2, c: 3 }).unwrap();
+    assert!(musli_storage::from_slice::<StrictIndexed>(&out).is_err());
+}
+
+#[test]
+fn reserved_variant_fields() {
+    let json = musli_json::to_string(&Command::Start { id: 1, force: true }).unwrap();
+    let actual: StrictCommand = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, StrictCommand::Start { id: 1 });
+
+    assert!(musli_json::from_str::<StrictCommand>(r#"{"Start":{"id":1,"other":true}}"#).is_err());
+}
+
+#[test]
+fn reserved_variants() {
+    // Reserved variant tags are decoded like unknown variant tags.
+    let out = musli_wire::to_vec(&OldCommand::Stop).unwrap();
+    assert!(musli_wire::from_slice::<StrictCommand>(&out).is_err());
+
+    let actual: FallbackCommand = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, FallbackCommand::Unknown);
+
+    let out = musli_wire::to_vec(&OldCommand::Start).unwrap();
+    let actual: FallbackCommand = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, FallbackCommand::Start);
+}
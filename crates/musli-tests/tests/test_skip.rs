@@ -0,0 +1,178 @@
+#![cfg(feature = "std")]
+
+use musli::{Decode, Encode, Mode};
+
+enum Json {}
+
+impl Mode for Json {}
+
+const JSON: musli_json::Encoding<Json> = musli_json::Encoding::new().with_mode();
+
+fn default_port() -> u16 {
+    8080
+}
+
+fn default_cache() -> Vec<u32> {
+    vec![1, 2, 3]
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Server {
+    name: String,
+    #[musli(default = default_port)]
+    port: u16,
+    #[musli(skip)]
+    handle: Option<u32>,
+    #[musli(skip, default = default_cache)]
+    cache: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+pub struct Packed {
+    a: u32,
+    #[musli(skip)]
+    b: u32,
+    c: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent)]
+pub struct Transparent {
+    value: u32,
+    #[musli(skip)]
+    cache: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", default_variant_name = "name")]
+pub enum Event {
+    Connected {
+        id: u32,
+        #[musli(skip)]
+        handle: Option<u32>,
+    },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub enum Unnamed {
+    Message(String, #[musli(skip)] u32),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct ModeAware {
+    name: String,
+    #[musli(mode = Json, skip)]
+    secret: String,
+}
+
+#[test]
+fn skip_fields() {
+    let server = Server {
+        name: String::from("api"),
+        port: 80,
+        handle: Some(42),
+        cache: Vec::new(),
+    };
+
+    let json = musli_json::to_string(&server).unwrap();
+    assert_eq!(json, r#"{"name":"api","port":80}"#);
+
+    let actual: Server = musli_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        actual,
+        Server {
+            name: String::from("api"),
+            port: 80,
+            handle: None,
+            cache: vec![1, 2, 3],
+        }
+    );
+
+    // Skipped fields are ignored if they are present.
+    let actual: Server = musli_json::from_str(r#"{"name":"api","handle":1}"#).unwrap();
+
+    assert_eq!(
+        actual,
+        Server {
+            name: String::from("api"),
+            port: 8080,
+            handle: None,
+            cache: vec![1, 2, 3],
+        }
+    );
+}
+
+#[test]
+fn skip_packed() {
+    let out = musli_storage::to_vec(&Packed { a: 1, b: 2, c: 3 }).unwrap();
+    assert_eq!(out, [1, 3]);
+
+    let actual: Packed = musli_storage::from_slice(&out).unwrap();
+    assert_eq!(actual, Packed { a: 1, b: 0, c: 3 });
+}
+
+#[test]
+fn skip_transparent() {
+    let value = Transparent {
+        value: 7,
+        cache: Some(7),
+    };
+
+    let json = musli_json::to_string(&value).unwrap();
+    assert_eq!(json, "7");
+
+    let actual: Transparent = musli_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        actual,
+        Transparent {
+            value: 7,
+            cache: None,
+        }
+    );
+}
+
+#[test]
+fn skip_variant_fields() {
+    let json = musli_json::to_string(&Event::Connected {
+        id: 1,
+        handle: Some(2),
+    })
+    .unwrap();
+
+    assert_eq!(json, r#"{"Connected":{"id":1}}"#);
+
+    let actual: Event = musli_json::from_str(&json).unwrap();
+    assert_eq!(
+        actual,
+        Event::Connected {
+            id: 1,
+            handle: None
+        }
+    );
+
+    let out = musli_wire::to_vec(&Unnamed::Message(String::from("hello"), 2)).unwrap();
+    let actual: Unnamed = musli_wire::from_slice(&out).unwrap();
+    assert_eq!(actual, Unnamed::Message(String::from("hello"), 0));
+}
+
+#[test]
+fn skip_mode() {
+    let value = ModeAware {
+        name: String::from("root"),
+        secret: String::from("hunter2"),
+    };
+
+    let json = JSON.to_string(&value).unwrap();
+    assert_eq!(json, r#"{"name":"root"}"#);
+
+    let json = musli_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"name":"root","secret":"hunter2"}"#);
+
+    let actual: ModeAware = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, value);
+}
@@ -0,0 +1,62 @@
+use std::rc::{Rc, Weak as RcWeak};
+use std::sync::{Arc, Weak as ArcWeak};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct SmartPointers {
+    boxed: Box<u32>,
+    rc: Rc<String>,
+    arc: Arc<Vec<u32>>,
+    boxed_str: Box<str>,
+    rc_str: Rc<str>,
+    arc_str: Arc<str>,
+    boxed_slice: Box<[u32]>,
+    rc_slice: Rc<[String]>,
+    arc_slice: Arc<[u64]>,
+}
+
+#[test]
+fn test_smart_pointers() {
+    musli_tests::rt!(SmartPointers {
+        boxed: Box::new(42),
+        rc: Rc::new(String::from("Hello")),
+        arc: Arc::new(vec![1, 2, 3]),
+        boxed_str: Box::from("Hello"),
+        rc_str: Rc::from("World"),
+        arc_str: Arc::from("Foo"),
+        boxed_slice: Box::from([1, 2, 3]),
+        rc_slice: Rc::from([String::from("a"), String::from("b")]),
+        arc_slice: Arc::from([u64::MAX, 0]),
+    });
+}
+
+#[test]
+fn test_smart_pointers_transcode() {
+    let value = musli_tests::wire::transcode::<_, Vec<u32>>(Arc::<[u32]>::from([1, 2, 3]));
+    assert_eq!(value, [1, 2, 3]);
+    let value = musli_tests::wire::transcode::<_, String>(Rc::<str>::from("Hello"));
+    assert_eq!(value, "Hello");
+}
+
+#[test]
+fn test_weak() {
+    let rc = Rc::new(42u32);
+    let weak = Rc::downgrade(&rc);
+    let out = musli_tests::wire::to_vec(&weak).expect("failed to encode");
+    let value: Option<u32> = musli_tests::wire::from_slice(&out).expect("failed to decode");
+    assert_eq!(value, Some(42));
+
+    let decoded: RcWeak<u32> = musli_tests::wire::from_slice(&out).expect("failed to decode");
+    assert!(decoded.upgrade().is_none());
+
+    drop(rc);
+    let out = musli_tests::wire::to_vec(&weak).expect("failed to encode");
+    let value: Option<u32> = musli_tests::wire::from_slice(&out).expect("failed to decode");
+    assert_eq!(value, None);
+
+    let arc = Arc::new(String::from("Hello"));
+    let out = musli_tests::wire::to_vec(&Arc::downgrade(&arc)).expect("failed to encode");
+    let decoded: ArcWeak<String> = musli_tests::wire::from_slice(&out).expect("failed to decode");
+    assert!(decoded.upgrade().is_none());
+}
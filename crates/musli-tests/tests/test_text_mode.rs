@@ -0,0 +1,49 @@
+#![cfg(feature = "std")]
+
+use musli::mode::TextMode;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = musli::mode::TextMode, default_field_name = "name")]
+struct Person {
+    name: String,
+    #[musli(mode = musli::mode::TextMode, rename = "years")]
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = TextMode, default_variant_name = "name")]
+enum Animal {
+    #[musli(mode = TextMode, rename = "doggo")]
+    Dog,
+    Cat,
+}
+
+fn person() -> Person {
+    Person {
+        name: String::from("Aristotle"),
+        age: 62,
+    }
+}
+
+#[test]
+fn text_mode_attributes() {
+    let expected = person();
+
+    let json = musli_json::to_string(&expected).unwrap();
+    assert_eq!(json, r#"{"name":"Aristotle","years":62}"#);
+    let actual: Person = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, expected);
+
+    // The default mode is unaffected by attributes scoped to the text mode.
+    musli_tests::rt!(Person, person());
+
+    let json = musli_json::to_string(&Animal::Dog).unwrap();
+    assert_eq!(json, r#"{"doggo":{}}"#);
+    let actual: Animal = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, Animal::Dog);
+
+    let json = musli_json::to_string(&Animal::Cat).unwrap();
+    assert_eq!(json, r#"{"Cat":{}}"#);
+    musli_tests::rt!(Animal, Animal::Cat);
+}
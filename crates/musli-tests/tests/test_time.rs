@@ -0,0 +1,56 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Times {
+    duration: Duration,
+    after: SystemTime,
+    before: SystemTime,
+}
+
+fn times() -> Times {
+    Times {
+        duration: Duration::new(1, 500_000_000),
+        after: UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+        before: UNIX_EPOCH - Duration::new(1, 500_000_000),
+    }
+}
+
+#[test]
+fn test_time() {
+    musli_tests::rt!(Times, times());
+
+    let (secs, nanos) = musli_tests::wire::transcode::<_, (i64, u32)>(times().before);
+    assert_eq!((secs, nanos), (-2, 500_000_000));
+
+    let (secs, nanos) = musli_tests::wire::transcode::<_, (u64, u32)>(Duration::new(10, 42));
+    assert_eq!((secs, nanos), (10, 42));
+}
+
+#[test]
+fn test_time_out_of_range() {
+    let out = musli_tests::wire::to_vec(&(1u64, 1_000_000_000u32)).expect("failed to encode");
+    assert!(musli_tests::wire::from_slice::<Duration>(out.as_slice()).is_err());
+}
+
+#[test]
+fn test_time_human_readable() {
+    let string = musli_json::to_string(&times()).expect("failed to encode");
+    assert_eq!(
+        string,
+        r#"{"duration":"1.5","after":"1700000000.123456789","before":"-1.5"}"#
+    );
+
+    let decoded: Times = musli_json::from_str(&string).expect("failed to decode");
+    assert_eq!(decoded, times());
+
+    let duration: Duration = musli_json::from_str(r#""0.000000001""#).expect("failed to decode");
+    assert_eq!(duration, Duration::from_nanos(1));
+
+    assert!(musli_json::from_str::<Duration>(r#""-1""#).is_err());
+    assert!(musli_json::from_str::<Duration>(r#""1.0000000001""#).is_err());
+    assert!(musli_json::from_str::<Duration>(r#""1.""#).is_err());
+    assert!(musli_json::from_str::<SystemTime>(r#""18446744073709551615""#).is_err());
+}
@@ -0,0 +1,503 @@
+#![cfg(all(feature = "std", feature = "musli-toml"))]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use musli::mode::TextMode;
+use musli::{Decode, Encode};
+use musli_tests::assert_golden;
+use musli_toml::context::{AllocBuf, AllocContext};
+use musli_toml::datetime::{Date, Offset, Time};
+use musli_toml::{Datetime, Position};
+
+/// Decode the given document with a context which collects errors, and
+/// return the first error prefixed with its line and column.
+#[track_caller]
+fn decode_error<T>(source: &str) -> String
+where
+    T: Debug + for<'de> Decode<'de, TextMode>,
+{
+    let mut buf = AllocBuf::default();
+    let mut cx = AllocContext::new(&mut buf);
+
+    if let Ok(value) = musli_toml::DEFAULT.from_str_with::<_, T>(&mut cx, source) {
+        panic!("expected decoding to fail, but got {value:?}");
+    }
+
+    let error = cx.iter().next().expect("expected an error");
+    let position = Position::from_offset(source, error.range().start);
+    format!("{position}: {error}")
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Server {
+    host: String,
+    port: u16,
+    #[musli(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Config {
+    title: String,
+    debug: bool,
+    ratio: f64,
+    server: Server,
+    #[musli(default)]
+    backends: Vec<Server>,
+}
+
+#[test]
+fn tables_and_arrays_of_tables() {
+    assert_golden!(
+        musli_toml::{to_string, from_str},
+        Config {
+            title: String::from("example"),
+            debug: true,
+            ratio: 0.5,
+            server: Server {
+                host: String::from("localhost"),
+                port: 8080,
+                tags: vec![String::from("a"), String::from("b")],
+            },
+            backends: vec![
+                Server {
+                    host: String::from("one"),
+                    port: 1,
+                    tags: Vec::new(),
+                },
+                Server {
+                    host: String::from("two"),
+                    port: 2,
+                    tags: Vec::new(),
+                },
+            ],
+        },
+        r#"title = "example"
+debug = true
+ratio = 0.5
+
+[server]
+host = "localhost"
+port = 8080
+tags = ["a", "b"]
+
+[[backends]]
+host = "one"
+port = 1
+tags = []
+
+[[backends]]
+host = "two"
+port = 2
+tags = []
+"#,
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Inner {
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Middle {
+    inner: Inner,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Outer {
+    middle: Middle,
+    map: BTreeMap<String, Inner>,
+    numbers: BTreeMap<u32, String>,
+}
+
+#[test]
+fn nested_tables_and_maps() {
+    let mut map = BTreeMap::new();
+    map.insert(String::from("first"), Inner { value: 1 });
+    map.insert(String::from("with space"), Inner { value: 2 });
+
+    let mut numbers = BTreeMap::new();
+    numbers.insert(1, String::from("one"));
+    numbers.insert(20, String::from("twenty"));
+
+    assert_golden!(
+        musli_toml::{to_string, from_str},
+        Outer {
+            middle: Middle {
+                inner: Inner { value: 42 },
+            },
+            map,
+            numbers,
+        },
+        r#"[middle.inner]
+value = 42
+
+[map.first]
+value = 1
+
+[map."with space"]
+value = 2
+
+[numbers]
+1 = "one"
+20 = "twenty"
+"#,
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_variant_name = "name")]
+enum Level {
+    Debug,
+    Info,
+    #[musli(default_field_name = "name")]
+    Custom {
+        priority: u8,
+    },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Logging {
+    level: Level,
+    other: Level,
+    #[musli(default)]
+    file: Option<String>,
+}
+
+#[test]
+fn enums_and_options() {
+    assert_golden!(
+        musli_toml::{to_string, from_str},
+        Logging {
+            level: Level::Info,
+            other: Level::Custom { priority: 3 },
+            file: None,
+        },
+        "level = \"Info\"\n\n[other.Custom]\npriority = 3\n",
+    );
+
+    assert_golden!(
+        musli_toml::{to_string, from_str},
+        Logging {
+            level: Level::Debug,
+            other: Level::Debug,
+            file: Some(String::from("out.log")),
+        },
+        "level = \"Debug\"\nother = \"Debug\"\nfile = \"out.log\"\n",
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Times {
+    offset: Datetime,
+    local: Datetime,
+    date: Datetime,
+    time: Datetime,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct TimesAsStrings {
+    offset: String,
+    local: String,
+    date: String,
+    time: String,
+}
+
+#[test]
+fn datetimes() {
+    let times = Times {
+        offset: Datetime {
+            date: Some(Date {
+                year: 1979,
+                month: 5,
+                day: 27,
+            }),
+            time: Some(Time {
+                hour: 7,
+                minute: 32,
+                second: 0,
+                nanosecond: 999_999_000,
+            }),
+            offset: Some(Offset::Custom { minutes: -420 }),
+        },
+        local: "1979-05-27T07:32:00".parse().unwrap(),
+        date: "1979-05-27".parse().unwrap(),
+        time: "00:32:00.5".parse().unwrap(),
+    };
+
+    let expected = r#"offset = 1979-05-27T07:32:00.999999-07:00
+local = 1979-05-27T07:32:00
+date = 1979-05-27
+time = 00:32:00.5
+"#;
+
+    assert_golden!(musli_toml::{to_string, from_str}, times, expected);
+
+    let strings: TimesAsStrings = musli_toml::from_str(expected).unwrap();
+
+    assert_eq!(
+        strings,
+        TimesAsStrings {
+            offset: String::from("1979-05-27T07:32:00.999999-07:00"),
+            local: String::from("1979-05-27T07:32:00"),
+            date: String::from("1979-05-27"),
+            time: String::from("00:32:00.5"),
+        }
+    );
+
+    // Alternative spellings are normalized.
+    let times: Times = musli_toml::from_str(
+        "offset = 1979-05-27 07:32:00Z\nlocal = 1979-05-27t07:32:00\ndate = 2000-02-29\ntime = 23:59:60",
+    )
+    .unwrap();
+
+    assert_eq!(times.offset.to_string(), "1979-05-27T07:32:00Z");
+    assert_eq!(times.local.to_string(), "1979-05-27T07:32:00");
+    assert_eq!(times.date.to_string(), "2000-02-29");
+    assert_eq!(times.time.to_string(), "23:59:60");
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Product {
+    name: String,
+    sku: u32,
+    #[musli(default)]
+    color: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Owner {
+    name: String,
+    bio: String,
+    path: String,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Document {
+    title: String,
+    owner: Owner,
+    numbers: Vec<i64>,
+    floats: Vec<f64>,
+    point: Point,
+    nested: Vec<Vec<u32>>,
+    products: Vec<Product>,
+    site: BTreeMap<String, bool>,
+}
+
+#[test]
+fn parse_document() {
+    let source = r#"
+# A document using most of what TOML has to offer.
+title = 'literal string' # trailing comment
+
+numbers = [ 0xdead_beef, 0o755, 0b1101, -17, +99, 1_000, ]
+floats = [3.25, -0.01, 5e+22, 1e06, -2E-2, 224_617.445_991]
+
+point = { x = 1, y = -2 }
+nested = [[1, 2], [3]]
+
+site."google.com" = true
+site.'example' = false
+
+[owner]
+name = "Tom \"Preston\" Werner\u00e9"
+bio = """
+Roses are red
+Violets are \
+    blue"""
+path = '''C:\Users\nodejs'''
+
+[[products]]
+name = "Hammer"
+sku = 738594937
+
+[[products]]
+name = "Nail"
+sku = 284758393
+color = "gray"
+"#;
+
+    let document: Document = musli_toml::from_str(source).unwrap();
+
+    let mut site = BTreeMap::new();
+    site.insert(String::from("google.com"), true);
+    site.insert(String::from("example"), false);
+
+    assert_eq!(
+        document,
+        Document {
+            title: String::from("literal string"),
+            owner: Owner {
+                name: String::from("Tom \"Preston\" Werner\u{e9}"),
+                bio: String::from("Roses are red\nViolets are blue"),
+                path: String::from("C:\\Users\\nodejs"),
+            },
+            numbers: vec![0xdead_beef, 0o755, 0b1101, -17, 99, 1000],
+            floats: vec![3.25, -0.01, 5e22, 1e6, -2e-2, 224_617.445_991],
+            point: Point { x: 1, y: -2 },
+            nested: vec![vec![1, 2], vec![3]],
+            products: vec![
+                Product {
+                    name: String::from("Hammer"),
+                    sku: 738594937,
+                    color: None,
+                },
+                Product {
+                    name: String::from("Nail"),
+                    sku: 284758393,
+                    color: Some(String::from("gray")),
+                },
+            ],
+            site,
+        }
+    );
+
+    // An element in an array of tables which is missing a field.
+    let source = source.replacen("[[products]]\nname = \"Hammer\"\n", "[[products]]\n", 1);
+
+    assert_eq!(
+        decode_error::<Document>(&source),
+        "23:7: .products[0]: expected tag: \"name\" (at byte 461)"
+    );
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(default_field_name = "name")]
+struct Floats {
+    inf: f64,
+    ninf: f64,
+    nan: f64,
+    int: f32,
+}
+
+#[test]
+fn special_floats() {
+    let floats: Floats =
+        musli_toml::from_str("inf = inf\nninf = -inf\nnan = nan\nint = 12\n").unwrap();
+    assert_eq!(floats.inf, f64::INFINITY);
+    assert_eq!(floats.ninf, f64::NEG_INFINITY);
+    assert!(floats.nan.is_nan());
+    assert_eq!(floats.int, 12.0);
+}
+
+#[test]
+fn skip_unknown_fields() {
+    let inner: Inner = musli_toml::from_str(
+        r#"
+before = { a = [1, { b = "c" }], d = 1979-05-27 }
+value = 7
+
+[after]
+x = 1
+
+[[after.list]]
+y = 2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(inner, Inner { value: 7 });
+}
+
+#[test]
+fn invalid_documents() {
+    assert_eq!(
+        decode_error::<Inner>("value = 1\nvalue = 2\n"),
+        "2:1: : duplicate key `value` (at bytes 10-15)"
+    );
+
+    assert_eq!(
+        decode_error::<Middle>("[inner]\nvalue = 1\n\n[inner]\nvalue = 2\n"),
+        "4:1: : table `inner` is defined more than once (at bytes 19-26)"
+    );
+
+    assert_eq!(
+        decode_error::<Inner>("value = \"\\x\"\n"),
+        "1:10: : invalid string escape (at bytes 9-11)"
+    );
+
+    assert_eq!(
+        decode_error::<Inner>("value = 1 2\n"),
+        "1:11: : expected a newline or a comment (at bytes 10-11)"
+    );
+
+    assert_eq!(
+        decode_error::<Inner>("value = \"unterminated\n"),
+        "1:9: : unterminated string (at bytes 8-21)"
+    );
+
+    assert_eq!(
+        decode_error::<Inner>("value = 1979-13-01\n"),
+        "1:9: : invalid datetime (at bytes 8-18)"
+    );
+
+    // Tables can't be extended once they've been defined as a value, and the
+    // same key can't be both a table and an array of tables.
+    for source in [
+        "value = 1\n[inner]\nvalue = 1\n[inner.value]\n",
+        "value = 1\ninner = { value = 1 }\n[inner]\n",
+        "value = 1\ninner = { value = 1 }\n[inner.other]\n",
+        "value = 1\n[[inner]]\n[inner]\n",
+        "value = 1\n[inner]\n[[inner]]\n",
+        "value = 1\ninner = []\n[[inner]]\n",
+        "value = 1\ninner.value = 1\ninner = 2\n",
+        "value = 01\n",
+        "value = 1__000\n",
+        "value = 9223372036854775808\n",
+    ] {
+        assert!(
+            musli_toml::from_str::<Inner>(source).is_err(),
+            "{source:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn type_errors() {
+    assert_eq!(
+        decode_error::<Middle>("[inner]\nvalue = \"nope\"\n"),
+        "2:9: .inner.value: expected integer, found string (at bytes 16-22)"
+    );
+
+    assert_eq!(
+        decode_error::<Middle>("[inner]\nvalue = -1\n"),
+        "2:9: .inner.value: integer `-1` is out of range (at bytes 16-18)"
+    );
+
+    assert_eq!(
+        decode_error::<Config>(
+            "title = \"x\"\ndebug = true\nratio = 1\n\n[[backends]]\nhost = 1\n"
+        ),
+        "6:8: .backends[0].host: expected string, found integer (at bytes 56-57)"
+    );
+}
+
+#[test]
+fn encode_errors() {
+    assert!(musli_toml::to_string(&10u32).is_err());
+    assert!(musli_toml::to_string(&vec![Some(1u32), None]).is_err());
+
+    let mut map = BTreeMap::new();
+    map.insert(String::from("value"), u64::MAX);
+    assert!(musli_toml::to_string(&map).is_err());
+}
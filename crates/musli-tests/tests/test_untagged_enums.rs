@@ -1,21 +1,21 @@
 use musli::compat::Packed;
 use musli::{Decode, Encode};
 
-#[derive(Debug, PartialEq, Encode)]
+#[derive(Debug, PartialEq, Encode, Decode)]
 #[musli(packed)]
 pub enum UntaggedEnum1 {
     Variant1,
     Variant2,
 }
 
-#[derive(Debug, PartialEq, Encode)]
+#[derive(Debug, PartialEq, Encode, Decode)]
 #[musli(packed)]
 pub enum UntaggedEnum2 {
     Variant1(String),
     Variant2(u32),
 }
 
-#[derive(Debug, PartialEq, Encode)]
+#[derive(Debug, PartialEq, Encode, Decode)]
 #[musli(packed)]
 pub enum UntaggedEnum3 {
     Variant1 { value: String },
@@ -26,8 +26,8 @@ pub enum UntaggedEnum3 {
 #[musli(packed)]
 pub struct Empty;
 
-/// Untagged enums may only implement `Encode`, and will be encoded according to
-/// the exact specification of fields part of the variant.
+/// Untagged enums are encoded according to the exact specification of fields
+/// part of the variant.
 #[test]
 fn test_untagged_enums() -> Result<(), Box<dyn std::error::Error>> {
     let out = musli_tests::wire::to_vec(&UntaggedEnum1::Variant1).unwrap();
@@ -56,3 +56,151 @@ fn test_untagged_enums() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(value, 42);
     Ok(())
 }
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+pub enum Untagged {
+    Pair {
+        first: bool,
+        second: bool,
+    },
+    Number(u32),
+    #[musli(transparent)]
+    String(String),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+pub enum UntaggedTransparent {
+    #[musli(transparent)]
+    Number(u32),
+    #[musli(transparent)]
+    String(String),
+    #[musli(transparent)]
+    Flag(bool),
+}
+
+/// Untagged enums are decoded by buffering the input and trying each variant in
+/// declaration order.
+#[test]
+#[cfg(feature = "std")]
+fn test_untagged_enums_decode() {
+    macro_rules! descriptive {
+        ($expr:expr) => {{
+            let expected = $expr;
+            let out = musli_descriptive::to_vec(&expected).unwrap();
+            let actual = musli_descriptive::from_slice(&out).unwrap();
+            assert_eq!(expected, actual);
+        }};
+    }
+
+    descriptive!(Untagged::Number(42));
+    descriptive!(Untagged::String(String::from("foo")));
+    descriptive!(Untagged::Pair {
+        first: true,
+        second: false
+    });
+    descriptive!(UntaggedEnum2::Variant1(String::from("foo")));
+    descriptive!(UntaggedEnum2::Variant2(42));
+    descriptive!(UntaggedTransparent::Flag(true));
+
+    macro_rules! json {
+        ($expr:expr, $json:expr) => {{
+            let expected = $expr;
+            let string = musli_json::to_string(&expected).unwrap();
+            assert_eq!(string, $json);
+            let actual = musli_json::from_str(&string).unwrap();
+            assert_eq!(expected, actual);
+
+            let value = musli_value::encode(&expected).unwrap();
+            let actual = musli_value::decode(&value).unwrap();
+            assert_eq!(expected, actual);
+        }};
+    }
+
+    json!(Untagged::Number(42), "[42]");
+    json!(Untagged::String(String::from("foo")), r#""foo""#);
+    json!(
+        Untagged::Pair {
+            first: true,
+            second: false
+        },
+        "[true,false]"
+    );
+    json!(UntaggedEnum2::Variant1(String::from("foo")), r#"["foo"]"#);
+    json!(UntaggedEnum2::Variant2(42), "[42]");
+    json!(
+        UntaggedEnum3::Variant1 {
+            value: String::from("foo")
+        },
+        r#"["foo"]"#
+    );
+    json!(UntaggedEnum3::Variant2 { value: 42 }, "[42]");
+
+    json!(UntaggedTransparent::Number(42), "42");
+    json!(UntaggedTransparent::String(String::from("foo")), r#""foo""#);
+    json!(UntaggedTransparent::Flag(false), "false");
+
+    let error = musli_json::from_str::<UntaggedTransparent>("{}").unwrap_err();
+    let error = error.to_string();
+    assert!(
+        error.contains("data did not match any variant of `UntaggedTransparent`"),
+        "{error}"
+    );
+    assert!(error.contains("Number: "), "{error}");
+    assert!(error.contains("String: "), "{error}");
+    assert!(error.contains("Flag: "), "{error}");
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+pub enum Shape {
+    #[musli(transparent)]
+    Point(Point),
+    #[musli(transparent)]
+    Tags(std::collections::BTreeMap<String, String>),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Container {
+    shape: Shape,
+    count: u32,
+}
+
+/// Errors raised while trying each variant include the path at which they
+/// were raised, and the outer context keeps track of where the untagged enum
+/// is.
+#[test]
+#[cfg(feature = "std")]
+fn test_untagged_enums_context() {
+    use musli_json::context::{AllocBuf, AllocContext};
+
+    fn errors(input: &str) -> Vec<String> {
+        let mut buf = AllocBuf::default();
+        let mut cx = AllocContext::new(&mut buf);
+
+        let result = musli_json::Encoding::new().from_str_with::<_, Container>(&mut cx, input);
+        assert!(result.is_err());
+
+        cx.iter().map(|e| e.to_string()).collect()
+    }
+
+    assert_eq!(
+        errors(r#"{"shape":{"x":1,"y":[]},"count":1}"#),
+        [".shape: data did not match any variant of `Shape`; Point: .y: expected u32, but found sequence; Tags: expected string, but found number (at bytes 9-23)"]
+    );
+
+    // The steps entered by a variant which failed are left again.
+    assert_eq!(
+        errors(r#"{"shape":{"x":"one"},"count":"two"}"#),
+        [".count: invalid numeric (at bytes 29-30)"]
+    );
+}
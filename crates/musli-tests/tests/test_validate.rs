@@ -0,0 +1,83 @@
+#![cfg(feature = "std")]
+
+use musli::mode::TextMode;
+use musli::{Decode, Encode};
+use musli_json::context::{AllocBuf, AllocContext};
+
+fn non_empty(value: &str) -> Result<(), &'static str> {
+    if value.is_empty() {
+        return Err("must not be empty");
+    }
+
+    Ok(())
+}
+
+fn ordered(range: &Range) -> Result<(), &'static str> {
+    if range.min > range.max {
+        return Err("min must not be greater than max");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name", validate = ordered)]
+pub struct Range {
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(default_field_name = "name")]
+pub struct Config {
+    #[musli(validate = non_empty)]
+    name: String,
+    range: Range,
+}
+
+fn errors<T>(input: &str) -> Vec<String>
+where
+    T: for<'de> Decode<'de, TextMode>,
+{
+    let mut buf = AllocBuf::default();
+    let mut cx = AllocContext::new(&mut buf);
+
+    let result = musli_json::Encoding::new().from_str_with::<_, T>(&mut cx, input);
+    assert!(result.is_err());
+    cx.iter().map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn validate_ok() {
+    let config = Config {
+        name: String::from("server"),
+        range: Range { min: 1, max: 2 },
+    };
+
+    let json = musli_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"name":"server","range":{"min":1,"max":2}}"#);
+
+    let actual: Config = musli_json::from_str(&json).unwrap();
+    assert_eq!(actual, config);
+}
+
+#[test]
+fn validate_field() {
+    assert_eq!(
+        errors::<Config>(r#"{"name":"","range":{"min":1,"max":2}}"#),
+        [".name: must not be empty (at byte 10)"]
+    );
+}
+
+#[test]
+fn validate_container() {
+    assert_eq!(
+        errors::<Config>(r#"{"name":"server","range":{"min":2,"max":1}}"#),
+        [".range: min must not be greater than max (at byte 42)"]
+    );
+
+    assert_eq!(
+        errors::<Range>(r#"{"min":2,"max":1}"#),
+        [": min must not be greater than max (at byte 17)"]
+    );
+}
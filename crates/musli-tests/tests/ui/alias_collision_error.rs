@@ -0,0 +1,19 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(default_field_name = "name")]
+struct Struct {
+    name: String,
+    #[musli(alias = "name")]
+    title: String,
+}
+
+#[derive(Encode, Decode)]
+enum Enum {
+    First,
+    #[musli(alias = 0)]
+    Second,
+}
+
+fn main() {
+}
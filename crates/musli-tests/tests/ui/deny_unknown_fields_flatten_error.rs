@@ -0,0 +1,17 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+struct Metadata {
+    id: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(deny_unknown_fields)]
+struct Message {
+    body: String,
+    #[musli(flatten)]
+    metadata: Metadata,
+}
+
+fn main() {
+}
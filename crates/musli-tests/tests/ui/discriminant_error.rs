@@ -0,0 +1,23 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(discriminant)]
+struct Struct {
+    field: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(discriminant)]
+enum Fields {
+    Empty,
+    Tuple(u32),
+}
+
+#[derive(Encode, Decode)]
+#[musli(discriminant, tag = "type")]
+enum Tagged {
+    Empty,
+}
+
+fn main() {
+}
@@ -0,0 +1,14 @@
+use musli::Decode;
+
+#[derive(Decode)]
+#[musli(from = String, try_from = String)]
+struct Email(String);
+
+impl From<String> for Email {
+    fn from(value: String) -> Self {
+        Email(value)
+    }
+}
+
+fn main() {
+}
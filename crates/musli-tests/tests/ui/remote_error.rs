@@ -0,0 +1,28 @@
+use musli::mode::Mode;
+use musli::{Decode, Encode};
+
+mod foreign {
+    pub struct Point {
+        pub x: u32,
+    }
+}
+
+enum Custom {}
+
+impl Mode for Custom {}
+
+#[derive(Encode)]
+struct Getter {
+    #[musli(getter = Getter::value)]
+    value: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(remote = foreign::Point)]
+#[musli(mode = Custom, packed)]
+struct PointDef {
+    x: u32,
+}
+
+fn main() {
+}
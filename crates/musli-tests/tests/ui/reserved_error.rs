@@ -0,0 +1,30 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(default_field_name = "name", reserved("old", "email"))]
+struct Struct {
+    name: String,
+    email: String,
+    #[musli(alias = "old")]
+    renamed: String,
+}
+
+#[derive(Encode, Decode)]
+#[musli(reserved(0, 2))]
+struct Indexed {
+    first: u32,
+    #[musli(rename = 2)]
+    third: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(default_variant_name = "name", reserved("Removed"))]
+enum Enum {
+    Kept,
+    Removed,
+    #[musli(reserved(0))]
+    Variant(u32),
+}
+
+fn main() {
+}
@@ -0,0 +1,16 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+struct Metadata {
+    id: u32,
+}
+
+#[derive(Encode, Decode)]
+struct Message {
+    body: String,
+    #[musli(skip, flatten)]
+    metadata: Metadata,
+}
+
+fn main() {
+}
@@ -1,9 +0,0 @@
-use musli::{Encode, Decode};
-
-#[derive(Encode, Decode)]
-#[musli(packed)]
-enum UntaggedEnum {
-}
-
-fn main() {
-}
\ No newline at end of file
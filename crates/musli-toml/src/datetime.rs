@@ -0,0 +1,444 @@
+//! The [Datetime] type, which corresponds to the TOML datetime types.
+
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use alloc::string::String;
+
+use musli::de::{Decode, Decoder, PairDecoder, PairsDecoder, ValueVisitor};
+use musli::en::{Encode, Encoder, PairsEncoder};
+use musli::mode::Mode;
+use musli::Context;
+
+/// The name of the single field that a [Datetime] is encoded as.
+///
+/// This is how the TOML encoder and decoder recognizes a datetime, since they
+/// are otherwise encoded as strings.
+pub(crate) const FIELD: &str = "$__musli_toml_private_datetime";
+
+/// A TOML datetime.
+///
+/// This covers all four datetime types which are supported by TOML, depending
+/// on which components are present:
+///
+/// * Offset date-time, like `1979-05-27T07:32:00Z`.
+/// * Local date-time, like `1979-05-27T07:32:00`.
+/// * Local date, like `1979-05-27`.
+/// * Local time, like `07:32:00`.
+///
+/// When encoded with `musli-toml` this is written as a bare TOML datetime.
+/// Other formats encode it as a struct with a single private field holding its
+/// string representation. Note that a datetime can also be decoded into a
+/// `String` to get its string representation.
+///
+/// ```rust
+/// use musli_toml::Datetime;
+///
+/// let datetime: Datetime = "1979-05-27T07:32:00.5-07:00".parse()?;
+/// assert_eq!(datetime.to_string(), "1979-05-27T07:32:00.5-07:00");
+///
+/// let time = datetime.time.unwrap();
+/// assert_eq!((time.hour, time.minute, time.second), (7, 32, 0));
+/// assert_eq!(time.nanosecond, 500_000_000);
+/// # Ok::<_, musli_toml::DatetimeParseError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Datetime {
+    /// The date component.
+    pub date: Option<Date>,
+    /// The time component.
+    pub time: Option<Time>,
+    /// The offset, which is only present if both date and time are present.
+    pub offset: Option<Offset>,
+}
+
+/// The date component of a [Datetime].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Year, from 0 to 9999.
+    pub year: u16,
+    /// Month, from 1 to 12.
+    pub month: u8,
+    /// Day of the month, from 1 to 31 depending on the month.
+    pub day: u8,
+}
+
+/// The time component of a [Datetime].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// Hour, from 0 to 23.
+    pub hour: u8,
+    /// Minute, from 0 to 59.
+    pub minute: u8,
+    /// Second, from 0 to 60 where 60 is a leap second.
+    pub second: u8,
+    /// Nanosecond, from 0 to 999999999.
+    pub nanosecond: u32,
+}
+
+/// The offset of a [Datetime] from UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Offset {
+    /// UTC, written as `Z`.
+    Z,
+    /// An offset in minutes, like `-07:00`.
+    Custom {
+        /// Minutes of offset, from -1439 to 1439.
+        minutes: i16,
+    },
+}
+
+/// Error raised when parsing a [Datetime] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DatetimeParseError;
+
+impl fmt::Display for DatetimeParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid datetime")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DatetimeParseError {}
+
+impl FromStr for Datetime {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            bytes: s.as_bytes(),
+            index: 0,
+        };
+
+        let datetime = parser.datetime().ok_or(DatetimeParseError)?;
+
+        if parser.index != parser.bytes.len() {
+            return Err(DatetimeParseError);
+        }
+
+        Ok(datetime)
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn datetime(&mut self) -> Option<Datetime> {
+        // Local time is the only form which has a `:` as its third character.
+        if self.bytes.get(2) == Some(&b':') {
+            return Some(Datetime {
+                date: None,
+                time: Some(self.time()?),
+                offset: None,
+            });
+        }
+
+        let date = self.date()?;
+
+        match self.bytes.get(self.index) {
+            Some(b'T' | b't') => {}
+            Some(b' ')
+                if self
+                    .bytes
+                    .get(self.index + 1)
+                    .map_or(false, u8::is_ascii_digit) => {}
+            _ => {
+                return Some(Datetime {
+                    date: Some(date),
+                    time: None,
+                    offset: None,
+                });
+            }
+        }
+
+        self.index += 1;
+        let time = self.time()?;
+
+        let offset = match self.bytes.get(self.index) {
+            Some(b'Z' | b'z') => {
+                self.index += 1;
+                Some(Offset::Z)
+            }
+            Some(&b @ (b'+' | b'-')) => {
+                self.index += 1;
+                let hours = self.digits(2)?;
+                self.expect(b':')?;
+                let minutes = self.digits(2)?;
+
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+
+                let minutes = (hours * 60 + minutes) as i16;
+
+                Some(Offset::Custom {
+                    minutes: if b == b'-' { -minutes } else { minutes },
+                })
+            }
+            _ => None,
+        };
+
+        Some(Datetime {
+            date: Some(date),
+            time: Some(time),
+            offset,
+        })
+    }
+
+    fn date(&mut self) -> Option<Date> {
+        let year = self.digits(4)?;
+        self.expect(b'-')?;
+        let month = self.digits(2)?;
+        self.expect(b'-')?;
+        let day = self.digits(2)?;
+
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    fn time(&mut self) -> Option<Time> {
+        let hour = self.digits(2)?;
+        self.expect(b':')?;
+        let minute = self.digits(2)?;
+        self.expect(b':')?;
+        let second = self.digits(2)?;
+
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let mut nanosecond = 0;
+
+        if self.bytes.get(self.index) == Some(&b'.') {
+            self.index += 1;
+            let start = self.index;
+            let mut scale = 100_000_000;
+
+            while let Some(&b) = self.bytes.get(self.index) {
+                if !b.is_ascii_digit() {
+                    break;
+                }
+
+                // Precision beyond nanoseconds is truncated.
+                nanosecond += u32::from(b - b'0') * scale;
+                scale /= 10;
+                self.index += 1;
+            }
+
+            if self.index == start {
+                return None;
+            }
+        }
+
+        Some(Time {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+        })
+    }
+
+    fn digits(&mut self, n: usize) -> Option<u32> {
+        let digits = self.bytes.get(self.index..self.index + n)?;
+        let mut value = 0;
+
+        for &b in digits {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+
+            value = value * 10 + u32::from(b - b'0');
+        }
+
+        self.index += n;
+        Some(value)
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        if self.bytes.get(self.index) != Some(&b) {
+            return None;
+        }
+
+        self.index += 1;
+        Some(())
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(date) = &self.date {
+            date.fmt(f)?;
+        }
+
+        if let Some(time) = &self.time {
+            if self.date.is_some() {
+                f.write_char('T')?;
+            }
+
+            time.fmt(f)?;
+        }
+
+        if let Some(offset) = &self.offset {
+            offset.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Date {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+
+        if self.nanosecond != 0 {
+            let mut nanosecond = self.nanosecond;
+            let mut width = 9;
+
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+
+            write!(f, ".{nanosecond:0width$}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Offset::Z => f.write_char('Z'),
+            Offset::Custom { minutes } => {
+                let sign = if minutes < 0 { '-' } else { '+' };
+                let minutes = minutes.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+impl<M> Encode<M> for Datetime
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let mut string = String::new();
+
+        if write!(string, "{self}").is_err() {
+            return Err(cx.message("failed to format datetime"));
+        }
+
+        let mut st = encoder.encode_struct(cx, 1)?;
+        st.insert::<M, _, _, _>(cx, FIELD, string.as_str())?;
+        st.end(cx)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for Datetime
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let mut st = decoder.decode_struct(cx, 1)?;
+
+        let datetime = {
+            let Some(mut pair) = st.next(cx)? else {
+                return Err(cx.message("expected a datetime"));
+            };
+
+            if !pair.first(cx)?.decode_string(cx, FieldVisitor)? {
+                return Err(cx.message("expected a datetime"));
+            }
+
+            pair.second(cx)?.decode_string(cx, DatetimeVisitor)?
+        };
+
+        if st.next(cx)?.is_some() {
+            return Err(cx.message("expected a datetime"));
+        }
+
+        st.end(cx)?;
+        Ok(datetime)
+    }
+}
+
+struct FieldVisitor;
+
+impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, str> for FieldVisitor
+where
+    C: Context<'buf>,
+{
+    type Ok = bool;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "datetime field")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: &mut C, string: &str) -> Result<Self::Ok, C::Error> {
+        Ok(string == FIELD)
+    }
+}
+
+struct DatetimeVisitor;
+
+impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, str> for DatetimeVisitor
+where
+    C: Context<'buf>,
+{
+    type Ok = Datetime;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "datetime string")
+    }
+
+    #[inline]
+    fn visit_ref(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error> {
+        match string.parse() {
+            Ok(datetime) => Ok(datetime),
+            Err(..) => Err(cx.message(format_args!("invalid datetime `{string}`"))),
+        }
+    }
+}
@@ -0,0 +1,987 @@
+use core::cell::Cell;
+use core::fmt;
+use core::slice;
+
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use musli::de::{
+    AsDecoder, Decoder, NumberHint, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder,
+    SizeHint, TypeHint, ValueVisitor, VariantDecoder, Visitor,
+};
+use musli::mode::Mode;
+use musli::Context;
+
+use crate::datetime::FIELD;
+use crate::error::{Error, ErrorKind};
+use crate::value::{Entry, Item, Span, Table, TableKind, Value};
+
+/// The key a datetime is presented as when it's decoded as a struct.
+static DATETIME_KEY: Cow<'static, str> = Cow::Borrowed(FIELD);
+
+/// The body of a variant which has been specified as a string.
+static EMPTY: Item<'static> =
+    Item::new(Value::Table(Table::new(TableKind::Inline)), Span::new(0, 0));
+
+/// Keeps track of how far the context has been advanced into the input.
+///
+/// The document is parsed before it's decoded, so this allows errors to be
+/// marked with the span of the value they concern. The context can only be
+/// advanced, so an error for a value before the current position is reported
+/// at the current position.
+#[derive(Clone, Copy)]
+pub(crate) struct Cursor<'a> {
+    pos: &'a Cell<usize>,
+}
+
+impl<'a> Cursor<'a> {
+    #[inline]
+    pub(crate) fn new(pos: &'a Cell<usize>) -> Self {
+        Self { pos }
+    }
+
+    /// Advance the context to the given position.
+    #[inline]
+    pub(crate) fn seek<'buf, C>(self, cx: &mut C, pos: usize)
+    where
+        C: Context<'buf>,
+    {
+        let current = self.pos.get();
+
+        if pos > current {
+            cx.advance(pos - current);
+            self.pos.set(pos);
+        }
+    }
+
+    /// Report an error marked with the given span.
+    pub(crate) fn report<'buf, C>(self, cx: &mut C, span: Span, error: Error) -> C::Error
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        if span.start < self.pos.get() {
+            return cx.report(error);
+        }
+
+        self.seek(cx, span.start);
+        let mark = cx.mark();
+        self.seek(cx, span.end);
+        cx.marked_report(mark, error)
+    }
+}
+
+/// A TOML decoder for Müsli.
+pub struct TomlDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    item: &'a Item<'de>,
+}
+
+impl<'a, 'de> TomlDecoder<'a, 'de> {
+    #[inline]
+    pub(crate) fn new(cursor: Cursor<'a>, item: &'a Item<'de>) -> Self {
+        Self { cursor, item }
+    }
+
+    /// Report that the value being decoded is not of the expected kind.
+    #[inline]
+    fn expected<'buf, C>(&self, cx: &mut C, expected: &'static str) -> C::Error
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let kind = ErrorKind::Expected(expected, self.item.value.kind());
+        self.cursor.report(cx, self.item.span, Error::new(kind))
+    }
+
+    #[inline]
+    fn decode_integer<'buf, C, T>(self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: TryFrom<i64>,
+    {
+        let Value::Integer(n) = self.item.value else {
+            return Err(self.expected(cx, "integer"));
+        };
+
+        match T::try_from(n) {
+            Ok(n) => Ok(n),
+            Err(..) => {
+                Err(self
+                    .cursor
+                    .report(cx, self.item.span, Error::new(ErrorKind::OutOfRange(n))))
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_float<'buf, C>(self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match self.item.value {
+            Value::Float(n) => Ok(n),
+            Value::Integer(n) => Ok(n as f64),
+            _ => Err(self.expected(cx, "float")),
+        }
+    }
+
+    /// Decode an array of integers as bytes.
+    fn decode_byte_array<'buf, C>(self, cx: &mut C) -> Result<Vec<u8>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let Value::Array(array) = &self.item.value else {
+            return Err(self.expected(cx, "array of bytes"));
+        };
+
+        let mut bytes = Vec::with_capacity(array.items.len());
+
+        for item in &array.items {
+            bytes.push(TomlDecoder::new(self.cursor, item).decode_integer(cx)?);
+        }
+
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn sequence<'buf, C>(self, cx: &mut C) -> Result<TomlSequenceDecoder<'a, 'de>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        let Value::Array(array) = &self.item.value else {
+            return Err(self.expected(cx, "array"));
+        };
+
+        Ok(TomlSequenceDecoder::new(
+            self.cursor,
+            &array.items,
+            self.item.span,
+        ))
+    }
+
+    #[inline]
+    fn table<'buf, C>(self, cx: &mut C) -> Result<TomlTableDecoder<'a, 'de>, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+    {
+        match &self.item.value {
+            Value::Table(table) => Ok(TomlTableDecoder::new(
+                self.cursor,
+                Pairs::Table(table.entries.iter()),
+            )),
+            Value::Datetime(..) => Ok(TomlTableDecoder::new(
+                self.cursor,
+                Pairs::Datetime(Some(self.item)),
+            )),
+            _ => Err(self.expected(cx, "table")),
+        }
+    }
+}
+
+#[musli::decoder]
+impl<'a, 'de> Decoder<'de> for TomlDecoder<'a, 'de> {
+    type Error = Error;
+    type Buffer = TomlBuffer<'a, 'de>;
+    type Some = Self;
+    type Pack = TomlSequenceDecoder<'a, 'de>;
+    type Sequence = TomlSequenceDecoder<'a, 'de>;
+    type Tuple = TomlSequenceDecoder<'a, 'de>;
+    type Map = TomlTableDecoder<'a, 'de>;
+    type Struct = TomlTableDecoder<'a, 'de>;
+    type Variant = TomlVariantDecoder<'a, 'de>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from TOML")
+    }
+
+    #[inline]
+    fn type_hint<'buf, C>(&mut self, _: &mut C) -> Result<TypeHint, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(match &self.item.value {
+            Value::String(string) => TypeHint::String(SizeHint::Exact(string.len())),
+            Value::Integer(..) => TypeHint::Number(NumberHint::I64),
+            Value::Float(..) => TypeHint::Number(NumberHint::F64),
+            Value::Boolean(..) => TypeHint::Bool,
+            Value::Datetime(..) => TypeHint::String(SizeHint::Any),
+            Value::Array(array) => TypeHint::Sequence(SizeHint::Exact(array.items.len())),
+            Value::Table(table) => TypeHint::Map(SizeHint::Exact(table.entries.len())),
+        })
+    }
+
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, _: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlBuffer {
+            cursor: self.cursor,
+            item: self.item,
+        })
+    }
+
+    #[inline]
+    fn decode_unit<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match &self.item.value {
+            Value::Table(table) if table.entries.is_empty() => Ok(()),
+            _ => Err(self.expected(cx, "empty table")),
+        }
+    }
+
+    #[inline]
+    fn decode_bool<'buf, C>(self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match self.item.value {
+            Value::Boolean(value) => Ok(value),
+            _ => Err(self.expected(cx, "boolean")),
+        }
+    }
+
+    #[inline]
+    fn decode_char<'buf, C>(self, cx: &mut C) -> Result<char, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Value::String(string) = &self.item.value else {
+            return Err(self.expected(cx, "string"));
+        };
+
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => {
+                Err(self
+                    .cursor
+                    .report(cx, self.item.span, Error::new(ErrorKind::CharEmptyString)))
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u128<'buf, C>(self, cx: &mut C) -> Result<u128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i128<'buf, C>(self, cx: &mut C) -> Result<i128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_f32<'buf, C>(self, cx: &mut C) -> Result<f32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self.decode_float(cx)? as f32)
+    }
+
+    #[inline]
+    fn decode_f64<'buf, C>(self, cx: &mut C) -> Result<f64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_float(cx)
+    }
+
+    #[inline]
+    fn decode_array<'buf, C, const N: usize>(self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let (cursor, span) = (self.cursor, self.item.span);
+        let bytes = self.decode_byte_array(cx)?;
+
+        match <[u8; N]>::try_from(bytes) {
+            Ok(array) => Ok(array),
+            Err(bytes) => {
+                let kind = if bytes.len() < N {
+                    ErrorKind::ShortArray
+                } else {
+                    ErrorKind::LongArray
+                };
+
+                Err(cursor.report(cx, span, Error::new(kind)))
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_bytes<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        let bytes = self.decode_byte_array(cx)?;
+        visitor.visit_ref(cx, &bytes)
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        match &self.item.value {
+            Value::String(Cow::Borrowed(string)) => visitor.visit_borrowed(cx, string),
+            Value::String(Cow::Owned(string)) => visitor.visit_ref(cx, string),
+            Value::Datetime(datetime) => visitor.visit_ref(cx, &datetime.to_string()),
+            _ => Err(self.expected(cx, "string")),
+        }
+    }
+
+    #[inline]
+    fn decode_option<'buf, C>(self, _: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // TOML has no null, so an absent value is represented by leaving out
+        // the entry.
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.sequence(cx)
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.sequence(cx)
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.sequence(cx)
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.table(cx)
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, cx: &mut C, _: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.table(cx)
+    }
+
+    #[inline]
+    fn decode_variant<'buf, C>(self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        match &self.item.value {
+            Value::String(tag) => Ok(TomlVariantDecoder {
+                cursor: self.cursor,
+                tag,
+                tag_span: self.item.span,
+                item: &EMPTY,
+            }),
+            Value::Table(table) if table.entries.len() == 1 => {
+                let entry = &table.entries[0];
+
+                Ok(TomlVariantDecoder {
+                    cursor: self.cursor,
+                    tag: &entry.key,
+                    tag_span: entry.key_span,
+                    item: &entry.item,
+                })
+            }
+            _ => {
+                Err(self
+                    .cursor
+                    .report(cx, self.item.span, Error::new(ErrorKind::ExpectedVariant)))
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_any<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: Visitor<'de, Error = Self::Error>,
+    {
+        match &self.item.value {
+            Value::String(string) => {
+                let visitor = visitor.visit_string(cx, SizeHint::Exact(string.len()))?;
+                self.decode_string(cx, visitor)
+            }
+            Value::Datetime(..) => {
+                let visitor = visitor.visit_string(cx, SizeHint::Any)?;
+                self.decode_string(cx, visitor)
+            }
+            Value::Integer(n) => visitor.visit_i64(cx, *n),
+            Value::Float(n) => visitor.visit_f64(cx, *n),
+            Value::Boolean(b) => visitor.visit_bool(cx, *b),
+            Value::Array(..) => {
+                let decoder = self.sequence(cx)?;
+                visitor.visit_sequence(cx, decoder)
+            }
+            Value::Table(..) => {
+                let decoder = self.table(cx)?;
+                visitor.visit_map(cx, decoder)
+            }
+        }
+    }
+}
+
+/// A buffered TOML value, which is simply a reference into the document.
+pub struct TomlBuffer<'a, 'de> {
+    cursor: Cursor<'a>,
+    item: &'a Item<'de>,
+}
+
+impl<'a, 'de> AsDecoder for TomlBuffer<'a, 'de> {
+    type Error = Error;
+    type Decoder<'this>
+        = TomlDecoder<'this, 'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn as_decoder<'buf, C>(&self, _: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlDecoder::new(self.cursor, self.item))
+    }
+}
+
+/// A TOML table key decoder for Müsli.
+pub struct TomlKeyDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    key: &'a Cow<'de, str>,
+    span: Span,
+}
+
+impl<'a, 'de> TomlKeyDecoder<'a, 'de> {
+    #[inline]
+    fn decode_integer<'buf, C, T>(self, cx: &mut C) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: core::str::FromStr,
+    {
+        match self.key.parse() {
+            Ok(n) => Ok(n),
+            Err(..) => Err(self
+                .cursor
+                .report(cx, self.span, Error::new(ErrorKind::InvalidNumber))),
+        }
+    }
+}
+
+#[musli::decoder]
+impl<'a, 'de> Decoder<'de> for TomlKeyDecoder<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from a table key")
+    }
+
+    #[inline]
+    fn type_hint<'buf, C>(&mut self, _: &mut C) -> Result<TypeHint, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TypeHint::String(SizeHint::Exact(self.key.len())))
+    }
+
+    #[inline]
+    fn decode_u8<'buf, C>(self, cx: &mut C) -> Result<u8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u16<'buf, C>(self, cx: &mut C) -> Result<u16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_u128<'buf, C>(self, cx: &mut C) -> Result<u128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i8<'buf, C>(self, cx: &mut C) -> Result<i8, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i16<'buf, C>(self, cx: &mut C) -> Result<i16, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_i128<'buf, C>(self, cx: &mut C) -> Result<i128, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_usize<'buf, C>(self, cx: &mut C) -> Result<usize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_isize<'buf, C>(self, cx: &mut C) -> Result<isize, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_integer(cx)
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        match self.key {
+            Cow::Borrowed(string) => visitor.visit_borrowed(cx, string),
+            Cow::Owned(string) => visitor.visit_ref(cx, string),
+        }
+    }
+
+    #[inline]
+    fn decode_any<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: Visitor<'de, Error = Self::Error>,
+    {
+        let visitor = visitor.visit_string(cx, SizeHint::Exact(self.key.len()))?;
+        self.decode_string(cx, visitor)
+    }
+}
+
+/// A decoder for TOML arrays.
+pub struct TomlSequenceDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    iter: slice::Iter<'a, Item<'de>>,
+    span: Span,
+}
+
+impl<'a, 'de> TomlSequenceDecoder<'a, 'de> {
+    #[inline]
+    fn new(cursor: Cursor<'a>, items: &'a [Item<'de>], span: Span) -> Self {
+        Self {
+            cursor,
+            iter: items.iter(),
+            span,
+        }
+    }
+}
+
+impl<'a, 'de> SequenceDecoder<'de> for TomlSequenceDecoder<'a, 'de> {
+    type Error = Error;
+    type Decoder<'this>
+        = TomlDecoder<'a, 'de>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Exact(self.iter.len())
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(item) = self.iter.next() else {
+            return Ok(None);
+        };
+
+        self.cursor.seek(cx, item.span.start);
+        Ok(Some(TomlDecoder::new(self.cursor, item)))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+impl<'a, 'de> PackDecoder<'de> for TomlSequenceDecoder<'a, 'de> {
+    type Error = Error;
+    type Decoder<'this>
+        = TomlDecoder<'a, 'de>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let Some(item) = self.iter.next() else {
+            // Point at the closing bracket of the array.
+            let span = Span::new(self.span.end.saturating_sub(1), self.span.end);
+            return Err(self
+                .cursor
+                .report(cx, span, Error::new(ErrorKind::ShortArray)));
+        };
+
+        self.cursor.seek(cx, item.span.start);
+        Ok(TomlDecoder::new(self.cursor, item))
+    }
+
+    #[inline]
+    fn end<'buf, C>(mut self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if let Some(item) = self.iter.next() {
+            return Err(self
+                .cursor
+                .report(cx, item.span, Error::new(ErrorKind::LongArray)));
+        }
+
+        Ok(())
+    }
+}
+
+/// The entries being decoded from a table.
+enum Pairs<'a, 'de> {
+    Table(slice::Iter<'a, Entry<'de>>),
+    /// A datetime, which is decoded as a table with a single private key.
+    Datetime(Option<&'a Item<'de>>),
+}
+
+/// A decoder for TOML tables.
+pub struct TomlTableDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    pairs: Pairs<'a, 'de>,
+}
+
+impl<'a, 'de> TomlTableDecoder<'a, 'de> {
+    #[inline]
+    fn new(cursor: Cursor<'a>, pairs: Pairs<'a, 'de>) -> Self {
+        Self { cursor, pairs }
+    }
+}
+
+impl<'a, 'de> PairsDecoder<'de> for TomlTableDecoder<'a, 'de> {
+    type Error = Error;
+    type Decoder<'this>
+        = TomlPairDecoder<'a, 'de>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match &self.pairs {
+            Pairs::Table(iter) => SizeHint::Exact(iter.len()),
+            Pairs::Datetime(item) => SizeHint::Exact(usize::from(item.is_some())),
+        }
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let (key, key_span, item) = match &mut self.pairs {
+            Pairs::Table(iter) => {
+                let Some(entry) = iter.next() else {
+                    return Ok(None);
+                };
+
+                (&entry.key, entry.key_span, &entry.item)
+            }
+            Pairs::Datetime(item) => {
+                let Some(item) = item.take() else {
+                    return Ok(None);
+                };
+
+                (&DATETIME_KEY, item.span, item)
+            }
+        };
+
+        self.cursor.seek(cx, key_span.start);
+
+        Ok(Some(TomlPairDecoder {
+            cursor: self.cursor,
+            key,
+            key_span,
+            item,
+        }))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+/// A decoder for a single entry in a TOML table.
+pub struct TomlPairDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    key: &'a Cow<'de, str>,
+    key_span: Span,
+    item: &'a Item<'de>,
+}
+
+impl<'a, 'de> PairDecoder<'de> for TomlPairDecoder<'a, 'de> {
+    type Error = Error;
+    type First<'this>
+        = TomlKeyDecoder<'a, 'de>
+    where
+        Self: 'this;
+    type Second = TomlDecoder<'a, 'de>;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlKeyDecoder {
+            cursor: self.cursor,
+            key: self.key,
+            span: self.key_span,
+        })
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, cx: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.cursor.seek(cx, self.item.span.start);
+        Ok(TomlDecoder::new(self.cursor, self.item))
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(true)
+    }
+}
+
+/// A decoder for enum variants, which are either represented as a string for
+/// variants without data, or as a table with a single entry.
+pub struct TomlVariantDecoder<'a, 'de> {
+    cursor: Cursor<'a>,
+    tag: &'a Cow<'de, str>,
+    tag_span: Span,
+    item: &'a Item<'de>,
+}
+
+impl<'a, 'de> VariantDecoder<'de> for TomlVariantDecoder<'a, 'de> {
+    type Error = Error;
+    type Tag<'this>
+        = TomlKeyDecoder<'a, 'de>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = TomlDecoder<'a, 'de>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlKeyDecoder {
+            cursor: self.cursor,
+            key: self.tag,
+            span: self.tag_span,
+        })
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.cursor.seek(cx, self.item.span.start);
+        Ok(TomlDecoder::new(self.cursor, self.item))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, _: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(true)
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
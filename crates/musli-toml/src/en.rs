@@ -0,0 +1,800 @@
+use core::fmt::{self, Write};
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use musli::en::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
+use musli::Context;
+
+use crate::datetime::FIELD;
+use crate::error::{Error, ErrorKind};
+use crate::value::{Array, Entry, Item, Span, Table, TableKind, Value};
+
+/// A TOML encoder for Müsli.
+///
+/// Values are encoded into a document tree, which is formatted once encoding
+/// is complete. An absent value is represented by `None`, which causes the
+/// entry it belongs to to be left out.
+pub struct TomlEncoder<'a> {
+    slot: &'a mut Option<Value<'static>>,
+}
+
+impl<'a> TomlEncoder<'a> {
+    #[inline]
+    pub(crate) fn new(slot: &'a mut Option<Value<'static>>) -> Self {
+        Self { slot }
+    }
+
+    #[inline]
+    fn set(self, value: Value<'static>) {
+        *self.slot = Some(value);
+    }
+
+    #[inline]
+    fn encode_integer<'buf, C, T>(self, cx: &mut C, value: T) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        i64: TryFrom<T>,
+    {
+        match i64::try_from(value) {
+            Ok(value) => {
+                self.set(Value::Integer(value));
+                Ok(())
+            }
+            Err(..) => Err(cx.report(Error::new(ErrorKind::IntegerOverflow))),
+        }
+    }
+
+    #[inline]
+    fn encode_byte_array(self, bytes: &[u8]) {
+        let items = bytes
+            .iter()
+            .map(|&b| Item::new(Value::Integer(i64::from(b)), Span::default()))
+            .collect();
+
+        self.set(Value::Array(Array {
+            items,
+            of_tables: false,
+        }));
+    }
+}
+
+#[musli::encoder]
+impl<'a> Encoder for TomlEncoder<'a> {
+    type Ok = ();
+    type Error = Error;
+    type Some = Self;
+    type Pack = TomlSequenceEncoder<'a>;
+    type Sequence = TomlSequenceEncoder<'a>;
+    type Tuple = TomlSequenceEncoder<'a>;
+    type Map = TomlTableEncoder<'a>;
+    type Struct = TomlTableEncoder<'a>;
+    type Variant = TomlVariantEncoder<'a>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the TOML encoder")
+    }
+
+    #[inline]
+    fn encode_unit<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.set(Value::Table(Table::new(TableKind::Header)));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_bool<'buf, C>(self, _: &mut C, value: bool) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.set(Value::Boolean(value));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_char<'buf, C>(self, _: &mut C, value: char) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.set(Value::String(Cow::Owned(value.to_string())));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_u8<'buf, C>(self, cx: &mut C, value: u8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_u16<'buf, C>(self, cx: &mut C, value: u16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_u128<'buf, C>(self, cx: &mut C, value: u128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_i8<'buf, C>(self, cx: &mut C, value: i8) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_i16<'buf, C>(self, cx: &mut C, value: i16) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_i128<'buf, C>(self, cx: &mut C, value: i128) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_usize<'buf, C>(self, cx: &mut C, value: usize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_isize<'buf, C>(self, cx: &mut C, value: isize) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_integer(cx, value)
+    }
+
+    #[inline]
+    fn encode_f32<'buf, C>(self, _: &mut C, value: f32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        // Go through the shortest representation of the `f32`, so that it's
+        // written as for example `0.1` rather than `0.10000000149011612`.
+        let value = if value.is_finite() {
+            let mut buffer = ryu::Buffer::new();
+            buffer
+                .format_finite(value)
+                .parse()
+                .unwrap_or(f64::from(value))
+        } else {
+            f64::from(value)
+        };
+
+        self.set(Value::Float(value));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_f64<'buf, C>(self, _: &mut C, value: f64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.set(Value::Float(value));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_array<'buf, C, const N: usize>(
+        self,
+        _: &mut C,
+        array: [u8; N],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_byte_array(&array);
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_bytes<'buf, C>(self, _: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_byte_array(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<'buf, C>(
+        self,
+        _: &mut C,
+        vectors: &[&[u8]],
+    ) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_byte_array(&vectors.concat());
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string<'buf, C>(self, _: &mut C, string: &str) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.set(Value::String(Cow::Owned(string.to_string())));
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_some<'buf, C>(self, _: &mut C) -> Result<Self::Some, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        *self.slot = None;
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_pack<'buf, C>(self, _: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlSequenceEncoder::new(self.slot, 0))
+    }
+
+    #[inline]
+    fn encode_sequence<'buf, C>(self, _: &mut C, len: usize) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlSequenceEncoder::new(self.slot, len))
+    }
+
+    #[inline]
+    fn encode_tuple<'buf, C>(self, _: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlSequenceEncoder::new(self.slot, len))
+    }
+
+    #[inline]
+    fn encode_map<'buf, C>(self, _: &mut C, len: usize) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlTableEncoder::new(self.slot, len))
+    }
+
+    #[inline]
+    fn encode_struct<'buf, C>(self, _: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlTableEncoder::new(self.slot, len))
+    }
+
+    #[inline]
+    fn encode_variant<'buf, C>(self, _: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlVariantEncoder {
+            slot: self.slot,
+            tag: None,
+            value: None,
+        })
+    }
+}
+
+/// Encoder for TOML arrays.
+pub struct TomlSequenceEncoder<'a> {
+    slot: &'a mut Option<Value<'static>>,
+    values: Vec<Option<Value<'static>>>,
+}
+
+impl<'a> TomlSequenceEncoder<'a> {
+    #[inline]
+    fn new(slot: &'a mut Option<Value<'static>>, len: usize) -> Self {
+        Self {
+            slot,
+            values: Vec::with_capacity(len),
+        }
+    }
+}
+
+impl<'a> SequenceEncoder for TomlSequenceEncoder<'a> {
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = TomlEncoder<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.values.push(None);
+        Ok(TomlEncoder::new(self.values.last_mut().unwrap()))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut items = Vec::with_capacity(self.values.len());
+
+        for value in self.values {
+            let Some(value) = value else {
+                return Err(cx.report(Error::new(ErrorKind::UnsupportedNone)));
+            };
+
+            items.push(Item::new(value, Span::default()));
+        }
+
+        *self.slot = Some(Value::Array(Array {
+            items,
+            of_tables: false,
+        }));
+
+        Ok(())
+    }
+}
+
+/// Encoder for TOML tables.
+pub struct TomlTableEncoder<'a> {
+    slot: &'a mut Option<Value<'static>>,
+    pairs: Vec<(Option<Value<'static>>, Option<Value<'static>>)>,
+}
+
+impl<'a> TomlTableEncoder<'a> {
+    #[inline]
+    fn new(slot: &'a mut Option<Value<'static>>, len: usize) -> Self {
+        Self {
+            slot,
+            pairs: Vec::with_capacity(len),
+        }
+    }
+}
+
+impl<'a> PairsEncoder for TomlTableEncoder<'a> {
+    type Ok = ();
+    type Error = Error;
+    type Encoder<'this>
+        = TomlPairEncoder<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, _: &mut C) -> Result<Self::Encoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.pairs.push((None, None));
+        let (key, value) = self.pairs.last_mut().unwrap();
+        Ok(TomlPairEncoder { key, value })
+    }
+
+    fn end<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let mut entries = Vec::with_capacity(self.pairs.len());
+
+        for (key, value) in self.pairs {
+            let key = match key {
+                Some(Value::String(key)) => key,
+                Some(Value::Integer(key)) => Cow::Owned(key.to_string()),
+                Some(Value::Boolean(key)) => Cow::Owned(key.to_string()),
+                Some(value) => {
+                    return Err(cx.report(Error::new(ErrorKind::UnsupportedKey(value.kind()))));
+                }
+                None => return Err(cx.report(Error::new(ErrorKind::UnsupportedNone))),
+            };
+
+            // Entries without a value are left out.
+            let Some(value) = value else {
+                continue;
+            };
+
+            entries.push(Entry {
+                key,
+                key_span: Span::default(),
+                item: Item::new(value, Span::default()),
+            });
+        }
+
+        *self.slot = Some(into_datetime(cx, entries)?);
+        Ok(())
+    }
+}
+
+/// Convert the entries of a table into a datetime if it consists of the single
+/// private field that datetimes are encoded with.
+fn into_datetime<'buf, C>(
+    cx: &mut C,
+    entries: Vec<Entry<'static>>,
+) -> Result<Value<'static>, C::Error>
+where
+    C: Context<'buf, Input = Error>,
+{
+    if let [Entry {
+        key,
+        item: Item {
+            value: Value::String(string),
+            ..
+        },
+        ..
+    }] = entries.as_slice()
+    {
+        if key == FIELD {
+            return match string.parse() {
+                Ok(datetime) => Ok(Value::Datetime(datetime)),
+                Err(..) => Err(cx.report(Error::new(ErrorKind::InvalidDatetime))),
+            };
+        }
+    }
+
+    Ok(Value::Table(Table {
+        entries,
+        kind: TableKind::Header,
+    }))
+}
+
+/// Encoder for a single entry in a TOML table.
+pub struct TomlPairEncoder<'a> {
+    key: &'a mut Option<Value<'static>>,
+    value: &'a mut Option<Value<'static>>,
+}
+
+impl<'a> PairEncoder for TomlPairEncoder<'a> {
+    type Ok = ();
+    type Error = Error;
+    type First<'this>
+        = TomlEncoder<'this>
+    where
+        Self: 'this;
+    type Second<'this>
+        = TomlEncoder<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, _: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlEncoder::new(self.key))
+    }
+
+    #[inline]
+    fn second<'buf, C>(&mut self, _: &mut C) -> Result<Self::Second<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlEncoder::new(self.value))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+}
+
+/// Encoder for enum variants, which are encoded as a string if they have no
+/// data, or as a table with a single entry.
+pub struct TomlVariantEncoder<'a> {
+    slot: &'a mut Option<Value<'static>>,
+    tag: Option<Value<'static>>,
+    value: Option<Value<'static>>,
+}
+
+impl<'a> VariantEncoder for TomlVariantEncoder<'a> {
+    type Ok = ();
+    type Error = Error;
+    type Tag<'this>
+        = TomlEncoder<'this>
+    where
+        Self: 'this;
+    type Variant<'this>
+        = TomlEncoder<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, _: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlEncoder::new(&mut self.tag))
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, _: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(TomlEncoder::new(&mut self.value))
+    }
+
+    fn end<'buf, C>(self, cx: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let tag = match self.tag {
+            Some(Value::String(tag)) => tag,
+            Some(Value::Integer(tag)) => Cow::Owned(tag.to_string()),
+            Some(value) => {
+                return Err(cx.report(Error::new(ErrorKind::UnsupportedKey(value.kind()))));
+            }
+            None => return Err(cx.report(Error::new(ErrorKind::UnsupportedNone))),
+        };
+
+        let value = match self.value {
+            Some(Value::Table(table)) if table.entries.is_empty() => Value::String(tag),
+            Some(value) => Value::Table(Table {
+                entries: alloc::vec![Entry {
+                    key: tag,
+                    key_span: Span::default(),
+                    item: Item::new(value, Span::default()),
+                }],
+                kind: TableKind::Header,
+            }),
+            None => return Err(cx.report(Error::new(ErrorKind::UnsupportedNone))),
+        };
+
+        *self.slot = Some(value);
+        Ok(())
+    }
+}
+
+/// Format the root table of a document.
+pub(crate) fn format(table: &Table<'_>) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+    let mut path = Vec::new();
+    format_table(&mut out, &mut path, table, Section::Root)?;
+    Ok(out)
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Root,
+    Table,
+    ArrayOfTables,
+}
+
+/// Test if the given value is written as its own section, rather than inline.
+fn is_section(value: &Value<'_>) -> bool {
+    match value {
+        Value::Table(table) => !table.entries.is_empty(),
+        Value::Array(array) => {
+            !array.items.is_empty()
+                && array
+                    .items
+                    .iter()
+                    .all(|item| matches!(item.value, Value::Table(..)))
+        }
+        _ => false,
+    }
+}
+
+fn format_table<'a>(
+    out: &mut String,
+    path: &mut Vec<&'a str>,
+    table: &'a Table<'_>,
+    section: Section,
+) -> fmt::Result {
+    let has_values = table.entries.iter().any(|e| !is_section(&e.item.value));
+
+    let brackets = match section {
+        Section::Root => None,
+        Section::Table if has_values => Some(("[", "]")),
+        Section::Table => None,
+        Section::ArrayOfTables => Some(("[[", "]]")),
+    };
+
+    if let Some((open, close)) = brackets {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str(open);
+
+        for (n, key) in path.iter().enumerate() {
+            if n > 0 {
+                out.push('.');
+            }
+
+            format_key(out, key)?;
+        }
+
+        out.push_str(close);
+        out.push('\n');
+    }
+
+    for entry in &table.entries {
+        if is_section(&entry.item.value) {
+            continue;
+        }
+
+        format_key(out, &entry.key)?;
+        out.push_str(" = ");
+        format_value(out, &entry.item.value)?;
+        out.push('\n');
+    }
+
+    for entry in &table.entries {
+        path.push(&entry.key);
+
+        match &entry.item.value {
+            Value::Table(table) if is_section(&entry.item.value) => {
+                format_table(out, path, table, Section::Table)?;
+            }
+            Value::Array(array) if is_section(&entry.item.value) => {
+                for item in &array.items {
+                    if let Value::Table(table) = &item.value {
+                        format_table(out, path, table, Section::ArrayOfTables)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        path.pop();
+    }
+
+    Ok(())
+}
+
+fn format_value(out: &mut String, value: &Value<'_>) -> fmt::Result {
+    match value {
+        Value::String(string) => format_string(out, string)?,
+        Value::Integer(n) => write!(out, "{n}")?,
+        Value::Float(n) => {
+            if n.is_nan() {
+                out.push_str("nan");
+            } else if n.is_infinite() {
+                out.push_str(if *n > 0.0 { "inf" } else { "-inf" });
+            } else {
+                let mut buffer = ryu::Buffer::new();
+                out.push_str(buffer.format_finite(*n));
+            }
+        }
+        Value::Boolean(b) => write!(out, "{b}")?,
+        Value::Datetime(datetime) => write!(out, "{datetime}")?,
+        Value::Array(array) => {
+            out.push('[');
+
+            for (n, item) in array.items.iter().enumerate() {
+                if n > 0 {
+                    out.push_str(", ");
+                }
+
+                format_value(out, &item.value)?;
+            }
+
+            out.push(']');
+        }
+        Value::Table(table) => {
+            if table.entries.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+
+            out.push_str("{ ");
+
+            for (n, entry) in table.entries.iter().enumerate() {
+                if n > 0 {
+                    out.push_str(", ");
+                }
+
+                format_key(out, &entry.key)?;
+                out.push_str(" = ");
+                format_value(out, &entry.item.value)?;
+            }
+
+            out.push_str(" }");
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a key, which is quoted unless it's a valid bare key.
+fn format_key(out: &mut String, key: &str) -> fmt::Result {
+    let is_bare = !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-'));
+
+    if is_bare {
+        out.push_str(key);
+        Ok(())
+    } else {
+        format_string(out, key)
+    }
+}
+
+fn format_string(out: &mut String, string: &str) -> fmt::Result {
+    out.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' || c == '\u{7f}' => write!(out, "\\u{:04X}", c as u32)?,
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    Ok(())
+}
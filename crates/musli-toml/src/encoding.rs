@@ -0,0 +1,316 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [DEFAULT] encoding configuration.
+
+use core::cell::Cell;
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::io;
+
+use musli::de::Decode;
+use musli::en::Encode;
+use musli::mode::{Mode, TextMode};
+use musli::Context;
+
+use crate::de::{Cursor, TomlDecoder};
+use crate::en::{self, TomlEncoder};
+use crate::error::{Error, ErrorKind};
+use crate::fixed_bytes::FixedBytes;
+use crate::parser;
+use crate::value::{Item, Span, Value};
+use crate::writer::Writer;
+
+/// The default configuration.
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Encode the given value to the given [Writer] using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn encode<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Writer,
+    Error: From<W::Error>,
+    T: ?Sized + Encode<TextMode>,
+{
+    DEFAULT.encode(writer, value)
+}
+
+/// Encode the given value to the given [Write][io::Write] using the [DEFAULT]
+/// configuration.
+#[cfg(feature = "std")]
+#[inline]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: io::Write,
+    Error: From<io::Error>,
+    T: ?Sized + Encode<TextMode>,
+{
+    DEFAULT.to_writer(writer, value)
+}
+
+/// Encode the given value to a [`Vec`] using the [DEFAULT] configuration.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: ?Sized + Encode<TextMode>,
+{
+    DEFAULT.to_vec(value)
+}
+
+/// Encode the given value to a [`String`] using the [DEFAULT] configuration.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Encode<TextMode>,
+{
+    DEFAULT.to_string(value)
+}
+
+/// Encode the given value to a fixed-size bytes using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn to_fixed_bytes<const N: usize, T>(value: &T) -> Result<FixedBytes<N>, Error>
+where
+    T: ?Sized + Encode<TextMode>,
+{
+    DEFAULT.to_fixed_bytes::<N, _>(value)
+}
+
+/// Decode the given type `T` from the given string using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn from_str<'de, T>(string: &'de str) -> Result<T, Error>
+where
+    T: Decode<'de, TextMode>,
+{
+    DEFAULT.from_str(string)
+}
+
+/// Decode the given type `T` from the given slice using the [DEFAULT]
+/// configuration.
+#[inline]
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Decode<'de, TextMode>,
+{
+    DEFAULT.from_slice(bytes)
+}
+
+/// Setting up encoding with parameters.
+///
+/// The default mode is [`TextMode`], which is human readable. This means that
+/// types like network addresses are encoded using their string form.
+pub struct Encoding<M = TextMode> {
+    _marker: marker::PhantomData<M>,
+}
+
+impl Encoding<TextMode> {
+    /// Construct a new [Encoding].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```rust
+    /// use musli_toml::Encoding;
+    /// use musli::{Encode, Decode, Mode};
+    ///
+    /// const CONFIG: Encoding<Toml> = Encoding::new().with_mode();
+    ///
+    /// // Mode marker indicating that some attributes should
+    /// // only apply when we're decoding in a TOML mode.
+    /// enum Toml {}
+    ///
+    /// impl Mode for Toml {
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(mode = Toml, default_field_name = "name")]
+    /// struct Struct<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    /// }
+    ///
+    /// let expected = Struct {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// let out = CONFIG.to_string(&expected)?;
+    /// assert_eq!(out, "name = \"Aristotle\"\nage = 61\n");
+    ///
+    /// let actual = CONFIG.from_str(&out)?;
+    /// assert_eq!(expected, actual);
+    /// # Ok::<_, musli_toml::Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: Mode,
+{
+    /// Change the mode of the encoding.
+    pub const fn with_mode<T>(self) -> Encoding<T>
+    where
+        T: Mode,
+    {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Encode the given value to the given [`Writer`] using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::encode`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn encode_with<'buf, C, W, T>(
+        self,
+        cx: &mut C,
+        mut writer: W,
+        value: &T,
+    ) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        W: Writer,
+        Error: From<W::Error>,
+        T: ?Sized + Encode<M>,
+    {
+        let mut root = None;
+        T::encode(value, cx, TomlEncoder::new(&mut root))?;
+
+        let Some(Value::Table(table)) = root else {
+            return Err(cx.report::<Error>(Error::new(ErrorKind::RootNotTable)));
+        };
+
+        let Ok(string) = en::format(&table) else {
+            return Err(cx.message("failed to format document"));
+        };
+
+        writer.write_bytes(cx.adapt(), string.as_bytes())
+    }
+
+    /// Encode the given value to a [`String`] using the current configuration.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        self.to_string_with(&mut cx, value)
+    }
+
+    /// Encode the given value to a [`String`] using the current configuration.
+    ///
+    /// This is the same as [`Encoding::to_string`] but allows for using a
+    /// configurable [`Context`].
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string_with<'buf, T, C>(self, cx: &mut C, value: &T) -> Result<String, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: ?Sized + Encode<M>,
+    {
+        let mut data = Vec::with_capacity(128);
+        self.encode_with(cx, &mut data, value)?;
+        // SAFETY: Encoder is guaranteed to produce valid UTF-8.
+        Ok(unsafe { String::from_utf8_unchecked(data) })
+    }
+
+    /// Decode the given type `T` from the given string using the current
+    /// configuration.
+    #[inline]
+    pub fn from_str<'de, T>(self, string: &'de str) -> Result<T, Error>
+    where
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        self.from_str_with(&mut cx, string)
+    }
+
+    /// Decode the given type `T` from the given string using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::from_str`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// Errors are marked with the byte range of the input that they concern,
+    /// which can be translated into a line and a column using
+    /// [`Position::from_offset`][crate::Position::from_offset].
+    #[inline]
+    pub fn from_str_with<'de, 'buf, C, T>(self, cx: &mut C, string: &'de str) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: Decode<'de, M>,
+    {
+        let pos = Cell::new(0);
+        let cursor = Cursor::new(&pos);
+
+        let table = match parser::parse(string) {
+            Ok(table) => table,
+            Err((span, kind)) => return Err(cursor.report(cx, span, Error::new(kind))),
+        };
+
+        let root = Item::new(Value::Table(table), Span::new(0, string.len()));
+        T::decode(cx, TomlDecoder::new(cursor, &root))
+    }
+
+    /// Decode the given type `T` from the given slice using the current
+    /// configuration.
+    #[inline]
+    pub fn from_slice<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+    where
+        T: Decode<'de, M>,
+    {
+        let mut cx = musli_common::context::Same::default();
+        self.from_slice_with(&mut cx, bytes)
+    }
+
+    /// Decode the given type `T` from the given slice using the current
+    /// configuration.
+    ///
+    /// This is the same as [`Encoding::from_slice`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn from_slice_with<'de, 'buf, C, T>(
+        self,
+        cx: &mut C,
+        bytes: &'de [u8],
+    ) -> Result<T, C::Error>
+    where
+        C: Context<'buf, Input = Error>,
+        T: Decode<'de, M>,
+    {
+        let Ok(string) = musli_common::str::from_utf8(bytes) else {
+            return Err(cx.report(Error::new(ErrorKind::InvalidUtf8)));
+        };
+
+        self.from_str_with(cx, string)
+    }
+
+    musli_common::encode_with_extensions!();
+}
+
+impl<M> Clone for Encoding<M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Copy for Encoding<M> {}
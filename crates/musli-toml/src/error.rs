@@ -0,0 +1,194 @@
+use core::convert::Infallible;
+use core::fmt;
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+
+use musli_common::fixed_bytes::FixedBytesOverflow;
+use musli_common::writer::SliceOverflow;
+
+use crate::value::Kind;
+
+/// Error raised during toml encoding.
+#[derive(Debug)]
+pub struct Error {
+    err: ErrorImpl,
+}
+
+impl Error {
+    #[inline(always)]
+    pub(crate) fn new(kind: ErrorKind) -> Self {
+        Self {
+            err: ErrorImpl::TomlError(kind),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.err.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum ErrorKind {
+    InvalidUtf8,
+    ControlCharacter,
+    UnterminatedString,
+    InvalidEscape,
+    InvalidNumber,
+    IntegerOverflow,
+    InvalidDatetime,
+    ExpectedKey,
+    ExpectedEquals,
+    ExpectedValue,
+    ExpectedNewline,
+    ExpectedCloseBracket,
+    ExpectedArrayEnd,
+    ExpectedInlineTableEnd,
+    DuplicateKey(Box<str>),
+    DuplicateTable(Box<str>),
+    NotATable(Box<str>),
+    Expected(&'static str, Kind),
+    ExpectedVariant,
+    CharEmptyString,
+    OutOfRange(i64),
+    ShortArray,
+    LongArray,
+    RootNotTable,
+    UnsupportedNone,
+    UnsupportedKey(Kind),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            ErrorKind::ControlCharacter => write!(f, "unexpected control character"),
+            ErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ErrorKind::InvalidEscape => write!(f, "invalid string escape"),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ErrorKind::IntegerOverflow => {
+                write!(f, "integer does not fit in a 64-bit signed integer")
+            }
+            ErrorKind::InvalidDatetime => write!(f, "invalid datetime"),
+            ErrorKind::ExpectedKey => write!(f, "expected a key"),
+            ErrorKind::ExpectedEquals => write!(f, "expected `=` after key"),
+            ErrorKind::ExpectedValue => write!(f, "expected a value"),
+            ErrorKind::ExpectedNewline => write!(f, "expected a newline or a comment"),
+            ErrorKind::ExpectedCloseBracket => write!(f, "expected closing bracket `]`"),
+            ErrorKind::ExpectedArrayEnd => write!(f, "expected `,` or closing bracket `]`"),
+            ErrorKind::ExpectedInlineTableEnd => {
+                write!(f, "expected `,` or closing brace `}}`")
+            }
+            ErrorKind::DuplicateKey(key) => write!(f, "duplicate key `{key}`"),
+            ErrorKind::DuplicateTable(key) => {
+                write!(f, "table `{key}` is defined more than once")
+            }
+            ErrorKind::NotATable(key) => {
+                write!(f, "cannot extend `{key}`, since it is not a table")
+            }
+            ErrorKind::Expected(expected, actual) => {
+                write!(f, "expected {expected}, found {actual}")
+            }
+            ErrorKind::ExpectedVariant => {
+                write!(f, "expected variant, a string or a table with a single key")
+            }
+            ErrorKind::CharEmptyString => {
+                write!(f, "expected string with a single character")
+            }
+            ErrorKind::OutOfRange(value) => write!(f, "integer `{value}` is out of range"),
+            ErrorKind::ShortArray => write!(f, "array has too few elements"),
+            ErrorKind::LongArray => write!(f, "array has too many elements"),
+            ErrorKind::RootNotTable => write!(f, "the root of a document must be a table"),
+            ErrorKind::UnsupportedNone => {
+                write!(f, "`None` can only be encoded as a missing table entry")
+            }
+            ErrorKind::UnsupportedKey(kind) => {
+                write!(f, "a {kind} can't be used as a key")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ErrorImpl {
+    SliceOverflow(SliceOverflow),
+    FixedBytesOverflow(FixedBytesOverflow),
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    TomlError(ErrorKind),
+    Message(Box<str>),
+}
+
+impl fmt::Display for ErrorImpl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorImpl::SliceOverflow(error) => error.fmt(f),
+            ErrorImpl::FixedBytesOverflow(error) => error.fmt(f),
+            #[cfg(feature = "std")]
+            ErrorImpl::Io(error) => error.fmt(f),
+            ErrorImpl::TomlError(error) => error.fmt(f),
+            ErrorImpl::Message(message) => message.fmt(f),
+        }
+    }
+}
+
+impl From<SliceOverflow> for Error {
+    #[inline(always)]
+    fn from(error: SliceOverflow) -> Self {
+        Self {
+            err: ErrorImpl::SliceOverflow(error),
+        }
+    }
+}
+
+impl From<FixedBytesOverflow> for Error {
+    #[inline(always)]
+    fn from(error: FixedBytesOverflow) -> Self {
+        Self {
+            err: ErrorImpl::FixedBytesOverflow(error),
+        }
+    }
+}
+
+impl From<Infallible> for Error {
+    #[inline(always)]
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    #[inline(always)]
+    fn from(error: std::io::Error) -> Self {
+        Self {
+            err: ErrorImpl::Io(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl musli::error::Error for Error {
+    #[inline]
+    fn custom<T>(error: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::message(error)
+    }
+
+    #[inline]
+    fn message<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self {
+            err: ErrorImpl::Message(message.to_string().into()),
+        }
+    }
+}
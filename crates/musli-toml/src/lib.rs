@@ -0,0 +1,154 @@
+//! [<img alt="github" src="https://img.shields.io/badge/github-udoprog/musli-8da0cb?style=for-the-badge&logo=github" height="20">](https://github.com/udoprog/musli)
+//! [<img alt="crates.io" src="https://img.shields.io/crates/v/musli-toml.svg?style=for-the-badge&color=fc8d62&logo=rust" height="20">](https://crates.io/crates/musli-toml)
+//! [<img alt="docs.rs" src="https://img.shields.io/badge/docs.rs-musli--toml-66c2a5?style=for-the-badge&logoColor=white&logo=data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHhtbG5zPSJodHRwOi8vd3d3LnczLm9yZy8yMDAwL3N2ZyIgdmlld0JveD0iMCAwIDUxMiA1MTIiPjxwYXRoIGZpbGw9IiNmNWY1ZjUiIGQ9Ik00ODguNiAyNTAuMkwzOTIgMjE0VjEwNS41YzAtMTUtOS4zLTI4LjQtMjMuNC0zMy43bC0xMDAtMzcuNWMtOC4xLTMuMS0xNy4xLTMuMS0yNS4zIDBsLTEwMCAzNy41Yy0xNC4xIDUuMy0yMy40IDE4LjctMjMuNCAzMy43VjIxNGwtOTYuNiAzNi4yQzkuMyAyNTUuNSAwIDI2OC45IDAgMjgzLjlWMzk0YzAgMTMuNiA3LjcgMjYuMSAxOS45IDMyLjJsMTAwIDUwYzEwLjEgNS4xIDIyLjEgNS4xIDMyLjIgMGwxMDMuOS01MiAxMDMuOSA1MmMxMC4xIDUuMSAyMi4xIDUuMSAzMi4yIDBsMTAwLTUwYzEyLjItNi4xIDE5LjktMTguNiAxOS45LTMyLjJWMjgzLjljMC0xNS05LjMtMjguNC0yMy40LTMzLjd6TTM1OCAyMTQuOGwtODUgMzEuOXYtNjguMmw4NS0zN3Y3My4zek0xNTQgMTA0LjFsMTAyLTM4LjIgMTAyIDM4LjJ2LjZsLTEwMiA0MS40LTEwMi00MS40di0uNnptODQgMjkxLjFsLTg1IDQyLjV2LTc5LjFsODUtMzguOHY3NS40em0wLTExMmwtMTAyIDQxLjQtMTAyLTQxLjR2LS42bDEwMi0zOC4yIDEwMiAzOC4ydi42em0yNDAgMTEybC04NSA0Mi41di03OS4xbDg1LTM4Ljh2NzUuNHptMC0xMTJsLTEwMiA0MS40LTEwMi00MS40di0uNmwxMDItMzguMiAxMDIgMzguMnYuNnoiPjwvcGF0aD48L3N2Zz4K" height="20">](https://docs.rs/musli-toml)
+//!
+//! A [TOML] format for [Müsli], which is suitable for configuration files.
+//!
+//! Structs and maps are encoded as tables, and sequences of structs are
+//! encoded as arrays of tables. Since TOML is a text format, fields should be
+//! given names through `#[musli(default_field_name = "name")]` or
+//! `#[musli(rename = "..")]`.
+//!
+//! This format is fully upgrade stable:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//!
+//! ```rust
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli_toml::to_string(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(61),
+//! })?;
+//!
+//! assert_eq!(version2, "name = \"Aristotle\"\nage = 61\n");
+//!
+//! let version1: Version1 = musli_toml::from_str(&version2)?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli_toml::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Data model
+//!
+//! The value being encoded must be a struct or a map, which is encoded as the
+//! root table of the document. Values are mapped to TOML like this:
+//!
+//! * Strings and `char` are strings, `bool` is a boolean and floats are
+//!   floats. Integers are integers, which in TOML are limited to the range of
+//!   an `i64`.
+//! * Structs and maps are tables. Map keys can be strings or integers.
+//!   Tables containing values are written as `[table]` sections after the
+//!   plain values of their parent, while empty tables and `()` are written
+//!   inline as `{}`.
+//! * Sequences, tuples and bytes are arrays. A non-empty sequence of tables is
+//!   written as an array of tables, with one `[[table]]` section per element.
+//! * `Option` maps to the presence of an entry, since TOML has no null. A
+//!   field which is `None` is not written at all, so it needs
+//!   `#[musli(default)]` when decoding. `None` can't be stored in an array.
+//! * Variants without a value are written as strings, and other variants as a
+//!   table with a single entry named after the variant.
+//! * Datetimes are decoded as their string representation into string types,
+//!   or into the dedicated [Datetime] type which is written as a bare TOML
+//!   datetime.
+//!
+//! Since a TOML document can define its tables in any order it's parsed in
+//! full before it is decoded, and values are collected before any of them are
+//! written. So this format requires an allocator.
+//!
+//! <br>
+//!
+//! ## Diagnostics
+//!
+//! When decoding with a context such as `AllocContext` through methods like
+//! [Encoding::from_str_with], errors are marked with the byte range of the
+//! input that they concern. This can be translated into a line and a column
+//! using [Position].
+//!
+//! ```rust
+//! use musli::Decode;
+//! use musli_toml::context::{AllocBuf, AllocContext};
+//! use musli_toml::Position;
+//!
+//! #[derive(Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Config {
+//!     name: String,
+//!     port: u16,
+//! }
+//!
+//! let source = "name = \"musli\"\nport = \"http\"\n";
+//!
+//! let mut buf = AllocBuf::default();
+//! let mut cx = AllocContext::new(&mut buf);
+//!
+//! let Err(..) = musli_toml::DEFAULT.from_str_with::<_, Config>(&mut cx, source) else {
+//!     panic!("expected decoding to fail");
+//! };
+//!
+//! let error = cx.iter().next().unwrap();
+//! let position = Position::from_offset(source, error.range().start);
+//! assert_eq!(format!("{position}: {error}"), "2:8: .port: expected integer, found string (at bytes 22-28)");
+//! ```
+//!
+//! [TOML]: https://toml.io
+//! [Müsli]: https://docs.rs/musli
+//! [Datetime]: https://docs.rs/musli-toml/latest/musli_toml/datetime/struct.Datetime.html
+//! [Position]: https://docs.rs/musli-toml/latest/musli_toml/struct.Position.html
+//! [Encoding::from_str_with]: https://docs.rs/musli-toml/latest/musli_toml/encoding/struct.Encoding.html#method.from_str_with
+
+#![deny(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod datetime;
+mod de;
+mod en;
+pub mod encoding;
+mod error;
+mod parser;
+mod position;
+#[cfg(feature = "test")]
+#[macro_use]
+pub mod test;
+mod value;
+
+/// Convenient result alias for use with `musli_toml`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+pub use self::datetime::{Datetime, DatetimeParseError};
+#[cfg(feature = "std")]
+pub use self::encoding::to_writer;
+pub use self::encoding::{encode, from_slice, from_str, to_fixed_bytes, Encoding, DEFAULT};
+#[cfg(feature = "alloc")]
+pub use self::encoding::{to_string, to_vec};
+pub use self::error::Error;
+pub use self::position::Position;
+#[cfg(feature = "test")]
+pub use self::test::transcode;
+#[doc(inline)]
+pub use musli_common::*;
@@ -0,0 +1,869 @@
+//! Parser from TOML text into the document tree in [crate::value].
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::datetime::Datetime;
+use crate::error::ErrorKind;
+use crate::value::{Array, Entry, Item, Span, Table, TableKind, Value};
+
+/// Result of parsing, where errors carry the span they should be reported at.
+pub(crate) type Result<T, E = (Span, ErrorKind)> = core::result::Result<T, E>;
+
+/// A parsed key, where each component of a dotted key has its own span.
+type Key<'de> = Vec<(Cow<'de, str>, Span)>;
+
+/// Parse a TOML document into its root table.
+pub(crate) fn parse(input: &str) -> Result<Table<'_>> {
+    let mut parser = Parser {
+        input,
+        bytes: input.as_bytes(),
+        pos: 0,
+    };
+
+    parser.document()
+}
+
+struct Parser<'de> {
+    input: &'de str,
+    bytes: &'de [u8],
+    pos: usize,
+}
+
+impl<'de> Parser<'de> {
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn peek_at(&self, n: usize) -> Option<u8> {
+        self.bytes.get(self.pos + n).copied()
+    }
+
+    #[inline]
+    fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.bytes[self.pos..].starts_with(prefix)
+    }
+
+    /// The span of the character at the current position.
+    #[inline]
+    fn here(&self) -> Span {
+        let len = match self.input[self.pos..].chars().next() {
+            Some(c) => c.len_utf8(),
+            None => 0,
+        };
+
+        Span::new(self.pos, self.pos + len)
+    }
+
+    #[inline]
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.pos)
+    }
+
+    fn document(&mut self) -> Result<Table<'de>> {
+        let mut root = Table::new(TableKind::Header);
+        let mut current = Vec::new();
+
+        loop {
+            self.skip_blank()?;
+
+            match self.peek() {
+                None => break,
+                Some(b'[') => {
+                    current = self.header(&mut root)?;
+                }
+                Some(..) => {
+                    let table = table_at(&mut root, &current);
+                    self.keyval(table)?;
+                }
+            }
+
+            self.end_of_line()?;
+        }
+
+        Ok(root)
+    }
+
+    /// Skip whitespace, comments and newlines.
+    fn skip_blank(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b'#') => self.comment()?,
+                Some(b'\n') => self.pos += 1,
+                Some(b'\r') if self.peek_at(1) == Some(b'\n') => self.pos += 2,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn comment(&mut self) -> Result<()> {
+        // Skip the `#`.
+        self.pos += 1;
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'\n' => break,
+                b'\r' if self.peek_at(1) == Some(b'\n') => break,
+                b'\t' => {}
+                b if b < 0x20 || b == 0x7f => {
+                    return Err((self.here(), ErrorKind::ControlCharacter));
+                }
+                _ => {}
+            }
+
+            self.pos += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Expect the end of a line, optionally preceeded by a comment.
+    fn end_of_line(&mut self) -> Result<()> {
+        self.skip_whitespace();
+
+        if self.peek() == Some(b'#') {
+            self.comment()?;
+        }
+
+        match self.peek() {
+            None => Ok(()),
+            Some(b'\n') => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(b'\r') if self.peek_at(1) == Some(b'\n') => {
+                self.pos += 2;
+                Ok(())
+            }
+            Some(..) => Err((self.here(), ErrorKind::ExpectedNewline)),
+        }
+    }
+
+    /// Parse a `[table]` or `[[array]]` header, returning the path to the
+    /// table that it defines.
+    fn header(&mut self, root: &mut Table<'de>) -> Result<Vec<Cow<'de, str>>> {
+        let start = self.pos;
+        let is_array = self.starts_with(b"[[");
+        self.pos += if is_array { 2 } else { 1 };
+
+        self.skip_whitespace();
+        let key = self.key()?;
+        self.skip_whitespace();
+
+        let close: &[u8] = if is_array { b"]]" } else { b"]" };
+
+        if !self.starts_with(close) {
+            return Err((self.here(), ErrorKind::ExpectedCloseBracket));
+        }
+
+        self.pos += close.len();
+        let span = self.span_from(start);
+
+        let Some(((last, last_span), prefix)) = key.split_last() else {
+            return Err((span, ErrorKind::ExpectedKey));
+        };
+
+        let mut table = root;
+
+        for (name, name_span) in prefix {
+            if !table.contains_key(name) {
+                table.entries.push(Entry {
+                    key: name.clone(),
+                    key_span: *name_span,
+                    item: Item::new(Value::Table(Table::new(TableKind::Implicit)), span),
+                });
+            }
+
+            let item = table.get_mut(name).expect("entry was just inserted");
+
+            table = match &mut item.value {
+                Value::Table(table) if table.kind != TableKind::Inline => table,
+                Value::Array(array) if array.of_tables => match array.items.last_mut() {
+                    Some(Item {
+                        value: Value::Table(table),
+                        ..
+                    }) => table,
+                    _ => return Err((*name_span, not_a_table(self.input, &key, name_span))),
+                },
+                _ => return Err((*name_span, not_a_table(self.input, &key, name_span))),
+            };
+        }
+
+        let new_table = || Item::new(Value::Table(Table::new(TableKind::Header)), span);
+
+        match table.get_mut(last) {
+            None if is_array => {
+                table.entries.push(Entry {
+                    key: last.clone(),
+                    key_span: *last_span,
+                    item: Item::new(
+                        Value::Array(Array {
+                            items: alloc::vec![new_table()],
+                            of_tables: true,
+                        }),
+                        span,
+                    ),
+                });
+            }
+            None => {
+                table.entries.push(Entry {
+                    key: last.clone(),
+                    key_span: *last_span,
+                    item: new_table(),
+                });
+            }
+            Some(Item {
+                value: Value::Array(array),
+                ..
+            }) if is_array && array.of_tables => {
+                array.items.push(new_table());
+            }
+            Some(Item {
+                value: Value::Table(table),
+                ..
+            }) if !is_array && table.kind == TableKind::Implicit => {
+                table.kind = TableKind::Header;
+            }
+            Some(..) => {
+                let name = key_text(self.input, &key, last_span);
+                return Err((span, ErrorKind::DuplicateTable(name)));
+            }
+        }
+
+        Ok(key.into_iter().map(|(name, _)| name).collect())
+    }
+
+    /// Parse a `key = value` pair and insert it into the given table.
+    fn keyval(&mut self, table: &mut Table<'de>) -> Result<()> {
+        let key = self.key()?;
+        self.skip_whitespace();
+
+        if self.peek() != Some(b'=') {
+            return Err((self.here(), ErrorKind::ExpectedEquals));
+        }
+
+        self.pos += 1;
+        self.skip_whitespace();
+        let item = self.value()?;
+
+        let Some(((last, last_span), prefix)) = key.split_last() else {
+            return Err((self.here(), ErrorKind::ExpectedKey));
+        };
+
+        let mut table = table;
+
+        for (name, name_span) in prefix {
+            if !table.contains_key(name) {
+                table.entries.push(Entry {
+                    key: name.clone(),
+                    key_span: *name_span,
+                    item: Item::new(Value::Table(Table::new(TableKind::Dotted)), *name_span),
+                });
+            }
+
+            let item = table.get_mut(name).expect("entry was just inserted");
+
+            table = match &mut item.value {
+                Value::Table(table) if table.kind == TableKind::Dotted => table,
+                Value::Table(..) => {
+                    let name = key_text(self.input, &key, name_span);
+                    return Err((*name_span, ErrorKind::DuplicateKey(name)));
+                }
+                _ => return Err((*name_span, not_a_table(self.input, &key, name_span))),
+            };
+        }
+
+        if table.contains_key(last) {
+            let name = key_text(self.input, &key, last_span);
+            return Err((*last_span, ErrorKind::DuplicateKey(name)));
+        }
+
+        table.entries.push(Entry {
+            key: last.clone(),
+            key_span: *last_span,
+            item,
+        });
+
+        Ok(())
+    }
+
+    /// Parse a possibly dotted key.
+    fn key(&mut self) -> Result<Key<'de>> {
+        let mut key = Vec::new();
+
+        loop {
+            let start = self.pos;
+
+            let name = match self.peek() {
+                Some(b'"') if !self.starts_with(b"\"\"\"") => self.basic_string()?,
+                Some(b'\'') if !self.starts_with(b"'''") => self.literal_string()?,
+                Some(b) if is_bare_key(b) => {
+                    while let Some(b) = self.peek() {
+                        if !is_bare_key(b) {
+                            break;
+                        }
+
+                        self.pos += 1;
+                    }
+
+                    Cow::Borrowed(&self.input[start..self.pos])
+                }
+                _ => return Err((self.here(), ErrorKind::ExpectedKey)),
+            };
+
+            key.push((name, self.span_from(start)));
+
+            let pos = self.pos;
+            self.skip_whitespace();
+
+            if self.peek() != Some(b'.') {
+                // Whitespace after the key belongs to whatever follows it.
+                self.pos = pos;
+                return Ok(key);
+            }
+
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn value(&mut self) -> Result<Item<'de>> {
+        let start = self.pos;
+
+        let value = match self.peek() {
+            Some(b'"') if self.starts_with(b"\"\"\"") => {
+                Value::String(self.multiline_basic_string()?)
+            }
+            Some(b'"') => Value::String(self.basic_string()?),
+            Some(b'\'') if self.starts_with(b"'''") => {
+                Value::String(self.multiline_literal_string()?)
+            }
+            Some(b'\'') => Value::String(self.literal_string()?),
+            Some(b't') if self.starts_with(b"true") => {
+                self.pos += 4;
+                Value::Boolean(true)
+            }
+            Some(b'f') if self.starts_with(b"false") => {
+                self.pos += 5;
+                Value::Boolean(false)
+            }
+            Some(b'[') => Value::Array(self.array()?),
+            Some(b'{') => Value::Table(self.inline_table()?),
+            Some(b'0'..=b'9' | b'+' | b'-' | b'i' | b'n') => {
+                if self.is_datetime() {
+                    Value::Datetime(self.datetime()?)
+                } else {
+                    self.number()?
+                }
+            }
+            _ => return Err((self.here(), ErrorKind::ExpectedValue)),
+        };
+
+        Ok(Item::new(value, self.span_from(start)))
+    }
+
+    fn array(&mut self) -> Result<Array<'de>> {
+        // Skip the `[`.
+        self.pos += 1;
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_blank()?;
+
+            if self.peek() == Some(b']') {
+                break;
+            }
+
+            items.push(self.value()?);
+            self.skip_blank()?;
+
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => break,
+                _ => return Err((self.here(), ErrorKind::ExpectedArrayEnd)),
+            }
+        }
+
+        self.pos += 1;
+
+        Ok(Array {
+            items,
+            of_tables: false,
+        })
+    }
+
+    fn inline_table(&mut self) -> Result<Table<'de>> {
+        // Skip the `{`.
+        self.pos += 1;
+        let mut table = Table::new(TableKind::Dotted);
+        self.skip_whitespace();
+
+        if self.peek() != Some(b'}') {
+            loop {
+                self.keyval(&mut table)?;
+                self.skip_whitespace();
+
+                match self.peek() {
+                    Some(b',') => {
+                        self.pos += 1;
+                        self.skip_whitespace();
+                    }
+                    Some(b'}') => break,
+                    _ => return Err((self.here(), ErrorKind::ExpectedInlineTableEnd)),
+                }
+            }
+        }
+
+        self.pos += 1;
+        table.kind = TableKind::Inline;
+        Ok(table)
+    }
+
+    fn basic_string(&mut self) -> Result<Cow<'de, str>> {
+        let start = self.pos;
+        // Skip the opening quote.
+        self.pos += 1;
+        let mut buf = None::<String>;
+        let mut run = self.pos;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    let string = finish(buf, &self.input[run..self.pos]);
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    let buf = buf.get_or_insert_with(String::new);
+                    buf.push_str(&self.input[run..self.pos]);
+                    self.escape(buf)?;
+                    run = self.pos;
+                }
+                Some(b'\t') => self.pos += 1,
+                None | Some(b'\n') => {
+                    return Err((self.span_from(start), ErrorKind::UnterminatedString));
+                }
+                Some(b) if b < 0x20 || b == 0x7f => {
+                    return Err((self.here(), ErrorKind::ControlCharacter));
+                }
+                Some(..) => self.pos += 1,
+            }
+        }
+    }
+
+    fn multiline_basic_string(&mut self) -> Result<Cow<'de, str>> {
+        let start = self.pos;
+        // Skip the opening quotes.
+        self.pos += 3;
+        self.skip_newline();
+        let mut buf = None::<String>;
+        let mut run = self.pos;
+
+        loop {
+            match self.peek() {
+                Some(b'"') if self.starts_with(b"\"\"\"") => {
+                    let extra = self.closing_quotes(b'"');
+                    let string = finish(buf, &self.input[run..self.pos + extra]);
+                    self.pos += extra + 3;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    let buf = buf.get_or_insert_with(String::new);
+                    buf.push_str(&self.input[run..self.pos]);
+
+                    if self.line_ending_backslash() {
+                        self.skip_blank_in_string();
+                    } else {
+                        self.escape(buf)?;
+                    }
+
+                    run = self.pos;
+                }
+                Some(b'\t' | b'\n') => self.pos += 1,
+                Some(b'\r') if self.peek_at(1) == Some(b'\n') => self.pos += 2,
+                None => return Err((self.span_from(start), ErrorKind::UnterminatedString)),
+                Some(b) if b < 0x20 || b == 0x7f => {
+                    return Err((self.here(), ErrorKind::ControlCharacter));
+                }
+                Some(..) => self.pos += 1,
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<Cow<'de, str>> {
+        let start = self.pos;
+        // Skip the opening quote.
+        self.pos += 1;
+
+        loop {
+            match self.peek() {
+                Some(b'\'') => {
+                    let string = &self.input[start + 1..self.pos];
+                    self.pos += 1;
+                    return Ok(Cow::Borrowed(string));
+                }
+                Some(b'\t') => self.pos += 1,
+                None | Some(b'\n') => {
+                    return Err((self.span_from(start), ErrorKind::UnterminatedString));
+                }
+                Some(b) if b < 0x20 || b == 0x7f => {
+                    return Err((self.here(), ErrorKind::ControlCharacter));
+                }
+                Some(..) => self.pos += 1,
+            }
+        }
+    }
+
+    fn multiline_literal_string(&mut self) -> Result<Cow<'de, str>> {
+        let start = self.pos;
+        // Skip the opening quotes.
+        self.pos += 3;
+        self.skip_newline();
+        let run = self.pos;
+
+        loop {
+            match self.peek() {
+                Some(b'\'') if self.starts_with(b"'''") => {
+                    let extra = self.closing_quotes(b'\'');
+                    let string = &self.input[run..self.pos + extra];
+                    self.pos += extra + 3;
+                    return Ok(Cow::Borrowed(string));
+                }
+                Some(b'\t' | b'\n') => self.pos += 1,
+                Some(b'\r') if self.peek_at(1) == Some(b'\n') => self.pos += 2,
+                None => return Err((self.span_from(start), ErrorKind::UnterminatedString)),
+                Some(b) if b < 0x20 || b == 0x7f => {
+                    return Err((self.here(), ErrorKind::ControlCharacter));
+                }
+                Some(..) => self.pos += 1,
+            }
+        }
+    }
+
+    /// Skip a newline immediately following the opening delimiter of a
+    /// multi-line string.
+    #[inline]
+    fn skip_newline(&mut self) {
+        if self.peek() == Some(b'\n') {
+            self.pos += 1;
+        } else if self.starts_with(b"\r\n") {
+            self.pos += 2;
+        }
+    }
+
+    /// Count the number of quotes beyond three at the end of a multi-line
+    /// string, which are part of its content. Up to two are permitted.
+    #[inline]
+    fn closing_quotes(&self, quote: u8) -> usize {
+        let n = self.bytes[self.pos..]
+            .iter()
+            .take_while(|&&b| b == quote)
+            .count();
+
+        n.min(5) - 3
+    }
+
+    /// Test if the backslash at the current position is followed by
+    /// whitespace up until the end of the line.
+    fn line_ending_backslash(&self) -> bool {
+        for &b in &self.bytes[self.pos + 1..] {
+            match b {
+                b' ' | b'\t' | b'\r' => {}
+                b'\n' => return true,
+                _ => return false,
+            }
+        }
+
+        false
+    }
+
+    /// Skip all whitespace and newlines following a line ending backslash.
+    fn skip_blank_in_string(&mut self) {
+        // Skip the `\`.
+        self.pos += 1;
+
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n') => self.pos += 1,
+                Some(b'\r') if self.peek_at(1) == Some(b'\n') => self.pos += 2,
+                _ => break,
+            }
+        }
+    }
+
+    fn escape(&mut self, buf: &mut String) -> Result<()> {
+        let start = self.pos;
+        // Skip the `\`.
+        self.pos += 1;
+
+        let c = match self.peek() {
+            Some(b'b') => '\u{8}',
+            Some(b't') => '\t',
+            Some(b'n') => '\n',
+            Some(b'f') => '\u{c}',
+            Some(b'r') => '\r',
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b @ (b'u' | b'U')) => {
+                let len = if b == b'u' { 4 } else { 8 };
+                let hex = self.bytes.get(self.pos + 1..self.pos + 1 + len);
+
+                let c = hex
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| u32::from_str_radix(from_ascii(hex), 16).ok())
+                    .and_then(char::from_u32);
+
+                let Some(c) = c else {
+                    self.pos += 1;
+                    return Err((self.span_from(start), ErrorKind::InvalidEscape));
+                };
+
+                self.pos += len;
+                c
+            }
+            _ => return Err((Span::new(start, self.here().end), ErrorKind::InvalidEscape)),
+        };
+
+        self.pos += 1;
+        buf.push(c);
+        Ok(())
+    }
+
+    /// Test if the value at the current position is a datetime, which either
+    /// starts with a full date or a time.
+    fn is_datetime(&self) -> bool {
+        let digits = |range: core::ops::Range<usize>| {
+            range
+                .into_iter()
+                .all(|n| self.peek_at(n).map_or(false, |b| b.is_ascii_digit()))
+        };
+
+        (digits(0..4) && self.peek_at(4) == Some(b'-'))
+            || (digits(0..2) && self.peek_at(2) == Some(b':'))
+    }
+
+    fn datetime(&mut self) -> Result<Datetime> {
+        let start = self.pos;
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'0'..=b'9' | b'T' | b't' | b'Z' | b'z' | b':' | b'.' | b'+' | b'-' => {}
+                // A space may only separate the date from the time.
+                b' ' if self.pos - start == 10
+                    && self.peek_at(1).map_or(false, |b| b.is_ascii_digit()) => {}
+                _ => break,
+            }
+
+            self.pos += 1;
+        }
+
+        let span = self.span_from(start);
+
+        match self.input[span.start..span.end].parse() {
+            Ok(datetime) => Ok(datetime),
+            Err(..) => Err((span, ErrorKind::InvalidDatetime)),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value<'de>> {
+        let start = self.pos;
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'.' | b'+' | b'-' => {}
+                _ => break,
+            }
+
+            self.pos += 1;
+        }
+
+        let span = self.span_from(start);
+
+        match parse_number(&self.input[span.start..span.end]) {
+            Ok(value) => Ok(value),
+            Err(kind) => Err((span, kind)),
+        }
+    }
+}
+
+/// Get the table at the given path, which has been previously established by
+/// a header.
+fn table_at<'a, 'de>(root: &'a mut Table<'de>, path: &[Cow<'de, str>]) -> &'a mut Table<'de> {
+    let mut table = root;
+
+    for name in path {
+        let item = table.get_mut(name).expect("path was defined by a header");
+
+        table = match &mut item.value {
+            Value::Table(table) => table,
+            Value::Array(array) => match array.items.last_mut() {
+                Some(Item {
+                    value: Value::Table(table),
+                    ..
+                }) => table,
+                _ => unreachable!("array of tables must contain a table"),
+            },
+            _ => unreachable!("path must point to a table"),
+        };
+    }
+
+    table
+}
+
+/// The text of a dotted key up until and including the given component.
+fn key_text(input: &str, key: &Key<'_>, until: &Span) -> Box<str> {
+    let start = key.first().map_or(until.start, |(_, span)| span.start);
+    input[start..until.end].into()
+}
+
+#[inline]
+fn not_a_table(input: &str, key: &Key<'_>, until: &Span) -> ErrorKind {
+    ErrorKind::NotATable(key_text(input, key, until))
+}
+
+#[inline]
+fn finish<'de>(buf: Option<String>, rest: &'de str) -> Cow<'de, str> {
+    match buf {
+        Some(mut buf) => {
+            buf.push_str(rest);
+            Cow::Owned(buf)
+        }
+        None => Cow::Borrowed(rest),
+    }
+}
+
+#[inline]
+fn is_bare_key(b: u8) -> bool {
+    matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-')
+}
+
+#[inline]
+fn from_ascii(bytes: &[u8]) -> &str {
+    // NB: Callers only pass in bytes which have been checked to be ASCII.
+    core::str::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Parse an integer or a float.
+fn parse_number(s: &str) -> Result<Value<'static>, ErrorKind> {
+    let (sign, unsigned) = match s.as_bytes().first() {
+        Some(b'+') => ("", &s[1..]),
+        Some(b'-') => ("-", &s[1..]),
+        _ => ("", s),
+    };
+
+    match unsigned {
+        "inf" => {
+            return Ok(Value::Float(if sign.is_empty() {
+                f64::INFINITY
+            } else {
+                f64::NEG_INFINITY
+            }))
+        }
+        "nan" => return Ok(Value::Float(f64::NAN)),
+        _ => {}
+    }
+
+    let radix = match s.get(..2) {
+        Some("0x") => Some((16, u8::is_ascii_hexdigit as fn(&u8) -> bool)),
+        Some("0o") => Some((8, (|b: &u8| matches!(b, b'0'..=b'7')) as fn(&u8) -> bool)),
+        Some("0b") => Some((2, (|b: &u8| matches!(b, b'0' | b'1')) as fn(&u8) -> bool)),
+        _ => None,
+    };
+
+    if let Some((radix, is_digit)) = radix {
+        let digits = &s[2..];
+
+        if !valid_digits(digits, is_digit) {
+            return Err(ErrorKind::InvalidNumber);
+        }
+
+        return match i64::from_str_radix(&strip_underscores("", digits), radix) {
+            Ok(n) => Ok(Value::Integer(n)),
+            Err(..) => Err(ErrorKind::IntegerOverflow),
+        };
+    }
+
+    let is_decimal = |s: &str| valid_digits(s, u8::is_ascii_digit);
+    let no_leading_zero = |s: &str| s == "0" || !s.starts_with('0');
+
+    let (int, rest) = match unsigned.find(['.', 'e', 'E']) {
+        Some(n) => unsigned.split_at(n),
+        None => (unsigned, ""),
+    };
+
+    if !is_decimal(int) || !no_leading_zero(int) {
+        return Err(ErrorKind::InvalidNumber);
+    }
+
+    if rest.is_empty() {
+        return match strip_underscores(sign, int).parse() {
+            Ok(n) => Ok(Value::Integer(n)),
+            Err(..) => Err(ErrorKind::IntegerOverflow),
+        };
+    }
+
+    let (frac, exp) = match rest.find(['e', 'E']) {
+        Some(n) => (&rest[..n], Some(&rest[n + 1..])),
+        None => (rest, None),
+    };
+
+    if let Some(frac) = frac.strip_prefix('.') {
+        if !is_decimal(frac) {
+            return Err(ErrorKind::InvalidNumber);
+        }
+    } else if !frac.is_empty() {
+        return Err(ErrorKind::InvalidNumber);
+    }
+
+    if let Some(exp) = exp {
+        let exp = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+
+        if !is_decimal(exp) {
+            return Err(ErrorKind::InvalidNumber);
+        }
+    }
+
+    match strip_underscores(sign, unsigned).parse() {
+        Ok(n) => Ok(Value::Float(n)),
+        Err(..) => Err(ErrorKind::InvalidNumber),
+    }
+}
+
+/// Test that a string of digits is non-empty, and that each underscore is
+/// surrounded by digits.
+fn valid_digits(s: &str, is_digit: fn(&u8) -> bool) -> bool {
+    let mut last = b'_';
+
+    for b in s.bytes() {
+        match b {
+            b'_' if last == b'_' => return false,
+            b'_' => {}
+            b if !is_digit(&b) => return false,
+            _ => {}
+        }
+
+        last = b;
+    }
+
+    last != b'_'
+}
+
+fn strip_underscores(sign: &str, s: &str) -> String {
+    let mut out = String::with_capacity(sign.len() + s.len());
+    out.push_str(sign);
+    out.extend(s.chars().filter(|&c| c != '_'));
+    out
+}
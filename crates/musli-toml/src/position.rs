@@ -0,0 +1,58 @@
+//! Helper to translate byte offsets into lines and columns.
+
+use core::fmt;
+
+/// A line and column in a TOML document, both of which are 1-based.
+///
+/// Errors collected by a context such as `AllocContext` are marked with the
+/// byte range of the input they concern. This can be used to translate the
+/// start of that range into a position which is suitable for diagnostics.
+///
+/// ```rust
+/// use musli_toml::Position;
+///
+/// let source = "name = \"musli\"\nport = \"http\"\n";
+/// let position = Position::from_offset(source, 22);
+/// assert_eq!(position, Position { line: 2, column: 8 });
+/// assert_eq!(position.to_string(), "2:8");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The line, starting at 1.
+    pub line: usize,
+    /// The column in characters, starting at 1.
+    pub column: usize,
+}
+
+impl Position {
+    /// Calculate the position of the given byte offset in `source`.
+    ///
+    /// Offsets past the end of `source` are clamped to its end, and an offset
+    /// inside of a character is treated as pointing to that character.
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &source[..offset];
+
+        let (line, start) = match before.rfind('\n') {
+            Some(n) => (before.matches('\n').count() + 1, n + 1),
+            None => (1, 0),
+        };
+
+        Self {
+            line,
+            column: before[start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
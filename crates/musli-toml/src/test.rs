@@ -0,0 +1,37 @@
+//! Helpers for writing tests.
+
+use core::fmt::Debug;
+
+use musli::mode::TextMode;
+use musli::{Decode, Encode};
+
+/// Roundtrip encode the given value.
+#[macro_export]
+macro_rules! rt {
+    ($enum:ident :: $variant:ident $($body:tt)?) => {
+        $crate::rt!($enum, $enum :: $variant $($body)*)
+    };
+
+    ($struct:ident $($body:tt)?) => {
+        $crate::rt!($struct, $struct $($body)*)
+    };
+
+    ($ty:ty, $expr:expr) => {{
+        let value: $ty = $expr;
+        let out = $crate::to_string(&value).expect(concat!("toml: ", stringify!($ty), ": failed to encode"));
+        let decoded: $ty = $crate::from_str(out.as_str()).expect(concat!("toml: ", stringify!($ty), ": failed to decode"));
+        assert_eq!(decoded, $expr, concat!("toml: ", stringify!($ty), ": roundtrip does not match"));
+        decoded
+    }};
+}
+
+/// Encode a type as one and decode as another.
+#[inline(never)]
+pub fn transcode<T, O>(value: T) -> O
+where
+    T: Debug + PartialEq + Encode<TextMode>,
+    O: for<'de> Decode<'de, TextMode>,
+{
+    let out = crate::to_string(&value).expect("failed to encode");
+    crate::from_str(out.as_str()).expect("failed to decode")
+}
@@ -0,0 +1,150 @@
+//! The document tree which sits between TOML text and Müsli.
+//!
+//! TOML allows tables to be defined in any order and extended through dotted
+//! keys, so a document is parsed into a tree before it's decoded. Encoding
+//! similarly builds a tree, so that plain values can be written before any
+//! tables regardless of the order in which fields are encoded.
+
+use core::fmt;
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::datetime::Datetime;
+
+/// A byte range in the input that a value was parsed from.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Span {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl Span {
+    #[inline]
+    pub(crate) const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// A value together with the span it was parsed from.
+#[derive(Debug)]
+pub(crate) struct Item<'de> {
+    pub(crate) value: Value<'de>,
+    pub(crate) span: Span,
+}
+
+impl<'de> Item<'de> {
+    #[inline]
+    pub(crate) const fn new(value: Value<'de>, span: Span) -> Self {
+        Self { value, span }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum Value<'de> {
+    String(Cow<'de, str>),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Datetime(Datetime),
+    Array(Array<'de>),
+    Table(Table<'de>),
+}
+
+impl Value<'_> {
+    pub(crate) fn kind(&self) -> Kind {
+        match self {
+            Value::String(..) => Kind::String,
+            Value::Integer(..) => Kind::Integer,
+            Value::Float(..) => Kind::Float,
+            Value::Boolean(..) => Kind::Boolean,
+            Value::Datetime(..) => Kind::Datetime,
+            Value::Array(..) => Kind::Array,
+            Value::Table(..) => Kind::Table,
+        }
+    }
+}
+
+/// The kind of a value, used in diagnostics.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Kind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Datetime,
+    Array,
+    Table,
+}
+
+impl fmt::Display for Kind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::String => write!(f, "string"),
+            Kind::Integer => write!(f, "integer"),
+            Kind::Float => write!(f, "float"),
+            Kind::Boolean => write!(f, "boolean"),
+            Kind::Datetime => write!(f, "datetime"),
+            Kind::Array => write!(f, "array"),
+            Kind::Table => write!(f, "table"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Array<'de> {
+    pub(crate) items: Vec<Item<'de>>,
+    /// If the array was defined through `[[header]]` sections, in which case
+    /// it can be extended by further sections.
+    pub(crate) of_tables: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Entry<'de> {
+    pub(crate) key: Cow<'de, str>,
+    pub(crate) key_span: Span,
+    pub(crate) item: Item<'de>,
+}
+
+/// How a table was defined, which determines how it may be extended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TableKind {
+    /// An inline table `{ .. }`, which is sealed once parsed.
+    Inline,
+    /// A table defined by a `[header]`.
+    Header,
+    /// A table which has been implicitly created by a header such as
+    /// `[a.b]`, which may still be defined by its own header.
+    Implicit,
+    /// A table created by a dotted key such as `a.b = 1`.
+    Dotted,
+}
+
+/// A table, which keeps its entries in the order they were defined.
+#[derive(Debug)]
+pub(crate) struct Table<'de> {
+    pub(crate) entries: Vec<Entry<'de>>,
+    pub(crate) kind: TableKind,
+}
+
+impl<'de> Table<'de> {
+    #[inline]
+    pub(crate) const fn new(kind: TableKind) -> Self {
+        Self {
+            entries: Vec::new(),
+            kind,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self, key: &str) -> Option<&mut Item<'de>> {
+        let entry = self.entries.iter_mut().find(|e| e.key == key)?;
+        Some(&mut entry.item)
+    }
+
+    #[inline]
+    pub(crate) fn contains_key(&self, key: &str) -> bool {
+        self.entries.iter().any(|e| e.key == key)
+    }
+}
@@ -14,6 +14,7 @@ use musli_common::reader::{SliceReader, SliceUnderflow};
 use musli_storage::de::StorageDecoder;
 use musli_storage::int::Variable;
 
+use crate::either::Either;
 use crate::error::ErrorKind;
 use crate::value::{Number, Value};
 use crate::AsValueDecoder;
@@ -54,7 +55,8 @@ where
     type Error = E;
     type Buffer = AsValueDecoder<E>;
     type Some = Self;
-    type Pack = StorageDecoder<SliceReader<'de>, Variable, Variable, E>;
+    type Pack =
+        Either<StorageDecoder<SliceReader<'de>, Variable, Variable, E>, IterValueDecoder<'de, E>>;
     type Sequence = IterValueDecoder<'de, E>;
     type Tuple = IterValueDecoder<'de, E>;
     type Map = IterValuePairsDecoder<'de, E>;
@@ -279,13 +281,19 @@ where
 
     #[cfg(feature = "alloc")]
     #[inline]
-    fn decode_option<'buf, C>(self, cx: &mut C) -> Result<Option<Self::Some>, C::Error>
+    fn decode_option<'buf, C>(self, _: &mut C) -> Result<Option<Self::Some>, C::Error>
     where
         C: Context<'buf, Input = Self::Error>,
     {
-        ensure!(self, cx, hint, ExpectedOption(hint), Value::Option(option) => {
-            Ok(option.as_ref().map(|some| ValueDecoder::new(some)))
-        })
+        // Formats which do not have a dedicated optional type, such as JSON,
+        // CBOR or MessagePack, buffer `None` as a unit and `Some` as the value
+        // itself. Mismatched types are still reported when the value is
+        // decoded.
+        match self.value {
+            Value::Option(option) => Ok(option.as_ref().map(|some| ValueDecoder::new(some))),
+            Value::Unit => Ok(None),
+            _ => Ok(Some(self)),
+        }
     }
 
     #[cfg(feature = "alloc")]
@@ -294,9 +302,14 @@ where
     where
         C: Context<'buf, Input = Self::Error>,
     {
-        ensure!(self, cx, hint, ExpectedPack(hint), Value::Bytes(pack) => {
-            Ok(StorageDecoder::new(SliceReader::new(pack)))
-        })
+        match self.value {
+            Value::Bytes(pack) => Ok(Either::Left(StorageDecoder::new(SliceReader::new(pack)))),
+            Value::Sequence(pack) => Ok(Either::Right(IterValueDecoder::new(pack))),
+            value => {
+                let hint = value.type_hint();
+                Err(cx.report(ErrorKind::ExpectedPack(hint)))
+            }
+        }
     }
 
     #[cfg(feature = "alloc")]
@@ -423,7 +436,6 @@ where
 }
 
 /// A decoder over a simple value iterator.
-
 pub struct IterValueDecoder<'de, E> {
     iter: slice::Iter<'de, Value>,
     _marker: marker::PhantomData<E>,
@@ -0,0 +1,398 @@
+//! A decoder which is one of two decoders sharing an error type.
+//!
+//! This is used to decode packs from a [`Value`], which are either stored as
+//! raw bytes when decoded from a format with opaque packs, or as a sequence of
+//! values when decoded from a format which stores packs as sequences.
+//!
+//! [`Value`]: crate::Value
+
+use core::fmt;
+
+use musli::de::{
+    AsDecoder, Decoder, NumberVisitor, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder,
+    SizeHint, TypeHint, ValueVisitor, VariantDecoder, Visitor,
+};
+use musli::mode::Mode;
+use musli::Context;
+
+/// One of two decoders.
+pub enum Either<A, B> {
+    /// The first kind of decoder.
+    Left(A),
+    /// The second kind of decoder.
+    Right(B),
+}
+
+macro_rules! either {
+    ($self:expr, $d:ident => Either($expr:expr)) => {
+        match $self {
+            Either::Left($d) => Ok(Either::Left($expr)),
+            Either::Right($d) => Ok(Either::Right($expr)),
+        }
+    };
+
+    ($self:expr, $d:ident => Option($expr:expr)) => {
+        match $self {
+            Either::Left($d) => Ok($expr.map(Either::Left)),
+            Either::Right($d) => Ok($expr.map(Either::Right)),
+        }
+    };
+
+    ($self:expr, $d:ident => $expr:expr) => {
+        match $self {
+            Either::Left($d) => $expr,
+            Either::Right($d) => $expr,
+        }
+    };
+}
+
+macro_rules! forward {
+    ($($method:ident -> $ty:ty),* $(,)?) => {
+        $(
+            #[inline]
+            fn $method<'buf, C>(self, cx: &mut C) -> Result<$ty, C::Error>
+            where
+                C: Context<'buf, Input = Self::Error>,
+            {
+                either!(self, d => d.$method(cx))
+            }
+        )*
+    };
+}
+
+#[musli::decoder]
+impl<'de, A, B> Decoder<'de> for Either<A, B>
+where
+    A: Decoder<'de>,
+    B: Decoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type Buffer = Either<A::Buffer, B::Buffer>;
+    type Some = Either<A::Some, B::Some>;
+    type Pack = Either<A::Pack, B::Pack>;
+    type Sequence = Either<A::Sequence, B::Sequence>;
+    type Tuple = Either<A::Tuple, B::Tuple>;
+    type Map = Either<A::Map, B::Map>;
+    type Struct = Either<A::Struct, B::Struct>;
+    type Variant = Either<A::Variant, B::Variant>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        either!(self, d => d.expecting(f))
+    }
+
+    #[inline]
+    fn type_hint<'buf, C>(&mut self, cx: &mut C) -> Result<TypeHint, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.type_hint(cx))
+    }
+
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, cx: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_buffer::<M, C>(cx)?))
+    }
+
+    forward! {
+        decode_unit -> (),
+        decode_bool -> bool,
+        decode_char -> char,
+        decode_u8 -> u8,
+        decode_u16 -> u16,
+        decode_u32 -> u32,
+        decode_u64 -> u64,
+        decode_u128 -> u128,
+        decode_i8 -> i8,
+        decode_i16 -> i16,
+        decode_i32 -> i32,
+        decode_i64 -> i64,
+        decode_i128 -> i128,
+        decode_usize -> usize,
+        decode_isize -> isize,
+        decode_f32 -> f32,
+        decode_f64 -> f64,
+    }
+
+    #[inline]
+    fn decode_number<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: NumberVisitor<'de, 'buf, C>,
+    {
+        either!(self, d => d.decode_number(cx, visitor))
+    }
+
+    #[inline]
+    fn decode_array<'buf, C, const N: usize>(self, cx: &mut C) -> Result<[u8; N], C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.decode_array(cx))
+    }
+
+    #[inline]
+    fn decode_bytes<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, [u8]>,
+    {
+        either!(self, d => d.decode_bytes(cx, visitor))
+    }
+
+    #[inline]
+    fn decode_string<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: ValueVisitor<'de, 'buf, C, str>,
+    {
+        either!(self, d => d.decode_string(cx, visitor))
+    }
+
+    #[inline]
+    fn decode_option<'buf, C>(self, cx: &mut C) -> Result<Option<Self::Some>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Option(d.decode_option(cx)?))
+    }
+
+    #[inline]
+    fn decode_pack<'buf, C>(self, cx: &mut C) -> Result<Self::Pack, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_pack(cx)?))
+    }
+
+    #[inline]
+    fn decode_sequence<'buf, C>(self, cx: &mut C) -> Result<Self::Sequence, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_sequence(cx)?))
+    }
+
+    #[inline]
+    fn decode_tuple<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Tuple, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_tuple(cx, len)?))
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_map(cx)?))
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_struct(cx, len)?))
+    }
+
+    #[inline]
+    fn decode_variant<'buf, C>(self, cx: &mut C) -> Result<Self::Variant, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.decode_variant(cx)?))
+    }
+
+    #[inline]
+    fn decode_any<'buf, C, V>(self, cx: &mut C, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+        V: Visitor<'de, Error = Self::Error>,
+    {
+        either!(self, d => d.decode_any(cx, visitor))
+    }
+}
+
+impl<A, B> AsDecoder for Either<A, B>
+where
+    A: AsDecoder,
+    B: AsDecoder<Error = A::Error>,
+{
+    type Error = A::Error;
+    type Decoder<'this> = Either<A::Decoder<'this>, B::Decoder<'this>> where Self: 'this;
+
+    #[inline]
+    fn as_decoder<'buf, C>(&self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.as_decoder(cx)?))
+    }
+}
+
+impl<'de, A, B> PackDecoder<'de> for Either<A, B>
+where
+    A: PackDecoder<'de>,
+    B: PackDecoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type Decoder<'this> = Either<A::Decoder<'this>, B::Decoder<'this>> where Self: 'this;
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Decoder<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.next(cx)?))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.end(cx))
+    }
+}
+
+impl<'de, A, B> SequenceDecoder<'de> for Either<A, B>
+where
+    A: SequenceDecoder<'de>,
+    B: SequenceDecoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type Decoder<'this> = Either<A::Decoder<'this>, B::Decoder<'this>> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        either!(self, d => d.size_hint())
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Option(d.next(cx)?))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.end(cx))
+    }
+}
+
+impl<'de, A, B> PairsDecoder<'de> for Either<A, B>
+where
+    A: PairsDecoder<'de>,
+    B: PairsDecoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type Decoder<'this> = Either<A::Decoder<'this>, B::Decoder<'this>> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        either!(self, d => d.size_hint())
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Option(d.next(cx)?))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.end(cx))
+    }
+}
+
+impl<'de, A, B> PairDecoder<'de> for Either<A, B>
+where
+    A: PairDecoder<'de>,
+    B: PairDecoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type First<'this> = Either<A::First<'this>, B::First<'this>> where Self: 'this;
+    type Second = Either<A::Second, B::Second>;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, cx: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.first(cx)?))
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, cx: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.second(cx)?))
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.skip_second(cx))
+    }
+}
+
+impl<'de, A, B> VariantDecoder<'de> for Either<A, B>
+where
+    A: VariantDecoder<'de>,
+    B: VariantDecoder<'de, Error = A::Error>,
+{
+    type Error = A::Error;
+    type Tag<'this> = Either<A::Tag<'this>, B::Tag<'this>> where Self: 'this;
+    type Variant<'this> = Either<A::Variant<'this>, B::Variant<'this>> where Self: 'this;
+
+    #[inline]
+    fn tag<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Tag<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.tag(cx)?))
+    }
+
+    #[inline]
+    fn variant<'buf, C>(&mut self, cx: &mut C) -> Result<Self::Variant<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => Either(d.variant(cx)?))
+    }
+
+    #[inline]
+    fn skip_variant<'buf, C>(&mut self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.skip_variant(cx))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        either!(self, d => d.end(cx))
+    }
+}
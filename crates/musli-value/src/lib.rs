@@ -17,6 +17,7 @@ extern crate alloc;
 extern crate std;
 
 mod de;
+mod either;
 mod en;
 mod error;
 mod value;
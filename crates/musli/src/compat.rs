@@ -1,14 +1,12 @@
 //! Wrapper types which ensures that a given field is encoded or decoded as a
 //! certain kind of value.
 
-#[cfg(feature = "alloc")]
-mod alloc;
 mod packed;
 
 pub use self::packed::Packed;
 
-use crate::de::{Decode, Decoder, SequenceDecoder};
-use crate::en::{Encode, Encoder, SequenceEncoder};
+use crate::de::{Decode, DecodeBytes, Decoder, SequenceDecoder};
+use crate::en::{Encode, EncodeBytes, Encoder, SequenceEncoder};
 use crate::mode::Mode;
 use crate::Context;
 
@@ -95,15 +93,23 @@ where
 /// for `Vec<T>` since it would conflict with `Vec<u8>` which is generalized to
 /// encode as a sequence.
 ///
+/// This is supported for any type implementing [`EncodeBytes`] and
+/// [`DecodeBytes`], such as `[u8; N]`, `&[u8]`, [`Vec<u8>`], [`VecDeque<u8>`],
+/// `Box<[u8]>` and `Cow<[u8]>`. Fields can also be encoded as bytes without
+/// the wrapper through the `#[musli(bytes)]` attribute.
+///
 /// [`Vec`]: std::vec::Vec
+/// [`Vec<u8>`]: std::vec::Vec
 /// [`VecDeque`]: std::collections::VecDeque
+/// [`VecDeque<u8>`]: std::collections::VecDeque
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Bytes<T>(pub T);
 
-impl<const N: usize, M> Encode<M> for Bytes<[u8; N]>
+impl<M, T> Encode<M> for Bytes<T>
 where
     M: Mode,
+    T: EncodeBytes<M>,
 {
     #[inline]
     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
@@ -111,13 +117,14 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
-        encoder.encode_array(cx, self.0)
+        self.0.encode_bytes(cx, encoder)
     }
 }
 
-impl<'de, M, const N: usize> Decode<'de, M> for Bytes<[u8; N]>
+impl<'de, M, T> Decode<'de, M> for Bytes<T>
 where
     M: Mode,
+    T: DecodeBytes<'de, M>,
 {
     #[inline]
     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
@@ -125,6 +132,6 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
-        decoder.decode_array(cx).map(Self)
+        T::decode_bytes(cx, decoder).map(Self)
     }
 }
@@ -23,7 +23,7 @@ mod type_hint;
 mod value_visitor;
 mod visitor;
 
-pub use self::decode::{Decode, TraceDecode};
+pub use self::decode::{Decode, DecodeBytes, TraceDecode};
 pub use self::decoder::{
     AsDecoder, Decoder, PackDecoder, PairDecoder, PairsDecoder, SequenceDecoder, VariantDecoder,
 };
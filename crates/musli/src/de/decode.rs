@@ -1,4 +1,5 @@
-use crate::de::Decoder;
+use crate::de::{Decoder, PackDecoder};
+use crate::internal::array::PartialArray;
 use crate::mode::{DefaultMode, Mode};
 use crate::Context;
 
@@ -16,6 +17,53 @@ where
     where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>;
+
+    /// Decode an array of this type.
+    ///
+    /// By default this decodes the array from a tuple, but it is overridden by
+    /// `u8` so that byte arrays are decoded through
+    /// [`Decoder::decode_array`].
+    #[doc(hidden)]
+    #[inline]
+    fn decode_array_of<'buf, C, D, const N: usize>(
+        cx: &mut C,
+        decoder: D,
+    ) -> Result<[Self; N], C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let mut unpack = decoder.decode_tuple(cx, N)?;
+        // Already decoded elements are dropped if decoding of a subsequent
+        // element fails or panics.
+        let mut array = PartialArray::<Self, N>::new();
+
+        for _ in 0..N {
+            let value = unpack.next(cx).and_then(|v| Self::decode(cx, v))?;
+            array.push(value);
+        }
+
+        unpack.end(cx)?;
+        Ok(array.into_array())
+    }
+}
+
+/// Trait governing how types are decoded as bytes.
+///
+/// This is used by [`Bytes`] and the `#[musli(bytes)]` field attribute for
+/// types which would otherwise be decoded as a sequence, such as [`Vec<u8>`].
+///
+/// [`Bytes`]: crate::compat::Bytes
+/// [`Vec<u8>`]: std::vec::Vec
+pub trait DecodeBytes<'de, M = DefaultMode>: Sized
+where
+    M: Mode,
+{
+    /// Decode the given input as bytes.
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>;
 }
 
 /// Trait governing how types are decoded specifically for tracing.
@@ -913,6 +913,150 @@ pub trait Decoder<'de>: Sized {
         )))
     }
 
+    /// Decode a 32-bit unsigned integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers decode
+    /// it in the same way as [`Decoder::decode_u32`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Decode, Decoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: u32,
+    /// }
+    ///
+    /// impl<'de, M> Decode<'de, M> for MyType where M: Mode {
+    ///     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = D::Error>,
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         Ok(Self {
+    ///             data: decoder.decode_fixed_u32(cx)?,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_fixed_u32<'buf, C>(self, cx: &mut C) -> Result<u32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_u32(cx)
+    }
+
+    /// Decode a 64-bit unsigned integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers decode
+    /// it in the same way as [`Decoder::decode_u64`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Decode, Decoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: u64,
+    /// }
+    ///
+    /// impl<'de, M> Decode<'de, M> for MyType where M: Mode {
+    ///     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = D::Error>,
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         Ok(Self {
+    ///             data: decoder.decode_fixed_u64(cx)?,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_fixed_u64<'buf, C>(self, cx: &mut C) -> Result<u64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_u64(cx)
+    }
+
+    /// Decode a 32-bit signed integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers decode
+    /// it in the same way as [`Decoder::decode_i32`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Decode, Decoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: i32,
+    /// }
+    ///
+    /// impl<'de, M> Decode<'de, M> for MyType where M: Mode {
+    ///     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = D::Error>,
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         Ok(Self {
+    ///             data: decoder.decode_fixed_i32(cx)?,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_fixed_i32<'buf, C>(self, cx: &mut C) -> Result<i32, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_i32(cx)
+    }
+
+    /// Decode a 64-bit signed integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers decode
+    /// it in the same way as [`Decoder::decode_i64`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Decode, Decoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: i64,
+    /// }
+    ///
+    /// impl<'de, M> Decode<'de, M> for MyType where M: Mode {
+    ///     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = D::Error>,
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         Ok(Self {
+    ///             data: decoder.decode_fixed_i64(cx)?,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_fixed_i64<'buf, C>(self, cx: &mut C) -> Result<i64, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decode_i64(cx)
+    }
+
     /// Decode an unknown number using a visitor that can handle arbitrary
     /// precision numbers.
     #[inline]
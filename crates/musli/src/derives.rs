@@ -58,7 +58,8 @@
 //! What this means is that if we want to serialize `Person` using named fields,
 //! we can simply turn on the `Json` mode for our given serializer. If we want
 //! to revert back to the default behavior and use indexed fields we can instead
-//! use [`DefaultMode`].
+//! use the default mode of the encoding, which for `musli-json` is
+//! [`TextMode`].
 //!
 //! ```
 //! # use musli::{Encode, Decode};
@@ -119,6 +120,26 @@
 //! }
 //! ```
 //!
+//! The built-in modes can be targeted the same way. Since [`TextMode`] is the
+//! default mode of human-readable formats like `musli-json`, this makes
+//! `Person` use string field names there:
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::mode::TextMode;
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(mode = TextMode, default_field_name = "name")]
+//! struct Person<'a> {
+//!     name: &'a str,
+//!     age: u32,
+//! }
+//!
+//! let named = musli_json::to_string(&Person { name: "Aristotle", age: 62 })?;
+//! assert_eq!(named, r#"{"name":"Aristotle","age":62}"#);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+//!
 //! <br>
 //!
 //! #### `#[musli(encode_only)]`
@@ -126,8 +147,8 @@
 //! The attributes only apply when implementing the `Encode` trait.
 //!
 //! An example where this is useful is if you want to apply `#[musli(packed)]`
-//! in a different mode, but only for encoding, since decoding packed types is
-//! not supported for enums.
+//! in a different mode, but only for encoding, since decoding packed enums
+//! requires a format which supports [`Decoder::decode_buffer`].
 //!
 //! ```
 //! use musli::mode::DefaultMode;
@@ -248,6 +269,48 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(rename_all = "..")]`
+//!
+//! This renames the fields of a struct, or the variants of an enum, by
+//! converting the name of the field or variant to the given case. Using it
+//! implies that fields or variants are tagged by name, and it takes precedence
+//! over `default_field_name` and `default_variant_name`. An explicit
+//! `#[musli(rename = ..)]` still takes precedence over it.
+//!
+//! The supported conventions are `"lowercase"`, `"UPPERCASE"`,
+//! `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
+//! `"kebab-case"` and `"SCREAMING-KEBAB-CASE"`. Runs of uppercase characters
+//! are treated as a single word, so `HTTPServer` becomes `http_server` in
+//! `"snake_case"`.
+//!
+//! Like other attributes this can be used in combination with a [mode](#modes)
+//! so that only a specific encoding uses the converted names.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! enum Json {}
+//! impl musli::mode::Mode for Json {}
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(mode = Json, rename_all = "camelCase")]
+//! struct Struct {
+//!     // Encoded as `userId` in the `Json` mode.
+//!     user_id: u32,
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(rename_all = "kebab-case")]
+//! enum Enum {
+//!     // Encoded as `first-variant`.
+//!     FirstVariant {
+//!         field: u32,
+//!     },
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! #### `#[musli(transparent)]`
 //!
 //! This can only be used on types which have a single field. It will cause that
@@ -372,6 +435,204 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(deny_unknown_fields)]`
+//!
+//! By default unknown fields are skipped while decoding if the format supports
+//! it. With this attribute any unknown field instead causes decoding to fail.
+//! When used on an enum it applies to the fields of every variant.
+//!
+//! This can be used in combination with a [mode](#modes) so that only certain
+//! encodings are strict. It cannot be combined with `#[musli(flatten)]` fields,
+//! since they are decoded from the fields which are unknown to the container.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Person {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, Decode)]
+//! #[musli(default_field_name = "name", deny_unknown_fields)]
+//! struct Strict {
+//!     name: String,
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let data = musli_json::to_vec(&Person { name: String::from("Aristotle") })?;
+//! assert!(musli_json::from_slice::<Strict>(b"{\"name\":\"Aristotle\",\"age\":61}").is_err());
+//! assert_eq!(musli_json::from_slice::<Strict>(&data)?.name, "Aristotle");
+//! # Ok(()) }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(reserved(..))]`
+//!
+//! Reserve a list of tags which can no longer be used. This is useful when a
+//! field or variant has been removed, to ensure that its tag isn't
+//! accidentally reused with a different meaning.
+//!
+//! Any field or variant whose tag or alias collides with a reserved tag is an
+//! error. When used on a struct, fields with a reserved tag are skipped while
+//! decoding even if `#[musli(deny_unknown_fields)]` is used.
+//!
+//! When used on an enum it reserves variant tags, which is only checked when
+//! deriving. Since a variant cannot be skipped, a reserved variant tag is
+//! decoded like any other unknown variant tag: as the variant marked with
+//! `#[musli(default)]` if there is one, and as an error otherwise.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! #[derive(Debug, Decode)]
+//! #[musli(default_field_name = "name", deny_unknown_fields, reserved("age", "email"))]
+//! struct Strict {
+//!     name: String,
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let data = musli_json::to_vec(&Person { name: String::from("Aristotle"), age: 61 })?;
+//! assert_eq!(musli_json::from_slice::<Strict>(&data)?.name, "Aristotle");
+//! # Ok(()) }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(from = <type>)]`, `#[musli(try_from = <type>)]` and `#[musli(into = <type>)]`
+//!
+//! These cause a type to be encoded and decoded through another type, which
+//! unlike `#[musli(transparent)]` doesn't require the type to be a single
+//! field wrapper.
+//!
+//! * `#[musli(into = <type>)]` encodes the type by converting a reference to it
+//!   into the given type through [`Into`], and then encoding that.
+//! * `#[musli(from = <type>)]` decodes the given type and converts it through
+//!   [`From`].
+//! * `#[musli(try_from = <type>)]` decodes the given type and converts it
+//!   through [`TryFrom`]. Conversion errors are reported through
+//!   [`Context::custom`].
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(try_from = String, into = String)]
+//! struct Email(String);
+//!
+//! impl TryFrom<String> for Email {
+//!     type Error = &'static str;
+//!
+//!     fn try_from(value: String) -> Result<Self, Self::Error> {
+//!         if !value.contains('@') {
+//!             return Err("invalid email");
+//!         }
+//!
+//!         Ok(Email(value))
+//!     }
+//! }
+//!
+//! impl From<&Email> for String {
+//!     fn from(value: &Email) -> Self {
+//!         value.0.clone()
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let data = musli_json::to_vec(&Email(String::from("root@example.com")))?;
+//! assert_eq!(data, b"\"root@example.com\"");
+//! assert!(musli_json::from_slice::<Email>(b"\"root\"").is_err());
+//! # Ok(()) }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(validate = <path>)]`
+//!
+//! This calls the given function with a reference to the type once it has been
+//! decoded. The function must have a signature like `fn(&T) -> Result<(), E>`,
+//! and any error it returns is reported through [`Context::custom`].
+//!
+//! This is useful for checking invariants which involve multiple fields.
+//! Individual fields can be validated using the field attribute of the same
+//! name.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(validate = Range::check)]
+//! struct Range {
+//!     min: u32,
+//!     max: u32,
+//! }
+//!
+//! impl Range {
+//!     fn check(&self) -> Result<(), &'static str> {
+//!         if self.min > self.max {
+//!             return Err("min must not be greater than max");
+//!         }
+//!
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(remote = <path>)]`
+//!
+//! This derives encoding and decoding for a foreign type, which can't implement
+//! [`Encode`] or [`Decode`] directly since neither the type nor the traits are
+//! defined in the current crate.
+//!
+//! The type being derived is a definition which mirrors the fields or variants
+//! of the remote type. Instead of implementing [`Encode`] or [`Decode`], this
+//! generates the `encode` and `decode` functions expected by the
+//! `#[musli(with = <path>)]` field attribute as associated functions of the
+//! definition.
+//!
+//! Fields of the remote type need to be public, unless they are accessed
+//! through `#[musli(getter = <path>)]`. Since the encode and decode functions
+//! are generic over the mode, this cannot be combined with `#[musli(mode =
+//! <path>)]`.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! mod foreign {
+//!     pub struct Duration {
+//!         pub secs: u64,
+//!         pub nanos: u32,
+//!     }
+//! }
+//!
+//! #[allow(dead_code)]
+//! #[derive(Encode, Decode)]
+//! #[musli(remote = foreign::Duration)]
+//! struct DurationDef {
+//!     secs: u64,
+//!     nanos: u32,
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Timeout {
+//!     #[musli(with = DurationDef)]
+//!     duration: foreign::Duration,
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! ## Enum attributes
 //!
 //! <br>
@@ -396,6 +657,33 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(discriminant)]`
+//!
+//! This causes variants to be tagged with their declared discriminant, so that
+//! reordering variants doesn't change how they are encoded. The enum is encoded
+//! as a plain integer of the type specified through `#[repr(..)]`, or `isize` if
+//! none is specified, without wrapping it in a variant.
+//!
+//! Variants cannot have fields. If a variant is marked with
+//! `#[musli(default)]`, it is used when decoding a discriminant which doesn't
+//! match any variant.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(discriminant)]
+//! #[repr(u8)]
+//! enum Op {
+//!     Add = 1,
+//!     Sub = 7,
+//!     #[musli(default)]
+//!     Unknown = 255,
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! ## Variant attributes
 //!
 //! *Variant attributes* are attributes which apply to each individual variant
@@ -433,6 +721,28 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(alias = ..)]`
+//!
+//! Accept an additional tag for the variant when decoding. This can be
+//! specified multiple times and is useful when a variant has been renamed but
+//! older data still uses its previous tag. Aliases are never used when
+//! encoding, and an alias which collides with the tag or alias of another
+//! variant is an error.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(default_variant_name = "name")]
+//! enum Animal {
+//!     #[musli(alias = "Doggo", alias = "Pupper")]
+//!     Dog { name: String },
+//!     Cat { name: String },
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! #### `#[musli(name_type = ..)]`
 //!
 //! This indicates which type any contained `#[musli(tag = ..)]` attributes
@@ -498,6 +808,35 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(rename_all = "..")]`
+//!
+//! This renames the fields in the current variant by converting their names to
+//! the given case. It supports the same conventions as the
+//! [container attribute](#container-attributes).
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(rename_all = "snake_case")]
+//! enum Enum {
+//!     // The variant is encoded as `variant` and its field as `fieldOne`.
+//!     #[musli(rename_all = "camelCase")]
+//!     Variant {
+//!         field_one: u32,
+//!     },
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(reserved(..))]`
+//!
+//! Reserve a list of field tags in the current variant. This works the same
+//! way as the [container attribute](#container-attributes) does for structs.
+//!
+//! <br>
+//!
 //! #### `#[musli(transparent)]`
 //!
 //! This can only be used on variants which have a single field. It will cause
@@ -572,6 +911,26 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(alias = ..)]`
+//!
+//! Accept an additional tag for the field when decoding. Like
+//! `#[musli(rename = ..)]` this can take any value which can be used as a tag,
+//! and it can be specified multiple times. Aliases are never used when
+//! encoding, and an alias which collides with the tag or alias of another
+//! field is an error.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! struct Struct {
+//!     #[musli(rename = 2, alias = 0)]
+//!     field: u32,
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! #### `#[musli(with = <path>)]`
 //!
 //! This specifies the path to a module to use instead of the fields default
@@ -688,11 +1047,41 @@
 //!
 //! <br>
 //!
-//! #### `#[musli(default)]`
+//! #### `#[musli(bytes)]`
+//!
+//! This encodes and decodes the field as bytes through [`EncodeBytes`] and
+//! [`DecodeBytes`], the same way as wrapping it in [`Bytes`] does. This is
+//! useful for types such as `Vec<u8>` which would otherwise be encoded as a
+//! sequence of individual numbers.
+//!
+//! Supported types include `Vec<u8>`, `&[u8]`, `Box<[u8]>`, `Cow<[u8]>`,
+//! `VecDeque<u8>` and `[u8; N]`. References and [`Cow`] borrow from the
+//! source when the format supports it.
+//!
+//! ```
+//! use std::borrow::Cow;
+//!
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! struct Packet<'a> {
+//!     #[musli(bytes)]
+//!     header: Vec<u8>,
+//!     #[musli(bytes)]
+//!     body: Cow<'a, [u8]>,
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(default [= <path>])]`
 //!
 //! This constructs the field using [Default::default] in case it's not
 //! available. This is only used when a field is missing during decoding.
 //!
+//! If a path is specified, the function it refers to is called without
+//! arguments to construct the field instead.
+//!
 //! ```
 //! use musli::{Encode, Decode};
 //!
@@ -701,6 +1090,37 @@
 //!     name: String,
 //!     #[musli(default)]
 //!     age: Option<u32>,
+//!     #[musli(default = default_country)]
+//!     country: String,
+//! }
+//!
+//! fn default_country() -> String {
+//!     String::from("Greece")
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(skip)]`
+//!
+//! This causes the field to never be encoded, and to always be constructed
+//! using its default value when decoding. The default value is constructed
+//! using [Default::default], or the function specified through
+//! `#[musli(default = <path>)]`.
+//!
+//! Skipped fields do not count towards the fields of a
+//! `#[musli(transparent)]` type.
+//!
+//! ```
+//! use std::cell::Cell;
+//!
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! struct Person {
+//!     name: String,
+//!     #[musli(skip)]
+//!     visits: Cell<u32>,
 //! }
 //! ```
 //!
@@ -722,6 +1142,75 @@
 //! }
 //! ```
 //!
+//! <br>
+//!
+//! #### `#[musli(validate = <path>)]`
+//!
+//! This calls the given function with a reference to the field right after it
+//! has been decoded. The function must have a signature like `fn(&T) ->
+//! Result<(), E>`, and any error it returns is reported through
+//! [`Context::custom`] while the field is still being decoded. So a context
+//! which keeps track of where errors occur reports the exact field which
+//! failed validation.
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! struct Person {
+//!     #[musli(validate = non_empty)]
+//!     name: String,
+//! }
+//!
+//! fn non_empty(value: &str) -> Result<(), &'static str> {
+//!     if value.is_empty() {
+//!         return Err("must not be empty");
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! #### `#[musli(getter = <path>)]`
+//!
+//! This is used in combination with `#[musli(remote = <path>)]` to access a
+//! field of the remote type which is not public. The function is called with a
+//! reference to the remote type and must return a reference to the field, like
+//! `fn(&Remote) -> &T`.
+//!
+//! Since private fields can't be constructed, this is only useful for types
+//! which are encoded.
+//!
+//! ```
+//! use musli::Encode;
+//!
+//! mod foreign {
+//!     pub struct Account {
+//!         pub id: u32,
+//!         name: String,
+//!     }
+//!
+//!     impl Account {
+//!         pub fn name(&self) -> &String {
+//!             &self.name
+//!         }
+//!     }
+//! }
+//!
+//! #[allow(dead_code)]
+//! #[derive(Encode)]
+//! #[musli(remote = foreign::Account)]
+//! struct AccountDef {
+//!     id: u32,
+//!     #[musli(getter = foreign::Account::name)]
+//!     name: String,
+//! }
+//! ```
+//!
+//! <br>
+//!
 //! #### `#[musli(trace)]`
 //!
 //! This causes the field to use the [`TraceDecode`] / [`TraceEncode`] when
@@ -756,6 +1245,54 @@
 //!
 //! <br>
 //!
+//! #### `#[musli(flatten)]`
+//!
+//! This causes the fields of the field to be encoded inline in the enclosing
+//! struct, rather than as a nested struct. The type of the field must
+//! implement [`EncodeFields`], which is implemented by `#[derive(Encode)]` for
+//! structs which are not `packed` or `transparent`, and for maps.
+//!
+//! Flattening a map collects all the fields which have not been decoded by
+//! the enclosing struct or by an earlier flattened field. So fields should
+//! use names, and a flattened map should come last.
+//!
+//! Decoding buffers the struct through [`Decoder::decode_buffer`], so like
+//! [internally tagged enums](#internally-tagged) this is only supported by
+//! formats which are *self descriptive*. Flattened fields cannot be used in a
+//! `packed` or `transparent` container, and decoding a flattened field whose
+//! type has flattened fields of its own results in an error.
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Metadata {
+//!     id: u32,
+//!     owner: String,
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! #[musli(default_field_name = "name")]
+//! struct Message {
+//!     body: String,
+//!     #[musli(flatten)]
+//!     metadata: Metadata,
+//!     #[musli(flatten)]
+//!     other: HashMap<String, String>,
+//! }
+//! ```
+//!
+//! In JSON, a `Message` would be represented as:
+//!
+//! ```json
+//! {"body": "...", "id": 42, "owner": "..."}
+//! ```
+//!
+//! <br>
+//!
 //! # Enum representations
 //!
 //! Müsli supports the following enum representations, which mimics the ones
@@ -824,14 +1361,47 @@
 //! a map for the field corresponding to the `tag`, and then use this to
 //! determine which decoder implementation to call.
 //!
+//! <br>
+//!
+//! ## Untagged
+//!
+//! ```rust
+//! # use musli::{Encode, Decode};
+//! #[derive(Encode, Decode)]
+//! #[musli(packed)]
+//! enum Message {
+//!     Request { id: String, method: String },
+//!     #[musli(transparent)]
+//!     Text(String),
+//! }
+//! ```
+//!
+//! When an enum is `#[musli(packed)]` only the content of the variant is
+//! encoded, without any tag indicating which variant it is.
+//!
+//! Decoding is only supported by formats which can buffer a value through
+//! [`Decoder::decode_buffer`]. The buffered value is decoded as each variant in
+//! the order in which they are declared and the first one which succeeds is
+//! used. If no variant matches, the error lists why each variant failed.
+//!
+//! Since variants are tried in order, variants which match a subset of the
+//! input of another variant should be declared after it.
+//!
+//! [`Bytes`]: crate::compat::Bytes
+//! [`Context::custom`]: crate::Context::custom
+//! [`Cow`]: std::borrow::Cow
 //! [`Decode`]: crate::Decode
+//! [`DecodeBytes`]: crate::de::DecodeBytes
 //! [`Decoder::decode_buffer`]: crate::Decoder::decode_buffer
 //! [`Decoder::decode_variant`]: crate::Decoder::decode_variant
 //! [`Decoder`]: crate::Decoder
 //! [`DefaultMode`]: crate::mode::DefaultMode
 //! [`Encode`]: crate::Encode
+//! [`EncodeBytes`]: crate::en::EncodeBytes
+//! [`EncodeFields`]: crate::en::EncodeFields
 //! [`Encoder::encode_variant`]: crate::Encoder::encode_variant
 //! [`Encoder`]: crate::Encoder
+//! [`TextMode`]: crate::mode::TextMode
 //! [`TraceDecode`]: crate::de::TraceDecode
 //! [`TraceEncode`]: crate::en::TraceEncode
 //! [default mode]: crate::mode::DefaultMode
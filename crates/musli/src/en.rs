@@ -19,5 +19,5 @@
 mod encode;
 mod encoder;
 
-pub use self::encode::{Encode, TraceEncode};
+pub use self::encode::{Encode, EncodeBytes, EncodeFields, TraceEncode};
 pub use self::encoder::{Encoder, PairEncoder, PairsEncoder, SequenceEncoder, VariantEncoder};
@@ -1,4 +1,4 @@
-use crate::en::Encoder;
+use crate::en::{Encoder, PairsEncoder, SequenceEncoder};
 use crate::mode::{DefaultMode, Mode};
 use crate::Context;
 
@@ -14,6 +14,51 @@ where
     where
         C: Context<'buf, Input = E::Error>,
         E: Encoder;
+
+    /// Encode an array of this type.
+    ///
+    /// By default this encodes the array as a tuple, but it is overridden by
+    /// `u8` so that byte arrays are encoded through
+    /// [`Encoder::encode_array`].
+    #[doc(hidden)]
+    #[inline]
+    fn encode_array_of<'buf, C, E, const N: usize>(
+        array: &[Self; N],
+        cx: &mut C,
+        encoder: E,
+    ) -> Result<E::Ok, C::Error>
+    where
+        Self: Sized,
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let mut tuple = encoder.encode_tuple(cx, N)?;
+
+        for value in array {
+            let encoder = tuple.next(cx)?;
+            Encode::<M>::encode(value, cx, encoder)?;
+        }
+
+        tuple.end(cx)
+    }
+}
+
+/// Trait governing how types are encoded as bytes.
+///
+/// This is used by [`Bytes`] and the `#[musli(bytes)]` field attribute for
+/// types which would otherwise be encoded as a sequence, such as [`Vec<u8>`].
+///
+/// [`Bytes`]: crate::compat::Bytes
+/// [`Vec<u8>`]: std::vec::Vec
+pub trait EncodeBytes<M = DefaultMode>
+where
+    M: Mode,
+{
+    /// Encode the given output as bytes.
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder;
 }
 
 /// Trait governing how types are encoded specifically for tracing.
@@ -35,6 +80,28 @@ where
         E: Encoder;
 }
 
+/// Trait governing how the fields of a type are encoded into an enclosing
+/// struct.
+///
+/// This is used by the `#[musli(flatten)]` field attribute, and is implemented
+/// by `#[derive(Encode)]` for structs which are not packed or transparent. It
+/// is also implemented for maps, which allows them to be flattened into their
+/// enclosing struct as well.
+pub trait EncodeFields<M = DefaultMode>
+where
+    M: Mode,
+{
+    /// The number of fields which will be encoded by
+    /// [`encode_fields`][EncodeFields::encode_fields].
+    fn fields_len(&self) -> usize;
+
+    /// Encode the fields of this type into the given pairs encoder.
+    fn encode_fields<'buf, C, E>(&self, cx: &mut C, encoder: &mut E) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: PairsEncoder;
+}
+
 impl<T, M> Encode<M> for &T
 where
     T: ?Sized + Encode<M>,
@@ -64,3 +131,18 @@ where
         T::encode(*self, cx, encoder)
     }
 }
+
+impl<T, M> EncodeBytes<M> for &T
+where
+    T: ?Sized + EncodeBytes<M>,
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        T::encode_bytes(*self, cx, encoder)
+    }
+}
@@ -766,6 +766,142 @@ pub trait Encoder: Sized {
         )))
     }
 
+    /// Encode a 32-bit unsigned integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers encode
+    /// it in the same way as [`Encoder::encode_u32`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Encode, Encoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: u32,
+    /// }
+    ///
+    /// impl<M> Encode<M> for MyType where M: Mode {
+    ///     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = E::Error>,
+    ///         E: Encoder
+    ///     {
+    ///         encoder.encode_fixed_u32(cx, self.data)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_fixed_u32<'buf, C>(self, cx: &mut C, value: u32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u32(cx, value)
+    }
+
+    /// Encode a 64-bit unsigned integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers encode
+    /// it in the same way as [`Encoder::encode_u64`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Encode, Encoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: u64,
+    /// }
+    ///
+    /// impl<M> Encode<M> for MyType where M: Mode {
+    ///     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = E::Error>,
+    ///         E: Encoder
+    ///     {
+    ///         encoder.encode_fixed_u64(cx, self.data)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_fixed_u64<'buf, C>(self, cx: &mut C, value: u64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_u64(cx, value)
+    }
+
+    /// Encode a 32-bit signed integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers encode
+    /// it in the same way as [`Encoder::encode_i32`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Encode, Encoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: i32,
+    /// }
+    ///
+    /// impl<M> Encode<M> for MyType where M: Mode {
+    ///     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = E::Error>,
+    ///         E: Encoder
+    ///     {
+    ///         encoder.encode_fixed_i32(cx, self.data)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_fixed_i32<'buf, C>(self, cx: &mut C, value: i32) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i32(cx, value)
+    }
+
+    /// Encode a 64-bit signed integer which prefers to be encoded with a
+    /// fixed width.
+    ///
+    /// Formats which do not have a fixed-width encoding for integers encode
+    /// it in the same way as [`Encoder::encode_i64`], which is what this does by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Context, Encode, Encoder, Mode};
+    ///
+    /// struct MyType {
+    ///     data: i64,
+    /// }
+    ///
+    /// impl<M> Encode<M> for MyType where M: Mode {
+    ///     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    ///     where
+    ///         C: Context<'buf, Input = E::Error>,
+    ///         E: Encoder
+    ///     {
+    ///         encoder.encode_fixed_i64(cx, self.data)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_fixed_i64<'buf, C>(self, cx: &mut C, value: i64) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.encode_i64(cx, value)
+    }
+
     /// Encode fixed-length array.
     ///
     /// # Examples
@@ -1,9 +1,14 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 #[cfg(feature = "std")]
+mod ffi;
+#[cfg(feature = "std")]
 mod net;
+mod range;
+mod time;
 mod tuples;
 
+use core::cell::{Cell, RefCell};
 use core::ffi::CStr;
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
@@ -11,12 +16,12 @@ use core::num::{
 };
 use core::sync::atomic::{
     AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
-    AtomicU64, AtomicU8, AtomicUsize,
+    AtomicU64, AtomicU8, AtomicUsize, Ordering,
 };
-use core::{fmt, marker};
+use core::{cmp, fmt, marker};
 
-use crate::de::{Decode, Decoder, ValueVisitor, VariantDecoder};
-use crate::en::{Encode, Encoder, VariantEncoder};
+use crate::de::{Decode, DecodeBytes, Decoder, ValueVisitor, VariantDecoder};
+use crate::en::{Encode, EncodeBytes, Encoder, VariantEncoder};
 use crate::mode::Mode;
 use crate::Context;
 
@@ -78,6 +83,22 @@ where
 
 macro_rules! atomic_impl {
     ($ty:ty) => {
+        /// The value is loaded using [`Ordering::Relaxed`] when encoded, so
+        /// encoding doesn't synchronize with concurrent modifications.
+        impl<M> Encode<M> for $ty
+        where
+            M: Mode,
+        {
+            #[inline]
+            fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: Encoder,
+            {
+                Encode::<M>::encode(&self.load(Ordering::Relaxed), cx, encoder)
+            }
+        }
+
         impl<'de, M> Decode<'de, M> for $ty
         where
             M: Mode,
@@ -105,6 +126,71 @@ atomic_impl!(AtomicU64);
 atomic_impl!(AtomicU8);
 atomic_impl!(AtomicUsize);
 
+impl<M, T> Encode<M> for Cell<T>
+where
+    M: Mode,
+    T: Copy + Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        T::encode(&self.get(), cx, encoder)
+    }
+}
+
+impl<'de, M, T> Decode<'de, M> for Cell<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        T::decode(cx, decoder).map(Cell::new)
+    }
+}
+
+/// Encoding a [`RefCell`] results in an error if it is currently mutably
+/// borrowed.
+impl<M, T> Encode<M> for RefCell<T>
+where
+    M: Mode,
+    T: ?Sized + Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        match self.try_borrow() {
+            Ok(value) => T::encode(&*value, cx, encoder),
+            Err(error) => Err(cx.message(error)),
+        }
+    }
+}
+
+impl<'de, M, T> Decode<'de, M> for RefCell<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        T::decode(cx, decoder).map(RefCell::new)
+    }
+}
+
 macro_rules! non_zero {
     ($ty:ty) => {
         impl<M> Encode<M> for $ty
@@ -175,9 +261,10 @@ where
     }
 }
 
-impl<M, const N: usize> Encode<M> for [u8; N]
+impl<M, T, const N: usize> Encode<M> for [T; N]
 where
     M: Mode,
+    T: Encode<M>,
 {
     #[inline]
     fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
@@ -185,13 +272,14 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
-        encoder.encode_array(cx, *self)
+        T::encode_array_of(self, cx, encoder)
     }
 }
 
-impl<'de, M, const N: usize> Decode<'de, M> for [u8; N]
+impl<'de, M, T, const N: usize> Decode<'de, M> for [T; N]
 where
     M: Mode,
+    T: Decode<'de, M>,
 {
     #[inline]
     fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
@@ -199,7 +287,7 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
-        decoder.decode_array(cx)
+        T::decode_array_of(cx, decoder)
     }
 }
 
@@ -235,6 +323,59 @@ macro_rules! impl_number {
     };
 }
 
+impl<M> Encode<M> for u8
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_u8(cx, *self)
+    }
+
+    #[inline]
+    fn encode_array_of<'buf, C, E, const N: usize>(
+        array: &[Self; N],
+        cx: &mut C,
+        encoder: E,
+    ) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_array(cx, *array)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for u8
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        decoder.decode_u8(cx)
+    }
+
+    #[inline]
+    fn decode_array_of<'buf, C, D, const N: usize>(
+        cx: &mut C,
+        decoder: D,
+    ) -> Result<[Self; N], C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        decoder.decode_array(cx)
+    }
+}
+
 impl<M> Encode<M> for bool
 where
     M: Mode,
@@ -293,7 +434,6 @@ where
 
 impl_number!(usize, decode_usize, encode_usize);
 impl_number!(isize, decode_isize, encode_isize);
-impl_number!(u8, decode_u8, encode_u8);
 impl_number!(u16, decode_u16, encode_u16);
 impl_number!(u32, decode_u32, encode_u32);
 impl_number!(u64, decode_u64, encode_u64);
@@ -399,6 +539,62 @@ where
     }
 }
 
+impl<M> EncodeBytes<M> for [u8]
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_bytes(cx, self)
+    }
+}
+
+impl<'de, M> DecodeBytes<'de, M> for &'de [u8]
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        <&[u8] as Decode<M>>::decode(cx, decoder)
+    }
+}
+
+impl<const N: usize, M> EncodeBytes<M> for [u8; N]
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_array(cx, *self)
+    }
+}
+
+impl<'de, M, const N: usize> DecodeBytes<'de, M> for [u8; N]
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        decoder.decode_array(cx)
+    }
+}
+
 impl<T, M> Encode<M> for Option<T>
 where
     M: Mode,
@@ -487,6 +683,54 @@ where
     }
 }
 
+/// [`cmp::Ordering`] is encoded as a signed integer, where `-1` is
+/// [`Less`][cmp::Ordering::Less], `0` is [`Equal`][cmp::Ordering::Equal] and
+/// `1` is [`Greater`][cmp::Ordering::Greater].
+impl<M> Encode<M> for cmp::Ordering
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_i8(cx, *self as i8)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for cmp::Ordering
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        match decoder.decode_i8(cx)? {
+            -1 => Ok(cmp::Ordering::Less),
+            0 => Ok(cmp::Ordering::Equal),
+            1 => Ok(cmp::Ordering::Greater),
+            value => Err(cx.message(InvalidOrdering(value))),
+        }
+    }
+}
+
+struct InvalidOrdering(i8);
+
+impl fmt::Display for InvalidOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ordering: expected one of -1, 0 or 1, but got `{}`",
+            self.0
+        )
+    }
+}
+
 impl<T, M> Encode<M> for Wrapping<T>
 where
     M: Mode,
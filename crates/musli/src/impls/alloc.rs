@@ -5,18 +5,27 @@ use core::hash::{BuildHasher, Hash};
 
 use alloc::borrow::{Cow, ToOwned};
 use alloc::boxed::Box;
-use alloc::collections::{BTreeMap, BinaryHeap, VecDeque};
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
 use alloc::ffi::CString;
+use alloc::rc::{Rc, Weak as RcWeak};
 use alloc::string::String;
+use alloc::sync::{Arc, Weak as ArcWeak};
 use alloc::vec::Vec;
 
 #[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
 
+use crate::compat::Sequence;
 use crate::de::{
-    Decode, Decoder, PairDecoder, PairsDecoder, SequenceDecoder, TraceDecode, ValueVisitor,
+    Decode, DecodeBytes, Decoder, PairDecoder, PairsDecoder, SequenceDecoder, TraceDecode,
+    ValueVisitor,
+};
+use crate::en::{
+    Encode, EncodeBytes, EncodeFields, Encoder, PairEncoder, PairsEncoder, SequenceEncoder,
+    TraceEncode,
 };
-use crate::en::{Encode, Encoder, PairEncoder, PairsEncoder, SequenceEncoder, TraceEncode};
 use crate::internal::size_hint;
 use crate::mode::Mode;
 use crate::Context;
@@ -78,34 +87,176 @@ where
     }
 }
 
-impl<M> Encode<M> for Box<str>
-where
-    M: Mode,
-{
-    #[inline]
-    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
-    where
-        C: Context<'buf, Input = E::Error>,
-        E: Encoder,
-    {
-        Encode::<M>::encode(self.as_ref(), cx, encoder)
-    }
+macro_rules! smart_pointer {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<M, T> Encode<M> for $ty<T>
+            where
+                M: Mode,
+                T: Encode<M>,
+            {
+                #[inline]
+                fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+                where
+                    C: Context<'buf, Input = E::Error>,
+                    E: Encoder,
+                {
+                    T::encode(&**self, cx, encoder)
+                }
+            }
+
+            impl<M, T> TraceEncode<M> for $ty<T>
+            where
+                M: Mode,
+                T: TraceEncode<M>,
+            {
+                #[inline]
+                fn trace_encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+                where
+                    C: Context<'buf, Input = E::Error>,
+                    E: Encoder,
+                {
+                    T::trace_encode(&**self, cx, encoder)
+                }
+            }
+
+            impl<'de, M, T> Decode<'de, M> for $ty<T>
+            where
+                M: Mode,
+                T: Decode<'de, M>,
+            {
+                #[inline]
+                fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+                where
+                    C: Context<'buf, Input = D::Error>,
+                    D: Decoder<'de>,
+                {
+                    Ok($ty::new(T::decode(cx, decoder)?))
+                }
+            }
+
+            impl<'de, M, T> TraceDecode<'de, M> for $ty<T>
+            where
+                M: Mode,
+                T: TraceDecode<'de, M>,
+            {
+                #[inline]
+                fn trace_decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+                where
+                    C: Context<'buf, Input = D::Error>,
+                    D: Decoder<'de>,
+                {
+                    Ok($ty::new(T::trace_decode(cx, decoder)?))
+                }
+            }
+
+            impl<M> Encode<M> for $ty<str>
+            where
+                M: Mode,
+            {
+                #[inline]
+                fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+                where
+                    C: Context<'buf, Input = E::Error>,
+                    E: Encoder,
+                {
+                    Encode::<M>::encode(self.as_ref(), cx, encoder)
+                }
+            }
+
+            impl<'de, M> Decode<'de, M> for $ty<str>
+            where
+                M: Mode,
+            {
+                #[inline]
+                fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+                where
+                    C: Context<'buf, Input = D::Error>,
+                    D: Decoder<'de>,
+                {
+                    Ok(<String as Decode<M>>::decode(cx, decoder)?.into())
+                }
+            }
+
+            impl<M, T> Encode<M> for $ty<[T]>
+            where
+                M: Mode,
+                T: Encode<M>,
+            {
+                #[inline]
+                fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+                where
+                    C: Context<'buf, Input = E::Error>,
+                    E: Encoder,
+                {
+                    Encode::<M>::encode(&Sequence(self.as_ref()), cx, encoder)
+                }
+            }
+
+            impl<'de, M, T> Decode<'de, M> for $ty<[T]>
+            where
+                M: Mode,
+                T: Decode<'de, M>,
+            {
+                #[inline]
+                fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+                where
+                    C: Context<'buf, Input = D::Error>,
+                    D: Decoder<'de>,
+                {
+                    Ok(<Vec<T> as Decode<M>>::decode(cx, decoder)?.into())
+                }
+            }
+        )*
+    };
 }
 
-impl<'de, M> Decode<'de, M> for Box<str>
-where
-    M: Mode,
-{
-    #[inline]
-    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
-    where
-        C: Context<'buf, Input = D::Error>,
-        D: Decoder<'de>,
-    {
-        Ok(<String as Decode<M>>::decode(cx, decoder)?.into())
-    }
+smart_pointer!(Box, Rc, Arc);
+
+macro_rules! weak {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            /// A weak reference is encoded as an optional value, which is
+            /// absent if it can no longer be upgraded.
+            impl<M, T> Encode<M> for $ty<T>
+            where
+                M: Mode,
+                T: Encode<M>,
+            {
+                #[inline]
+                fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+                where
+                    C: Context<'buf, Input = E::Error>,
+                    E: Encoder,
+                {
+                    Encode::<M>::encode(&self.upgrade(), cx, encoder)
+                }
+            }
+
+            /// Since there is no strong reference to keep the decoded value
+            /// alive, decoding a weak reference always produces an empty
+            /// reference.
+            impl<'de, M, T> Decode<'de, M> for $ty<T>
+            where
+                M: Mode,
+                T: Decode<'de, M>,
+            {
+                #[inline]
+                fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+                where
+                    C: Context<'buf, Input = D::Error>,
+                    D: Decoder<'de>,
+                {
+                    <Option<T> as Decode<M>>::decode(cx, decoder)?;
+                    Ok($ty::new())
+                }
+            }
+        )*
+    };
 }
 
+weak!(RcWeak, ArcWeak);
+
 macro_rules! cow {
     ($ty:ty, $source:ty, $decode:ident, $cx:pat, |$owned:ident| $owned_expr:expr, |$borrowed:ident| $borrowed_expr:expr, |$reference:ident| $reference_expr:expr) => {
         impl<M> Encode<M> for Cow<'_, $ty>
@@ -256,6 +407,38 @@ macro_rules! sequence {
                 Ok(out)
             }
         }
+
+        impl<M, T $(, $extra)*> TraceEncode<M> for $ty<T $(, $extra)*>
+        where
+            M: Mode,
+            T: Encode<M>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            #[inline]
+            fn trace_encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: Encoder,
+            {
+                Encode::<M>::encode(self, cx, encoder)
+            }
+        }
+
+        impl<'de, M, T $(, $extra)*> TraceDecode<'de, M> for $ty<T $(, $extra)*>
+        where
+            M: Mode,
+            T: Decode<'de, M> $(+ $trait0 $(+ $trait)*)*,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            #[inline]
+            fn trace_decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+            where
+                C: Context<'buf, Input = D::Error>,
+                D: Decoder<'de>,
+            {
+                Decode::<M>::decode(cx, decoder)
+            }
+        }
     }
 }
 
@@ -284,6 +467,8 @@ sequence!(
     seq,
     BinaryHeap::with_capacity(size_hint::cautious(seq.size_hint()))
 );
+sequence!(BTreeSet<T: Ord>, insert, seq, BTreeSet::new());
+sequence!(LinkedList<T>, push_back, seq, LinkedList::new());
 
 macro_rules! map {
     (
@@ -319,6 +504,32 @@ macro_rules! map {
             }
         }
 
+        impl<M, K, V $(, $extra)*> EncodeFields<M> for $ty<K, V $(, $extra)*>
+        where
+            M: Mode,
+            K: Encode<M>,
+            V: Encode<M>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            #[inline]
+            fn fields_len(&self) -> usize {
+                self.len()
+            }
+
+            #[inline]
+            fn encode_fields<'buf, C, E>(&self, cx: &mut C, encoder: &mut E) -> Result<(), C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: PairsEncoder,
+            {
+                for (k, v) in self {
+                    encoder.insert::<M, _, _, _>(cx, k, v)?;
+                }
+
+                Ok(())
+            }
+        }
+
         impl<'de, M, K, V $(, $extra)*> TraceEncode<M> for $ty<K, V $(, $extra)*>
         where
             M: Mode,
@@ -474,3 +685,249 @@ where
         decoder.decode_bytes(cx, Visitor)
     }
 }
+
+impl<M> EncodeBytes<M> for Vec<u8>
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_bytes(cx, self.as_slice())
+    }
+}
+
+impl<'de, M> DecodeBytes<'de, M> for Vec<u8>
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        struct Visitor;
+
+        impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, [u8]> for Visitor
+        where
+            C: Context<'buf>,
+        {
+            type Ok = Vec<u8>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "bytes")
+            }
+
+            #[inline]
+            fn visit_owned(self, _: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                Ok(bytes)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, _: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                Ok(bytes.to_vec())
+            }
+
+            #[inline]
+            fn visit_ref(self, _: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                Ok(bytes.to_vec())
+            }
+        }
+
+        decoder.decode_bytes(cx, Visitor)
+    }
+}
+
+impl<M> EncodeBytes<M> for VecDeque<u8>
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let (first, second) = self.as_slices();
+        encoder.encode_bytes_vectored(cx, &[first, second])
+    }
+}
+
+impl<'de, M> DecodeBytes<'de, M> for VecDeque<u8>
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        <Vec<u8> as DecodeBytes<M>>::decode_bytes(cx, decoder).map(VecDeque::from)
+    }
+}
+
+impl<M> EncodeBytes<M> for Box<[u8]>
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_bytes(cx, self)
+    }
+}
+
+impl<'de, M> DecodeBytes<'de, M> for Box<[u8]>
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        <Vec<u8> as DecodeBytes<M>>::decode_bytes(cx, decoder).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<M> EncodeBytes<M> for Cow<'_, [u8]>
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode_bytes<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_bytes(cx, self)
+    }
+}
+
+/// Decoding bytes into a [`Cow`] borrows them from the source if possible.
+impl<'de, M> DecodeBytes<'de, M> for Cow<'de, [u8]>
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode_bytes<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        struct Visitor;
+
+        impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, [u8]> for Visitor
+        where
+            C: Context<'buf>,
+        {
+            type Ok = Cow<'de, [u8]>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "bytes")
+            }
+
+            #[inline]
+            fn visit_owned(self, _: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                Ok(Cow::Owned(bytes))
+            }
+
+            #[inline]
+            fn visit_borrowed(self, _: &mut C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                Ok(Cow::Borrowed(bytes))
+            }
+
+            #[inline]
+            fn visit_ref(self, _: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                Ok(Cow::Owned(bytes.to_vec()))
+            }
+        }
+
+        decoder.decode_bytes(cx, Visitor)
+    }
+}
+
+/// Encoding a [`Mutex`] locks it for the duration of encoding, and results in
+/// an error if the lock is poisoned.
+#[cfg(feature = "std")]
+impl<M, T> Encode<M> for Mutex<T>
+where
+    M: Mode,
+    T: ?Sized + Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        match self.lock() {
+            Ok(value) => T::encode(&*value, cx, encoder),
+            Err(error) => Err(cx.message(error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, M, T> Decode<'de, M> for Mutex<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        T::decode(cx, decoder).map(Mutex::new)
+    }
+}
+
+/// Encoding a [`RwLock`] acquires a read lock for the duration of encoding,
+/// and results in an error if the lock is poisoned.
+#[cfg(feature = "std")]
+impl<M, T> Encode<M> for RwLock<T>
+where
+    M: Mode,
+    T: ?Sized + Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        match self.read() {
+            Ok(value) => T::encode(&*value, cx, encoder),
+            Err(error) => Err(cx.message(error)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, M, T> Decode<'de, M> for RwLock<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        T::decode(cx, decoder).map(RwLock::new)
+    }
+}
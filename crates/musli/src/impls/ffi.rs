@@ -0,0 +1,247 @@
+//! Implementations for platform strings and paths.
+//!
+//! [`OsStr`] and [`Path`] are encoded as a variant. Values which are valid
+//! UTF-8 are encoded as a string, which can be decoded on any platform. Other
+//! values are encoded as raw bytes on Unix and as WTF-16 code units on Windows,
+//! and can only be decoded on the same platform family that encoded them.
+//!
+//! In human readable modes values which are valid UTF-8 are encoded as a plain
+//! string instead, like `"/tmp/x"`. Decoding such a string relies on the
+//! format providing a [`TypeHint`] for it.
+
+use core::fmt;
+
+use alloc::string::String;
+#[cfg(any(unix, windows))]
+use alloc::vec::Vec;
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use crate::de::ValueVisitor;
+use crate::de::{Decode, Decoder, TypeHint, VariantDecoder};
+use crate::en::{Encode, Encoder, VariantEncoder};
+use crate::mode::Mode;
+use crate::Context;
+
+/// The value is valid UTF-8 and encoded as a string.
+const UTF8: usize = 0;
+/// The value is encoded as raw Unix bytes.
+const UNIX: usize = 1;
+/// The value is encoded as WTF-16 code units.
+const WINDOWS: usize = 2;
+
+impl<M> Encode<M> for OsStr
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        if let Some(string) = self.to_str() {
+            if M::is_human_readable() {
+                return encoder.encode_string(cx, string);
+            }
+
+            return encoder
+                .encode_variant(cx)?
+                .insert::<M, _, _, _>(cx, UTF8, string);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let mut variant = encoder.encode_variant(cx)?;
+            variant
+                .tag(cx)
+                .and_then(|e| Encode::<M>::encode(&UNIX, cx, e))?;
+            variant
+                .variant(cx)
+                .and_then(|e| e.encode_bytes(cx, self.as_bytes()))?;
+            variant.end(cx)
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+
+            let wide = self.encode_wide().collect::<Vec<u16>>();
+
+            encoder
+                .encode_variant(cx)?
+                .insert::<M, _, _, _>(cx, WINDOWS, &wide)
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = encoder;
+            Err(cx.message(NotUtf8))
+        }
+    }
+}
+
+impl<M> Encode<M> for OsString
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        Encode::<M>::encode(self.as_os_str(), cx, encoder)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for OsString
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, mut decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        if M::is_human_readable() {
+            if let TypeHint::String(..) = decoder.type_hint(cx)? {
+                let string = <String as Decode<M>>::decode(cx, decoder)?;
+                return Ok(OsString::from(string));
+            }
+        }
+
+        let mut variant = decoder.decode_variant(cx)?;
+
+        let tag = variant
+            .tag(cx)
+            .and_then(|v| <usize as Decode<M>>::decode(cx, v))?;
+
+        let this = match tag {
+            UTF8 => {
+                let string = variant
+                    .variant(cx)
+                    .and_then(|v| <String as Decode<M>>::decode(cx, v))?;
+                OsString::from(string)
+            }
+            #[cfg(unix)]
+            UNIX => {
+                use std::os::unix::ffi::OsStringExt;
+
+                struct Visitor;
+
+                impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, [u8]> for Visitor
+                where
+                    C: Context<'buf>,
+                {
+                    type Ok = OsString;
+
+                    #[inline]
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "unix bytes")
+                    }
+
+                    #[inline]
+                    fn visit_owned(self, _: &mut C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                        Ok(OsString::from_vec(bytes))
+                    }
+
+                    #[inline]
+                    fn visit_ref(self, _: &mut C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                        Ok(OsString::from_vec(bytes.to_vec()))
+                    }
+                }
+
+                variant
+                    .variant(cx)
+                    .and_then(|v| v.decode_bytes(cx, Visitor))?
+            }
+            #[cfg(windows)]
+            WINDOWS => {
+                use std::os::windows::ffi::OsStringExt;
+
+                let wide = variant
+                    .variant(cx)
+                    .and_then(|v| <Vec<u16> as Decode<M>>::decode(cx, v))?;
+                OsString::from_wide(&wide)
+            }
+            #[cfg(not(unix))]
+            UNIX => return Err(cx.message(UnsupportedPlatform("Unix"))),
+            #[cfg(not(windows))]
+            WINDOWS => return Err(cx.message(UnsupportedPlatform("Windows"))),
+            tag => return Err(cx.invalid_variant_tag("OsString", tag)),
+        };
+
+        variant.end(cx)?;
+        Ok(this)
+    }
+}
+
+impl<M> Encode<M> for Path
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        Encode::<M>::encode(self.as_os_str(), cx, encoder)
+    }
+}
+
+impl<M> Encode<M> for PathBuf
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        Encode::<M>::encode(self.as_os_str(), cx, encoder)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for PathBuf
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        Ok(PathBuf::from(<OsString as Decode<M>>::decode(cx, decoder)?))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+struct NotUtf8;
+
+#[cfg(not(any(unix, windows)))]
+impl fmt::Display for NotUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot encode a non-UTF-8 string on this platform")
+    }
+}
+
+struct UnsupportedPlatform(&'static str);
+
+impl fmt::Display for UnsupportedPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot decode a non-UTF-8 {} string on this platform",
+            self.0
+        )
+    }
+}
@@ -1,10 +1,23 @@
+//! Implementations for network addresses.
+//!
+//! In human readable modes addresses are encoded using their canonical string
+//! form, like `"127.0.0.1:80"`. Note that this does not include the flow
+//! information of a [`SocketAddrV6`].
+
+use core::fmt::{self, Write};
+use core::marker;
+use core::str::FromStr;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
-use crate::de::{Decode, Decoder, PackDecoder, VariantDecoder};
+use crate::de::{Decode, Decoder, PackDecoder, ValueVisitor, VariantDecoder};
 use crate::en::{Encode, Encoder, SequenceEncoder, VariantEncoder};
+use crate::internal::fixed::FixedString;
 use crate::mode::Mode;
 use crate::Context;
 
+/// Enough to fit `[ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255%4294967295]:65535`.
+const MAX_ADDRESS_LEN: usize = 64;
+
 impl<M> Encode<M> for Ipv4Addr
 where
     M: Mode,
@@ -15,6 +28,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         encoder.encode_array(cx, self.octets())
     }
 }
@@ -29,6 +46,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "Ipv4Addr");
+        }
+
         decoder.decode_array::<C, 4>(cx).map(Ipv4Addr::from)
     }
 }
@@ -43,6 +64,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         encoder.encode_array(cx, self.octets())
     }
 }
@@ -57,6 +82,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "Ipv6Addr");
+        }
+
         decoder.decode_array::<C, 16>(cx).map(Ipv6Addr::from)
     }
 }
@@ -71,6 +100,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         let variant = encoder.encode_variant(cx)?;
 
         match self {
@@ -90,6 +123,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "IpAddr");
+        }
+
         let mut variant = decoder.decode_variant(cx)?;
 
         let this = match variant
@@ -126,6 +163,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         let mut pack = encoder.encode_pack(cx)?;
         pack.push::<M, _, _>(cx, self.ip())?;
         pack.push::<M, _, _>(cx, self.port())?;
@@ -143,6 +184,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "SocketAddrV4");
+        }
+
         let mut unpack = decoder.decode_pack(cx)?;
         let ip = unpack
             .next(cx)
@@ -165,6 +210,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         let mut pack = encoder.encode_pack(cx)?;
         pack.push::<M, _, _>(cx, self.ip())?;
         pack.push::<M, _, _>(cx, self.port())?;
@@ -184,6 +233,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "SocketAddrV6");
+        }
+
         let mut unpack = decoder.decode_pack(cx)?;
         let ip = unpack
             .next(cx)
@@ -212,6 +265,10 @@ where
         C: Context<'buf, Input = E::Error>,
         E: Encoder,
     {
+        if M::is_human_readable() {
+            return encode_string(cx, encoder, self);
+        }
+
         let variant = encoder.encode_variant(cx)?;
 
         match self {
@@ -231,6 +288,10 @@ where
         C: Context<'buf, Input = D::Error>,
         D: Decoder<'de>,
     {
+        if M::is_human_readable() {
+            return decode_string(cx, decoder, "SocketAddr");
+        }
+
         let mut variant = decoder.decode_variant(cx)?;
 
         let this = match variant
@@ -256,3 +317,70 @@ where
         Ok(this)
     }
 }
+
+/// Encode an address using its canonical string form.
+fn encode_string<'buf, C, E, T>(cx: &mut C, encoder: E, value: &T) -> Result<E::Ok, C::Error>
+where
+    C: Context<'buf, Input = E::Error>,
+    E: Encoder,
+    T: fmt::Display,
+{
+    let mut string = FixedString::<MAX_ADDRESS_LEN>::new();
+
+    if write!(string, "{value}").is_err() {
+        return Err(cx.message(format_args!("{value}: failed to format address")));
+    }
+
+    encoder.encode_string(cx, string.as_str())
+}
+
+/// Decode an address from its canonical string form.
+fn decode_string<'de, 'buf, C, D, T>(
+    cx: &mut C,
+    decoder: D,
+    type_name: &'static str,
+) -> Result<T, C::Error>
+where
+    C: Context<'buf, Input = D::Error>,
+    D: Decoder<'de>,
+    T: FromStr,
+{
+    struct Visitor<T>(&'static str, marker::PhantomData<T>);
+
+    impl<'de, 'buf, C, T> ValueVisitor<'de, 'buf, C, str> for Visitor<T>
+    where
+        C: Context<'buf>,
+        T: FromStr,
+    {
+        type Ok = T;
+
+        #[inline]
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} string", self.0)
+        }
+
+        #[inline]
+        fn visit_ref(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error> {
+            match string.parse() {
+                Ok(value) => Ok(value),
+                Err(..) => Err(cx.message(InvalidAddress {
+                    type_name: self.0,
+                    string,
+                })),
+            }
+        }
+    }
+
+    decoder.decode_string(cx, Visitor(type_name, marker::PhantomData))
+}
+
+struct InvalidAddress<'a> {
+    type_name: &'static str,
+    string: &'a str,
+}
+
+impl fmt::Display for InvalidAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: invalid address `{}`", self.type_name, self.string)
+    }
+}
@@ -0,0 +1,187 @@
+//! Implementations for ranges.
+//!
+//! Decoding a [`Range`] or a [`RangeInclusive`] whose start is greater than its
+//! end is an error. Note that a [`RangeInclusive`] is encoded using only its
+//! bounds, so a range which has been exhausted through iteration is no longer
+//! exhausted once it has been decoded.
+
+use core::fmt;
+use core::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::de::{Decode, Decoder, PackDecoder, VariantDecoder};
+use crate::en::{Encode, Encoder, SequenceEncoder, VariantEncoder};
+use crate::mode::Mode;
+use crate::Context;
+
+macro_rules! range {
+    (
+        $ty:ident $(: $bound:path)?,
+        |$this:ident| [$($get:expr),*],
+        |$($field:ident),*| $build:expr
+        $(, reject if $invalid:expr)?
+    ) => {
+        impl<M, T> Encode<M> for $ty<T>
+        where
+            M: Mode,
+            T: Encode<M>,
+        {
+            #[inline]
+            fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+            where
+                C: Context<'buf, Input = E::Error>,
+                E: Encoder,
+            {
+                let $this = self;
+                let mut pack = encoder.encode_pack(cx)?;
+                $(pack.push::<M, _, _>(cx, $get)?;)*
+                pack.end(cx)
+            }
+        }
+
+        impl<'de, M, T> Decode<'de, M> for $ty<T>
+        where
+            M: Mode,
+            T: Decode<'de, M> $(+ $bound)?,
+        {
+            #[inline]
+            fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+            where
+                C: Context<'buf, Input = D::Error>,
+                D: Decoder<'de>,
+            {
+                let mut unpack = decoder.decode_pack(cx)?;
+                $(let $field = unpack.next(cx).and_then(|v| T::decode(cx, v))?;)*
+                unpack.end(cx)?;
+
+                $(
+                    if $invalid {
+                        return Err(cx.message(StartAfterEnd(stringify!($ty))));
+                    }
+                )?
+
+                Ok($build)
+            }
+        }
+    };
+}
+
+range! {
+    Range: PartialOrd,
+    |this| [&this.start, &this.end],
+    |start, end| Range { start, end },
+    reject if start > end
+}
+
+range! {
+    RangeInclusive: PartialOrd,
+    |this| [this.start(), this.end()],
+    |start, end| RangeInclusive::new(start, end),
+    reject if start > end
+}
+
+range! {
+    RangeFrom,
+    |this| [&this.start],
+    |start| RangeFrom { start }
+}
+
+range! {
+    RangeTo,
+    |this| [&this.end],
+    |end| RangeTo { end }
+}
+
+range! {
+    RangeToInclusive,
+    |this| [&this.end],
+    |end| RangeToInclusive { end }
+}
+
+impl<M> Encode<M> for RangeFull
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        encoder.encode_unit(cx)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for RangeFull
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        decoder.decode_unit(cx)?;
+        Ok(..)
+    }
+}
+
+impl<M, T> Encode<M> for Bound<T>
+where
+    M: Mode,
+    T: Encode<M>,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let variant = encoder.encode_variant(cx)?;
+
+        match self {
+            Bound::Included(value) => variant.insert::<M, _, _, _>(cx, 0usize, value),
+            Bound::Excluded(value) => variant.insert::<M, _, _, _>(cx, 1usize, value),
+            Bound::Unbounded => variant.insert::<M, _, _, _>(cx, 2usize, ()),
+        }
+    }
+}
+
+impl<'de, M, T> Decode<'de, M> for Bound<T>
+where
+    M: Mode,
+    T: Decode<'de, M>,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let mut variant = decoder.decode_variant(cx)?;
+
+        let this = match variant
+            .tag(cx)
+            .and_then(|v| <usize as Decode<M>>::decode(cx, v))?
+        {
+            0 => Bound::Included(variant.variant(cx).and_then(|v| T::decode(cx, v))?),
+            1 => Bound::Excluded(variant.variant(cx).and_then(|v| T::decode(cx, v))?),
+            2 => {
+                variant.variant(cx).and_then(|v| v.decode_unit(cx))?;
+                Bound::Unbounded
+            }
+            tag => return Err(cx.invalid_variant_tag("Bound", tag)),
+        };
+
+        variant.end(cx)?;
+        Ok(this)
+    }
+}
+
+struct StartAfterEnd(&'static str);
+
+impl fmt::Display for StartAfterEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: start is greater than end", self.0)
+    }
+}
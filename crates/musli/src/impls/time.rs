@@ -0,0 +1,390 @@
+//! Implementations for time types.
+//!
+//! [`Duration`] is encoded as a tuple of seconds and sub-second nanoseconds,
+//! and [`SystemTime`] as a tuple of a signed number of seconds relative to
+//! [`UNIX_EPOCH`] and a positive number of nanoseconds added to it.
+//!
+//! In human readable modes both are instead encoded as a string of fractional
+//! seconds, like `"1.5"` or `"-0.000000001"`.
+//!
+//! [`SystemTime`]: std::time::SystemTime
+//! [`UNIX_EPOCH`]: std::time::UNIX_EPOCH
+
+use core::fmt::{self, Write};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::de::{Decode, Decoder, PackDecoder, ValueVisitor};
+use crate::en::{Encode, Encoder, SequenceEncoder};
+use crate::internal::fixed::FixedString;
+use crate::mode::Mode;
+use crate::Context;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Number of digits used to represent sub-second nanoseconds.
+const NANOS_DIGITS: usize = 9;
+
+/// Enough to fit `-18446744073709551615.999999999`.
+const MAX_SECONDS_LEN: usize = 32;
+
+impl<M> Encode<M> for Duration
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        if M::is_human_readable() {
+            let seconds = Seconds {
+                negative: false,
+                secs: self.as_secs(),
+                nanos: self.subsec_nanos(),
+            };
+
+            return seconds.encode(cx, encoder);
+        }
+
+        let mut tuple = encoder.encode_tuple(cx, 2)?;
+        tuple.push::<M, _, _>(cx, self.as_secs())?;
+        tuple.push::<M, _, _>(cx, self.subsec_nanos())?;
+        tuple.end(cx)
+    }
+}
+
+impl<'de, M> Decode<'de, M> for Duration
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        if M::is_human_readable() {
+            let seconds = Seconds::decode(cx, decoder, "Duration")?;
+
+            if seconds.negative {
+                return Err(cx.message(OutOfRange {
+                    type_name: "Duration",
+                    seconds,
+                }));
+            }
+
+            return Ok(Duration::new(seconds.secs, seconds.nanos));
+        }
+
+        let mut unpack = decoder.decode_tuple(cx, 2)?;
+        let secs = unpack
+            .next(cx)
+            .and_then(|v| <u64 as Decode<M>>::decode(cx, v))?;
+        let nanos = unpack
+            .next(cx)
+            .and_then(|v| <u32 as Decode<M>>::decode(cx, v))?;
+        unpack.end(cx)?;
+
+        if nanos >= NANOS_PER_SEC {
+            return Err(cx.message(InvalidNanos {
+                type_name: "Duration",
+                nanos,
+            }));
+        }
+
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<M> Encode<M> for SystemTime
+where
+    M: Mode,
+{
+    #[inline]
+    fn encode<'buf, C, E>(&self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let (negative, offset) = match self.duration_since(UNIX_EPOCH) {
+            Ok(offset) => (false, offset),
+            Err(error) => (true, error.duration()),
+        };
+
+        let seconds = Seconds {
+            negative,
+            secs: offset.as_secs(),
+            nanos: offset.subsec_nanos(),
+        };
+
+        if M::is_human_readable() {
+            return seconds.encode(cx, encoder);
+        }
+
+        // Nanoseconds are always added to the number of seconds, so negative
+        // offsets with a sub-second component are rounded down.
+        let (secs, nanos) = match (seconds.negative, seconds.nanos) {
+            (false, nanos) => (i128::from(seconds.secs), nanos),
+            (true, 0) => (-i128::from(seconds.secs), 0),
+            (true, nanos) => (-i128::from(seconds.secs) - 1, NANOS_PER_SEC - nanos),
+        };
+
+        let Ok(secs) = i64::try_from(secs) else {
+            return Err(cx.message(OutOfRange {
+                type_name: "SystemTime",
+                seconds,
+            }));
+        };
+
+        let mut tuple = encoder.encode_tuple(cx, 2)?;
+        tuple.push::<M, _, _>(cx, secs)?;
+        tuple.push::<M, _, _>(cx, nanos)?;
+        tuple.end(cx)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, M> Decode<'de, M> for SystemTime
+where
+    M: Mode,
+{
+    #[inline]
+    fn decode<'buf, C, D>(cx: &mut C, decoder: D) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        let seconds = if M::is_human_readable() {
+            Seconds::decode(cx, decoder, "SystemTime")?
+        } else {
+            let mut unpack = decoder.decode_tuple(cx, 2)?;
+            let secs = unpack
+                .next(cx)
+                .and_then(|v| <i64 as Decode<M>>::decode(cx, v))?;
+            let nanos = unpack
+                .next(cx)
+                .and_then(|v| <u32 as Decode<M>>::decode(cx, v))?;
+            unpack.end(cx)?;
+
+            if nanos >= NANOS_PER_SEC {
+                return Err(cx.message(InvalidNanos {
+                    type_name: "SystemTime",
+                    nanos,
+                }));
+            }
+
+            match (secs < 0, nanos) {
+                (false, nanos) => Seconds {
+                    negative: false,
+                    secs: secs.unsigned_abs(),
+                    nanos,
+                },
+                (true, 0) => Seconds {
+                    negative: true,
+                    secs: secs.unsigned_abs(),
+                    nanos: 0,
+                },
+                (true, nanos) => Seconds {
+                    negative: true,
+                    secs: secs.unsigned_abs() - 1,
+                    nanos: NANOS_PER_SEC - nanos,
+                },
+            }
+        };
+
+        let offset = Duration::new(seconds.secs, seconds.nanos);
+
+        let time = if seconds.negative {
+            UNIX_EPOCH.checked_sub(offset)
+        } else {
+            UNIX_EPOCH.checked_add(offset)
+        };
+
+        match time {
+            Some(time) => Ok(time),
+            None => Err(cx.message(OutOfRange {
+                type_name: "SystemTime",
+                seconds,
+            })),
+        }
+    }
+}
+
+/// A signed number of seconds with nanosecond precision, which is the human
+/// readable representation of time types.
+#[derive(Debug, Clone, Copy)]
+struct Seconds {
+    negative: bool,
+    secs: u64,
+    nanos: u32,
+}
+
+impl Seconds {
+    fn encode<'buf, C, E>(self, cx: &mut C, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: Context<'buf, Input = E::Error>,
+        E: Encoder,
+    {
+        let mut string = FixedString::<MAX_SECONDS_LEN>::new();
+
+        if write!(string, "{self}").is_err() {
+            return Err(cx.message(format_args!("{self}: failed to format seconds")));
+        }
+
+        encoder.encode_string(cx, string.as_str())
+    }
+
+    fn decode<'de, 'buf, C, D>(
+        cx: &mut C,
+        decoder: D,
+        type_name: &'static str,
+    ) -> Result<Self, C::Error>
+    where
+        C: Context<'buf, Input = D::Error>,
+        D: Decoder<'de>,
+    {
+        struct Visitor(&'static str);
+
+        impl<'de, 'buf, C> ValueVisitor<'de, 'buf, C, str> for Visitor
+        where
+            C: Context<'buf>,
+        {
+            type Ok = Seconds;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "fractional seconds")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &mut C, string: &str) -> Result<Self::Ok, C::Error> {
+                match Seconds::parse(string) {
+                    Some(seconds) => Ok(seconds),
+                    None => Err(cx.message(InvalidSeconds {
+                        type_name: self.0,
+                        string,
+                    })),
+                }
+            }
+        }
+
+        decoder.decode_string(cx, Visitor(type_name))
+    }
+
+    /// Parse a string like `-1.000000001` into seconds.
+    fn parse(string: &str) -> Option<Self> {
+        let (negative, string) = match string.strip_prefix('-') {
+            Some(string) => (true, string),
+            None => (false, string),
+        };
+
+        let (secs, fraction) = match string.split_once('.') {
+            Some((secs, fraction)) => (secs, Some(fraction)),
+            None => (string, None),
+        };
+
+        let secs = parse_digits(secs)?;
+
+        let nanos = match fraction {
+            Some(fraction) if fraction.len() <= NANOS_DIGITS => {
+                let mut nanos = u32::try_from(parse_digits(fraction)?).ok()?;
+
+                for _ in fraction.len()..NANOS_DIGITS {
+                    nanos *= 10;
+                }
+
+                nanos
+            }
+            Some(..) => return None,
+            None => 0,
+        };
+
+        Some(Self {
+            negative,
+            secs,
+            nanos,
+        })
+    }
+}
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_char('-')?;
+        }
+
+        write!(f, "{}", self.secs)?;
+
+        if self.nanos != 0 {
+            let mut nanos = self.nanos;
+            let mut width = NANOS_DIGITS;
+
+            while nanos % 10 == 0 {
+                nanos /= 10;
+                width -= 1;
+            }
+
+            write!(f, ".{nanos:0width$}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a non-empty string of ascii digits.
+fn parse_digits(string: &str) -> Option<u64> {
+    if string.is_empty() || !string.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    string.parse().ok()
+}
+
+struct InvalidNanos {
+    type_name: &'static str,
+    nanos: u32,
+}
+
+impl fmt::Display for InvalidNanos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: nanoseconds `{}` out of range",
+            self.type_name, self.nanos
+        )
+    }
+}
+
+struct OutOfRange {
+    type_name: &'static str,
+    seconds: Seconds,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: `{}` seconds out of range",
+            self.type_name, self.seconds
+        )
+    }
+}
+
+struct InvalidSeconds<'a> {
+    type_name: &'static str,
+    string: &'a str,
+}
+
+impl fmt::Display for InvalidSeconds<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: invalid fractional seconds `{}`",
+            self.type_name, self.string
+        )
+    }
+}
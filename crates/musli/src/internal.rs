@@ -1 +1,3 @@
+pub(crate) mod array;
+pub(crate) mod fixed;
 pub(crate) mod size_hint;
@@ -0,0 +1,65 @@
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// A partially initialized array which keeps track of how many elements have
+/// been initialized, so that they can be dropped in case construction is
+/// aborted through an error or a panic.
+pub(crate) struct PartialArray<T, const N: usize> {
+    /// Element storage.
+    data: [MaybeUninit<T>; N],
+    /// How many elements have been initialized.
+    init: usize,
+}
+
+impl<T, const N: usize> PartialArray<T, N> {
+    /// Construct a new empty partial array.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            // SAFETY: An array of uninitialized values does not require
+            // initialization. MaybeUninit::uninit_array is not stable.
+            data: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            init: 0,
+        }
+    }
+
+    /// Push an element onto the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array is already full.
+    #[inline]
+    pub(crate) fn push(&mut self, value: T) {
+        self.data[self.init].write(value);
+        self.init += 1;
+    }
+
+    /// Convert into an initialized array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not every element has been initialized.
+    #[inline]
+    pub(crate) fn into_array(mut self) -> [T; N] {
+        assert_eq!(self.init, N, "array is not fully initialized");
+
+        // Ownership of the elements is transferred to the returned array.
+        self.init = 0;
+
+        // SAFETY: Every element has been initialized, and `[MaybeUninit<T>;
+        // N]` has the same layout as `[T; N]`.
+        unsafe { ptr::read(&self.data as *const _ as *const [T; N]) }
+    }
+}
+
+impl<T, const N: usize> Drop for PartialArray<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        let init = self.data.as_mut_ptr() as *mut T;
+
+        // SAFETY: The first `init` elements have been initialized.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(init, self.init));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt;
+use core::str;
+
+/// A fixed capacity string buffer which can be formatted into without
+/// allocating.
+pub(crate) struct FixedString<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedString<N> {
+    /// Construct a new empty string buffer.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            data: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Access the formatted string.
+    #[inline]
+    pub(crate) fn as_str(&self) -> &str {
+        // SAFETY: Only complete string slices are ever written to the buffer.
+        unsafe { str::from_utf8_unchecked(&self.data[..self.len]) }
+    }
+}
+
+impl<const N: usize> fmt::Write for FixedString<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let Some(target) = self.data.get_mut(self.len..self.len + s.len()) else {
+            return Err(fmt::Error);
+        };
+
+        target.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
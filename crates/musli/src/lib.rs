@@ -174,6 +174,10 @@
 //! | [`musli-storage`]                    | ✔ | ✔ | ✗ | ✗ |
 //! | [`musli-wire`]                       | ✔ | ✔ | ✔ | ✗ |
 //! | [`musli-descriptive`]                | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli-msgpack`]                    | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli-cbor`]                       | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli-protobuf`]                   | ✔ | ✔ | ✔ | ✗ |
+//! | [`musli-toml`]                       | ✔ | ✔ | ✔ | ✔ |
 //! | [`musli-json`][`musli-json`][^json]  | ✔ | ✔ | ✔ | ✔ |
 //!
 //! `reorder` determines whether fields must occur in exactly the order in which
@@ -285,16 +289,16 @@
 //!
 //! If a mode is not specified, an implementation will apply to all modes (`M:
 //! Mode`), if at least one mode is specified it will be implemented for all
-//! modes which are present in a model and [`DefaultMode`]. This way, an
-//! encoding which uses `DefaultMode` (which it does by default) should always
-//! work.
+//! modes which are present in a model, [`DefaultMode`] and [`TextMode`]. This
+//! way, an encoding which uses `DefaultMode` or `TextMode` (which binary and
+//! text formats respectively do by default) should always work.
 //!
 //! For more information on how to configure modes, see the [`derives`] module.
 //! Below is a simple example of how we can use two modes to provide two
 //! different kinds of serialization to a single struct.
 //!
 //! ```
-//! use musli::mode::{DefaultMode, Mode};
+//! use musli::mode::{Mode, TextMode};
 //! use musli::{Decode, Encode};
 //! use musli_json::Encoding;
 //!
@@ -309,7 +313,7 @@
 //!     teineigo: bool,
 //! }
 //!
-//! let CONFIG: Encoding<DefaultMode> = Encoding::new();
+//! let CONFIG: Encoding<TextMode> = Encoding::new();
 //! let ALT_CONFIG: Encoding<Alt> = Encoding::new().with_mode();
 //!
 //! let word = Word {
@@ -418,9 +422,14 @@
 //! [`bincode`]: https://docs.rs/bincode
 //! [`Decode`]: https://docs.rs/musli/latest/musli/de/trait.Decode.html
 //! [`DefaultMode`]: https://docs.rs/musli/latest/musli/mode/enum.DefaultMode.html
+//! [`TextMode`]: https://docs.rs/musli/latest/musli/mode/enum.TextMode.html
 //! [`derives`]: https://docs.rs/musli/latest/musli/derives/
 //! [`Encode`]: https://docs.rs/musli/latest/musli/en/trait.Encode.html
 //! [`musli-descriptive`]: https://docs.rs/musli-descriptive
+//! [`musli-msgpack`]: https://docs.rs/musli-msgpack
+//! [`musli-cbor`]: https://docs.rs/musli-cbor
+//! [`musli-protobuf`]: https://docs.rs/musli-protobuf
+//! [`musli-toml`]: https://docs.rs/musli-toml
 //! [`musli-json`]: https://docs.rs/musli-json
 //! [`musli-storage`]: https://docs.rs/musli-storage
 //! [`musli-tests`]: https://github.com/udoprog/musli/tree/main/crates/musli-tests
@@ -13,3 +13,18 @@ pub trait Mode {
 pub enum DefaultMode {}
 
 impl Mode for DefaultMode {}
+
+/// The default human readable encoding mode, used by text formats such as
+/// `musli-json`.
+///
+/// Types which customize their encoding for specific modes also implement
+/// their default encoding for this mode.
+#[derive(Clone, Copy)]
+pub enum TextMode {}
+
+impl Mode for TextMode {
+    #[inline]
+    fn is_human_readable() -> bool {
+        true
+    }
+}
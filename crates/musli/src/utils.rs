@@ -2,6 +2,14 @@
 //!
 //! [Müsli]: https://docs.rs/musli
 
+#[cfg(feature = "alloc")]
+mod flatten;
+#[cfg(feature = "alloc")]
+mod untagged;
 mod visit_owned_fn;
 
+#[cfg(feature = "alloc")]
+pub use self::flatten::{FlattenDecoder, FlattenPairDecoder, FlattenPairsDecoder};
+#[cfg(feature = "alloc")]
+pub use self::untagged::{UntaggedContext, UntaggedErrors};
 pub use self::visit_owned_fn::visit_owned_fn;
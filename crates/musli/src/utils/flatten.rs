@@ -0,0 +1,414 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use crate::de::{
+    Decoder, NumberHint, NumberVisitor, PairDecoder, PairsDecoder, SizeHint, ValueVisitor, Visitor,
+};
+use crate::error::Error;
+use crate::mode::Mode;
+use crate::never::Never;
+use crate::Context;
+
+/// A decoder used to implement `#[musli(flatten)]`.
+///
+/// This wraps a decoder produced by [`AsDecoder::as_decoder`] so that the same
+/// buffered struct can be decoded several times, once for the enclosing type
+/// and once for each flattened field. Entries which have been decoded by one
+/// pass are recorded in `claimed` and are hidden from every pass which follows
+/// it.
+///
+/// Since entries are skipped without being decoded, this must only wrap
+/// decoders over buffered values.
+///
+/// A flattened field can not itself be buffered, so decoding a flattened
+/// struct which contains flattened fields of its own is an error.
+///
+/// [`AsDecoder::as_decoder`]: crate::de::AsDecoder::as_decoder
+pub struct FlattenDecoder<'a, D> {
+    decoder: D,
+    claimed: &'a mut Vec<bool>,
+}
+
+impl<'a, D> FlattenDecoder<'a, D> {
+    /// Construct a new flattening decoder.
+    #[inline]
+    pub fn new(decoder: D, claimed: &'a mut Vec<bool>) -> Self {
+        Self { decoder, claimed }
+    }
+}
+
+impl<'a, 'de, D> Decoder<'de> for FlattenDecoder<'a, D>
+where
+    D: Decoder<'de>,
+{
+    type Error = D::Error;
+    type Buffer = Never<Self::Error>;
+    type Some = Never<Self::Error>;
+    type Pack = Never<Self::Error>;
+    type Sequence = Never<Self::Error>;
+    type Tuple = Never<Self::Error>;
+    type Map = FlattenPairsDecoder<'a, D::Map>;
+    type Struct = FlattenPairsDecoder<'a, D::Struct>;
+    type Variant = Never<Self::Error>;
+    type __UseMusliDecoderAttributeMacro = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.decoder.expecting(f)
+    }
+
+    #[inline]
+    fn decode_buffer<'buf, M, C>(self, cx: &mut C) -> Result<Self::Buffer, C::Error>
+    where
+        M: Mode,
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Err(cx.message(NestedFlatten))
+    }
+
+    #[inline]
+    fn decode_map<'buf, C>(self, cx: &mut C) -> Result<Self::Map, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let decoder = self.decoder.decode_map(cx)?;
+        Ok(FlattenPairsDecoder::new(decoder, self.claimed))
+    }
+
+    #[inline]
+    fn decode_struct<'buf, C>(self, cx: &mut C, len: usize) -> Result<Self::Struct, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        let decoder = self.decoder.decode_struct(cx, len)?;
+        Ok(FlattenPairsDecoder::new(decoder, self.claimed))
+    }
+}
+
+struct NestedFlatten;
+
+impl fmt::Display for NestedFlatten {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "decoding a flattened field which has flattened fields of its own is not supported"
+        )
+    }
+}
+
+/// Pairs decoder which skips over claimed entries.
+pub struct FlattenPairsDecoder<'a, D> {
+    decoder: D,
+    claimed: &'a mut Vec<bool>,
+    index: usize,
+}
+
+impl<'a, D> FlattenPairsDecoder<'a, D> {
+    #[inline]
+    fn new(decoder: D, claimed: &'a mut Vec<bool>) -> Self {
+        Self {
+            decoder,
+            claimed,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, 'de, D> PairsDecoder<'de> for FlattenPairsDecoder<'a, D>
+where
+    D: PairsDecoder<'de>,
+{
+    type Error = D::Error;
+
+    type Decoder<'this>
+        = FlattenPairDecoder<'this, D::Decoder<'this>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::Any
+    }
+
+    #[inline]
+    fn next<'buf, C>(&mut self, cx: &mut C) -> Result<Option<Self::Decoder<'_>>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        while self.claimed.get(self.index).copied().unwrap_or_default() {
+            let Some(mut pair) = self.decoder.next(cx)? else {
+                return Ok(None);
+            };
+
+            pair.first(cx)?.decode_any(cx, SkipKey::new())?;
+            pair.skip_second(cx)?;
+            self.index += 1;
+        }
+
+        let index = self.index;
+
+        let Some(decoder) = self.decoder.next(cx)? else {
+            return Ok(None);
+        };
+
+        self.index += 1;
+
+        Ok(Some(FlattenPairDecoder {
+            decoder,
+            claimed: self.claimed,
+            index,
+        }))
+    }
+
+    #[inline]
+    fn end<'buf, C>(self, cx: &mut C) -> Result<(), C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decoder.end(cx)
+    }
+}
+
+/// Pair decoder which claims its entry once its value is decoded.
+pub struct FlattenPairDecoder<'a, D> {
+    decoder: D,
+    claimed: &'a mut Vec<bool>,
+    index: usize,
+}
+
+impl<'a, 'de, D> PairDecoder<'de> for FlattenPairDecoder<'a, D>
+where
+    D: PairDecoder<'de>,
+{
+    type Error = D::Error;
+
+    type First<'this>
+        = D::First<'this>
+    where
+        Self: 'this;
+
+    type Second = D::Second;
+
+    #[inline]
+    fn first<'buf, C>(&mut self, cx: &mut C) -> Result<Self::First<'_>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decoder.first(cx)
+    }
+
+    #[inline]
+    fn second<'buf, C>(self, cx: &mut C) -> Result<Self::Second, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        if self.claimed.len() <= self.index {
+            self.claimed.resize(self.index + 1, false);
+        }
+
+        self.claimed[self.index] = true;
+        self.decoder.second(cx)
+    }
+
+    #[inline]
+    fn skip_second<'buf, C>(self, cx: &mut C) -> Result<bool, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        self.decoder.skip_second(cx)
+    }
+}
+
+/// Visitor which skips over the key of a claimed entry.
+struct SkipKey<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E> SkipKey<E> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+macro_rules! skip_numbers {
+    ($($ty:ty, $method:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $method(self, _: &mut C, _: $ty) -> Result<Self::Ok, C::Error> {
+                Ok(())
+            }
+        )*
+    };
+}
+
+macro_rules! skip_visited {
+    ($($ty:ty, $method:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $method<'buf, C>(self, _: &mut C, _: $ty) -> Result<Self::Ok, C::Error>
+            where
+                C: Context<'buf, Input = Self::Error>,
+            {
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'de, E> Visitor<'de> for SkipKey<E>
+where
+    E: Error,
+{
+    type Ok = ();
+    type Error = E;
+    type String<'buf, C>
+        = Self
+    where
+        C: Context<'buf, Input = Self::Error>;
+    type Bytes<'buf, C>
+        = Self
+    where
+        C: Context<'buf, Input = Self::Error>;
+    type Number<'buf, C>
+        = Self
+    where
+        C: Context<'buf, Input = Self::Error>;
+    type __UseMusliVisitorAttributeMacro = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a key which can be skipped")
+    }
+
+    #[inline]
+    fn visit_unit<'buf, C>(self, _: &mut C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(())
+    }
+
+    skip_visited! {
+        bool, visit_bool,
+        char, visit_char,
+        u8, visit_u8,
+        u16, visit_u16,
+        u32, visit_u32,
+        u64, visit_u64,
+        u128, visit_u128,
+        i8, visit_i8,
+        i16, visit_i16,
+        i32, visit_i32,
+        i64, visit_i64,
+        i128, visit_i128,
+        usize, visit_usize,
+        isize, visit_isize,
+        f32, visit_f32,
+        f64, visit_f64,
+    }
+
+    #[inline]
+    fn visit_string<'buf, C>(
+        self,
+        _: &mut C,
+        _: SizeHint,
+    ) -> Result<Self::String<'buf, C>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn visit_bytes<'buf, C>(self, _: &mut C, _: SizeHint) -> Result<Self::Bytes<'buf, C>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+
+    #[inline]
+    fn visit_number<'buf, C>(
+        self,
+        _: &mut C,
+        _: NumberHint,
+    ) -> Result<Self::Number<'buf, C>, C::Error>
+    where
+        C: Context<'buf, Input = Self::Error>,
+    {
+        Ok(self)
+    }
+}
+
+impl<'de, 'buf, C, E> ValueVisitor<'de, 'buf, C, str> for SkipKey<E>
+where
+    C: Context<'buf>,
+{
+    type Ok = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string key")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: &mut C, _: &str) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'de, 'buf, C, E> ValueVisitor<'de, 'buf, C, [u8]> for SkipKey<E>
+where
+    C: Context<'buf>,
+{
+    type Ok = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a bytes key")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: &mut C, _: &[u8]) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'de, 'buf, C, E> NumberVisitor<'de, 'buf, C> for SkipKey<E>
+where
+    C: Context<'buf>,
+{
+    type Ok = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a number key")
+    }
+
+    skip_numbers! {
+        u8, visit_u8,
+        u16, visit_u16,
+        u32, visit_u32,
+        u64, visit_u64,
+        u128, visit_u128,
+        i8, visit_i8,
+        i16, visit_i16,
+        i32, visit_i32,
+        i64, visit_i64,
+        i128, visit_i128,
+        usize, visit_usize,
+        isize, visit_isize,
+        f32, visit_f32,
+        f64, visit_f64,
+    }
+
+    #[inline]
+    fn visit_bytes(self, _: &mut C, _: &'de [u8]) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
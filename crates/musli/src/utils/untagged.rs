@@ -0,0 +1,295 @@
+use core::fmt::{self, Write};
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::context::Error;
+use crate::Context;
+
+/// Errors collected while decoding an untagged enum.
+///
+/// Untagged enums are decoded by buffering the input and trying each variant
+/// in declaration order through [`UntaggedErrors::try_variant`]. If no variant
+/// matches, this is reported through [`Context::message`] and lists why each
+/// variant failed.
+pub struct UntaggedErrors {
+    name: &'static str,
+    errors: Vec<(&'static str, String)>,
+}
+
+impl UntaggedErrors {
+    /// Construct a new collection of errors for the untagged enum `name`.
+    #[inline]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Try to decode the given `variant` using a context which captures the
+    /// error it raises.
+    ///
+    /// Tracing and marks are forwarded to the outer context `cx`. Returns
+    /// `None` and records the error if decoding failed.
+    pub fn try_variant<'buf, C, T, F>(
+        &mut self,
+        cx: &mut C,
+        variant: &'static str,
+        f: F,
+    ) -> Option<T>
+    where
+        C: Context<'buf>,
+        C::Input: crate::error::Error,
+        F: FnOnce(&mut UntaggedContext<'_, C>) -> Result<T, Error>,
+    {
+        let mut cx = UntaggedContext {
+            cx,
+            error: None,
+            path: Vec::new(),
+        };
+
+        let result = f(&mut cx);
+
+        // Steps which were entered but not left because decoding bailed out
+        // early are left in reverse order so that the outer context is
+        // restored.
+        while let Some(step) = cx.path.pop() {
+            step.leave(cx.cx);
+        }
+
+        match result {
+            Ok(value) => Some(value),
+            Err(Error) => {
+                let error = cx.error.unwrap_or_else(|| String::from("unknown error"));
+                self.errors.push((variant, error));
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for UntaggedErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "data did not match any variant of `{}`", self.name)?;
+
+        for (variant, error) in &self.errors {
+            write!(f, "; {variant}: {error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The context used by [`UntaggedErrors::try_variant`], which captures the
+/// last error reported to it.
+///
+/// Captured errors are prefixed with the path at which they were reported,
+/// relative to the untagged enum. Everything else is forwarded to the outer
+/// context.
+pub struct UntaggedContext<'a, C> {
+    cx: &'a mut C,
+    error: Option<String>,
+    path: Vec<Step>,
+}
+
+impl<C> UntaggedContext<'_, C> {
+    fn capture<T>(&mut self, error: T) -> Error
+    where
+        T: fmt::Display,
+    {
+        let mut string = String::new();
+
+        for step in &self.path {
+            let _ = step.format(&mut string);
+        }
+
+        if string.is_empty() {
+            self.error = Some(error.to_string());
+        } else {
+            let _ = write!(string, ": {error}");
+            self.error = Some(string);
+        }
+
+        Error
+    }
+}
+
+impl<'buf, C> Context<'buf> for UntaggedContext<'_, C>
+where
+    C: Context<'buf>,
+    C::Input: crate::error::Error,
+{
+    type Input = C::Input;
+    type Error = Error;
+    type Mark = C::Mark;
+
+    #[inline]
+    fn report<T>(&mut self, error: T) -> Error
+    where
+        C::Input: From<T>,
+    {
+        self.capture(C::Input::from(error))
+    }
+
+    #[inline]
+    fn custom<T>(&mut self, error: T) -> Error
+    where
+        T: 'static + Send + Sync + fmt::Display + fmt::Debug,
+    {
+        self.capture(error)
+    }
+
+    #[inline]
+    fn message<T>(&mut self, message: T) -> Error
+    where
+        T: fmt::Display,
+    {
+        self.capture(message)
+    }
+
+    #[inline]
+    fn mark(&mut self) -> Self::Mark {
+        self.cx.mark()
+    }
+
+    #[inline]
+    fn store_string(&mut self, string: &str) {
+        self.cx.store_string(string);
+    }
+
+    #[inline]
+    fn get_string(&self) -> Option<&'buf str> {
+        self.cx.get_string()
+    }
+
+    #[inline]
+    fn enter_struct(&mut self, name: &'static str) {
+        self.cx.enter_struct(name);
+        self.path.push(Step::Struct);
+    }
+
+    #[inline]
+    fn leave_struct(&mut self) {
+        self.cx.leave_struct();
+        self.path.pop();
+    }
+
+    #[inline]
+    fn enter_enum(&mut self, name: &'static str) {
+        self.cx.enter_enum(name);
+        self.path.push(Step::Enum);
+    }
+
+    #[inline]
+    fn leave_enum(&mut self) {
+        self.cx.leave_enum();
+        self.path.pop();
+    }
+
+    #[inline]
+    fn enter_named_field<T>(&mut self, name: &'static str, tag: T)
+    where
+        T: fmt::Display,
+    {
+        self.cx.enter_named_field(name, tag);
+        self.path.push(Step::Named(name));
+    }
+
+    #[inline]
+    fn enter_unnamed_field<T>(&mut self, index: u32, tag: T)
+    where
+        T: fmt::Display,
+    {
+        self.cx.enter_unnamed_field(index, tag);
+        self.path.push(Step::Unnamed(index));
+    }
+
+    #[inline]
+    fn leave_field(&mut self) {
+        self.cx.leave_field();
+        self.path.pop();
+    }
+
+    #[inline]
+    fn enter_variant<T>(&mut self, name: &'static str, tag: T)
+    where
+        T: fmt::Display,
+    {
+        self.cx.enter_variant(name, tag);
+        self.path.push(Step::Variant);
+    }
+
+    #[inline]
+    fn leave_variant(&mut self) {
+        self.cx.leave_variant();
+        self.path.pop();
+    }
+
+    #[inline]
+    fn enter_map_key<T>(&mut self, field: T)
+    where
+        T: fmt::Display,
+    {
+        let key = field.to_string();
+        self.cx.enter_map_key(field);
+        self.path.push(Step::Key(key));
+    }
+
+    #[inline]
+    fn leave_map_key(&mut self) {
+        self.cx.leave_map_key();
+        self.path.pop();
+    }
+
+    #[inline]
+    fn enter_sequence_index(&mut self, index: usize) {
+        self.cx.enter_sequence_index(index);
+        self.path.push(Step::Index(index));
+    }
+
+    #[inline]
+    fn leave_sequence_index(&mut self) {
+        self.cx.leave_sequence_index();
+        self.path.pop();
+    }
+}
+
+/// A step which has been entered in an [`UntaggedContext`].
+enum Step {
+    Struct,
+    Enum,
+    Variant,
+    Named(&'static str),
+    Unnamed(u32),
+    Key(String),
+    Index(usize),
+}
+
+impl Step {
+    /// Leave this step in the outer context.
+    fn leave<'buf, C>(self, cx: &mut C)
+    where
+        C: Context<'buf>,
+    {
+        match self {
+            Step::Struct => cx.leave_struct(),
+            Step::Enum => cx.leave_enum(),
+            Step::Variant => cx.leave_variant(),
+            Step::Named(..) | Step::Unnamed(..) => cx.leave_field(),
+            Step::Key(..) => cx.leave_map_key(),
+            Step::Index(..) => cx.leave_sequence_index(),
+        }
+    }
+
+    /// Format the path component of this step, if it has one.
+    fn format(&self, out: &mut String) -> fmt::Result {
+        match self {
+            Step::Struct | Step::Enum | Step::Variant => Ok(()),
+            Step::Named(name) => write!(out, ".{name}"),
+            Step::Unnamed(index) => write!(out, ".{index}"),
+            Step::Key(key) => write!(out, "[{key}]"),
+            Step::Index(index) => write!(out, "[{index}]"),
+        }
+    }
+}